@@ -25,6 +25,7 @@ interface TypeDefLine {
   original_name?: string
   def: string
   extends?: string
+  implements?: string
   js_doc?: string
   js_mod?: string
 }
@@ -72,7 +73,10 @@ function prettyPrint(
             `\nnext(value?: ${TNext}): IteratorResult<${T}, ${TResult}>`
         }
       }
-      s += `${exportDeclare(ambient)} class ${line.name}${extendsDef} {\n${line.def}\n}`
+      const implementsDef = line.implements
+        ? ` implements ${line.implements}`
+        : ''
+      s += `${exportDeclare(ambient)} class ${line.name}${extendsDef}${implementsDef} {\n${line.def}\n}`
       if (line.original_name && line.original_name !== line.name) {
         s += `\nexport type ${line.original_name} = ${line.name}`
       }
@@ -209,6 +213,12 @@ function preprocessTypeDef(defs: TypeDefLine[]): Map<string, TypeDefLine[]> {
       // merge `impl` into class definition
       const classDef = classDefs.get(def.name)
       if (classDef) {
+        if (def.implements) {
+          classDef.implements = classDef.implements
+            ? `${classDef.implements}, ${def.implements}`
+            : def.implements
+        }
+
         if (classDef.def) {
           classDef.def += '\n'
         }
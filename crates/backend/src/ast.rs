@@ -25,6 +25,8 @@ pub struct NapiFn {
   pub ts_type: Option<String>,
   pub ts_args_type: Option<String>,
   pub ts_return_type: Option<String>,
+  /// `#[napi(return_with = Def)]`, the return value is converted through the `#[napi(object, remote = ...)]` mirror `Def`
+  pub return_with: Option<syn::Path>,
   pub skip_typescript: bool,
   pub comments: Vec<String>,
   pub parent_is_generator: bool,
@@ -36,6 +38,8 @@ pub struct NapiFn {
   pub unsafe_: bool,
   pub register_name: Ident,
   pub no_export: bool,
+  /// `dispose` or `asyncDispose`, the method is registered under `Symbol.dispose` or `Symbol.asyncDispose`
+  pub well_known_symbol: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -49,6 +53,8 @@ pub struct CallbackArg {
 pub struct NapiFnArg {
   pub kind: NapiFnArgKind,
   pub ts_arg_type: Option<String>,
+  /// `#[napi(with = Def)]`, the argument is converted through the `#[napi(object, remote = ...)]` mirror `Def`
+  pub with: Option<syn::Path>,
 }
 
 impl NapiFnArg {
@@ -118,6 +124,16 @@ pub struct NapiClass {
   pub implement_async_iterator: bool,
   pub is_tuple: bool,
   pub use_custom_finalize: bool,
+  /// `#[napi(custom_finalize = "post")]`, `finalize` is run after the GC callback
+  pub post_finalize: bool,
+  pub extends: Option<NapiClassExtends>,
+}
+
+/// `#[napi(extends = Base)]`, `field` is the field of the struct holding the `Base` value
+#[derive(Debug, Clone)]
+pub struct NapiClassExtends {
+  pub base: syn::Path,
+  pub field: syn::Member,
 }
 
 #[derive(Debug, Clone)]
@@ -126,6 +142,10 @@ pub struct NapiObject {
   pub object_from_js: bool,
   pub object_to_js: bool,
   pub is_tuple: bool,
+  /// `#[napi(object, remote = Type)]`, the struct mirrors the fields of `Type` from another crate
+  pub remote: Option<syn::Path>,
+  /// `#[napi(object, collect_errors)]`, validate all the fields and throw every error found in one `TypeError`
+  pub collect_errors: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -165,6 +185,40 @@ pub struct NapiStructField {
   pub skip_typescript: bool,
   pub ts_type: Option<String>,
   pub has_lifetime: bool,
+  /// `#[napi(getter = method)]` on a field of a remote object, the value is read by calling `remote.method()`
+  pub remote_getter: Option<Ident>,
+  /// `#[napi(default)]` or `#[napi(default = expr)]`, the value used when the property is absent in the JavaScript object
+  pub default: Option<syn::Expr>,
+  /// `#[napi(flatten)]`, the properties of the field are merged into the parent object
+  pub flatten: bool,
+  /// `#[napi(with = Def)]`, the field is converted through the `#[napi(object, remote = ...)]` mirror `Def`
+  pub with: Option<syn::Path>,
+}
+
+/// `#[napi(error)]`, a Rust error type thrown as instances of JavaScript `Error` subclasses
+#[derive(Debug, Clone)]
+pub struct NapiError {
+  pub name: Ident,
+  pub js_name: String,
+  pub js_mod: Option<String>,
+  pub comments: Vec<String>,
+  pub register_name: Ident,
+  /// The variants of an enum, each has its own class extending the class of the enum.
+  /// A struct has a single variant with the class of the struct.
+  pub variants: Vec<NapiErrorVariant>,
+  pub is_enum: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct NapiErrorVariant {
+  /// `None` for a struct
+  pub name: Option<Ident>,
+  pub js_name: String,
+  /// `#[napi(code = "...")]`, the `code` property of the error, the name in `UPPER_SNAKE_CASE` by default
+  pub code: String,
+  pub fields: Vec<NapiStructField>,
+  pub is_tuple: bool,
+  pub comments: Vec<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -174,6 +228,7 @@ pub struct NapiImpl {
   pub has_lifetime: bool,
   pub items: Vec<NapiFn>,
   pub task_output_type: Option<Type>,
+  pub task_progress_type: Option<Type>,
   pub iterator_yield_type: Option<Type>,
   pub iterator_next_type: Option<Type>,
   pub iterator_return_type: Option<Type>,
@@ -195,6 +250,8 @@ pub struct NapiEnum {
   pub skip_typescript: bool,
   pub register_name: Ident,
   pub is_string_enum: bool,
+  /// `#[napi(flags)]`, the enum is expanded to a `bitflags` struct
+  pub is_flags: bool,
   pub object_from_js: bool,
   pub object_to_js: bool,
 }
@@ -203,6 +260,8 @@ pub struct NapiEnum {
 pub enum NapiEnumValue {
   String(String),
   Number(i32),
+  /// The bits of a `#[napi(flags)]` flag
+  Bits(u32),
 }
 
 impl From<&NapiEnumValue> for Literal {
@@ -210,6 +269,7 @@ impl From<&NapiEnumValue> for Literal {
     match val {
       NapiEnumValue::String(string) => Literal::string(string),
       NapiEnumValue::Number(number) => Literal::i32_unsuffixed(number.to_owned()),
+      NapiEnumValue::Bits(bits) => Literal::u32_unsuffixed(bits.to_owned()),
     }
   }
 }
@@ -249,3 +309,29 @@ pub struct NapiType {
   pub js_mod: Option<String>,
   pub comments: Vec<String>,
 }
+
+#[derive(Debug, Clone)]
+pub struct NapiTrait {
+  pub name: Ident,
+  pub js_name: String,
+  pub vis: syn::Visibility,
+  pub methods: Vec<NapiTraitMethod>,
+  pub js_mod: Option<String>,
+  pub comments: Vec<String>,
+  pub skip_typescript: bool,
+  /// `#[napi(threadsafe)]`, also generate a `Send + Sync` proxy with async methods
+  pub threadsafe: bool,
+}
+
+/// A required method of a `#[napi] trait`, implemented by the generated proxy
+#[derive(Debug, Clone)]
+pub struct NapiTraitMethod {
+  pub name: Ident,
+  pub js_name: String,
+  pub receiver: syn::Receiver,
+  pub args: Vec<(Ident, syn::Type)>,
+  /// the `T` of the `Result<T>` returned by the method
+  pub ret: syn::Type,
+  pub ts_type: Option<String>,
+  pub comments: Vec<String>,
+}
@@ -4,8 +4,10 @@ use crate::BindgenResult;
 
 mod r#const;
 mod r#enum;
+mod error;
 mod r#fn;
 mod r#struct;
+mod r#trait;
 mod r#type;
 
 pub use r#struct::rm_raw_prefix;
@@ -31,6 +33,18 @@ fn get_intermediate_ident(name: &str) -> Ident {
   Ident::new(&new_name, Span::call_site())
 }
 
+/// `cfg` of the ctor that registers an export on native targets.
+///
+/// Exports aren't registered in `cargo test` builds unless the `test-register` feature is enabled,
+/// which `napi-mock` does so that tests can load the module into the mock host.
+fn native_register_cfg() -> TokenStream {
+  if cfg!(feature = "test-register") {
+    quote! { #[cfg(not(target_family = "wasm"))] }
+  } else {
+    quote! { #[cfg(all(not(test), not(target_family = "wasm")))] }
+  }
+}
+
 fn js_mod_to_token_stream(js_mod: Option<&String>) -> TokenStream {
   js_mod
     .map(|i| {
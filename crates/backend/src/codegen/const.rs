@@ -1,7 +1,10 @@
 use proc_macro2::{Ident, Literal, TokenStream};
 use quote::ToTokens;
 
-use crate::{codegen::js_mod_to_token_stream, BindgenResult, NapiConst, TryToTokens};
+use crate::{
+  codegen::{js_mod_to_token_stream, native_register_cfg},
+  BindgenResult, NapiConst, TryToTokens,
+};
 
 impl TryToTokens for NapiConst {
   fn try_to_tokens(&self, tokens: &mut TokenStream) -> BindgenResult<()> {
@@ -29,6 +32,7 @@ impl NapiConst {
       &format!("__register__const__{register_name}_callback__"),
       self.name.span(),
     );
+    let native_register_cfg = native_register_cfg();
     let js_mod_ident = js_mod_to_token_stream(self.js_mod.as_ref());
 
     quote! {
@@ -39,7 +43,7 @@ impl NapiConst {
       }
       #[allow(non_snake_case)]
       #[allow(clippy::all)]
-      #[cfg(all(not(test), not(target_family = "wasm")))]
+      #native_register_cfg
       #[napi::ctor::ctor(crate_path=::napi::ctor)]
       fn #register_name() {
         napi::bindgen_prelude::register_module_export(#js_mod_ident, #js_name_lit, #cb_name);
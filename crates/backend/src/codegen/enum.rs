@@ -1,7 +1,10 @@
 use proc_macro2::{Ident, Literal, Span, TokenStream};
 use quote::ToTokens;
 
-use crate::{codegen::js_mod_to_token_stream, BindgenResult, NapiEnum, TryToTokens};
+use crate::{
+  codegen::{js_mod_to_token_stream, native_register_cfg},
+  BindgenResult, NapiEnum, TryToTokens,
+};
 
 impl TryToTokens for NapiEnum {
   fn try_to_tokens(&self, tokens: &mut TokenStream) -> BindgenResult<()> {
@@ -20,6 +23,10 @@ impl TryToTokens for NapiEnum {
 
 impl NapiEnum {
   fn gen_napi_value_map_impl(&self) -> TokenStream {
+    if self.is_flags {
+      return self.gen_flags_napi_value_map_impl();
+    }
+
     let name = &self.name;
     let name_str = self.name.to_string();
     let mut from_napi_branches = vec![];
@@ -214,14 +221,113 @@ impl NapiEnum {
     }
   }
 
+  /// The conversions of a `#[napi(flags)]` enum, expanded to a `bitflags` struct, from and to the bit mask number
+  fn gen_flags_napi_value_map_impl(&self) -> TokenStream {
+    let name = &self.name;
+    let name_str = self.name.to_string();
+
+    let from_napi_value = if self.object_from_js {
+      quote! {
+        impl napi::bindgen_prelude::FromNapiValue for #name {
+          unsafe fn from_napi_value(
+            env: napi::bindgen_prelude::sys::napi_env,
+            napi_val: napi::bindgen_prelude::sys::napi_value
+          ) -> napi::bindgen_prelude::Result<Self> {
+            let val: f64 = napi::bindgen_prelude::FromNapiValue::from_napi_value(env, napi_val).map_err(|e| {
+              napi::bindgen_prelude::error!(
+                e.status,
+                "Failed to convert napi value into flags `{}`. {}",
+                #name_str,
+                e,
+              )
+            })?;
+            if val.fract() != 0.0 || !(0.0..=u32::MAX as f64).contains(&val) {
+              return Err(napi::bindgen_prelude::error!(
+                napi::bindgen_prelude::Status::InvalidArg,
+                "value `{}` is not a bit mask of flags `{}`",
+                val,
+                #name_str
+              ));
+            }
+            let bits = val as u32;
+            #name::from_bits(bits).ok_or_else(|| {
+              napi::bindgen_prelude::error!(
+                napi::bindgen_prelude::Status::InvalidArg,
+                "value `{}` has bits `{:#x}` which are not flags of `{}`",
+                bits,
+                bits & !#name::all().bits(),
+                #name_str
+              )
+            })
+          }
+        }
+      }
+    } else {
+      quote! {}
+    };
+
+    let to_napi_value = if self.object_to_js {
+      let impls = [quote! { #name }, quote! { &#name }, quote! { &mut #name }]
+        .into_iter()
+        .map(|ty| {
+          quote! {
+            impl napi::bindgen_prelude::ToNapiValue for #ty {
+              unsafe fn to_napi_value(
+                env: napi::bindgen_prelude::sys::napi_env,
+                val: Self
+              ) -> napi::bindgen_prelude::Result<napi::bindgen_prelude::sys::napi_value> {
+                napi::bindgen_prelude::ToNapiValue::to_napi_value(env, val.bits())
+              }
+            }
+          }
+        });
+      quote! { #(#impls)* }
+    } else {
+      quote! {}
+    };
+
+    quote! {
+      impl napi::bindgen_prelude::TypeName for #name {
+        fn type_name() -> &'static str {
+          #name_str
+        }
+
+        fn value_type() -> napi::ValueType {
+          napi::ValueType::Number
+        }
+      }
+
+      impl napi::bindgen_prelude::ValidateNapiValue for #name {
+        unsafe fn validate(
+          env: napi::bindgen_prelude::sys::napi_env,
+          napi_val: napi::bindgen_prelude::sys::napi_value
+        ) -> napi::bindgen_prelude::Result<napi::sys::napi_value> {
+          napi::bindgen_prelude::assert_type_of!(env, napi_val, napi::bindgen_prelude::ValueType::Number)?;
+          Ok(std::ptr::null_mut())
+        }
+      }
+
+      #from_napi_value
+
+      #to_napi_value
+    }
+  }
+
   fn gen_module_register(&self) -> TokenStream {
     if cfg!(test) {
       return quote! {};
     }
 
+    let name = &self.name;
     let name_str = self.name.to_string();
     let js_name_lit = Literal::string(&format!("{}\0", &self.js_name));
     let register_name = &self.register_name;
+    // the flags are listed by `Object.keys` and can't be changed
+    let attributes = if self.is_flags {
+      quote! { napi::bindgen_prelude::sys::PropertyAttributes::enumerable }
+    } else {
+      quote! { napi::bindgen_prelude::sys::PropertyAttributes::default }
+    };
 
     let mut value_conversions = vec![];
     let mut property_descriptors = vec![];
@@ -235,8 +341,14 @@ impl NapiEnum {
       value_names.push(value_var.clone());
 
       // Convert the value first
+      let val = if self.is_flags {
+        let v_name = &variant.name;
+        quote! { #name::#v_name.bits() }
+      } else {
+        quote! { #val_lit }
+      };
       value_conversions.push(quote! {
-        let #value_var = napi::bindgen_prelude::ToNapiValue::to_napi_value(env, #val_lit)?;
+        let #value_var = napi::bindgen_prelude::ToNapiValue::to_napi_value(env, #val)?;
       });
 
       // Create property descriptor using the pre-computed value
@@ -248,7 +360,7 @@ impl NapiEnum {
           getter: None,
           setter: None,
           value: #value_var,
-          attributes: napi::bindgen_prelude::sys::PropertyAttributes::default,
+          attributes: #attributes,
           data: std::ptr::null_mut(),
         }
       });
@@ -271,6 +383,12 @@ impl NapiEnum {
 
       let obj_ptr = napi::bindgen_prelude::create_object_with_properties(env, &properties)?;
     };
+    let freeze = if self.is_flags {
+      quote! { napi::bindgen_prelude::freeze_object(env, obj_ptr)?; }
+    } else {
+      quote! {}
+    };
+    let native_register_cfg = native_register_cfg();
 
     quote! {
       #[allow(non_snake_case)]
@@ -280,12 +398,13 @@ impl NapiEnum {
         use std::ptr;
 
         #object_creation
+        #freeze
 
         Ok(obj_ptr)
       }
       #[allow(non_snake_case)]
       #[allow(clippy::all)]
-      #[cfg(all(not(test), not(target_family = "wasm")))]
+      #native_register_cfg
       #[napi::ctor::ctor(crate_path=napi::ctor)]
       fn #register_name() {
         napi::bindgen_prelude::register_module_export(#js_mod_ident, #js_name_lit, #callback_name);
@@ -0,0 +1,207 @@
+use proc_macro2::{Ident, Literal, TokenStream};
+use quote::{format_ident, ToTokens};
+
+use crate::{
+  codegen::{js_mod_to_token_stream, native_register_cfg, r#struct::rm_raw_prefix},
+  BindgenResult, NapiError, NapiErrorVariant, TryToTokens,
+};
+
+impl TryToTokens for NapiError {
+  fn try_to_tokens(&self, tokens: &mut TokenStream) -> BindgenResult<()> {
+    let classes = self.gen_classes();
+    let conversions = self.gen_conversions();
+    let register = self.gen_module_register();
+    (quote! {
+      #classes
+      #conversions
+      #register
+    })
+    .to_tokens(tokens);
+
+    Ok(())
+  }
+}
+
+impl NapiError {
+  /// The `ErrorClass` static of the enum if `variant` is `None`, otherwise the one of the variant
+  fn class_ident(&self, variant: Option<&NapiErrorVariant>) -> Ident {
+    let name = rm_raw_prefix(&self.name.to_string()).to_owned();
+    match variant.and_then(|variant| variant.name.as_ref()) {
+      Some(variant) => format_ident!("__napi_error_class_{}_{}", name, variant),
+      None => format_ident!("__napi_error_class_{}", name),
+    }
+  }
+
+  /// `(static, js_name, parent static)` of the classes, the class of an enum goes first
+  fn classes(&self) -> Vec<(Ident, &str, Option<Ident>)> {
+    if self.is_enum {
+      let base = self.class_ident(None);
+      std::iter::once((base.clone(), self.js_name.as_str(), None))
+        .chain(self.variants.iter().map(|variant| {
+          (
+            self.class_ident(Some(variant)),
+            variant.js_name.as_str(),
+            Some(base.clone()),
+          )
+        }))
+        .collect()
+    } else {
+      vec![(self.class_ident(None), self.js_name.as_str(), None)]
+    }
+  }
+
+  fn gen_classes(&self) -> TokenStream {
+    let classes = self.classes().into_iter().map(|(ident, js_name, parent)| {
+      let parent = match parent {
+        Some(parent) => quote! { Some(&#parent) },
+        None => quote! { None },
+      };
+      quote! {
+        #[doc(hidden)]
+        #[allow(non_upper_case_globals)]
+        static #ident: napi::bindgen_prelude::ErrorClass =
+          napi::bindgen_prelude::ErrorClass::new(#js_name, #parent);
+      }
+    });
+    quote! { #(#classes)* }
+  }
+
+  fn gen_conversions(&self) -> TokenStream {
+    let name = &self.name;
+    let js_name = &self.js_name;
+    let arms = self.variants.iter().map(|variant| {
+      let class = self.class_ident(Some(variant));
+      let code = &variant.code;
+      let mut destructions = vec![];
+      let mut setters = vec![];
+      for (i, field) in variant.fields.iter().enumerate() {
+        if !field.getter {
+          continue;
+        }
+        let member = &field.name;
+        let field_js_name = &field.js_name;
+        let alias = format_ident!("__field_{}", i);
+        destructions.push(quote! { #member: #alias });
+        setters.push(if is_option(&field.ty) {
+          quote! {
+            if let Some(value) = #alias {
+              error.set_with_property_key(#field_js_name, value)?;
+            }
+          }
+        } else {
+          quote! { error.set_with_property_key(#field_js_name, #alias)?; }
+        });
+      }
+      let path = match &variant.name {
+        Some(variant) => quote! { Self::#variant },
+        None => quote! { Self },
+      };
+      quote! {
+        #path { #(#destructions,)* .. } => {
+          let mut error = #class.new_instance(env, &message)?;
+          error.set_with_property_key("code", #code)?;
+          #(#setters)*
+          Ok(napi::bindgen_prelude::JsValue::raw(&error))
+        }
+      }
+    });
+
+    quote! {
+      #[automatically_derived]
+      impl napi::bindgen_prelude::TypeName for #name {
+        fn type_name() -> &'static str {
+          #js_name
+        }
+
+        fn value_type() -> napi::ValueType {
+          napi::ValueType::Object
+        }
+      }
+
+      #[automatically_derived]
+      impl napi::bindgen_prelude::ToNapiValue for #name {
+        unsafe fn to_napi_value(
+          env: napi::bindgen_prelude::sys::napi_env,
+          val: #name,
+        ) -> napi::bindgen_prelude::Result<napi::bindgen_prelude::sys::napi_value> {
+          let message = ::std::string::ToString::to_string(&val);
+          #[allow(unused_variables)]
+          match val {
+            #(#arms)*
+          }
+        }
+      }
+
+      #[automatically_derived]
+      impl From<#name> for napi::bindgen_prelude::Error {
+        fn from(err: #name) -> Self {
+          napi::bindgen_prelude::Error::with_value(::std::string::ToString::to_string(&err), err)
+        }
+      }
+
+      #[automatically_derived]
+      impl From<#name> for napi::bindgen_prelude::JsError {
+        fn from(err: #name) -> Self {
+          napi::bindgen_prelude::JsError::from(napi::bindgen_prelude::Error::from(err))
+        }
+      }
+    }
+  }
+
+  fn gen_module_register(&self) -> TokenStream {
+    if cfg!(test) {
+      return quote! {};
+    }
+
+    let register_name = &self.register_name;
+    let js_mod_ident = js_mod_to_token_stream(self.js_mod.as_ref());
+    let (callbacks, registers): (Vec<_>, Vec<_>) = self
+      .classes()
+      .into_iter()
+      .map(|(class, js_name, _)| {
+        let cb_name = format_ident!("__register__error__{}_callback__", class);
+        let js_name_lit = Literal::string(&format!("{js_name}\0"));
+        (
+          quote! {
+            #[allow(non_snake_case)]
+            #[allow(clippy::all)]
+            unsafe fn #cb_name(env: napi::sys::napi_env) -> napi::Result<napi::sys::napi_value> {
+              #class.constructor(env)
+            }
+          },
+          quote! {
+            napi::bindgen_prelude::register_module_export(#js_mod_ident, #js_name_lit, #cb_name);
+          },
+        )
+      })
+      .unzip();
+    let native_register_cfg = native_register_cfg();
+
+    quote! {
+      #(#callbacks)*
+      #[allow(non_snake_case)]
+      #[allow(clippy::all)]
+      #native_register_cfg
+      #[napi::ctor::ctor(crate_path=::napi::ctor)]
+      fn #register_name() {
+        #(#registers)*
+      }
+
+      #[allow(non_snake_case)]
+      #[allow(clippy::all)]
+      #[cfg(all(not(test), target_family = "wasm"))]
+      #[no_mangle]
+      extern "C" fn #register_name() {
+        #(#registers)*
+      }
+    }
+  }
+}
+
+fn is_option(ty: &syn::Type) -> bool {
+  matches!(
+    ty,
+    syn::Type::Path(syn::TypePath { path, .. })
+      if path.segments.last().is_some_and(|segment| segment.ident == "Option")
+  )
+}
@@ -3,7 +3,7 @@ use quote::ToTokens;
 use syn::{spanned::Spanned, Type, TypePath, TypeReference};
 
 use crate::{
-  codegen::{get_intermediate_ident, js_mod_to_token_stream},
+  codegen::{get_intermediate_ident, js_mod_to_token_stream, native_register_cfg},
   BindgenResult, CallbackArg, Diagnostic, FnKind, FnSelf, NapiFn, NapiFnArgKind, TryToTokens,
   TYPEDARRAY_SLICE_TYPES,
 };
@@ -36,6 +36,7 @@ impl TryToTokens for NapiFn {
       args: arg_names,
       refs,
       mut_ref_spans,
+      abort_signals,
       unsafe_,
     } = self.gen_arg_conversions()?;
     let attrs = &self.attrs;
@@ -155,7 +156,8 @@ impl TryToTokens for NapiFn {
       }
     } else {
       let call = if self.is_ret_result {
-        quote! { #receiver(#(#arg_names),*).await }
+        let into_error = convert_err(quote! { into_error });
+        quote! { #receiver(#(#arg_names),*).await.map_err(|err| #into_error) }
       } else {
         let ret_type = if let Some(t) = &self.ret {
           quote! { #t }
@@ -164,12 +166,23 @@ impl TryToTokens for NapiFn {
         };
         quote! { Ok::<#ret_type, napi::Error>(#receiver(#(#arg_names),*).await) }
       };
-      quote! {
-        napi::bindgen_prelude::execute_tokio_future_with_finalize_callback(env, async move { #call }, move |env, #receiver_ret_name| {
-          #ret
-        }, Some(Box::new(move |env| {
-          _args_ref.drop(env);
-        })))
+      if abort_signals.is_empty() {
+        quote! {
+          napi::bindgen_prelude::execute_tokio_future_with_finalize_callback(env, async move { #call }, move |env, #receiver_ret_name| {
+            #ret
+          }, Some(Box::new(move |env| {
+            _args_ref.drop(env);
+          })))
+        }
+      } else {
+        quote! {
+          let _abort_signals: Vec<napi::bindgen_prelude::AbortSignal> = [#(#abort_signals),*].into_iter().flatten().collect();
+          napi::bindgen_prelude::execute_tokio_future_with_abort_signals(env, async move { #call }, move |env, #receiver_ret_name| {
+            #ret
+          }, Some(Box::new(move |env| {
+            _args_ref.drop(env);
+          })), _abort_signals)
+        }
       }
     };
 
@@ -271,6 +284,7 @@ impl NapiFn {
     let mut args = vec![];
     let mut refs = vec![];
     let mut mut_ref_spans = vec![];
+    let mut abort_signals = vec![];
 
     // fetch this
     if let Some(parent) = &self.parent {
@@ -402,7 +416,8 @@ impl NapiFn {
                 }
               }
             }
-            let (arg_conversion, arg_type) = self.gen_ty_arg_conversion(&ident, i, path)?;
+            let (arg_conversion, arg_type) =
+              self.gen_ty_arg_conversion(&ident, i, path, arg.with.as_ref())?;
             if NapiArgType::MutRef == arg_type {
               mut_ref_spans.push(path.ty.span());
             }
@@ -414,6 +429,14 @@ impl NapiFn {
               skipped_arg_count += 1;
               continue;
             }
+            if self.is_async {
+              // `AbortSignal` in `async fn` is used to abort the spawned future
+              match abort_signal_arg(&path.ty) {
+                Some(true) => abort_signals.push(quote! { #ident.clone() }),
+                Some(false) => abort_signals.push(quote! { Some(#ident.clone()) }),
+                None => {}
+              }
+            }
             arg_conversions.push(arg_conversion);
             args.push(quote! { #ident });
           }
@@ -430,6 +453,7 @@ impl NapiFn {
       args,
       refs,
       mut_ref_spans,
+      abort_signals,
       unsafe_: self.unsafe_,
     })
   }
@@ -441,11 +465,31 @@ impl NapiFn {
     arg_name: &Ident,
     index: usize,
     path: &syn::PatType,
+    with: Option<&syn::Path>,
   ) -> BindgenResult<(TokenStream, NapiArgType)> {
     let mut ty = *path.ty.clone();
+    // `impl Trait` of a `#[napi] trait` is converted through its generated `TraitProxy`
+    if let syn::Type::ImplTrait(impl_trait) = &ty {
+      if let Some(syn::TypeParamBound::Trait(bound)) = impl_trait.bounds.first() {
+        let mut proxy = bound.path.clone();
+        if let Some(last) = proxy.segments.last_mut() {
+          last.ident = format_ident!("{}Proxy", last.ident);
+        }
+        ty = syn::Type::Path(syn::TypePath {
+          qself: None,
+          path: proxy,
+        });
+      }
+    }
+    // `#[napi(with = Def)]` arguments are types from other crates, converted through their mirror `Def`
+    let validate = if let Some(with) = with {
+      quote! { <#ty as napi::bindgen_prelude::RemoteNapiValue<#with>>::validate }
+    } else {
+      quote! { <#ty as napi::bindgen_prelude::ValidateNapiValue>::validate }
+    };
     let type_check = if self.return_if_invalid {
       quote! {
-        if let Ok(maybe_promise) = <#ty as napi::bindgen_prelude::ValidateNapiValue>::validate(env, cb.get_arg(#index)) {
+        if let Ok(maybe_promise) = #validate(env, cb.get_arg(#index)) {
           if !maybe_promise.is_null() {
             return Ok(maybe_promise);
           }
@@ -455,7 +499,7 @@ impl NapiFn {
       }
     } else if self.strict {
       quote! {
-        let maybe_promise = <#ty as napi::bindgen_prelude::ValidateNapiValue>::validate(env, cb.get_arg(#index))?;
+        let maybe_promise = #validate(env, cb.get_arg(#index))?;
         if !maybe_promise.is_null() {
           return Ok(maybe_promise);
         }
@@ -568,7 +612,11 @@ impl NapiFn {
         }
         // Array::validate only validates by the `Array.isArray`
         // For the elements of the Array, we need to return rather than throw if they are invalid when `return_if_invalid` is true
-        let from_napi_value = if is_array && self.return_if_invalid {
+        let from_napi_value = if let Some(with) = with {
+          quote! {
+            <#ty as napi::bindgen_prelude::RemoteNapiValue<#with>>::from_napi_value(env, #arg_conversion)?
+          }
+        } else if is_array && self.return_if_invalid {
           quote! {
             match <#ty as napi::bindgen_prelude::FromNapiValue>::from_napi_value(env, #arg_conversion) {
               Ok(value) => value,
@@ -672,6 +720,7 @@ impl NapiFn {
 
   fn gen_fn_return(&self, ret: &Ident) -> BindgenResult<TokenStream> {
     let js_name = &self.js_name;
+    let into_js_error = convert_err(quote! { into_js_error });
 
     if let Some(ty) = &self.ret {
       let ty_string = ty.into_token_stream().to_string();
@@ -693,7 +742,7 @@ impl NapiFn {
                   cb.construct::<false, _>(#js_name, value)
                 }
                 Err(err) => {
-                  napi::bindgen_prelude::JsError::from(err).throw_into(env);
+                  #into_js_error.throw_into(env);
                   Ok(std::ptr::null_mut())
                 }
               }
@@ -721,7 +770,7 @@ impl NapiFn {
                   cb.factory(#js_name, value)
                 }
                 Err(err) => {
-                  napi::bindgen_prelude::JsError::from(err).throw_into(env);
+                  #into_js_error.throw_into(env);
                   Ok(std::ptr::null_mut())
                 }
               }
@@ -735,18 +784,25 @@ impl NapiFn {
           Ok(quote! { cb.factory(#js_name, #ret) })
         }
       } else if self.is_ret_result {
+        let to_napi_value = if let Some(with) = &self.return_with {
+          quote! { <#ty as napi::bindgen_prelude::RemoteNapiValue<#with>>::to_napi_value }
+        } else if self.is_async {
+          quote! { <#ty as napi::bindgen_prelude::ToNapiValue>::to_napi_value }
+        } else {
+          quote! { napi::bindgen_prelude::ToNapiValue::to_napi_value }
+        };
         if self.is_async {
           Ok(quote! {
-            <#ty as napi::bindgen_prelude::ToNapiValue>::to_napi_value(env, #ret)
+            #to_napi_value(env, #ret)
           })
         } else if is_return_self {
           Ok(quote! { #ret.map(|_| cb.this) })
         } else {
           Ok(quote! {
             match #ret {
-              Ok(value) => napi::bindgen_prelude::ToNapiValue::to_napi_value(env, value),
+              Ok(value) => #to_napi_value(env, value),
               Err(err) => {
-                napi::bindgen_prelude::JsError::from(err).throw_into(env);
+                #into_js_error.throw_into(env);
                 Ok(std::ptr::null_mut())
               },
             }
@@ -757,9 +813,15 @@ impl NapiFn {
       } else {
         let mut return_ty = ty.clone();
         hidden_ty_lifetime(&mut return_ty)?;
-        Ok(quote! {
-          <#return_ty as napi::bindgen_prelude::ToNapiValue>::to_napi_value(env, #ret)
-        })
+        if let Some(with) = &self.return_with {
+          Ok(quote! {
+            <#return_ty as napi::bindgen_prelude::RemoteNapiValue<#with>>::to_napi_value(env, #ret)
+          })
+        } else {
+          Ok(quote! {
+            <#return_ty as napi::bindgen_prelude::ToNapiValue>::to_napi_value(env, #ret)
+          })
+        }
       }
     } else {
       Ok(quote! {
@@ -783,6 +845,7 @@ impl NapiFn {
         Span::call_site(),
       );
 
+      let native_register_cfg = native_register_cfg();
       if self.module_exports {
         return quote! {
           #[doc(hidden)]
@@ -796,7 +859,7 @@ impl NapiFn {
           #[doc(hidden)]
           #[allow(clippy::all)]
           #[allow(non_snake_case)]
-          #[cfg(all(not(test), not(target_family = "wasm")))]
+          #native_register_cfg
           #[napi::ctor::ctor(crate_path=::napi::ctor)]
           fn #module_register_name() {
             napi::bindgen_prelude::register_module_export_hook(#cb_name);
@@ -819,7 +882,7 @@ impl NapiFn {
           #[doc(hidden)]
           #[allow(clippy::all)]
           #[allow(non_snake_case)]
-          #[cfg(all(not(test), not(target_family = "wasm")))]
+          #native_register_cfg
           #[napi::ctor::ctor(crate_path=::napi::ctor)]
           fn #module_register_name() {
             napi::bindgen_prelude::register_module_export(#js_mod_ident, #js_name, #cb_name);
@@ -898,6 +961,18 @@ fn hidden_ty_lifetime(ty: &mut syn::Type) -> BindgenResult<()> {
   Ok(())
 }
 
+/// Convert `err` with the first conversion it has, see `napi::bindgen_prelude::ErrorProbe`.
+/// `method` is `into_js_error` or `into_error`
+fn convert_err(method: TokenStream) -> TokenStream {
+  quote! {
+    {
+      #[allow(unused_imports)]
+      use napi::bindgen_prelude::{IntoErrorKind as _, IntoJsErrorKind as _, StdErrorKind as _};
+      (&&napi::bindgen_prelude::ErrorProbe(&err)).napi_error_kind().#method(err)
+    }
+  }
+}
+
 fn make_ref(input: TokenStream) -> TokenStream {
   quote! {
     _args_array[_arg_write_index] = _make_ref(
@@ -908,11 +983,33 @@ fn make_ref(input: TokenStream) -> TokenStream {
   }
 }
 
+/// Returns `Some(is_optional)` if the type is `AbortSignal` or `Option<AbortSignal>`
+fn abort_signal_arg(ty: &syn::Type) -> Option<bool> {
+  let syn::Type::Path(TypePath { path, .. }) = ty else {
+    return None;
+  };
+  let segment = path.segments.last()?;
+  if segment.ident == "AbortSignal" {
+    return Some(false);
+  }
+  if segment.ident == "Option" {
+    if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
+      if let Some(syn::GenericArgument::Type(inner)) = args.args.first() {
+        return abort_signal_arg(inner)
+          .filter(|is_optional| !is_optional)
+          .map(|_| true);
+      }
+    }
+  }
+  None
+}
+
 struct ArgConversions {
   pub args: Vec<TokenStream>,
   pub arg_conversions: Vec<TokenStream>,
   pub refs: Vec<TokenStream>,
   pub mut_ref_spans: Vec<Span>,
+  pub abort_signals: Vec<TokenStream>,
   pub unsafe_: bool,
 }
 
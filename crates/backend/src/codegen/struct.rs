@@ -7,10 +7,12 @@ use quote::ToTokens;
 use crate::util::to_case;
 
 use crate::{
-  codegen::{get_intermediate_ident, js_mod_to_token_stream},
+  codegen::{get_intermediate_ident, js_mod_to_token_stream, native_register_cfg},
   BindgenResult, FnKind, NapiImpl, NapiStruct, NapiStructKind, TryToTokens,
 };
-use crate::{NapiArray, NapiClass, NapiObject, NapiStructuredEnum, NapiTransparent};
+use crate::{
+  NapiArray, NapiClass, NapiObject, NapiStructField, NapiStructuredEnum, NapiTransparent,
+};
 
 static NAPI_IMPL_ID: AtomicU32 = AtomicU32::new(0);
 
@@ -115,13 +117,7 @@ fn gen_napi_value_map_impl(
         env: napi::bindgen_prelude::sys::napi_env,
         napi_val: napi::bindgen_prelude::sys::napi_value
       ) -> napi::bindgen_prelude::Result<&'static Self> {
-        let mut wrapped_val: *mut std::ffi::c_void = std::ptr::null_mut();
-
-        napi::bindgen_prelude::check_status!(
-          napi::bindgen_prelude::sys::napi_unwrap(env, napi_val, &mut wrapped_val),
-          "Failed to recover `{}` type from napi value",
-          #name_str,
-        )?;
+        let wrapped_val = napi::bindgen_prelude::unwrap_class_instance::<#name>(env, napi_val)?;
 
         Ok(&*(wrapped_val as *const #name))
       }
@@ -133,13 +129,7 @@ fn gen_napi_value_map_impl(
         env: napi::bindgen_prelude::sys::napi_env,
         napi_val: napi::bindgen_prelude::sys::napi_value
       ) -> napi::bindgen_prelude::Result<&'static mut Self> {
-        let mut wrapped_val: *mut std::ffi::c_void = std::ptr::null_mut();
-
-        napi::bindgen_prelude::check_status!(
-          napi::bindgen_prelude::sys::napi_unwrap(env, napi_val, &mut wrapped_val),
-          "Failed to recover `{}` type from napi value",
-          #name_str,
-        )?;
+        let wrapped_val = napi::bindgen_prelude::unwrap_class_instance::<#name>(env, napi_val)?;
 
         Ok(&mut *(wrapped_val as *mut #name))
       }
@@ -276,6 +266,39 @@ impl NapiStruct {
     }
   }
 
+  /// `ObjectFinalize` is implemented by the user with `#[napi(custom_finalize)]`, `ObjectFinalizeMode` is always generated
+  fn gen_finalize_trait(&self, class: &NapiClass) -> TokenStream {
+    let name = &self.name;
+    let (object_finalize_impl, object_finalize_mode_impl) = if self.has_lifetime {
+      (
+        quote! { impl <'_javascript_function_scope> napi::bindgen_prelude::ObjectFinalize for #name<'_javascript_function_scope> },
+        quote! { impl <'_javascript_function_scope> napi::bindgen_prelude::ObjectFinalizeMode for #name<'_javascript_function_scope> },
+      )
+    } else {
+      (
+        quote! { impl napi::bindgen_prelude::ObjectFinalize for #name },
+        quote! { impl napi::bindgen_prelude::ObjectFinalizeMode for #name },
+      )
+    };
+    let object_finalize = if class.use_custom_finalize {
+      quote! {}
+    } else {
+      quote! {
+        #[automatically_derived]
+        #object_finalize_impl {}
+      }
+    };
+    let post_finalize = class.post_finalize;
+    quote! {
+      #object_finalize
+
+      #[automatically_derived]
+      #object_finalize_mode_impl {
+        const POST_FINALIZE: bool = #post_finalize;
+      }
+    }
+  }
+
   fn gen_to_napi_value_ctor_impl_for_non_default_constructor_struct(
     &self,
     class: &NapiClass,
@@ -285,29 +308,19 @@ impl NapiStruct {
     let js_name_str = format!("{js_name_raw}\0");
     let iterator_implementation = self.gen_iterator_property(class, name);
     let async_iterator_implementation = self.gen_async_iterator_property(class, name);
-    let (object_finalize_impl, to_napi_value_impl, javascript_class_ext_impl) = if self.has_lifetime
-    {
+    let (to_napi_value_impl, javascript_class_ext_impl) = if self.has_lifetime {
       let name = quote! { #name<'_javascript_function_scope> };
       (
-        quote! { impl <'_javascript_function_scope> napi::bindgen_prelude::ObjectFinalize for #name {} },
         quote! { impl <'_javascript_function_scope> napi::bindgen_prelude::ToNapiValue for #name },
         quote! { impl <'_javascript_function_scope> napi::bindgen_prelude::JavaScriptClassExt for #name },
       )
     } else {
       (
-        quote! { impl napi::bindgen_prelude::ObjectFinalize for #name {} },
         quote! { impl napi::bindgen_prelude::ToNapiValue for #name },
         quote! { impl napi::bindgen_prelude::JavaScriptClassExt for #name },
       )
     };
-    let finalize_trait = if class.use_custom_finalize {
-      quote! {}
-    } else {
-      quote! {
-        #[automatically_derived]
-        #object_finalize_impl
-      }
-    };
+    let finalize_trait = self.gen_finalize_trait(class);
     quote! {
       #[automatically_derived]
       #to_napi_value_impl {
@@ -459,13 +472,7 @@ impl NapiStruct {
       }
     };
 
-    let finalize_trait = if class.use_custom_finalize {
-      quote! {}
-    } else if self.has_lifetime {
-      quote! { impl <'_javascript_function_scope> napi::bindgen_prelude::ObjectFinalize for #name<'_javascript_function_scope> {} }
-    } else {
-      quote! { impl napi::bindgen_prelude::ObjectFinalize for #name {} }
-    };
+    let finalize_trait = self.gen_finalize_trait(class);
 
     let to_napi_value_impl = if self.has_lifetime {
       quote! { impl <'_javascript_function_scope> napi::bindgen_prelude::ToNapiValue for #name<'_javascript_function_scope> }
@@ -523,10 +530,12 @@ impl NapiStruct {
     let mut property_descriptors = vec![];
     let mut conditional_setters = vec![];
     let mut value_names = vec![];
+    let mut remote_field_wrappers = vec![];
+    // Used when the struct is a `#[napi(flatten)]` field, the fields are set on the object of the parent
+    let mut field_setters = vec![];
 
     for (idx, field) in obj.fields.iter().enumerate() {
       let field_js_name = &field.js_name;
-      let field_js_name_lit = Literal::string(&format!("{}\0", field.js_name));
       let mut ty = field.ty.clone();
       remove_lifetime_in_type(&mut ty);
       let is_optional_field = if let syn::Type::Path(syn::TypePath {
@@ -543,9 +552,53 @@ impl NapiStruct {
         false
       };
 
+      if field.flatten {
+        let alias_ident = flattened_field_alias(field, &mut field_destructions);
+        conditional_setters.push(quote! {
+          napi::bindgen_prelude::ToNapiObjectFields::set_object_fields(&mut obj, #alias_ident)?;
+        });
+        field_setters.push(quote! {
+          napi::bindgen_prelude::ToNapiObjectFields::set_object_fields(obj, #alias_ident)?;
+        });
+        obj_field_getters.push(quote! {
+          let #alias_ident: #ty = napi::bindgen_prelude::FromNapiValue::from_napi_value(env, napi_val)?;
+        });
+        continue;
+      }
+
       // Determine if this field is always set or conditionally set
       let is_always_set = !is_optional_field || self.use_nullable;
 
+      let field_ident = match &field.name {
+        syn::Member::Named(ident) => format_ident!("{}_", ident),
+        syn::Member::Unnamed(i) => format_ident!("arg{}", i),
+      };
+      field_setters.push(if is_always_set {
+        quote! { obj.set_with_property_key(#field_js_name, #field_ident)?; }
+      } else {
+        quote! {
+          if #field_ident.is_some() {
+            obj.set_with_property_key(#field_js_name, #field_ident)?;
+          }
+        }
+      });
+      let remote = field.with.as_ref().map(|with| {
+        RemoteField::new(
+          with,
+          &ty,
+          &field_ident,
+          is_optional_field,
+          field.default.is_some() || self.use_nullable,
+        )
+      });
+      let getter_ty = remote.as_ref().map_or(&ty, |remote| &remote.getter_ty);
+      let default = field.default.as_ref().map(|default| {
+        remote.as_ref().map_or_else(
+          || quote! { #default },
+          |remote| remote.wrap_default(default),
+        )
+      });
+
       match &field.name {
         syn::Member::Named(ident) => {
           let alias_ident = format_ident!("{}_", ident);
@@ -574,8 +627,8 @@ impl NapiStruct {
 
             property_descriptors.push(quote! {
               napi::bindgen_prelude::sys::napi_property_descriptor {
-                utf8name: std::ffi::CStr::from_bytes_with_nul_unchecked(#field_js_name_lit.as_bytes()).as_ptr(),
-                name: std::ptr::null_mut(),
+                utf8name: std::ptr::null(),
+                name: napi::bindgen_prelude::intern_property_key(env, #field_js_name)?,
                 method: None,
                 getter: None,
                 setter: None,
@@ -590,22 +643,29 @@ impl NapiStruct {
             // Optional with use_nullable=false: conditionally set
             conditional_setters.push(quote! {
               if #alias_ident.is_some() {
-                obj.set(#field_js_name, #alias_ident)?;
+                obj.set_with_property_key(#field_js_name, #alias_ident)?;
               }
             });
           }
 
           // Getters remain the same
-          if is_optional_field && !self.use_nullable {
+          if let Some(default) = &default {
+            obj_field_getters.push(quote! {
+              let #alias_ident: #getter_ty = obj.get_with_property_key(#field_js_name).map_err(|mut err| {
+                err.reason = format!("{} on {}.{}", err.reason, #name_str, #field_js_name);
+                err
+              })?.unwrap_or_else(|| #default);
+            });
+          } else if is_optional_field && !self.use_nullable {
             obj_field_getters.push(quote! {
-              let #alias_ident: #ty = obj.get(#field_js_name).map_err(|mut err| {
+              let #alias_ident: #getter_ty = obj.get_with_property_key(#field_js_name).map_err(|mut err| {
                 err.reason = format!("{} on {}.{}", err.reason, #name_str, #field_js_name);
                 err
               })?;
             });
           } else {
             obj_field_getters.push(quote! {
-              let #alias_ident: #ty = obj.get(#field_js_name).map_err(|mut err| {
+              let #alias_ident: #getter_ty = obj.get_with_property_key(#field_js_name).map_err(|mut err| {
                 err.reason = format!("{} on {}.{}", err.reason, #name_str, #field_js_name);
                 err
               })?.ok_or_else(|| napi::bindgen_prelude::Error::new(
@@ -642,8 +702,8 @@ impl NapiStruct {
 
             property_descriptors.push(quote! {
               napi::bindgen_prelude::sys::napi_property_descriptor {
-                utf8name: std::ffi::CStr::from_bytes_with_nul_unchecked(#field_js_name_lit.as_bytes()).as_ptr(),
-                name: std::ptr::null_mut(),
+                utf8name: std::ptr::null(),
+                name: napi::bindgen_prelude::intern_property_key(env, #field_js_name)?,
                 method: None,
                 getter: None,
                 setter: None,
@@ -658,17 +718,23 @@ impl NapiStruct {
             // Optional with use_nullable=false: conditionally set
             conditional_setters.push(quote! {
               if #arg_name.is_some() {
-                obj.set(#field_js_name, #arg_name)?;
+                obj.set_with_property_key(#field_js_name, #arg_name)?;
               }
             });
           }
 
           // Getters remain the same
-          if is_optional_field && !self.use_nullable {
-            obj_field_getters.push(quote! { let #arg_name: #ty = obj.get(#field_js_name)?; });
+          if let Some(default) = &default {
+            obj_field_getters.push(quote! {
+              let #arg_name: #getter_ty = obj.get_with_property_key(#field_js_name)?.unwrap_or_else(|| #default);
+            });
+          } else if is_optional_field && !self.use_nullable {
+            obj_field_getters.push(
+              quote! { let #arg_name: #getter_ty = obj.get_with_property_key(#field_js_name)?; },
+            );
           } else {
             obj_field_getters.push(quote! {
-              let #arg_name: #ty = obj.get(#field_js_name)?.ok_or_else(|| napi::bindgen_prelude::Error::new(
+              let #arg_name: #getter_ty = obj.get_with_property_key(#field_js_name)?.ok_or_else(|| napi::bindgen_prelude::Error::new(
                 napi::bindgen_prelude::Status::InvalidArg,
                 format!("Missing field `{}`", #field_js_name),
               ))?;
@@ -676,6 +742,11 @@ impl NapiStruct {
           }
         }
       }
+
+      if let Some(remote) = remote {
+        obj_field_getters.push(remote.unwrap);
+        remote_field_wrappers.push(remote.wrap);
+      }
     }
 
     let destructed_fields = if obj.is_tuple {
@@ -693,22 +764,29 @@ impl NapiStruct {
     } else {
       quote! { #name }
     };
-    let (from_napi_value_impl, to_napi_value_impl, validate_napi_value_impl, type_name_impl) =
-      if self.has_lifetime {
-        (
-          quote! { impl <'_javascript_function_scope> napi::bindgen_prelude::FromNapiValue for #name<'_javascript_function_scope> },
-          quote! { impl <'_javascript_function_scope> napi::bindgen_prelude::ToNapiValue for #name<'_javascript_function_scope> },
-          quote! { impl <'_javascript_function_scope> napi::bindgen_prelude::ValidateNapiValue for #name<'_javascript_function_scope> },
-          quote! { impl <'_javascript_function_scope> napi::bindgen_prelude::TypeName for #name<'_javascript_function_scope> },
-        )
-      } else {
-        (
-          quote! { impl napi::bindgen_prelude::FromNapiValue for #name },
-          quote! { impl napi::bindgen_prelude::ToNapiValue for #name },
-          quote! { impl napi::bindgen_prelude::ValidateNapiValue for #name },
-          quote! { impl napi::bindgen_prelude::TypeName for #name },
-        )
-      };
+    let (
+      from_napi_value_impl,
+      to_napi_value_impl,
+      to_napi_object_fields_impl,
+      validate_napi_value_impl,
+      type_name_impl,
+    ) = if self.has_lifetime {
+      (
+        quote! { impl <'_javascript_function_scope> napi::bindgen_prelude::FromNapiValue for #name<'_javascript_function_scope> },
+        quote! { impl <'_javascript_function_scope> napi::bindgen_prelude::ToNapiValue for #name<'_javascript_function_scope> },
+        quote! { impl <'_javascript_function_scope> napi::bindgen_prelude::ToNapiObjectFields for #name<'_javascript_function_scope> },
+        quote! { impl <'_javascript_function_scope> napi::bindgen_prelude::ValidateNapiValue for #name<'_javascript_function_scope> },
+        quote! { impl <'_javascript_function_scope> napi::bindgen_prelude::TypeName for #name<'_javascript_function_scope> },
+      )
+    } else {
+      (
+        quote! { impl napi::bindgen_prelude::FromNapiValue for #name },
+        quote! { impl napi::bindgen_prelude::ToNapiValue for #name },
+        quote! { impl napi::bindgen_prelude::ToNapiObjectFields for #name },
+        quote! { impl napi::bindgen_prelude::ValidateNapiValue for #name },
+        quote! { impl napi::bindgen_prelude::TypeName for #name },
+      )
+    };
 
     // Generate object creation code
     let object_creation = if conditional_setters.is_empty() {
@@ -751,9 +829,24 @@ impl NapiStruct {
         #to_napi_value_impl {
           unsafe fn to_napi_value(env: napi::bindgen_prelude::sys::napi_env, val: #name_with_lifetime) -> napi::bindgen_prelude::Result<napi::bindgen_prelude::sys::napi_value> {
             let #destructed_fields = val;
+            #(#remote_field_wrappers)*
             #object_creation
           }
         }
+
+        #[automatically_derived]
+        #to_napi_object_fields_impl {
+          #[allow(unused_variables)]
+          fn set_object_fields(
+            obj: &mut napi::bindgen_prelude::Object,
+            val: #name_with_lifetime,
+          ) -> napi::bindgen_prelude::Result<()> {
+            let #destructed_fields = val;
+            #(#remote_field_wrappers)*
+            #(#field_setters)*
+            Ok(())
+          }
+        }
       }
     } else {
       quote! {}
@@ -765,9 +858,33 @@ impl NapiStruct {
       } else {
         quote! { #name }
       };
-      quote! {
-        #[automatically_derived]
-        #from_napi_value_impl {
+      let from_napi_value_fns = if obj.collect_errors {
+        let field_getters = self.gen_collect_errors_field_getters(obj, &destructed_fields);
+        quote! {
+          unsafe fn from_napi_value(
+            env: napi::bindgen_prelude::sys::napi_env,
+            napi_val: napi::bindgen_prelude::sys::napi_value
+          ) -> napi::bindgen_prelude::Result<#return_type> {
+            napi::bindgen_prelude::collect_validation_errors(env, #name_str, |errors| {
+              <#return_type as napi::bindgen_prelude::FromNapiValue>::from_napi_value_collecting(env, napi_val, errors)
+            })
+          }
+
+          unsafe fn from_napi_value_collecting(
+            env: napi::bindgen_prelude::sys::napi_env,
+            napi_val: napi::bindgen_prelude::sys::napi_value,
+            errors: &mut napi::bindgen_prelude::ValidationErrors,
+          ) -> napi::bindgen_prelude::Result<#return_type> {
+            #[allow(unused_variables)]
+            let env_wrapper = napi::bindgen_prelude::Env::from(env);
+            #[allow(unused_mut)]
+            let mut obj = napi::bindgen_prelude::Object::from_napi_value(env, napi_val)?;
+
+            #field_getters
+          }
+        }
+      } else {
+        quote! {
           unsafe fn from_napi_value(
             env: napi::bindgen_prelude::sys::napi_env,
             napi_val: napi::bindgen_prelude::sys::napi_value
@@ -784,6 +901,12 @@ impl NapiStruct {
             Ok(val)
           }
         }
+      };
+      quote! {
+        #[automatically_derived]
+        #from_napi_value_impl {
+          #from_napi_value_fns
+        }
 
         #[automatically_derived]
         #validate_napi_value_impl {}
@@ -792,6 +915,12 @@ impl NapiStruct {
       quote! {}
     };
 
+    let remote_napi_value = obj
+      .remote
+      .as_ref()
+      .map(|remote| self.gen_remote_napi_value_impl(obj, remote))
+      .unwrap_or_default();
+
     quote! {
       #[automatically_derived]
       #type_name_impl {
@@ -807,6 +936,147 @@ impl NapiStruct {
       #to_napi_value
 
       #from_napi_value
+
+      #remote_napi_value
+    }
+  }
+
+  /// Convert every field with its path recorded, the errors of all the fields are thrown together
+  fn gen_collect_errors_field_getters(
+    &self,
+    obj: &NapiObject,
+    destructed_fields: &TokenStream,
+  ) -> TokenStream {
+    let mut field_results = vec![];
+    let mut field_values = vec![];
+    for field in obj.fields.iter() {
+      let field_js_name = &field.js_name;
+      let mut ty = field.ty.clone();
+      remove_lifetime_in_type(&mut ty);
+      let ident = match &field.name {
+        syn::Member::Named(ident) => format_ident!("{}_", ident),
+        syn::Member::Unnamed(i) => format_ident!("arg{}", i),
+      };
+      let is_optional_field = matches!(
+        &ty,
+        syn::Type::Path(syn::TypePath { path, .. })
+          if path.segments.last().is_some_and(|segment| segment.ident == "Option")
+      );
+      let remote = field.with.as_ref().map(|with| {
+        RemoteField::new(
+          with,
+          &ty,
+          &ident,
+          is_optional_field,
+          field.default.is_some() || self.use_nullable,
+        )
+      });
+      let getter_ty = remote.as_ref().map_or(&ty, |remote| &remote.getter_ty);
+      let default = field.default.as_ref().map(|default| {
+        remote.as_ref().map_or_else(
+          || quote! { #default },
+          |remote| remote.wrap_default(default),
+        )
+      });
+      if field.flatten {
+        field_results.push(quote! {
+          let #ident = errors.validate_flattened(
+            |errors| -> napi::bindgen_prelude::Result<#ty> {
+              napi::bindgen_prelude::FromNapiValue::from_napi_value_collecting(env, napi_val, errors)
+            },
+          );
+        });
+        field_values.push(quote! { let #ident = #ident?; });
+        continue;
+      }
+      let value = if let Some(default) = &default {
+        quote! { Ok(obj.get_with_property_key_collecting(#field_js_name, errors)?.unwrap_or_else(|| #default)) }
+      } else if is_optional_field && !self.use_nullable {
+        quote! { obj.get_with_property_key_collecting(#field_js_name, errors) }
+      } else {
+        quote! {
+          obj.get_with_property_key_collecting(#field_js_name, errors)?.ok_or_else(|| napi::bindgen_prelude::Error::new(
+            napi::bindgen_prelude::Status::InvalidArg,
+            format!("Missing field `{}`", #field_js_name),
+          ))
+        }
+      };
+      field_results.push(quote! {
+        let #ident = errors.validate_path(
+          #field_js_name,
+          |errors| -> napi::bindgen_prelude::Result<#getter_ty> { #value },
+        );
+      });
+      field_values.push(quote! { let #ident = #ident?; });
+      if let Some(remote) = remote {
+        field_values.push(remote.unwrap);
+      }
+    }
+
+    quote! {
+      #(#field_results)*
+      #(#field_values)*
+
+      let val = #destructed_fields;
+
+      Ok(val)
+    }
+  }
+
+  /// Convert the remote type through the mirror struct, fields with `#[napi(getter = method)]` are read by calling the method,
+  /// and in that case the remote type is built back with its `From<Mirror>` implementation.
+  fn gen_remote_napi_value_impl(&self, obj: &NapiObject, remote: &syn::Path) -> TokenStream {
+    let name = &self.name;
+    let mut getter_values = vec![];
+    let mut moved_values = vec![];
+    let mut mirror_fields = vec![];
+    let mut remote_fields = vec![];
+    for (idx, field) in obj.fields.iter().enumerate() {
+      let member = &field.name;
+      let value_var = format_ident!("__remote_value_{}", idx);
+      if let Some(getter) = &field.remote_getter {
+        getter_values.push(quote! { let #value_var = remote.#getter(); });
+      } else {
+        moved_values.push(quote! { let #value_var = remote.#member; });
+      }
+      mirror_fields.push(quote! { #member: #value_var });
+      remote_fields.push(quote! { #member: mirror.#member });
+    }
+    let into_remote = if obj.fields.iter().any(|f| f.remote_getter.is_some()) {
+      quote! { <#remote as From<#name>>::from(mirror) }
+    } else {
+      quote! { #remote { #(#remote_fields),* } }
+    };
+
+    quote! {
+      #[automatically_derived]
+      impl napi::bindgen_prelude::RemoteNapiValue<#name> for #remote {
+        unsafe fn to_napi_value(
+          env: napi::bindgen_prelude::sys::napi_env,
+          remote: #remote,
+        ) -> napi::bindgen_prelude::Result<napi::bindgen_prelude::sys::napi_value> {
+          // values read by getters borrow `remote`, read them before moving the fields out
+          #(#getter_values)*
+          #(#moved_values)*
+          let mirror = #name { #(#mirror_fields),* };
+          <#name as napi::bindgen_prelude::ToNapiValue>::to_napi_value(env, mirror)
+        }
+
+        unsafe fn from_napi_value(
+          env: napi::bindgen_prelude::sys::napi_env,
+          napi_val: napi::bindgen_prelude::sys::napi_value,
+        ) -> napi::bindgen_prelude::Result<#remote> {
+          let mirror = <#name as napi::bindgen_prelude::FromNapiValue>::from_napi_value(env, napi_val)?;
+          Ok(#into_remote)
+        }
+
+        unsafe fn validate(
+          env: napi::bindgen_prelude::sys::napi_env,
+          napi_val: napi::bindgen_prelude::sys::napi_value,
+        ) -> napi::bindgen_prelude::Result<napi::bindgen_prelude::sys::napi_value> {
+          <#name as napi::bindgen_prelude::ValidateNapiValue>::validate(env, napi_val)
+        }
+      }
     }
   }
 
@@ -972,13 +1242,24 @@ impl NapiStruct {
       props.push(prop);
     }
     let js_mod_ident = js_mod_to_token_stream(self.js_mod.as_ref());
+    let register_extends = class.extends.as_ref().map(|extends| {
+      let base = &extends.base;
+      let field = &extends.field;
+      quote! {
+        napi::__private::register_class_extends::<#name, #base>(#js_name, |wrapped| unsafe {
+          std::ptr::addr_of_mut!((*wrapped.cast::<#name>()).#field).cast()
+        });
+      }
+    });
+    let native_register_cfg = native_register_cfg();
     quote! {
       #[allow(non_snake_case)]
       #[allow(clippy::all)]
-      #[cfg(all(not(test), not(target_family = "wasm")))]
+      #native_register_cfg
       #[napi::ctor::ctor(crate_path=napi::ctor)]
       fn #struct_register_name() {
         napi::__private::register_class(std::any::TypeId::of::<#name>(), #js_mod_ident, #js_name, vec![#(#props),*]);
+        #register_extends
       }
 
       #[allow(non_snake_case)]
@@ -987,6 +1268,7 @@ impl NapiStruct {
       #[no_mangle]
       extern "C" fn #struct_register_name() {
         napi::__private::register_class(std::any::TypeId::of::<#name>(), #js_mod_ident, #js_name, vec![#(#props),*]);
+        #register_extends
       }
     }
   }
@@ -998,7 +1280,6 @@ impl NapiStruct {
     let name = &self.name;
     let name_str = self.name.to_string();
     let discriminant = structured_enum.discriminant.as_str();
-    let discriminant_null_terminated = format!("{}\0", discriminant);
 
     let mut variant_arm_setters = vec![];
     let mut variant_arm_getters = vec![];
@@ -1025,8 +1306,8 @@ impl NapiStruct {
       });
       property_descriptors.push(quote! {
         napi::bindgen_prelude::sys::napi_property_descriptor {
-          utf8name: std::ffi::CStr::from_bytes_with_nul_unchecked(#discriminant_null_terminated.as_bytes()).as_ptr(),
-          name: std::ptr::null_mut(),
+          utf8name: std::ptr::null(),
+          name: napi::bindgen_prelude::intern_property_key(env, #discriminant)?,
           method: None,
           getter: None,
           setter: None,
@@ -1040,7 +1321,6 @@ impl NapiStruct {
 
       for (idx, field) in variant.fields.iter().enumerate() {
         let field_js_name = &field.js_name;
-        let field_js_name_lit = Literal::string(&format!("{}\0", field.js_name));
         let mut ty = field.ty.clone();
         remove_lifetime_in_type(&mut ty);
         let is_optional_field = if let syn::Type::Path(syn::TypePath {
@@ -1057,6 +1337,17 @@ impl NapiStruct {
           false
         };
 
+        if field.flatten {
+          let alias_ident = flattened_field_alias(field, &mut field_destructions);
+          conditional_setters.push(quote! {
+            napi::bindgen_prelude::ToNapiObjectFields::set_object_fields(&mut obj, #alias_ident)?;
+          });
+          obj_field_getters.push(quote! {
+            let #alias_ident: #ty = napi::bindgen_prelude::FromNapiValue::from_napi_value(env, napi_val)?;
+          });
+          continue;
+        }
+
         // Determine if this field is always set or conditionally set
         let is_always_set = !is_optional_field || self.use_nullable;
 
@@ -1087,8 +1378,8 @@ impl NapiStruct {
 
               property_descriptors.push(quote! {
                 napi::bindgen_prelude::sys::napi_property_descriptor {
-                  utf8name: std::ffi::CStr::from_bytes_with_nul_unchecked(#field_js_name_lit.as_bytes()).as_ptr(),
-                  name: std::ptr::null_mut(),
+                  utf8name: std::ptr::null(),
+                  name: napi::bindgen_prelude::intern_property_key(env, #field_js_name)?,
                   method: None,
                   getter: None,
                   setter: None,
@@ -1103,7 +1394,7 @@ impl NapiStruct {
               // Optional with use_nullable=false: conditionally set
               conditional_setters.push(quote! {
                 if #alias_ident.is_some() {
-                  obj.set(#field_js_name, #alias_ident)?;
+                  obj.set_with_property_key(#field_js_name, #alias_ident)?;
                 }
               });
             }
@@ -1111,14 +1402,14 @@ impl NapiStruct {
             // Getters remain the same
             if is_optional_field && !self.use_nullable {
               obj_field_getters.push(quote! {
-                let #alias_ident: #ty = obj.get(#field_js_name).map_err(|mut err| {
+                let #alias_ident: #ty = obj.get_with_property_key(#field_js_name).map_err(|mut err| {
                   err.reason = format!("{} on {}.{}", err.reason, #name_str, #field_js_name);
                   err
                 })?;
               });
             } else {
               obj_field_getters.push(quote! {
-                let #alias_ident: #ty = obj.get(#field_js_name).map_err(|mut err| {
+                let #alias_ident: #ty = obj.get_with_property_key(#field_js_name).map_err(|mut err| {
                   err.reason = format!("{} on {}.{}", err.reason, #name_str, #field_js_name);
                   err
                 })?.ok_or_else(|| napi::bindgen_prelude::Error::new(
@@ -1154,8 +1445,8 @@ impl NapiStruct {
 
               property_descriptors.push(quote! {
                 napi::bindgen_prelude::sys::napi_property_descriptor {
-                  utf8name: std::ffi::CStr::from_bytes_with_nul_unchecked(#field_js_name_lit.as_bytes()).as_ptr(),
-                  name: std::ptr::null_mut(),
+                  utf8name: std::ptr::null(),
+                  name: napi::bindgen_prelude::intern_property_key(env, #field_js_name)?,
                   method: None,
                   getter: None,
                   setter: None,
@@ -1170,17 +1461,18 @@ impl NapiStruct {
               // Optional with use_nullable=false: conditionally set
               conditional_setters.push(quote! {
                 if #arg_name.is_some() {
-                  obj.set(#field_js_name, #arg_name)?;
+                  obj.set_with_property_key(#field_js_name, #arg_name)?;
                 }
               });
             }
 
             // Getters remain the same
             if is_optional_field && !self.use_nullable {
-              obj_field_getters.push(quote! { let #arg_name: #ty = obj.get(#field_js_name)?; });
+              obj_field_getters
+                .push(quote! { let #arg_name: #ty = obj.get_with_property_key(#field_js_name)?; });
             } else {
               obj_field_getters.push(quote! {
-                let #arg_name: #ty = obj.get(#field_js_name)?.ok_or_else(|| napi::bindgen_prelude::Error::new(
+                let #arg_name: #ty = obj.get_with_property_key(#field_js_name)?.ok_or_else(|| napi::bindgen_prelude::Error::new(
                   napi::bindgen_prelude::Status::InvalidArg,
                   format!("Missing field `{}`", #field_js_name),
                 ))?;
@@ -1269,7 +1561,7 @@ impl NapiStruct {
             let env_wrapper = napi::bindgen_prelude::Env::from(env);
             #[allow(unused_mut)]
             let mut obj = napi::bindgen_prelude::Object::from_napi_value(env, napi_val)?;
-            let type_: String = obj.get(#discriminant).map_err(|mut err| {
+            let type_: String = obj.get_with_property_key(#discriminant).map_err(|mut err| {
               err.reason = format!("{} on {}.{}", err.reason, #name_str, #discriminant);
               err
             })?.ok_or_else(|| napi::bindgen_prelude::Error::new(
@@ -1589,11 +1881,21 @@ impl NapiImpl {
         attribute |= super::PROPERTY_ATTRIBUTE_CONFIGURABLE;
       }
 
-      let prop = props.entry(&item.js_name).or_insert_with(|| {
-        quote! {
-          napi::bindgen_prelude::Property::new().with_utf8_name(#js_name).unwrap().with_property_attributes(napi::bindgen_prelude::PropertyAttributes::from_bits(#attribute).unwrap())
-        }
-      });
+      let prop = if let Some(symbol) = &item.well_known_symbol {
+        props
+          .entry(format!("[Symbol.{symbol}]"))
+          .or_insert_with(|| {
+            quote! {
+              napi::bindgen_prelude::Property::new().with_well_known_symbol(#symbol).with_property_attributes(napi::bindgen_prelude::PropertyAttributes::from_bits(#attribute).unwrap())
+            }
+          })
+      } else {
+        props.entry(item.js_name.clone()).or_insert_with(|| {
+          quote! {
+            napi::bindgen_prelude::Property::new().with_utf8_name(#js_name).unwrap().with_property_attributes(napi::bindgen_prelude::PropertyAttributes::from_bits(#attribute).unwrap())
+          }
+        })
+      };
 
       let appendix = match item.kind {
         FnKind::Constructor => quote! { .with_ctor(#intermediate_name) },
@@ -1616,6 +1918,7 @@ impl NapiImpl {
     let props = props.into_iter().map(|(_, prop)| prop);
     let props_wasm = props.clone();
     let js_mod_ident = js_mod_to_token_stream(self.js_mod.as_ref());
+    let native_register_cfg = native_register_cfg();
     Ok(quote! {
       #[allow(non_snake_case)]
       #[allow(clippy::all)]
@@ -1623,7 +1926,7 @@ impl NapiImpl {
         use super::*;
         #(#methods)*
 
-        #[cfg(all(not(test), not(target_family = "wasm")))]
+        #native_register_cfg
         #[napi::ctor::ctor(crate_path=napi::ctor)]
         fn #register_name() {
           napi::__private::register_class(std::any::TypeId::of::<#name>(), #js_mod_ident, #js_name, vec![#(#props),*]);
@@ -1664,3 +1967,90 @@ fn remove_lifetime_in_type(ty: &mut syn::Type) {
     });
   }
 }
+
+/// The conversions of a `#[napi(with = Def)]` field of `#[napi(object)]`,
+/// its value is converted as `RemoteValue<_, Def>` and bound to the field type again
+struct RemoteField {
+  /// the type the value is read as from the JavaScript object
+  getter_ty: syn::Type,
+  /// wrap the value of the field before converting the object into JavaScript
+  wrap: TokenStream,
+  /// unwrap the value read from the JavaScript object
+  unwrap: TokenStream,
+}
+
+impl RemoteField {
+  /// `is_optional` fields are `Option<T>` whose absent properties are `None`,
+  /// unless `read_whole` when the `Option` is read as a whole with `#[napi(default)]` or `use_nullable`
+  fn new(
+    with: &syn::Path,
+    ty: &syn::Type,
+    ident: &Ident,
+    is_optional: bool,
+    read_whole: bool,
+  ) -> Self {
+    let inner_ty = match ty {
+      syn::Type::Path(syn::TypePath { path, .. }) if is_optional => path
+        .segments
+        .last()
+        .and_then(|segment| match &segment.arguments {
+          syn::PathArguments::AngleBracketed(args) => args.args.first(),
+          _ => None,
+        })
+        .and_then(|arg| match arg {
+          syn::GenericArgument::Type(ty) => Some(ty.clone()),
+          _ => None,
+        }),
+      _ => None,
+    };
+    match inner_ty {
+      // the `Option` is kept, the value inside of it is converted through `Def`
+      Some(inner_ty) => Self {
+        getter_ty: if read_whole {
+          syn::parse_quote! { napi::bindgen_prelude::RemoteValue<#ty, #with> }
+        } else {
+          syn::parse_quote! { Option<napi::bindgen_prelude::RemoteValue<#inner_ty, #with>> }
+        },
+        wrap: quote! {
+          let #ident = #ident.map(napi::bindgen_prelude::RemoteValue::<#inner_ty, #with>::new);
+        },
+        unwrap: if read_whole {
+          quote! { let #ident: #ty = #ident.into_inner(); }
+        } else {
+          quote! { let #ident: #ty = #ident.map(napi::bindgen_prelude::RemoteValue::into_inner); }
+        },
+      },
+      None => Self {
+        getter_ty: syn::parse_quote! { napi::bindgen_prelude::RemoteValue<#ty, #with> },
+        wrap: quote! {
+          let #ident = napi::bindgen_prelude::RemoteValue::<#ty, #with>::new(#ident);
+        },
+        unwrap: quote! { let #ident: #ty = #ident.into_inner(); },
+      },
+    }
+  }
+
+  fn wrap_default(&self, default: &syn::Expr) -> TokenStream {
+    let getter_ty = &self.getter_ty;
+    quote! { <#getter_ty>::new(#default) }
+  }
+}
+
+/// Destruct the `#[napi(flatten)]` field and return the identifier its value is bound to
+fn flattened_field_alias(
+  field: &NapiStructField,
+  field_destructions: &mut Vec<TokenStream>,
+) -> Ident {
+  match &field.name {
+    syn::Member::Named(ident) => {
+      let alias_ident = format_ident!("{}_", ident);
+      field_destructions.push(quote! { #ident: #alias_ident });
+      alias_ident
+    }
+    syn::Member::Unnamed(i) => {
+      let arg_name = format_ident!("arg{}", i);
+      field_destructions.push(quote! { #arg_name });
+      arg_name
+    }
+  }
+}
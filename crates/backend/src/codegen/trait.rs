@@ -0,0 +1,172 @@
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::ToTokens;
+
+use crate::{BindgenResult, NapiTrait, TryToTokens};
+
+impl TryToTokens for NapiTrait {
+  fn try_to_tokens(&self, tokens: &mut TokenStream) -> BindgenResult<()> {
+    let name = &self.name;
+    let vis = &self.vis;
+    let js_name = &self.js_name;
+    let proxy = Ident::new(&format!("{name}Proxy"), Span::call_site());
+    let proxy_doc = format!(
+      " A JavaScript object implementing the `{js_name}` interface, its methods are forwarded to the object."
+    );
+
+    let methods = self.methods.iter().map(|method| {
+      let method_name = &method.name;
+      let method_js_name = &method.js_name;
+      let receiver = &method.receiver;
+      let ret = &method.ret;
+      let arg_names = method.args.iter().map(|(name, _)| name).collect::<Vec<_>>();
+      let arg_types = method.args.iter().map(|(_, ty)| ty);
+      quote! {
+        fn #method_name(#receiver, #(#arg_names: #arg_types),*) -> napi::bindgen_prelude::Result<#ret> {
+          self.0.call_method(
+            #method_js_name,
+            napi::bindgen_prelude::FnArgs::from((#(#arg_names,)*)),
+          )
+        }
+      }
+    });
+
+    if self.threadsafe {
+      self.threadsafe_proxy_to_tokens(tokens);
+    }
+
+    (quote! {
+      #[doc = #proxy_doc]
+      #vis struct #proxy(napi::bindgen_prelude::ObjectProxy);
+
+      impl #proxy {
+        /// The JavaScript object behind this proxy
+        pub fn as_object_proxy(&self) -> &napi::bindgen_prelude::ObjectProxy {
+          &self.0
+        }
+      }
+
+      impl #name for #proxy {
+        #(#methods)*
+      }
+
+      impl napi::bindgen_prelude::TypeName for #proxy {
+        fn type_name() -> &'static str {
+          #js_name
+        }
+
+        fn value_type() -> napi::ValueType {
+          napi::ValueType::Object
+        }
+      }
+
+      impl napi::bindgen_prelude::ValidateNapiValue for #proxy {}
+
+      impl napi::bindgen_prelude::FromNapiValue for #proxy {
+        unsafe fn from_napi_value(
+          env: napi::bindgen_prelude::sys::napi_env,
+          napi_val: napi::bindgen_prelude::sys::napi_value,
+        ) -> napi::bindgen_prelude::Result<Self> {
+          Ok(#proxy(unsafe {
+            <napi::bindgen_prelude::ObjectProxy as napi::bindgen_prelude::FromNapiValue>::from_napi_value(env, napi_val)
+          }?))
+        }
+      }
+
+      impl napi::bindgen_prelude::TypeName for Box<dyn #name> {
+        fn type_name() -> &'static str {
+          #js_name
+        }
+
+        fn value_type() -> napi::ValueType {
+          napi::ValueType::Object
+        }
+      }
+
+      impl napi::bindgen_prelude::ValidateNapiValue for Box<dyn #name> {}
+
+      impl napi::bindgen_prelude::FromNapiValue for Box<dyn #name> {
+        unsafe fn from_napi_value(
+          env: napi::bindgen_prelude::sys::napi_env,
+          napi_val: napi::bindgen_prelude::sys::napi_value,
+        ) -> napi::bindgen_prelude::Result<Self> {
+          Ok(Box::new(unsafe {
+            <#proxy as napi::bindgen_prelude::FromNapiValue>::from_napi_value(env, napi_val)
+          }?))
+        }
+      }
+    })
+    .to_tokens(tokens);
+
+    Ok(())
+  }
+}
+
+impl NapiTrait {
+  /// `{Trait}ThreadsafeProxy`, its methods queue the calls of the JavaScript methods through one `ThreadsafeObject`
+  fn threadsafe_proxy_to_tokens(&self, tokens: &mut TokenStream) {
+    let vis = &self.vis;
+    let js_name = &self.js_name;
+    let proxy = Ident::new(&format!("{}ThreadsafeProxy", self.name), Span::call_site());
+    let proxy_doc = format!(
+      " A `Send + Sync` handle to a JavaScript object implementing the `{js_name}` interface, its methods can be called from any thread."
+    );
+
+    let methods = self.methods.iter().map(|method| {
+      let method_name = &method.name;
+      let method_js_name = &method.js_name;
+      let ret = &method.ret;
+      let arg_names = method.args.iter().map(|(name, _)| name).collect::<Vec<_>>();
+      let arg_types = method.args.iter().map(|(_, ty)| ty);
+      quote! {
+        pub fn #method_name(
+          &self,
+          #(#arg_names: #arg_types),*
+        ) -> impl std::future::Future<Output = napi::bindgen_prelude::Result<#ret>> + Send + 'static {
+          self.0.call_method(
+            #method_js_name,
+            napi::bindgen_prelude::FnArgs::from((#(#arg_names,)*)),
+          )
+        }
+      }
+    });
+
+    (quote! {
+      #[doc = #proxy_doc]
+      #[derive(Clone)]
+      #vis struct #proxy(napi::threadsafe_function::ThreadsafeObject);
+
+      impl #proxy {
+        /// The `ThreadsafeObject` behind this proxy
+        pub fn as_threadsafe_object(&self) -> &napi::threadsafe_function::ThreadsafeObject {
+          &self.0
+        }
+
+        #(#methods)*
+      }
+
+      impl napi::bindgen_prelude::TypeName for #proxy {
+        fn type_name() -> &'static str {
+          #js_name
+        }
+
+        fn value_type() -> napi::ValueType {
+          napi::ValueType::Object
+        }
+      }
+
+      impl napi::bindgen_prelude::ValidateNapiValue for #proxy {}
+
+      impl napi::bindgen_prelude::FromNapiValue for #proxy {
+        unsafe fn from_napi_value(
+          env: napi::bindgen_prelude::sys::napi_env,
+          napi_val: napi::bindgen_prelude::sys::napi_value,
+        ) -> napi::bindgen_prelude::Result<Self> {
+          Ok(#proxy(unsafe {
+            <napi::threadsafe_function::ThreadsafeObject as napi::bindgen_prelude::FromNapiValue>::from_napi_value(env, napi_val)
+          }?))
+        }
+      }
+    })
+    .to_tokens(tokens);
+  }
+}
@@ -29,17 +29,19 @@ pub struct Napi {
 }
 
 macro_rules! napi_ast_impl {
-  ( $( ($v:ident, $ast:ident), )* ) => {
+  ( $( ($v:ident, $ast:ident), )* ; $( ($unregistered_v:ident, $unregistered_ast:ident), )* ) => {
     #[derive(Debug)]
     #[allow(clippy::large_enum_variant)]
     pub enum NapiItem {
-      $($v($ast)),*
+      $($v($ast),)*
+      $($unregistered_v($unregistered_ast),)*
     }
 
     impl TryToTokens for Napi {
       fn try_to_tokens(&self, tokens: &mut TokenStream) -> BindgenResult<()> {
         match self.item {
-          $( NapiItem::$v(ref ast) => ast.try_to_tokens(tokens) ),*
+          $( NapiItem::$v(ref ast) => ast.try_to_tokens(tokens), )*
+          $( NapiItem::$unregistered_v(ref ast) => ast.try_to_tokens(tokens), )*
         }
       }
     }
@@ -48,15 +50,19 @@ macro_rules! napi_ast_impl {
 		impl ToTypeDef for Napi {
 			fn to_type_def(&self) -> Option<TypeDef> {
 				match self.item {
-          $( NapiItem::$v(ref ast) => ast.to_type_def() ),*
+          $( NapiItem::$v(ref ast) => ast.to_type_def(), )*
+          $( NapiItem::$unregistered_v(ref ast) => ast.to_type_def(), )*
         }
 			}
 		}
 
     impl Napi {
-      pub fn register_name(&self) -> String {
+      /// The name of the module registration function, `None` for items that aren't registered
+      /// into the module like `#[napi] trait`
+      pub fn register_name(&self) -> Option<String> {
         match self.item {
-          $( NapiItem::$v(ref ast) => ast.register_name.to_string() ),*
+          $( NapiItem::$v(ref ast) => Some(ast.register_name.to_string()), )*
+          $( NapiItem::$unregistered_v(_) => None, )*
         }
       }
     }
@@ -70,6 +76,22 @@ napi_ast_impl! {
  (Enum, NapiEnum),
  (Const, NapiConst),
  (Type, NapiType),
+ (Error, NapiError),
+ ;
+ (Trait, NapiTrait),
+}
+
+#[cfg(feature = "type-def")]
+impl Napi {
+  /// All the type definitions of the item, a `#[napi(error)]` enum has a class for each variant
+  /// and a `#[napi(flags)]` enum has a branded number type
+  pub fn to_type_defs(&self) -> Vec<TypeDef> {
+    match self.item {
+      NapiItem::Error(ref error) => error.to_type_defs(),
+      NapiItem::Enum(ref napi_enum) => napi_enum.to_type_defs(),
+      _ => self.to_type_def().into_iter().collect(),
+    }
+  }
 }
 
 pub(crate) static PRIMITIVE_TYPES: &[(&str, (&str, bool, bool))] = &[
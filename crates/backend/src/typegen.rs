@@ -7,18 +7,25 @@ use std::{
 
 mod r#const;
 mod r#enum;
+mod error;
 mod r#fn;
+mod json_schema;
 pub(crate) mod r#struct;
+mod r#trait;
 mod r#type;
 
 use syn::{PathSegment, Type, TypePath, TypeSlice};
 
+pub use json_schema::{ty_to_json_schema, Json, JsonSchema, ToJsonSchema};
+
 #[derive(Default, Debug)]
 pub struct TypeDef {
   pub kind: String,
   pub name: String,
   pub original_name: Option<String>,
   pub def: String,
+  pub extends: Option<String>,
+  pub implements: Option<String>,
   pub js_mod: Option<String>,
   pub js_doc: JSDoc,
 }
@@ -236,15 +243,27 @@ impl Display for TypeDef {
     } else {
       "".to_string()
     };
+    let extends = if let Some(extends) = &self.extends {
+      format!(", \"extends\": \"{}\"", escape_json(extends))
+    } else {
+      "".to_string()
+    };
+    let implements = if let Some(implements) = &self.implements {
+      format!(", \"implements\": \"{}\"", escape_json(implements))
+    } else {
+      "".to_string()
+    };
 
     write!(
       f,
-      r#"{{"kind": "{}", "name": "{}", "js_doc": "{}", "def": "{}"{}{}}}"#,
+      r#"{{"kind": "{}", "name": "{}", "js_doc": "{}", "def": "{}"{}{}{}{}}}"#,
       self.kind,
       self.name,
       escape_json(&self.js_doc.to_string()),
       escape_json(&self.def),
       original_name,
+      extends,
+      implements,
       js_mod,
     )
   }
@@ -263,6 +282,7 @@ static KNOWN_TYPES: LazyLock<HashMap<&'static str, (&'static str, bool, bool)>>
       ("JsObject", ("object", false, false)),
       ("Object", ("object", false, false)),
       ("ObjectRef", ("object", false, false)),
+      ("ThreadsafeObject", ("object", false, false)),
       ("Array", ("unknown[]", false, false)),
       ("Value", ("any", false, false)),
       ("ClassInstance", ("{}", false, false)),
@@ -315,6 +335,10 @@ static KNOWN_TYPES: LazyLock<HashMap<&'static str, (&'static str, bool, bool)>>
       ("NaiveDateTime", ("Date", false, false)),
       ("Date", ("Date", false, false)),
       ("JsDate", ("Date", false, false)),
+      ("SystemTime", ("Date", false, false)),
+      ("Instant", ("Date", false, false)),
+      ("Duration", ("number", false, false)),
+      ("DurationNanos", ("bigint", false, false)),
     ]);
 
     // Buffer types
@@ -331,6 +355,7 @@ static KNOWN_TYPES: LazyLock<HashMap<&'static str, (&'static str, bool, bool)>>
       ("JsError", ("Error", false, false)),
       ("JsTypeError", ("TypeError", false, false)),
       ("JsRangeError", ("RangeError", false, false)),
+      ("JsAggregateError", ("AggregateError", false, false)),
     ]);
 
     // Function types (note: these use function type notation)
@@ -516,6 +541,19 @@ fn handle_async_task_type(args: &[(String, bool)]) -> Option<(String, bool)> {
   })
 }
 
+/// Handles conversion of AsyncProgressTask<T> to Promise<T> with the `progress` async iterable
+fn handle_async_progress_task_type(args: &[(String, bool)]) -> Option<(String, bool)> {
+  let (promise, _) = handle_async_task_type(args)?;
+  let (task, _) = args.first()?;
+  let progress = r#struct::TASK_PROGRESS_STRUCTS
+    .with(|t| t.borrow().get(task).cloned())
+    .unwrap_or_else(|| "unknown".to_owned());
+  Some((
+    format!("{promise} & {{ progress: AsyncIterable<{progress}> }}"),
+    false,
+  ))
+}
+
 /// Handles conversion of Reference<T> and WeakReference<T>
 fn handle_reference_type(args: &[(String, bool)], rust_ty: String) -> Option<(String, bool)> {
   r#struct::TASK_STRUCTS.with(|t| {
@@ -544,6 +582,23 @@ fn handle_async_block_type(args: &[(String, bool)], rust_ty: String) -> Option<(
   }
 }
 
+/// Handles conversion of JsMap<HashMap<K, V>> to Map<K, V>
+fn handle_js_map_type(arguments: &syn::PathArguments) -> Option<(String, bool)> {
+  if let syn::PathArguments::AngleBracketed(arguments) = arguments {
+    if let Some(syn::GenericArgument::Type(Type::Path(syn::TypePath { path, .. }))) =
+      arguments.args.first()
+    {
+      if let Some(syn::PathSegment { ident, arguments }) = path.segments.last() {
+        let args = process_generic_arguments(arguments, &ident.to_string());
+        if let [(key, _), (value, _), ..] = args.as_slice() {
+          return Some((format!("Map<{key}, {value}>"), false));
+        }
+      }
+    }
+  }
+  Some(("Map<unknown, unknown>".to_owned(), false))
+}
+
 /// Handles conversion of ThreadsafeFunction to TypeScript function type
 fn handle_threadsafe_function_type(args: &[(String, bool)]) -> Option<(String, bool)> {
   let handled_tsfn = match args.get(4) {
@@ -709,6 +764,54 @@ fn process_generic_arguments(arguments: &syn::PathArguments, rust_ty: &str) -> V
   }
 }
 
+/// The type a `#[napi(with = Def)]` value is described as,
+/// the type from another crate is replaced with its mirror `Def`, also inside of `Option` and `Vec`
+pub(crate) fn with_remote_mirror(ty: &Type, with: &syn::Path) -> Type {
+  if let Type::Path(TypePath { qself: None, path }) = ty {
+    if let Some(PathSegment {
+      ident,
+      arguments: syn::PathArguments::AngleBracketed(args),
+    }) = path.segments.last()
+    {
+      if let (true, Some(syn::GenericArgument::Type(inner))) = (
+        (ident == "Option" || ident == "Vec") && args.args.len() == 1,
+        args.args.first(),
+      ) {
+        let mut ty = ty.clone();
+        if let Type::Path(TypePath { path, .. }) = &mut ty {
+          if let Some(syn::PathArguments::AngleBracketed(args)) = path
+            .segments
+            .last_mut()
+            .map(|segment| &mut segment.arguments)
+          {
+            args.args[0] = syn::GenericArgument::Type(with_remote_mirror(inner, with));
+          }
+        }
+        return ty;
+      }
+    }
+  }
+  Type::Path(TypePath {
+    qself: None,
+    path: with.clone(),
+  })
+}
+
+/// The `dyn Trait` or `impl Trait` inside of a `Box<dyn Trait>`
+fn boxed_trait_object(path: &syn::Path) -> Option<&Type> {
+  let segment = path.segments.last()?;
+  if segment.ident != "Box" {
+    return None;
+  }
+  let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+    return None;
+  };
+  match args.args.first()? {
+    syn::GenericArgument::Type(ty @ (Type::TraitObject(_) | Type::ImplTrait(_))) => Some(ty),
+    _ => None,
+  }
+}
+
 /// Handles Type::Path conversion to TypeScript
 fn handle_type_path(
   path: &syn::Path,
@@ -729,10 +832,14 @@ fn handle_type_path(
       handle_option_type(&args, is_struct_field, is_return_ty)
     } else if rust_ty == "AsyncTask" {
       handle_async_task_type(&args)
+    } else if rust_ty == "AsyncProgressTask" {
+      handle_async_progress_task_type(&args)
     } else if rust_ty == "Reference" || rust_ty == "WeakReference" {
       handle_reference_type(&args, rust_ty)
     } else if rust_ty == "AsyncBlock" {
       handle_async_block_type(&args, rust_ty)
+    } else if rust_ty == "JsMap" {
+      handle_js_map_type(arguments)
     } else if rust_ty == "FnArgs" {
       is_passthrough_type = true;
       Some(args.first().unwrap().to_owned())
@@ -812,12 +919,16 @@ pub fn ty_to_ts_type(
         )
       }
     }
-    Type::Path(syn::TypePath { qself: None, path }) => handle_type_path(
-      path,
-      is_return_ty,
-      is_struct_field,
-      convert_tuple_to_variadic,
-    ),
+    Type::Path(syn::TypePath { qself: None, path }) => match boxed_trait_object(path) {
+      // `Box<dyn Trait>` of a `#[napi] trait` resolves to its interface
+      Some(trait_object) => ty_to_ts_type(trait_object, is_return_ty, is_struct_field, false),
+      None => handle_type_path(
+        path,
+        is_return_ty,
+        is_struct_field,
+        convert_tuple_to_variadic,
+      ),
+    },
     Type::Group(g) => ty_to_ts_type(&g.elem, is_return_ty, is_struct_field, false),
     Type::Array(a) => {
       let (element_type, is_optional) =
@@ -839,13 +950,27 @@ pub fn ty_to_ts_type(
       }
       ("any[]".to_owned(), false)
     }
+    // `dyn Trait` and `impl Trait` of a `#[napi] trait` resolve to its interface
+    Type::TraitObject(syn::TypeTraitObject { bounds, .. })
+    | Type::ImplTrait(syn::TypeImplTrait { bounds, .. }) => bounds
+      .iter()
+      .find_map(|bound| match bound {
+        syn::TypeParamBound::Trait(t) => Some(handle_type_path(
+          &t.path,
+          is_return_ty,
+          is_struct_field,
+          convert_tuple_to_variadic,
+        )),
+        _ => None,
+      })
+      .unwrap_or_else(|| ("any".to_owned(), false)),
     _ => ("any".to_owned(), false),
   }
 }
 
 #[cfg(test)]
 mod tests {
-  use super::{escape_json, format_js_property_name};
+  use super::{escape_json, format_js_property_name, ty_to_ts_type};
 
   #[test]
   fn test_escape_json_escaped_quotes() {
@@ -887,6 +1012,48 @@ mod tests {
     assert_eq!(escape_json(r#"test\"#), r#"test\\"#);
   }
 
+  #[test]
+  fn test_js_map_type() {
+    let ty: syn::Type = syn::parse_quote!(JsMap<HashMap<u32, String>>);
+    assert_eq!(
+      ty_to_ts_type(&ty, false, false, false).0,
+      "Map<number, string>"
+    );
+    let ty: syn::Type = syn::parse_quote!(JsMap<IndexMap<BigInt, Vec<u8>, FxBuildHasher>>);
+    assert_eq!(
+      ty_to_ts_type(&ty, true, false, false).0,
+      "Map<bigint, Array<number>>"
+    );
+  }
+
+  #[test]
+  fn test_trait_object_type() {
+    let ty: syn::Type = syn::parse_quote!(Box<dyn Resolver>);
+    assert_eq!(ty_to_ts_type(&ty, false, false, false).0, "Resolver");
+    let ty: syn::Type = syn::parse_quote!(Option<Box<dyn Resolver>>);
+    assert_eq!(
+      ty_to_ts_type(&ty, false, false, false).0,
+      "Resolver | undefined | null"
+    );
+    let ty: syn::Type = syn::parse_quote!(impl Resolver);
+    assert_eq!(ty_to_ts_type(&ty, false, false, false).0, "Resolver");
+  }
+
+  #[test]
+  fn test_std_time_types() {
+    let ty: syn::Type = syn::parse_quote!(std::time::Duration);
+    assert_eq!(ty_to_ts_type(&ty, false, false, false).0, "number");
+    let ty: syn::Type = syn::parse_quote!(DurationNanos);
+    assert_eq!(ty_to_ts_type(&ty, false, false, false).0, "bigint");
+    let ty: syn::Type = syn::parse_quote!(Option<SystemTime>);
+    assert_eq!(
+      ty_to_ts_type(&ty, false, false, false).0,
+      "Date | undefined | null"
+    );
+    let ty: syn::Type = syn::parse_quote!(Vec<Instant>);
+    assert_eq!(ty_to_ts_type(&ty, false, false, false).0, "Array<Date>");
+  }
+
   // Tests for format_js_property_name
   #[test]
   fn test_format_js_property_name_valid_identifiers() {
@@ -17,6 +17,8 @@ impl ToTypeDef for NapiConst {
     Some(TypeDef {
       kind: "const".to_owned(),
       name: self.js_name.to_owned(),
+      extends: None,
+      implements: None,
       original_name: Some(self.name.to_string()),
       def: format!(
         "export const {}: {}",
@@ -3,28 +3,60 @@ use crate::{typegen::JSDoc, NapiEnum, NapiEnumValue};
 
 impl ToTypeDef for NapiEnum {
   fn to_type_def(&self) -> Option<TypeDef> {
+    self.to_type_defs().into_iter().next()
+  }
+}
+
+impl NapiEnum {
+  /// The enum, a `#[napi(flags)]` enum is followed by the branded number type used by the signatures
+  pub fn to_type_defs(&self) -> Vec<TypeDef> {
     if self.skip_typescript {
-      return None;
+      return vec![];
     }
 
-    add_alias(self.name.to_string(), self.js_name.to_string());
-
-    Some(TypeDef {
+    let enum_def = TypeDef {
       kind: if self.is_string_enum {
         "string_enum".to_owned()
       } else {
         "enum".to_owned()
       },
       name: self.js_name.to_owned(),
+      extends: None,
+      implements: None,
       original_name: Some(self.name.to_string()),
       def: self.gen_ts_variants(),
       js_doc: JSDoc::new(&self.comments),
       js_mod: self.js_mod.to_owned(),
-    })
+    };
+
+    if !self.is_flags {
+      add_alias(self.name.to_string(), self.js_name.to_string());
+      return vec![enum_def];
+    }
+
+    let flags_name = self.flags_js_name();
+    add_alias(self.name.to_string(), flags_name.clone());
+    let flags_def = TypeDef {
+      kind: "type".to_owned(),
+      name: flags_name.clone(),
+      extends: None,
+      implements: None,
+      original_name: None,
+      def: format!("number & {{ readonly __flags: '{}' }}", self.js_name),
+      js_doc: JSDoc::new([format!(
+        "A bit mask of `{0}` flags, e.g. `({0}.A | {0}.B) as {1}`",
+        self.js_name, flags_name
+      )]),
+      js_mod: self.js_mod.to_owned(),
+    };
+    vec![enum_def, flags_def]
+  }
+
+  /// The JavaScript name of the branded number type of a `#[napi(flags)]` enum
+  pub(crate) fn flags_js_name(&self) -> String {
+    format!("{}Flags", self.js_name)
   }
-}
 
-impl NapiEnum {
   fn gen_ts_variants(&self) -> String {
     self
       .variants
@@ -32,6 +64,7 @@ impl NapiEnum {
       .map(|v| {
         let val = match &v.val {
           NapiEnumValue::Number(num) => format!("{num}"),
+          NapiEnumValue::Bits(bits) => format!("{bits}"),
           NapiEnumValue::String(string) => format!("'{string}'"),
         };
         format!("{}{} = {}", JSDoc::new(&v.comments), v.name, val)
@@ -0,0 +1,79 @@
+use super::{add_alias, format_js_property_name, ty_to_ts_type, ToTypeDef, TypeDef};
+use crate::{typegen::JSDoc, NapiError, NapiErrorVariant};
+
+impl ToTypeDef for NapiError {
+  fn to_type_def(&self) -> Option<TypeDef> {
+    self.to_type_defs().into_iter().next()
+  }
+}
+
+impl NapiError {
+  /// The class of a struct, or the class of an enum followed by the classes of its variants
+  pub fn to_type_defs(&self) -> Vec<TypeDef> {
+    add_alias(self.name.to_string(), self.js_name.to_string());
+
+    if !self.is_enum {
+      let variant = &self.variants[0];
+      return vec![self.gen_class(&self.js_name, "Error", &self.comments, variant)];
+    }
+
+    let codes = self
+      .variants
+      .iter()
+      .map(|variant| format!("'{}'", variant.code))
+      .collect::<Vec<_>>()
+      .join(" | ");
+    let base = TypeDef {
+      kind: "struct".to_owned(),
+      name: self.js_name.to_owned(),
+      extends: Some("Error".to_owned()),
+      implements: None,
+      original_name: Some(self.name.to_string()),
+      def: format!("code: {codes}"),
+      js_mod: self.js_mod.to_owned(),
+      js_doc: JSDoc::new(&self.comments),
+    };
+    std::iter::once(base)
+      .chain(
+        self.variants.iter().map(|variant| {
+          self.gen_class(&variant.js_name, &self.js_name, &variant.comments, variant)
+        }),
+      )
+      .collect()
+  }
+
+  fn gen_class(
+    &self,
+    js_name: &str,
+    extends: &str,
+    comments: &[String],
+    variant: &NapiErrorVariant,
+  ) -> TypeDef {
+    let fields = variant
+      .fields
+      .iter()
+      .filter(|field| field.getter && !field.skip_typescript)
+      .map(|field| {
+        let (ty, is_optional) = ty_to_ts_type(&field.ty, false, true, false);
+        let ty = field.ts_type.clone().unwrap_or(ty);
+        let js_name = format_js_property_name(&field.js_name);
+        let optional = if is_optional { "?" } else { "" };
+        format!("{}{js_name}{optional}: {ty}", JSDoc::new(&field.comments))
+      });
+    let def = std::iter::once(format!("code: '{}'", variant.code))
+      .chain(fields)
+      .collect::<Vec<_>>()
+      .join("\n");
+
+    TypeDef {
+      kind: "struct".to_owned(),
+      name: js_name.to_owned(),
+      extends: Some(extends.to_owned()),
+      implements: None,
+      original_name: variant.name.is_none().then(|| self.name.to_string()),
+      def,
+      js_mod: self.js_mod.to_owned(),
+      js_doc: JSDoc::new(comments),
+    }
+  }
+}
@@ -4,7 +4,7 @@ use convert_case::Case;
 use quote::ToTokens;
 use syn::{Member, Pat, PathArguments, PathSegment};
 
-use super::{r#struct::CLASS_STRUCTS, ty_to_ts_type, ToTypeDef, TypeDef};
+use super::{r#struct::CLASS_STRUCTS, ty_to_ts_type, with_remote_mirror, ToTypeDef, TypeDef};
 use crate::{typegen::JSDoc, util::to_case, CallbackArg, FnKind, NapiFn};
 
 pub(crate) struct FnArg {
@@ -84,11 +84,14 @@ impl ToTypeDef for NapiFn {
     }
 
     let prefix = self.gen_ts_func_prefix();
+    let name = match &self.well_known_symbol {
+      Some(symbol) => format!("[Symbol.{symbol}]"),
+      None => self.js_name.clone(),
+    };
     let def = match self.ts_type.as_ref() {
-      Some(ts_type) => format!("{prefix} {name}{ts_type}", name = self.js_name),
+      Some(ts_type) => format!("{prefix} {name}{ts_type}"),
       None => format!(
         r#"{prefix} {name}{generic}({args}){ret}"#,
-        name = &self.js_name,
         generic = &self
           .ts_generic_types
           .as_ref()
@@ -109,6 +112,8 @@ impl ToTypeDef for NapiFn {
     Some(TypeDef {
       kind: "fn".to_owned(),
       name: self.js_name.clone(),
+      extends: None,
+      implements: None,
       original_name: None,
       def,
       js_mod: self.js_mod.to_owned(),
@@ -140,7 +145,7 @@ fn gen_callback_type(callback: &CallbackArg) -> String {
   )
 }
 
-fn gen_ts_func_arg(pat: &Pat) -> String {
+pub(super) fn gen_ts_func_arg(pat: &Pat) -> String {
   match pat {
     Pat::Struct(s) => format!(
       "{{ {} }}",
@@ -269,7 +274,11 @@ impl NapiFn {
               i.mutability = None;
             }
 
-            let (ts_type, is_optional) = ty_to_ts_type(&path.ty, false, false, false);
+            let ty = match &arg.with {
+              Some(with) => with_remote_mirror(&path.ty, with),
+              None => (*path.ty).clone(),
+            };
+            let (ts_type, is_optional) = ty_to_ts_type(&ty, false, false, false);
             let ts_type = arg.use_overridden_type_or(|| ts_type);
             let arg = gen_ts_func_arg(&path.pat);
             Some(FnArg {
@@ -332,7 +341,11 @@ impl NapiFn {
         .unwrap_or_else(|| "".to_owned()),
       _ => {
         let ret = if let Some(ret) = &self.ret {
-          let (ts_type, _) = ty_to_ts_type(ret, true, false, false);
+          let ret = match &self.return_with {
+            Some(with) => with_remote_mirror(ret, with),
+            None => ret.clone(),
+          };
+          let (ts_type, _) = ty_to_ts_type(&ret, true, false, false);
           if ts_type == "undefined" {
             "void".to_owned()
           } else if ts_type == "Self" {
@@ -0,0 +1,850 @@
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::fmt::{self, Display, Formatter};
+
+use quote::ToTokens;
+use syn::{GenericArgument, PathArguments, Type};
+
+use super::{
+  escape_json, r#fn::gen_ts_func_arg, with_remote_mirror, ALIAS, KNOWN_TYPES, TSFN_RUST_TY,
+};
+use crate::{
+  util::to_case, FnKind, Napi, NapiEnum, NapiEnumValue, NapiFn, NapiFnArgKind, NapiItem,
+  NapiStruct, NapiStructField, NapiStructKind, NapiType,
+};
+
+const DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+thread_local! {
+  /// The Rust names of the `#[napi]` classes, their instances are not JSON values
+  static CLASSES: RefCell<HashSet<String>> = Default::default();
+}
+
+/// The JSON Schema of an exported object, enum, type alias or the arguments of a function
+///
+/// The other exported types are referred to as `#/$defs/<name>`,
+/// so the schemas of a crate can be bundled together under `$defs`.
+#[derive(Debug)]
+pub struct JsonSchema {
+  /// `object`, `type`, `enum` or `fn`
+  pub kind: String,
+  pub name: String,
+  pub js_mod: Option<String>,
+  pub schema: Json,
+}
+
+pub trait ToJsonSchema {
+  fn to_json_schema(&self) -> Option<JsonSchema>;
+}
+
+/// The JSON values the schemas are built from
+#[derive(Debug, Clone, PartialEq)]
+pub enum Json {
+  Bool(bool),
+  Number(i64),
+  String(String),
+  Array(Vec<Json>),
+  Object(Vec<(String, Json)>),
+}
+
+impl Json {
+  fn object<K: Into<String>, I: IntoIterator<Item = (K, Json)>>(entries: I) -> Json {
+    Json::Object(entries.into_iter().map(|(k, v)| (k.into(), v)).collect())
+  }
+
+  fn string<S: Into<String>>(s: S) -> Json {
+    Json::String(s.into())
+  }
+
+  fn ty(ty: &str) -> Json {
+    Json::object([("type", Json::string(ty))])
+  }
+
+  /// The schema any value is valid against
+  fn any() -> Json {
+    Json::Object(vec![])
+  }
+
+  fn with(mut self, key: &str, value: Json) -> Json {
+    if let Json::Object(entries) = &mut self {
+      entries.push((key.to_owned(), value));
+    }
+    self
+  }
+
+  /// Add the keywords of the object `schema`, or require it with `allOf` if it's not an object
+  fn with_merged(self, schema: Json) -> Json {
+    match schema {
+      Json::Object(entries) => entries
+        .into_iter()
+        .fold(self, |json, (key, value)| json.with(&key, value)),
+      schema => self.with("allOf", Json::Array(vec![schema])),
+    }
+  }
+
+  fn with_description(self, comments: &[String]) -> Json {
+    match description(comments) {
+      Some(description) => self.with("description", Json::String(description)),
+      None => self,
+    }
+  }
+}
+
+impl Display for Json {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    match self {
+      Json::Bool(b) => write!(f, "{b}"),
+      Json::Number(n) => write!(f, "{n}"),
+      Json::String(s) => write!(f, "\"{}\"", escape_json(s)),
+      Json::Array(items) => {
+        write!(f, "[")?;
+        for (i, item) in items.iter().enumerate() {
+          if i != 0 {
+            write!(f, ", ")?;
+          }
+          write!(f, "{item}")?;
+        }
+        write!(f, "]")
+      }
+      Json::Object(entries) => {
+        write!(f, "{{")?;
+        for (i, (key, value)) in entries.iter().enumerate() {
+          if i != 0 {
+            write!(f, ", ")?;
+          }
+          write!(f, "\"{}\": {value}", escape_json(key))?;
+        }
+        write!(f, "}}")
+      }
+    }
+  }
+}
+
+impl Display for JsonSchema {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    let js_mod = if let Some(js_mod) = &self.js_mod {
+      format!(", \"js_mod\": \"{js_mod}\"")
+    } else {
+      "".to_string()
+    };
+    write!(
+      f,
+      r#"{{"kind": "{}", "name": "{}"{}, "schema": {}}}"#,
+      self.kind, self.name, js_mod, self.schema
+    )
+  }
+}
+
+/// The doc comments as a description, `None` if there are none
+fn description(comments: &[String]) -> Option<String> {
+  let description = comments
+    .iter()
+    .map(|line| line.trim())
+    .collect::<Vec<_>>()
+    .join("\n");
+  let description = description.trim();
+  (!description.is_empty()).then(|| description.to_owned())
+}
+
+/// The schema of the item itself, with the dialect, the title and the description
+fn root(name: &str, comments: &[String], schema: Json) -> Json {
+  let mut entries = vec![
+    ("$schema".to_owned(), Json::string(DIALECT)),
+    ("title".to_owned(), Json::string(name)),
+  ];
+  if let Some(description) = description(comments) {
+    entries.push(("description".to_owned(), Json::String(description)));
+  }
+  match schema {
+    Json::Object(schema) => entries.extend(schema),
+    schema => entries.push(("allOf".to_owned(), Json::Array(vec![schema]))),
+  }
+  Json::Object(entries)
+}
+
+fn one_of(schemas: Vec<Json>) -> Json {
+  // like the union types, `Either<f64, u32>` has the same schemas
+  let mut schemas = schemas.into_iter().fold(vec![], |mut acc, cur| {
+    if !acc.contains(&cur) {
+      acc.push(cur);
+    }
+    acc
+  });
+  if schemas.len() == 1 {
+    schemas.remove(0)
+  } else {
+    Json::object([("oneOf", Json::Array(schemas))])
+  }
+}
+
+fn nullable(schema: Json) -> Json {
+  one_of(vec![schema, Json::ty("null")])
+}
+
+fn generic_args(arguments: &PathArguments) -> Vec<&Type> {
+  match arguments {
+    PathArguments::AngleBracketed(arguments) => arguments
+      .args
+      .iter()
+      .filter_map(|arg| match arg {
+        GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+      })
+      .collect(),
+    _ => vec![],
+  }
+}
+
+fn is_integer(rust_ty: &str) -> bool {
+  matches!(
+    rust_ty,
+    "i8"
+      | "i16"
+      | "i32"
+      | "i64"
+      | "u8"
+      | "u16"
+      | "u32"
+      | "u64"
+      | "i64n"
+      | "u128"
+      | "i128"
+      | "usize"
+      | "isize"
+      | "BigInt"
+      | "JsBigInt"
+  )
+}
+
+// return (schema, is_optional)
+pub fn ty_to_json_schema(ty: &Type) -> (Json, bool) {
+  match ty {
+    Type::Reference(r) => ty_to_json_schema(&r.elem),
+    Type::Group(g) => ty_to_json_schema(&g.elem),
+    Type::Paren(p) => ty_to_json_schema(&p.elem),
+    Type::Tuple(tuple) if tuple.elems.is_empty() => (Json::any(), false),
+    Type::Tuple(tuple) => (tuple_schema(tuple.elems.iter()), false),
+    Type::Array(a) => (
+      Json::ty("array").with("items", ty_to_json_schema(&a.elem).0),
+      false,
+    ),
+    Type::Slice(s) => (
+      Json::ty("array").with("items", ty_to_json_schema(&s.elem).0),
+      false,
+    ),
+    Type::Path(syn::TypePath { qself: None, path }) => match path.segments.last() {
+      Some(segment) => path_to_json_schema(&segment.ident.to_string(), &segment.arguments),
+      None => (Json::any(), false),
+    },
+    _ => (Json::any(), false),
+  }
+}
+
+fn tuple_schema<'a, I: ExactSizeIterator<Item = &'a Type>>(elems: I) -> Json {
+  let len = elems.len() as i64;
+  Json::ty("array")
+    .with(
+      "prefixItems",
+      Json::Array(elems.map(|elem| ty_to_json_schema(elem).0).collect()),
+    )
+    .with("minItems", Json::Number(len))
+    .with("maxItems", Json::Number(len))
+}
+
+fn path_to_json_schema(rust_ty: &str, arguments: &PathArguments) -> (Json, bool) {
+  let args = generic_args(arguments);
+  let arg = |index: usize| {
+    args
+      .get(index)
+      .map_or_else(Json::any, |ty| ty_to_json_schema(ty).0)
+  };
+  let schema = match rust_ty {
+    "Option" => return (arg(0), true),
+    _ if rust_ty.starts_with("Either") => one_of(
+      args
+        .iter()
+        .map(|ty| ty_to_json_schema(ty).0)
+        .collect::<Vec<_>>(),
+    ),
+    "Box" | "Rc" | "Arc" | "Mutex" | "FnArgs" => {
+      return args
+        .first()
+        .map_or((Json::any(), false), |ty| ty_to_json_schema(ty))
+    }
+    "Vec" | "HashSet" | "BTreeSet" | "IndexSet" => Json::ty("array").with("items", arg(0)),
+    "Array" => Json::ty("array"),
+    "HashMap" | "BTreeMap" | "IndexMap" => Json::ty("object").with("additionalProperties", arg(1)),
+    "Map" | "Object" | "JsObject" => Json::ty("object"),
+    _ if is_integer(rust_ty) => Json::ty("integer"),
+    "f32" | "f64" | "Number" | "JsNumber" => Json::ty("number"),
+    "bool" | "JsBoolean" => Json::ty("boolean"),
+    "Null" | "JsNull" | "null" => Json::ty("null"),
+    _ => match KNOWN_TYPES.get(rust_ty) {
+      Some(&("string", _, _)) => Json::ty("string"),
+      // `undefined`, symbols, functions, buffers and the other JavaScript values are not JSON values
+      Some(_) => Json::any(),
+      None
+        if matches!(
+          rust_ty,
+          TSFN_RUST_TY | "JsMap" | "TypedArray" | "Reference" | "WeakReference" | "AsyncTask"
+        ) =>
+      {
+        Json::any()
+      }
+      None => type_ref(rust_ty),
+    },
+  };
+  (schema, false)
+}
+
+/// The reference to another exported type, by its JavaScript name if it's known like the type definitions
+fn type_ref(rust_ty: &str) -> Json {
+  if CLASSES.with(|classes| classes.borrow().contains(rust_ty)) {
+    return Json::any();
+  }
+  let js_name = ALIAS
+    .with(|aliases| aliases.borrow().get(rust_ty).cloned())
+    .unwrap_or_else(|| rust_ty.to_owned());
+  // the schemas of the generic types are the same for all the type arguments
+  let js_name = js_name.split_once('<').map_or(&*js_name, |(name, _)| name);
+  Json::object([("$ref", Json::String(format!("#/$defs/{js_name}")))])
+}
+
+impl ToJsonSchema for Napi {
+  fn to_json_schema(&self) -> Option<JsonSchema> {
+    match &self.item {
+      NapiItem::Fn(f) => f.to_json_schema(),
+      NapiItem::Struct(s) => s.to_json_schema(),
+      NapiItem::Enum(e) => e.to_json_schema(),
+      NapiItem::Type(t) => t.to_json_schema(),
+      _ => None,
+    }
+  }
+}
+
+impl ToJsonSchema for NapiEnum {
+  fn to_json_schema(&self) -> Option<JsonSchema> {
+    if self.skip_typescript {
+      return None;
+    }
+    if self.is_flags {
+      // the bit masks, any combination of the flags
+      let all_bits = self.variants.iter().fold(0, |bits, v| match v.val {
+        NapiEnumValue::Bits(flag) => bits | flag,
+        _ => bits,
+      });
+      let name = self.flags_js_name();
+      return Some(JsonSchema {
+        kind: "type".to_owned(),
+        js_mod: self.js_mod.clone(),
+        schema: root(
+          &name,
+          &self.comments,
+          Json::ty("integer")
+            .with("minimum", Json::Number(0))
+            .with("maximum", Json::Number(i64::from(all_bits))),
+        ),
+        name,
+      });
+    }
+    let variants = self
+      .variants
+      .iter()
+      .map(|v| {
+        let value = match &v.val {
+          NapiEnumValue::Number(num) => Json::Number(i64::from(*num)),
+          NapiEnumValue::Bits(bits) => Json::Number(i64::from(*bits)),
+          NapiEnumValue::String(string) => Json::string(string),
+        };
+        Json::object([
+          ("const", value),
+          ("title", Json::string(v.name.to_string())),
+        ])
+        .with_description(&v.comments)
+      })
+      .collect();
+    Some(JsonSchema {
+      kind: "enum".to_owned(),
+      name: self.js_name.clone(),
+      js_mod: self.js_mod.clone(),
+      schema: root(
+        &self.js_name,
+        &self.comments,
+        Json::object([("oneOf", Json::Array(variants))]),
+      ),
+    })
+  }
+}
+
+impl ToJsonSchema for NapiType {
+  fn to_json_schema(&self) -> Option<JsonSchema> {
+    if self.skip_typescript {
+      return None;
+    }
+    // referred to without the type parameters, like the other generic types
+    let name = self
+      .js_name
+      .split_once('<')
+      .map_or(&*self.js_name, |(name, _)| name);
+    Some(JsonSchema {
+      kind: "type".to_owned(),
+      name: name.to_owned(),
+      js_mod: self.js_mod.clone(),
+      schema: root(
+        &self.js_name,
+        &self.comments,
+        ty_to_json_schema(&self.value).0,
+      ),
+    })
+  }
+}
+
+impl ToJsonSchema for NapiStruct {
+  fn to_json_schema(&self) -> Option<JsonSchema> {
+    let (kind, schema) = match &self.kind {
+      NapiStructKind::Object(object) => ("object", self.gen_object_schema(&object.fields, None)),
+      NapiStructKind::Array(array) => (
+        "object",
+        tuple_schema(
+          array
+            .fields
+            .iter()
+            .filter(|f| !f.skip_typescript)
+            .map(|f| &f.ty)
+            .collect::<Vec<_>>()
+            .into_iter(),
+        ),
+      ),
+      NapiStructKind::StructuredEnum(structured_enum) => (
+        "object",
+        Json::object([(
+          "oneOf",
+          Json::Array(
+            structured_enum
+              .variants
+              .iter()
+              .map(|variant| {
+                let tag = match structured_enum.discriminant_case {
+                  Some(case) => to_case(variant.name.to_string(), case),
+                  None => variant.name.to_string(),
+                };
+                self.gen_object_schema(&variant.fields, Some((&structured_enum.discriminant, tag)))
+              })
+              .collect(),
+          ),
+        )]),
+      ),
+      NapiStructKind::Transparent(transparent) => ("type", ty_to_json_schema(&transparent.ty).0),
+      NapiStructKind::Class(_) => {
+        CLASSES.with(|classes| classes.borrow_mut().insert(self.name.to_string()));
+        return None;
+      }
+    };
+    Some(JsonSchema {
+      kind: kind.to_owned(),
+      name: self.js_name.clone(),
+      js_mod: self.js_mod.clone(),
+      schema: root(&self.js_name, &self.comments, schema),
+    })
+  }
+}
+
+impl NapiStruct {
+  fn gen_object_schema(&self, fields: &[NapiStructField], tag: Option<(&str, String)>) -> Json {
+    let mut properties = vec![];
+    let mut required = vec![];
+    if let Some((discriminant, tag)) = tag {
+      properties.push((
+        discriminant.to_owned(),
+        Json::object([("const", Json::String(tag))]),
+      ));
+      required.push(Json::string(discriminant));
+    }
+    let mut flattened = vec![];
+    for f in fields.iter().filter(|f| f.getter && !f.skip_typescript) {
+      let (schema, is_optional) = match &f.with {
+        Some(with) => ty_to_json_schema(&with_remote_mirror(&f.ty, with)),
+        None => ty_to_json_schema(&f.ty),
+      };
+      if f.flatten {
+        flattened.push(schema);
+        continue;
+      }
+      // the same as the type definitions, a field with default value can be absent in the JavaScript object
+      let (schema, is_required) = match (is_optional, f.default.is_some()) {
+        (false, has_default) => (schema, !has_default),
+        (true, _) if !self.use_nullable => (schema, false),
+        (true, has_default) => (nullable(schema), !has_default),
+      };
+      if is_required {
+        required.push(Json::string(&f.js_name));
+      }
+      properties.push((f.js_name.clone(), schema.with_description(&f.comments)));
+    }
+    let mut schema = Json::ty("object").with("properties", Json::Object(properties));
+    if !required.is_empty() {
+      schema = schema.with("required", Json::Array(required));
+    }
+    if !flattened.is_empty() {
+      schema = schema.with("allOf", Json::Array(flattened));
+    }
+    schema
+  }
+}
+
+impl ToJsonSchema for NapiFn {
+  fn to_json_schema(&self) -> Option<JsonSchema> {
+    // only the functions exported from the module, not the methods of the classes
+    if self.skip_typescript
+      || self.module_exports
+      || self.no_export
+      || self.parent.is_some()
+      || self.kind != FnKind::Normal
+    {
+      return None;
+    }
+    let mut args = vec![];
+    let mut required = 0;
+    for arg in &self.args {
+      let (name, schema, is_optional) = match &arg.kind {
+        NapiFnArgKind::PatType(path) => {
+          let (schema, is_optional) = match &arg.with {
+            Some(with) => ty_to_json_schema(&with_remote_mirror(&path.ty, with)),
+            None => ty_to_json_schema(&path.ty),
+          };
+          let rust_ty = match path.ty.as_ref() {
+            Type::Reference(r) => r.elem.to_token_stream().to_string(),
+            ty => ty.to_token_stream().to_string(),
+          };
+          // `Env` and `This` are not passed in the arguments
+          if rust_ty == "Env" || rust_ty.starts_with("This") {
+            continue;
+          }
+          let mut pat = path.pat.clone();
+          if let syn::Pat::Ident(i) = pat.as_mut() {
+            i.mutability = None;
+          }
+          (gen_ts_func_arg(&pat), schema, is_optional)
+        }
+        // the functions are not JSON values
+        NapiFnArgKind::Callback(cb) => (
+          to_case(
+            cb.pat.to_token_stream().to_string(),
+            convert_case::Case::Camel,
+          ),
+          Json::any(),
+          false,
+        ),
+      };
+      if !is_optional {
+        required = args.len() + 1;
+      }
+      args.push(Json::object([("title", Json::String(name))]).with_merged(schema));
+    }
+    let len = args.len() as i64;
+    let schema = Json::ty("array")
+      .with("prefixItems", Json::Array(args))
+      .with("items", Json::Bool(false))
+      .with("minItems", Json::Number(required as i64))
+      .with("maxItems", Json::Number(len));
+    Some(JsonSchema {
+      kind: "fn".to_owned(),
+      name: self.js_name.clone(),
+      js_mod: self.js_mod.clone(),
+      schema: root(&self.js_name, &self.comments, schema),
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{ty_to_json_schema, ToJsonSchema};
+  use crate::{
+    typegen::ToTypeDef, NapiClass, NapiEnum, NapiEnumValue, NapiEnumVariant, NapiObject,
+    NapiStruct, NapiStructField, NapiStructKind,
+  };
+
+  fn schema(ty: syn::Type) -> (serde_json::Value, bool) {
+    let (schema, is_optional) = ty_to_json_schema(&ty);
+    (
+      serde_json::from_str(&schema.to_string()).expect("Should be valid JSON"),
+      is_optional,
+    )
+  }
+
+  fn field(name: &str, ty: syn::Type) -> NapiStructField {
+    NapiStructField {
+      name: syn::Member::Named(syn::Ident::new(name, proc_macro2::Span::call_site())),
+      js_name: name.to_owned(),
+      ty,
+      getter: true,
+      setter: true,
+      writable: true,
+      enumerable: true,
+      configurable: true,
+      comments: vec![],
+      skip_typescript: false,
+      ts_type: None,
+      has_lifetime: false,
+      remote_getter: None,
+      default: None,
+      flatten: false,
+      with: None,
+    }
+  }
+
+  fn napi_struct(name: &str, kind: NapiStructKind) -> NapiStruct {
+    NapiStruct {
+      name: syn::Ident::new(name, proc_macro2::Span::call_site()),
+      js_name: name.to_owned(),
+      comments: vec![],
+      js_mod: None,
+      use_nullable: false,
+      register_name: syn::Ident::new(
+        &format!("__napi_register__{name}"),
+        proc_macro2::Span::call_site(),
+      ),
+      kind,
+      has_lifetime: false,
+      is_generator: false,
+      is_async_generator: false,
+    }
+  }
+
+  fn object(name: &str, fields: Vec<NapiStructField>) -> NapiStruct {
+    napi_struct(
+      name,
+      NapiStructKind::Object(NapiObject {
+        fields,
+        object_from_js: true,
+        object_to_js: true,
+        is_tuple: false,
+        remote: None,
+        collect_errors: false,
+      }),
+    )
+  }
+
+  fn class(name: &str) -> NapiStruct {
+    napi_struct(
+      name,
+      NapiStructKind::Class(NapiClass {
+        fields: vec![],
+        ctor: false,
+        implement_iterator: false,
+        implement_async_iterator: false,
+        is_tuple: false,
+        use_custom_finalize: false,
+        post_finalize: false,
+        extends: None,
+      }),
+    )
+  }
+
+  /// The schema of the `#[napi]` struct, without the root keywords
+  fn struct_schema(napi_struct: &NapiStruct) -> Option<serde_json::Value> {
+    // the type defs are generated first like in `napi-derive`, they register the names of the types
+    napi_struct.to_type_def();
+    let schema = napi_struct.to_json_schema()?;
+    let line: serde_json::Value = serde_json::from_str(&schema.to_string()).unwrap();
+    Some(line["schema"].clone())
+  }
+
+  #[test]
+  fn test_object_refs() {
+    let route = object(
+      "SchemaRoute",
+      vec![field("path", syn::parse_quote!(String))],
+    );
+    let client = class("SchemaClient");
+    assert!(struct_schema(&route).is_some());
+    assert_eq!(struct_schema(&client), None);
+    // the objects rendered before are referred to, the class instances are not JSON values
+    let server = object(
+      "SchemaServer",
+      vec![
+        field("route", syn::parse_quote!(SchemaRoute)),
+        field("routes", syn::parse_quote!(Vec<SchemaRoute>)),
+        field("client", syn::parse_quote!(SchemaClient)),
+      ],
+    );
+    let schema = struct_schema(&server).unwrap();
+    assert_eq!(
+      schema["properties"],
+      serde_json::json!({
+        "route": { "$ref": "#/$defs/SchemaRoute" },
+        "routes": { "type": "array", "items": { "$ref": "#/$defs/SchemaRoute" } },
+        "client": {},
+      })
+    );
+  }
+
+  #[test]
+  fn test_flatten_schema() {
+    let base = object(
+      "SchemaBaseOptions",
+      vec![field("timeout", syn::parse_quote!(u32))],
+    );
+    assert!(struct_schema(&base).is_some());
+    let mut flattened = field("base", syn::parse_quote!(SchemaBaseOptions));
+    flattened.flatten = true;
+    let options = object(
+      "SchemaFetchOptions",
+      vec![field("url", syn::parse_quote!(String)), flattened],
+    );
+    let schema = struct_schema(&options).unwrap();
+    assert_eq!(
+      schema["properties"],
+      serde_json::json!({ "url": { "type": "string" } })
+    );
+    assert_eq!(schema["required"], serde_json::json!(["url"]));
+    assert_eq!(
+      schema["allOf"],
+      serde_json::json!([{ "$ref": "#/$defs/SchemaBaseOptions" }])
+    );
+  }
+
+  #[test]
+  fn test_primitive_types() {
+    assert_eq!(
+      schema(syn::parse_quote!(u32)),
+      (serde_json::json!({ "type": "integer" }), false)
+    );
+    assert_eq!(
+      schema(syn::parse_quote!(f64)).0,
+      serde_json::json!({ "type": "number" })
+    );
+    assert_eq!(
+      schema(syn::parse_quote!(&str)).0,
+      serde_json::json!({ "type": "string" })
+    );
+    assert_eq!(
+      schema(syn::parse_quote!(Option<bool>)),
+      (serde_json::json!({ "type": "boolean" }), true)
+    );
+  }
+
+  #[test]
+  fn test_either_type() {
+    assert_eq!(
+      schema(syn::parse_quote!(Either<String, u32>)).0,
+      serde_json::json!({ "oneOf": [{ "type": "string" }, { "type": "integer" }] })
+    );
+    // the same schemas are merged
+    assert_eq!(
+      schema(syn::parse_quote!(Either3<u32, i32, String>)).0,
+      serde_json::json!({ "oneOf": [{ "type": "integer" }, { "type": "string" }] })
+    );
+  }
+
+  #[test]
+  fn test_collection_types() {
+    assert_eq!(
+      schema(syn::parse_quote!(Vec<Option<String>>)).0,
+      serde_json::json!({ "type": "array", "items": { "type": "string" } })
+    );
+    assert_eq!(
+      schema(syn::parse_quote!(HashMap<String, f64, FxBuildHasher>)).0,
+      serde_json::json!({ "type": "object", "additionalProperties": { "type": "number" } })
+    );
+    assert_eq!(
+      schema(syn::parse_quote!((u32, String))).0,
+      serde_json::json!({
+        "type": "array",
+        "prefixItems": [{ "type": "integer" }, { "type": "string" }],
+        "minItems": 2,
+        "maxItems": 2,
+      })
+    );
+  }
+
+  #[test]
+  fn test_enum_schema() {
+    let napi_enum = NapiEnum {
+      name: syn::parse_quote!(Kind),
+      js_name: "Kind".to_owned(),
+      variants: vec![
+        NapiEnumVariant {
+          name: syn::parse_quote!(Dog),
+          val: NapiEnumValue::Number(0),
+          comments: vec![" Woof".to_owned()],
+        },
+        NapiEnumVariant {
+          name: syn::parse_quote!(Cat),
+          val: NapiEnumValue::Number(1),
+          comments: vec![],
+        },
+      ],
+      js_mod: None,
+      comments: vec![" The kind of pets".to_owned()],
+      skip_typescript: false,
+      register_name: syn::parse_quote!(__napi_register__Kind),
+      is_string_enum: false,
+      is_flags: false,
+      object_from_js: true,
+      object_to_js: true,
+    };
+    let schema = napi_enum.to_json_schema().unwrap();
+    let line: serde_json::Value = serde_json::from_str(&schema.to_string()).unwrap();
+    assert_eq!(
+      line,
+      serde_json::json!({
+        "kind": "enum",
+        "name": "Kind",
+        "schema": {
+          "$schema": "https://json-schema.org/draft/2020-12/schema",
+          "title": "Kind",
+          "description": "The kind of pets",
+          "oneOf": [
+            { "const": 0, "title": "Dog", "description": "Woof" },
+            { "const": 1, "title": "Cat" },
+          ],
+        },
+      })
+    );
+  }
+
+  #[test]
+  fn test_flags_schema() {
+    let napi_enum = NapiEnum {
+      name: syn::parse_quote!(WatchEvent),
+      js_name: "WatchEvent".to_owned(),
+      variants: vec![
+        NapiEnumVariant {
+          name: syn::parse_quote!(Create),
+          val: NapiEnumValue::Bits(1),
+          comments: vec![],
+        },
+        NapiEnumVariant {
+          name: syn::parse_quote!(Remove),
+          val: NapiEnumValue::Bits(4),
+          comments: vec![],
+        },
+      ],
+      js_mod: None,
+      comments: vec![],
+      skip_typescript: false,
+      register_name: syn::parse_quote!(__napi_register__WatchEvent),
+      is_string_enum: false,
+      is_flags: true,
+      object_from_js: true,
+      object_to_js: true,
+    };
+    let schema = napi_enum.to_json_schema().unwrap();
+    let line: serde_json::Value = serde_json::from_str(&schema.to_string()).unwrap();
+    assert_eq!(
+      line,
+      serde_json::json!({
+        "kind": "type",
+        "name": "WatchEventFlags",
+        "schema": {
+          "$schema": "https://json-schema.org/draft/2020-12/schema",
+          "title": "WatchEventFlags",
+          "type": "integer",
+          "minimum": 0,
+          "maximum": 5,
+        },
+      })
+    );
+  }
+}
@@ -2,11 +2,16 @@ use std::collections::HashMap;
 use std::vec::Vec;
 use std::{cell::RefCell, iter};
 
-use super::{add_alias, format_js_property_name, ty_to_ts_type, ToTypeDef, TypeDef};
-use crate::{typegen::JSDoc, util::to_case, NapiImpl, NapiStruct, NapiStructField, NapiStructKind};
+use super::{
+  add_alias, format_js_property_name, ty_to_ts_type, with_remote_mirror, ToTypeDef, TypeDef,
+};
+use crate::{
+  typegen::JSDoc, util::to_case, NapiClass, NapiImpl, NapiStruct, NapiStructField, NapiStructKind,
+};
 
 thread_local! {
   pub(crate) static TASK_STRUCTS: RefCell<HashMap<String, String>> = Default::default();
+  pub(crate) static TASK_PROGRESS_STRUCTS: RefCell<HashMap<String, String>> = Default::default();
   pub(crate) static CLASS_STRUCTS: RefCell<HashMap<String, String>> = Default::default();
 }
 
@@ -39,14 +44,18 @@ impl ToTypeDef for NapiStruct {
     }
 
     Some(TypeDef {
-      kind: String::from(match self.kind {
+      kind: String::from(match &self.kind {
         NapiStructKind::Transparent(_) => "type",
         NapiStructKind::Class(_) => "struct",
+        // an interface can't extend the flattened types, they are intersected
+        NapiStructKind::Object(object) if object.fields.iter().any(|f| f.flatten) => "type",
         NapiStructKind::Object(_) => "interface",
         NapiStructKind::StructuredEnum(_) => "type",
         NapiStructKind::Array(_) => "type",
       }),
       name: self.js_name.to_owned(),
+      extends: self.gen_ts_extends(),
+      implements: None,
       original_name: Some(self.name.to_string()),
       def: self.gen_ts_class(),
       js_mod: self.js_mod.to_owned(),
@@ -73,6 +82,13 @@ impl ToTypeDef for NapiImpl {
       });
     }
 
+    if let Some(progress_type) = &self.task_progress_type {
+      TASK_PROGRESS_STRUCTS.with(|t| {
+        let (resolved_type, _) = ty_to_ts_type(progress_type, false, false, false);
+        t.borrow_mut().insert(self.name.to_string(), resolved_type);
+      });
+    }
+
     if let Some(output_type) = &self.iterator_yield_type {
       let next_type = if let Some(ref ty) = self.iterator_next_type {
         ty_to_ts_type(ty, false, false, false).0
@@ -87,6 +103,8 @@ impl ToTypeDef for NapiImpl {
       Some(TypeDef {
         kind: "extends".to_owned(),
         name: self.js_name.to_owned(),
+        extends: None,
+        implements: None,
         original_name: None,
         def: format!(
           "Iterator<{}, {}, {}>",
@@ -120,6 +138,8 @@ impl ToTypeDef for NapiImpl {
       Some(TypeDef {
         kind: "impl".to_owned(),
         name: self.js_name.to_owned(),
+        extends: None,
+        implements: None,
         original_name: None,
         def: format!(
           "[Symbol.asyncIterator](): AsyncGenerator<{}, {}, {}>",
@@ -129,9 +149,21 @@ impl ToTypeDef for NapiImpl {
         js_doc: JSDoc::new::<Vec<String>, String>(Vec::default()),
       })
     } else {
+      let implements = self
+        .items
+        .iter()
+        .filter(|f| !f.skip_typescript)
+        .filter_map(|f| match f.well_known_symbol.as_deref() {
+          Some("dispose") => Some("Disposable"),
+          Some("asyncDispose") => Some("AsyncDisposable"),
+          _ => None,
+        })
+        .collect::<Vec<_>>();
       Some(TypeDef {
         kind: "impl".to_owned(),
         name: self.js_name.to_owned(),
+        extends: None,
+        implements: (!implements.is_empty()).then(|| implements.join(", ")),
         original_name: None,
         def: self
           .items
@@ -159,7 +191,7 @@ impl ToTypeDef for NapiImpl {
 
 impl NapiStruct {
   fn gen_field(&self, f: &NapiStructField) -> Option<(String, String)> {
-    if f.skip_typescript {
+    if f.skip_typescript || f.flatten {
       return None;
     }
 
@@ -173,14 +205,21 @@ impl NapiStruct {
       field_str.push_str("readonly ")
     }
 
-    let (arg, is_optional) = ty_to_ts_type(&f.ty, false, true, false);
+    let ty = match &f.with {
+      Some(with) => with_remote_mirror(&f.ty, with),
+      None => f.ty.clone(),
+    };
+    let (arg, is_optional) = ty_to_ts_type(&ty, false, true, false);
     let arg = f.ts_type.as_ref().map(|ty| ty.to_string()).unwrap_or(arg);
     let js_name = format_js_property_name(&f.js_name);
 
-    let arg = match is_optional {
-      false => format!("{}: {}", &js_name, arg),
-      true => match self.use_nullable {
+    // a field with default value can be absent in the JavaScript object
+    let arg = match (is_optional, f.default.is_some()) {
+      (false, false) => format!("{}: {}", &js_name, arg),
+      (false, true) => format!("{}?: {}", &js_name, arg),
+      (true, has_default) => match self.use_nullable {
         false => format!("{}?: {}", &js_name, arg),
+        true if has_default => format!("{}?: {} | null", &js_name, arg),
         true => format!("{}: {} | null", &js_name, arg),
       },
     };
@@ -188,6 +227,27 @@ impl NapiStruct {
     Some((field_str, arg))
   }
 
+  fn gen_ts_extends(&self) -> Option<String> {
+    match &self.kind {
+      NapiStructKind::Class(NapiClass {
+        extends: Some(extends),
+        ..
+      }) => Some(
+        ty_to_ts_type(
+          &syn::Type::Path(syn::TypePath {
+            qself: None,
+            path: extends.base.clone(),
+          }),
+          false,
+          false,
+          false,
+        )
+        .0,
+      ),
+      _ => None,
+    }
+  }
+
   fn gen_ts_class(&self) -> String {
     match &self.kind {
       NapiStructKind::Transparent(transparent) => {
@@ -222,13 +282,20 @@ impl NapiStruct {
           def
         }
       }
-      NapiStructKind::Object(object) => object
-        .fields
-        .iter()
-        .filter(|f| f.getter)
-        .filter_map(|f| self.gen_field(f).map(|(field, _)| field))
-        .collect::<Vec<_>>()
-        .join("\\n"),
+      NapiStructKind::Object(object) => {
+        let def = object
+          .fields
+          .iter()
+          .filter(|f| f.getter)
+          .filter_map(|f| self.gen_field(f).map(|(field, _)| field))
+          .collect::<Vec<_>>()
+          .join("\\n");
+        match gen_flattened_types(&object.fields) {
+          Some(flattened) if def.is_empty() => flattened,
+          Some(flattened) => format!("{flattened} & {{\\n{def}\\n}}"),
+          None => def,
+        }
+      }
       NapiStructKind::StructuredEnum(structured_enum) => structured_enum
         .variants
         .iter()
@@ -251,10 +318,27 @@ impl NapiStruct {
           )
           .collect::<Vec<_>>()
           .join(", ");
-          format!("  | {{ {def} }} ")
+          match gen_flattened_types(&variant.fields) {
+            Some(flattened) => format!("  | {{ {def} }} & {flattened} "),
+            None => format!("  | {{ {def} }} "),
+          }
         })
         .collect::<Vec<_>>()
         .join("\\n"),
     }
   }
 }
+
+/// The intersection of the `#[napi(flatten)]` field types
+fn gen_flattened_types(fields: &[NapiStructField]) -> Option<String> {
+  let flattened = fields
+    .iter()
+    .filter(|f| f.flatten && !f.skip_typescript)
+    .map(|f| {
+      f.ts_type
+        .clone()
+        .unwrap_or_else(|| ty_to_ts_type(&f.ty, false, false, false).0)
+    })
+    .collect::<Vec<_>>();
+  (!flattened.is_empty()).then(|| flattened.join(" & "))
+}
@@ -0,0 +1,69 @@
+use convert_case::Case;
+
+use super::{r#fn::FnArg, ToTypeDef, TypeDef};
+
+use crate::{
+  to_case, ty_to_ts_type,
+  typegen::{add_alias, JSDoc},
+  NapiTrait,
+};
+
+impl ToTypeDef for NapiTrait {
+  fn to_type_def(&self) -> Option<TypeDef> {
+    if self.skip_typescript {
+      return None;
+    }
+
+    add_alias(self.name.to_string(), self.js_name.to_owned());
+    add_alias(format!("{}Proxy", self.name), self.js_name.to_owned());
+    if self.threadsafe {
+      add_alias(
+        format!("{}ThreadsafeProxy", self.name),
+        self.js_name.to_owned(),
+      );
+    }
+
+    let def = self
+      .methods
+      .iter()
+      .map(|method| {
+        let mut method_str = String::new();
+        if !method.comments.is_empty() {
+          method_str.push_str(&format!("{}", JSDoc::new(&method.comments)));
+        }
+        let args = method
+          .args
+          .iter()
+          .map(|(name, ty)| {
+            let (ts_type, is_optional) = ty_to_ts_type(ty, false, false, false);
+            FnArg {
+              arg: to_case(name.to_string(), Case::Camel),
+              ts_type,
+              is_optional,
+            }
+          })
+          .collect::<super::r#fn::FnArgList>();
+        let ret = method.ts_type.clone().unwrap_or_else(|| {
+          match ty_to_ts_type(&method.ret, true, false, false).0.as_str() {
+            "undefined" => "void".to_owned(),
+            ts_type => ts_type.to_owned(),
+          }
+        });
+        method_str.push_str(&format!("{}({args}): {ret}", method.js_name));
+        method_str
+      })
+      .collect::<Vec<_>>()
+      .join("\\n");
+
+    Some(TypeDef {
+      kind: "interface".to_owned(),
+      name: self.js_name.to_owned(),
+      original_name: Some(self.name.to_string()),
+      def,
+      extends: None,
+      implements: None,
+      js_mod: self.js_mod.to_owned(),
+      js_doc: JSDoc::new(&self.comments),
+    })
+  }
+}
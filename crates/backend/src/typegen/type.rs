@@ -17,6 +17,8 @@ impl ToTypeDef for NapiType {
     Some(TypeDef {
       kind: "type".to_owned(),
       name: self.js_name.to_owned(),
+      extends: None,
+      implements: None,
       original_name: Some(self.name.to_string()),
       def: ty_to_ts_type(&self.value, false, false, false).0,
       js_mod: self.js_mod.to_owned(),
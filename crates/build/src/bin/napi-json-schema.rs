@@ -0,0 +1,35 @@
+//! Bundle the JSON Schemas embedded in a built artifact into one schema, and print it or write it into a file
+//!
+//! ```sh
+//! napi-json-schema target/release/libmy_addon.so [schema.json]
+//! ```
+
+use std::env;
+use std::fs;
+use std::process;
+
+fn main() {
+  let mut args = env::args_os().skip(1);
+  let Some(artifact) = args.next() else {
+    eprintln!("Usage: napi-json-schema <artifact> [output]");
+    process::exit(2);
+  };
+  let schema = napi_build::json_schema::extract(&artifact)
+    .and_then(|lines| napi_build::json_schema::bundle(&lines))
+    .unwrap_or_else(|err| {
+      eprintln!(
+        "Failed to read JSON Schemas from {}: {err}",
+        artifact.display()
+      );
+      process::exit(1);
+    });
+  let mut output = serde_json::to_string_pretty(&schema).expect("Serialize JSON Schema failed");
+  output.push('\n');
+  match args.next() {
+    Some(file) => fs::write(&file, output).unwrap_or_else(|err| {
+      eprintln!("Failed to write JSON Schema into {}: {err}", file.display());
+      process::exit(1);
+    }),
+    None => print!("{output}"),
+  }
+}
@@ -0,0 +1,36 @@
+//! Print the type definitions embedded in a built artifact, or write them into a file
+//!
+//! ```sh
+//! napi-type-defs target/release/libmy_addon.so [type-defs.txt]
+//! ```
+
+use std::env;
+use std::fs;
+use std::process;
+
+fn main() {
+  let mut args = env::args_os().skip(1);
+  let Some(artifact) = args.next() else {
+    eprintln!("Usage: napi-type-defs <artifact> [output]");
+    process::exit(2);
+  };
+  let type_defs = napi_build::type_def::extract(&artifact).unwrap_or_else(|err| {
+    eprintln!(
+      "Failed to read type defs from {}: {err}",
+      artifact.display()
+    );
+    process::exit(1);
+  });
+  let mut output = String::new();
+  for type_def in type_defs {
+    output.push_str(&type_def);
+    output.push('\n');
+  }
+  match args.next() {
+    Some(file) => fs::write(&file, output).unwrap_or_else(|err| {
+      eprintln!("Failed to write type defs into {}: {err}", file.display());
+      process::exit(1);
+    }),
+    None => print!("{output}"),
+  }
+}
@@ -0,0 +1,210 @@
+//! Read the JSON Schemas `napi-derive` embeds in the built artifacts back and bundle them
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde_json::{json, Map, Value};
+
+use crate::type_def::{extract_section_lines, invalid_data};
+
+/// The link sections the JSON Schemas are embedded in, on ELF and wasm, Mach-O and PE
+const SECTION_NAMES: [&str; 3] = ["napi_json_schemas", "__napi_schemas", ".napijs"];
+
+const DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Read the JSON Schemas embedded in the `.node`, `.so`, `.dylib`, `.dll` or `.wasm` artifact at `path`
+/// by the `embed-json-schema` feature of `napi-derive`.
+///
+/// There's one line per exported object, enum, type alias and function arguments,
+/// `{"kind": "fn", "name": "plus", "schema": {...}}`, including the ones of the crates linked into the artifact.
+/// They refer to each other as `#/$defs/<name>`, see [`bundle`].
+pub fn extract<P: AsRef<Path>>(path: P) -> io::Result<Vec<String>> {
+  let data = fs::read(path)?;
+  extract_from_bytes(&data)
+}
+
+/// Read the JSON Schemas embedded in the artifact `data`, see [`extract`].
+pub fn extract_from_bytes(data: &[u8]) -> io::Result<Vec<String>> {
+  extract_section_lines(data, &SECTION_NAMES)
+}
+
+/// Bundle the JSON Schema `lines` read by [`extract`] into one schema, with each of them under `$defs` by its name.
+///
+/// The `#/$defs/<name>` references between them are resolved in the bundle,
+/// e.g. the arguments of the function `plus` are validated against `<bundle>#/$defs/plus`.
+/// The references to the types without a JSON Schema, like the traits, functions and type parameters,
+/// are removed since any value is valid against them.
+pub fn bundle<S: AsRef<str>>(lines: &[S]) -> io::Result<Value> {
+  let mut defs = Map::new();
+  for line in lines {
+    let mut item: Value = serde_json::from_str(line.as_ref()).map_err(invalid_data)?;
+    let Some(name) = item.get("name").and_then(Value::as_str).map(str::to_owned) else {
+      return Err(invalid_schema(format!(
+        "Missing the name of the JSON Schema `{}`",
+        line.as_ref()
+      )));
+    };
+    let Some(mut schema) = item.get_mut("schema").map(Value::take) else {
+      return Err(invalid_schema(format!(
+        "Missing the JSON Schema of `{name}`"
+      )));
+    };
+    // only the root of the bundle declares the dialect
+    if let Some(schema) = schema.as_object_mut() {
+      schema.remove("$schema");
+    }
+    if defs.insert(name.clone(), schema).is_some() {
+      return Err(invalid_schema(format!(
+        "Found more than one JSON Schema named `{name}`"
+      )));
+    }
+  }
+  let names = defs.keys().cloned().collect::<Vec<_>>();
+  for schema in defs.values_mut() {
+    remove_unresolved_refs(schema, &names);
+  }
+  Ok(json!({
+    "$schema": DIALECT,
+    "$defs": defs,
+  }))
+}
+
+fn remove_unresolved_refs(schema: &mut Value, names: &[String]) {
+  match schema {
+    Value::Object(entries) => {
+      let unresolved = entries
+        .get("$ref")
+        .and_then(Value::as_str)
+        .and_then(|reference| reference.strip_prefix("#/$defs/"))
+        .is_some_and(|name| !names.iter().any(|n| n == name));
+      if unresolved {
+        entries.remove("$ref");
+      }
+      for value in entries.values_mut() {
+        remove_unresolved_refs(value, names);
+      }
+    }
+    Value::Array(items) => {
+      for item in items {
+        remove_unresolved_refs(item, names);
+      }
+    }
+    _ => {}
+  }
+}
+
+fn invalid_schema(message: String) -> io::Error {
+  io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+  use object::write::{Object, Symbol, SymbolSection};
+  use object::{
+    Architecture, BinaryFormat, Endianness, SectionKind, SymbolFlags, SymbolKind, SymbolScope,
+  };
+  use serde_json::{json, Value};
+
+  use super::{bundle, extract_from_bytes, SECTION_NAMES};
+
+  const SCHEMAS: &str = concat!(
+    r##"{"kind": "object", "name": "Point", "schema": {"$schema": "https://json-schema.org/draft/2020-12/schema", "title": "Point", "type": "object", "properties": {"x": {"type": "number"}}, "required": ["x"]}}"##,
+    "\n",
+    r##"{"kind": "fn", "name": "distance", "schema": {"$schema": "https://json-schema.org/draft/2020-12/schema", "title": "distance", "type": "array", "prefixItems": [{"$ref": "#/$defs/Point"}], "items": false, "minItems": 1, "maxItems": 1}}"##,
+    "\n",
+  );
+
+  fn object_with_section(format: BinaryFormat, segment: &str, section: &str) -> Vec<u8> {
+    let mut obj = Object::new(format, Architecture::X86_64, Endianness::Little);
+    let id = obj.add_section(
+      segment.as_bytes().to_vec(),
+      section.as_bytes().to_vec(),
+      SectionKind::Data,
+    );
+    let offset = obj.append_section_data(id, SCHEMAS.as_bytes(), 1);
+    obj.add_symbol(Symbol {
+      name: b"LINES".to_vec(),
+      value: offset,
+      size: SCHEMAS.len() as u64,
+      kind: SymbolKind::Data,
+      scope: SymbolScope::Compilation,
+      weak: false,
+      section: SymbolSection::Section(id),
+      flags: SymbolFlags::None,
+    });
+    obj.write().unwrap()
+  }
+
+  /// All the `$ref`s in `schema`
+  fn refs(schema: &Value) -> Vec<&str> {
+    match schema {
+      Value::Object(entries) => entries
+        .iter()
+        .flat_map(|(key, value)| match (key.as_str(), value) {
+          ("$ref", Value::String(reference)) => vec![reference.as_str()],
+          (_, value) => refs(value),
+        })
+        .collect(),
+      Value::Array(items) => items.iter().flat_map(refs).collect(),
+      _ => vec![],
+    }
+  }
+
+  #[test]
+  fn test_extract() {
+    for (format, segment, section) in [
+      (BinaryFormat::Elf, "", SECTION_NAMES[0]),
+      (BinaryFormat::MachO, "__DATA", SECTION_NAMES[1]),
+      (BinaryFormat::Coff, "", SECTION_NAMES[2]),
+    ] {
+      let data = object_with_section(format, segment, section);
+      assert_eq!(
+        extract_from_bytes(&data).unwrap(),
+        SCHEMAS.lines().collect::<Vec<_>>(),
+        "{format:?}"
+      );
+    }
+  }
+
+  #[test]
+  fn test_bundle() {
+    let lines = SCHEMAS.lines().collect::<Vec<_>>();
+    let bundled = bundle(&lines).unwrap();
+    assert_eq!(
+      bundled["$schema"],
+      "https://json-schema.org/draft/2020-12/schema"
+    );
+    assert_eq!(
+      bundled["$defs"]["Point"],
+      json!({
+        "title": "Point",
+        "type": "object",
+        "properties": {"x": {"type": "number"}},
+        "required": ["x"],
+      })
+    );
+    assert_eq!(bundled["$defs"]["distance"]["title"], "distance");
+    for reference in refs(&bundled) {
+      let pointer = reference.strip_prefix('#').unwrap();
+      assert!(bundled.pointer(pointer).is_some(), "{reference}");
+    }
+  }
+
+  #[test]
+  fn test_bundle_unresolved_refs() {
+    let line = SCHEMAS.lines().nth(1).unwrap();
+    let bundled = bundle(&[line]).unwrap();
+    assert_eq!(bundled["$defs"]["distance"]["prefixItems"], json!([{}]));
+  }
+
+  #[test]
+  fn test_bundle_duplicated_names() {
+    let line = SCHEMAS.lines().next().unwrap();
+    let err = bundle(&[line, line]).unwrap_err();
+    assert_eq!(
+      err.to_string(),
+      "Found more than one JSON Schema named `Point`"
+    );
+  }
+}
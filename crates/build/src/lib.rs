@@ -1,6 +1,10 @@
 use std::env;
 
 mod android;
+#[cfg(feature = "json-schema")]
+pub mod json_schema;
+#[cfg(feature = "type-def")]
+pub mod type_def;
 mod wasi;
 mod windows;
 
@@ -0,0 +1,189 @@
+//! Read the type definitions `napi-derive` embeds in the built artifacts back
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use object::{Object, ObjectSection};
+
+/// The link sections the type definitions are embedded in, on ELF and wasm, Mach-O and PE
+const SECTION_NAMES: [&str; 3] = ["napi_type_defs", "__napi_typedefs", ".napitd"];
+
+/// Read the type definitions embedded in the `.node`, `.so`, `.dylib`, `.dll` or `.wasm` artifact at `path`.
+///
+/// They're the same lines `napi-derive` writes into the `NAPI_TYPE_DEF_TMP_FOLDER` files,
+/// including the ones of the crates linked into the artifact,
+/// but they only depend on the artifact, not on whether the macros were expanded by the last build.
+pub fn extract<P: AsRef<Path>>(path: P) -> io::Result<Vec<String>> {
+  let data = fs::read(path)?;
+  extract_from_bytes(&data)
+}
+
+/// Read the type definitions embedded in the artifact `data`, see [`extract`].
+pub fn extract_from_bytes(data: &[u8]) -> io::Result<Vec<String>> {
+  extract_section_lines(data, &SECTION_NAMES)
+}
+
+/// Read the lines of the sections named `section_names` in the artifact `data`
+pub(crate) fn extract_section_lines(
+  data: &[u8],
+  section_names: &[&str],
+) -> io::Result<Vec<String>> {
+  let file = object::File::parse(data).map_err(invalid_data)?;
+  let mut lines = Vec::new();
+  for section in file.sections() {
+    if !section
+      .name()
+      .is_ok_and(|name| section_names.contains(&name))
+    {
+      continue;
+    }
+    let data = section.data().map_err(invalid_data)?;
+    let section_lines = std::str::from_utf8(data).map_err(invalid_data)?;
+    lines.extend(
+      section_lines
+        .split('\n')
+        // the linkers may pad the contributions of the object files with zeros
+        .map(|line| line.trim_matches('\0'))
+        .filter(|line| !line.is_empty())
+        .map(str::to_owned),
+    );
+  }
+  Ok(lines)
+}
+
+pub(crate) fn invalid_data<E: std::error::Error + Send + Sync + 'static>(err: E) -> io::Error {
+  io::Error::new(io::ErrorKind::InvalidData, err)
+}
+
+#[cfg(test)]
+mod tests {
+  use object::pe;
+  use object::write::pe::{NtHeaders, Writer};
+  use object::write::{Object, Symbol, SymbolSection};
+  use object::{
+    Architecture, BinaryFormat, Endianness, SectionKind, SymbolFlags, SymbolKind, SymbolScope,
+  };
+
+  use super::{extract_from_bytes, SECTION_NAMES};
+
+  const FIRST_CRATE: &str = "{\"kind\": \"fn\", \"name\": \"plus\", \"def\": \"function plus(a: number, b: number): number\"}\n";
+  const SECOND_CRATE: &str =
+    "{\"kind\": \"interface\", \"name\": \"Shared\", \"def\": \"value: number\"}\n";
+
+  fn expected() -> Vec<String> {
+    FIRST_CRATE
+      .lines()
+      .chain(SECOND_CRATE.lines())
+      .map(str::to_owned)
+      .collect()
+  }
+
+  /// An object file with the `TYPE_DEFS` statics of two crates in `section`, aligned like the linkers do
+  fn object_with_section(format: BinaryFormat, segment: &str, section: &str) -> Vec<u8> {
+    let mut obj = Object::new(format, Architecture::X86_64, Endianness::Little);
+    let id = obj.add_section(
+      segment.as_bytes().to_vec(),
+      section.as_bytes().to_vec(),
+      SectionKind::Data,
+    );
+    let offset = obj.append_section_data(id, FIRST_CRATE.as_bytes(), 1);
+    obj.append_section_data(id, SECOND_CRATE.as_bytes(), 64);
+    obj.add_symbol(Symbol {
+      name: b"TYPE_DEFS".to_vec(),
+      value: offset,
+      size: FIRST_CRATE.len() as u64,
+      kind: SymbolKind::Data,
+      scope: SymbolScope::Compilation,
+      weak: false,
+      section: SymbolSection::Section(id),
+      flags: SymbolFlags::None,
+    });
+    obj.write().unwrap()
+  }
+
+  #[test]
+  fn test_extract_from_elf() {
+    let data = object_with_section(BinaryFormat::Elf, "", SECTION_NAMES[0]);
+    assert_eq!(extract_from_bytes(&data).unwrap(), expected());
+  }
+
+  #[test]
+  fn test_extract_from_mach_o() {
+    let data = object_with_section(BinaryFormat::MachO, "__DATA", SECTION_NAMES[1]);
+    assert_eq!(extract_from_bytes(&data).unwrap(), expected());
+  }
+
+  #[test]
+  fn test_extract_from_coff() {
+    let data = object_with_section(BinaryFormat::Coff, "", SECTION_NAMES[2]);
+    assert_eq!(extract_from_bytes(&data).unwrap(), expected());
+  }
+
+  #[test]
+  fn test_extract_from_pe() {
+    let type_defs = format!("{FIRST_CRATE}{SECOND_CRATE}");
+    let len = type_defs.len() as u32;
+    let mut data = Vec::new();
+    let mut writer = Writer::new(true, 0x1000, 0x200, &mut data);
+    writer.reserve_dos_header_and_stub();
+    writer.reserve_nt_headers(pe::IMAGE_NUMBEROF_DIRECTORY_ENTRIES);
+    writer.reserve_section_headers(1);
+    let mut name = [0; 8];
+    name[..SECTION_NAMES[2].len()].copy_from_slice(SECTION_NAMES[2].as_bytes());
+    let range = writer.reserve_section(
+      name,
+      pe::IMAGE_SCN_CNT_INITIALIZED_DATA | pe::IMAGE_SCN_MEM_READ,
+      len,
+      len,
+    );
+    writer.write_dos_header_and_stub().unwrap();
+    writer.write_nt_headers(NtHeaders {
+      machine: pe::IMAGE_FILE_MACHINE_AMD64,
+      time_date_stamp: 0,
+      characteristics: pe::IMAGE_FILE_EXECUTABLE_IMAGE | pe::IMAGE_FILE_DLL,
+      major_linker_version: 14,
+      minor_linker_version: 0,
+      address_of_entry_point: 0,
+      image_base: 0x1_8000_0000,
+      major_operating_system_version: 6,
+      minor_operating_system_version: 0,
+      major_image_version: 0,
+      minor_image_version: 0,
+      major_subsystem_version: 6,
+      minor_subsystem_version: 0,
+      subsystem: pe::IMAGE_SUBSYSTEM_WINDOWS_CUI,
+      dll_characteristics: 0,
+      size_of_stack_reserve: 0x10_0000,
+      size_of_stack_commit: 0x1000,
+      size_of_heap_reserve: 0x10_0000,
+      size_of_heap_commit: 0x1000,
+    });
+    writer.write_section_headers();
+    // the section is padded with zeros up to the file alignment
+    writer.write_section(range.file_offset, type_defs.as_bytes());
+    assert_eq!(extract_from_bytes(&data).unwrap(), expected());
+  }
+
+  #[test]
+  fn test_extract_from_wasm() {
+    // a module with only the custom section the linker emits for `link_section`
+    let name = SECTION_NAMES[0].as_bytes();
+    let payload = format!("{FIRST_CRATE}{SECOND_CRATE}");
+    let section_len = 1 + name.len() + payload.len();
+    assert!(section_len < 0x80 * 0x80);
+    let mut data = b"\0asm\x01\0\0\0".to_vec();
+    data.push(0);
+    data.extend([(section_len as u8 & 0x7f) | 0x80, (section_len >> 7) as u8]);
+    data.push(name.len() as u8);
+    data.extend(name);
+    data.extend(payload.as_bytes());
+    assert_eq!(extract_from_bytes(&data).unwrap(), expected());
+  }
+
+  #[test]
+  fn test_extract_without_type_defs() {
+    let data = object_with_section(BinaryFormat::Elf, "", ".data");
+    assert!(extract_from_bytes(&data).unwrap().is_empty());
+  }
+}
@@ -45,6 +45,7 @@ pub fn expand(attr: TokenStream, input: TokenStream) -> BindgenResult<TokenStrea
             Item::Enum(ref mut enum_) => &mut enum_.attrs,
             Item::Const(ref mut const_) => &mut const_.attrs,
             Item::Impl(ref mut impl_) => &mut impl_.attrs,
+            Item::Trait(ref mut trait_) => &mut trait_.attrs,
             Item::Mod(mod_) => {
               let mod_in_mod = mod_
                 .attrs
@@ -66,7 +67,7 @@ pub fn expand(attr: TokenStream, input: TokenStream) -> BindgenResult<TokenStrea
           let napi = item.parse_napi(&mut tokens, &item_opts)?;
           item_opts.check_used()?;
           napi.try_to_tokens(&mut tokens)?;
-          typedef::output_type_def(&napi);
+          typedef::output_type_def(&napi, &mut tokens);
         } else {
           item.to_tokens(&mut tokens);
         };
@@ -88,7 +89,7 @@ pub fn expand(attr: TokenStream, input: TokenStream) -> BindgenResult<TokenStrea
     let napi = item.parse_napi(&mut tokens, &opts)?;
     opts.check_used()?;
     napi.try_to_tokens(&mut tokens)?;
-    typedef::output_type_def(&napi);
+    typedef::output_type_def(&napi, &mut tokens);
     Ok(tokens)
   }
 }
@@ -14,6 +14,14 @@ pub fn expand(_attr: TokenStream, input: TokenStream) -> BindgenResult<TokenStre
       .for_each(|field| find_and_remove_napi_attr(&mut field.attrs))
   }
 
+  if let syn::Item::Trait(ref mut trait_) = item {
+    trait_.items.iter_mut().for_each(|item| {
+      if let syn::TraitItem::Fn(method) = item {
+        find_and_remove_napi_attr(&mut method.attrs)
+      }
+    })
+  }
+
   item.to_tokens(&mut tokens);
 
   Ok(tokens)
@@ -1,4 +1,5 @@
 use napi_derive_backend::Napi;
+use proc_macro2::TokenStream;
 
 pub fn prepare_type_def_file() {}
-pub fn output_type_def(_napi: &Napi) {}
+pub fn output_type_def(_napi: &Napi, _tokens: &mut TokenStream) {}
@@ -4,7 +4,9 @@ use std::io::{BufWriter, Write};
 use std::path::PathBuf;
 use std::sync::LazyLock;
 
-use napi_derive_backend::{Napi, ToTypeDef};
+use napi_derive_backend::{Napi, ToJsonSchema};
+use proc_macro2::{Literal, TokenStream};
+use quote::{quote, ToTokens};
 
 static PKG_NAME: LazyLock<String> =
   LazyLock::new(|| env::var("CARGO_PKG_NAME").expect("Expected `CARGO_PKG_NAME` to be set"));
@@ -43,22 +45,72 @@ fn remove_existed_def_file() {
   }
 }
 
-pub fn output_type_def(napi: &Napi) {
-  if let Some(file) = get_type_def_file() {
-    if let Some(type_def) = napi.to_type_def() {
-      fs::OpenOptions::new()
-        .append(true)
-        .create(true)
-        .open(&file)
-        .and_then(|file| {
-          let mut writer = BufWriter::<fs::File>::new(file);
-          writer.write_all(type_def.to_string().as_bytes())?;
-          writer.write_all("\n".as_bytes())?;
-          writer.flush()
-        })
-        .unwrap_or_else(|e| {
-          println!("Failed to write type def file: {e:?}");
-        });
+fn append_lines(file: &PathBuf, lines: &str) {
+  fs::OpenOptions::new()
+    .append(true)
+    .create(true)
+    .open(file)
+    .and_then(|file| {
+      let mut writer = BufWriter::<fs::File>::new(file);
+      writer.write_all(lines.as_bytes())?;
+      writer.flush()
+    })
+    .unwrap_or_else(|e| {
+      println!("Failed to write type def file: {e:?}");
+    });
+}
+
+pub fn output_type_def(napi: &Napi, tokens: &mut TokenStream) {
+  let type_defs = napi
+    .to_type_defs()
+    .into_iter()
+    .map(|type_def| format!("{type_def}\n"))
+    .collect::<String>();
+  if !type_defs.is_empty() {
+    if let Some(file) = get_type_def_file() {
+      append_lines(&file, &type_defs);
+    }
+    if cfg!(feature = "embed-type-def") {
+      embed_lines(&type_defs, EmbeddedSection::TypeDefs, tokens);
+    }
+  }
+  // after the type defs, which register the JavaScript names of the types
+  if cfg!(feature = "embed-json-schema") {
+    if let Some(json_schema) = napi.to_json_schema() {
+      embed_lines(
+        &format!("{json_schema}\n"),
+        EmbeddedSection::JsonSchemas,
+        tokens,
+      );
     }
   }
 }
+
+/// The link sections `napi_build` reads the embedded lines back from
+enum EmbeddedSection {
+  /// The type def lines, with the `embed-type-def` feature
+  TypeDefs,
+  /// The JSON Schema lines, with the `embed-json-schema` feature
+  JsonSchemas,
+}
+
+/// Embed the `lines` in the `section` of the artifact, `napi_build::type_def::extract` and
+/// `napi_build::json_schema::extract` read them back even if the macros are not expanded again by incremental builds.
+fn embed_lines(lines: &str, section: EmbeddedSection, tokens: &mut TokenStream) {
+  let (apple, windows, other) = match section {
+    EmbeddedSection::TypeDefs => ("__DATA,__napi_typedefs", ".napitd", "napi_type_defs"),
+    EmbeddedSection::JsonSchemas => ("__DATA,__napi_schemas", ".napijs", "napi_json_schemas"),
+  };
+  let len = lines.len();
+  let bytes = Literal::byte_string(lines.as_bytes());
+  quote! {
+    const _: () = {
+      #[used]
+      #[cfg_attr(target_vendor = "apple", link_section = #apple)]
+      #[cfg_attr(windows, link_section = #windows)]
+      #[cfg_attr(not(any(target_vendor = "apple", windows)), link_section = #other)]
+      static LINES: [u8; #len] = *#bytes;
+    };
+  }
+  .to_tokens(tokens);
+}
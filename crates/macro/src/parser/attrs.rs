@@ -68,7 +68,19 @@ macro_rules! attrgen {
       (object, Object(Span)),
       (object_from_js, ObjectFromJs(Span, Option<bool>), true),
       (object_to_js, ObjectToJs(Span, Option<bool>), true),
-      (custom_finalize, CustomFinalize(Span)),
+      (collect_errors, CollectErrors(Span)),
+      (custom_finalize, CustomFinalize(Span, Option<(String, Span)>)),
+      (dispose, Dispose(Span)),
+      (async_dispose, AsyncDispose(Span)),
+      (extends, Extends(Span, syn::Path)),
+      (remote, Remote(Span, syn::Path)),
+      (with, With(Span, syn::Path)),
+      (return_with, ReturnWith(Span, syn::Path)),
+      (rename_all, RenameAll(Span, String, Span)),
+      (field_default, Default(Span, Option<syn::Expr>)),
+      (flatten, Flatten(Span)),
+      (error, Error(Span)),
+      (code, Code(Span, String, Span)),
       (namespace, Namespace(Span, String, Span)),
       (iterator, Iterator(Span)),
       (async_iterator, AsyncIterator(Span)),
@@ -77,12 +89,14 @@ macro_rules! attrgen {
       (ts_type, TsType(Span, String, Span)),
       (ts_generic_types, TsGenericTypes(Span, String, Span)),
       (string_enum, StringEnum(Span, Option<(String, Span)>)),
+      (flags, Flags(Span)),
       (use_nullable, UseNullable(Span, Option<bool>), false),
       (discriminant, Discriminant(Span, String, Span)),
       (discriminant_case, DiscriminantCase(Span, String, Span)),
       (transparent, Transparent(Span)),
       (array, Array(Span)),
       (no_export, NoExport(Span)),
+      (threadsafe, Threadsafe(Span)),
 
       // impl later
       // (inspectable, Inspectable(Span)),
@@ -416,10 +430,20 @@ impl Parse for BindgenAttr {
     let attr_string = attr.to_string();
     let raw_attr_string = format!("r#{attr_string}");
 
+    // the accessor of `default` is `field_default`, so it doesn't shadow `Default::default`
+    macro_rules! keyword {
+      (field_default) => {
+        "default"
+      };
+      ($name:ident) => {
+        stringify!($name)
+      };
+    }
+
     macro_rules! parsers {
       ($(($name:ident, $($contents:tt)*),)*) => {
         $(
-          if attr_string == stringify!($name) || raw_attr_string == stringify!($name) {
+          if attr_string == keyword!($name) || raw_attr_string == keyword!($name) {
             parsers!(
               @parser
               $($contents)*
@@ -457,6 +481,14 @@ impl Parse for BindgenAttr {
             return Ok(BindgenAttr::$variant(attr_span, input.parse()?));
         });
 
+        (@parser $variant:ident(Span, Option<syn::Expr>)) => ({
+            if input.parse::<Token![=]>().is_ok() {
+              return Ok(BindgenAttr::$variant(attr_span, Some(input.parse()?)));
+            } else {
+              return Ok(BindgenAttr::$variant(attr_span, None));
+            }
+        });
+
         (@parser $variant:ident(Span, String, Span)) => ({
           input.parse::<Token![=]>()?;
           let (val, span) = match input.parse::<syn::LitStr>() {
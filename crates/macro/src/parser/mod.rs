@@ -10,9 +10,10 @@ use attrs::BindgenAttrs;
 use convert_case::Case;
 use napi_derive_backend::{
   rm_raw_prefix, to_case, BindgenResult, CallbackArg, Diagnostic, FnKind, FnSelf, Napi, NapiArray,
-  NapiClass, NapiConst, NapiEnum, NapiEnumValue, NapiEnumVariant, NapiFn, NapiFnArg, NapiFnArgKind,
-  NapiImpl, NapiItem, NapiObject, NapiStruct, NapiStructField, NapiStructKind, NapiStructuredEnum,
-  NapiStructuredEnumVariant, NapiTransparent, NapiType,
+  NapiClass, NapiClassExtends, NapiConst, NapiEnum, NapiEnumValue, NapiEnumVariant, NapiError,
+  NapiErrorVariant, NapiFn, NapiFnArg, NapiFnArgKind, NapiImpl, NapiItem, NapiObject, NapiStruct,
+  NapiStructField, NapiStructKind, NapiStructuredEnum, NapiStructuredEnumVariant, NapiTrait,
+  NapiTraitMethod, NapiTransparent, NapiType,
 };
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::ToTokens;
@@ -83,6 +84,8 @@ pub trait ParseNapi {
 /// This function does a few things:
 /// - parses the tokens for the given argument `p` to find the `#[napi(ts_arg_type = "MyType")]`
 ///   attribute and return the manually overridden type.
+/// - finds the `#[napi(with = MyTypeDef)]` attribute and return the `#[napi(object, remote = ...)]`
+///   mirror the argument is converted through.
 /// - If both the `ts_args_type` override and the `ts_arg_type` override are present, bail
 ///   since it should only allow one at a time.
 /// - Bails if it finds the `#[napi...]` attribute but it has the wrong data.
@@ -91,26 +94,20 @@ pub trait ParseNapi {
 ///   `  `turns into
 ///   `pub fn add(u: u32, f: String)`
 ///   `  `otherwise it won't compile
-fn find_ts_arg_type_and_remove_attribute(
+fn find_arg_attrs_and_remove_attribute(
   p: &mut PatType,
   ts_args_type: Option<&(&str, Span)>,
-) -> BindgenResult<Option<String>> {
-  let mut ts_type_attr: Option<(usize, String)> = None;
+) -> BindgenResult<(Option<String>, Option<syn::Path>)> {
+  let mut ts_type_attr: Option<String> = None;
+  let mut with_attr: Option<syn::Path> = None;
+  let mut napi_attr_idx = None;
   for (idx, attr) in p.attrs.iter().enumerate() {
     if attr.path().is_ident("napi") {
-      if let Some((ts_args_type, _)) = ts_args_type {
-        bail_span!(
-          attr,
-          "Found a 'ts_args_type'=\"{}\" override. Cannot use 'ts_arg_type' at the same time since they are mutually exclusive.",
-          ts_args_type
-        );
-      }
-
       match &attr.meta {
         syn::Meta::Path(_) | syn::Meta::NameValue(_) => {
           bail_span!(
             attr,
-            "Expects an assignment #[napi(ts_arg_type = \"MyType\")]"
+            "Expects an assignment #[napi(ts_arg_type = \"MyType\")] or #[napi(with = MyTypeDef)]"
           )
         }
         syn::Meta::List(list) => {
@@ -138,7 +135,7 @@ fn find_ts_arg_type_and_remove_attribute(
                       }) => {
                         let value = str.value();
                         found = true;
-                        ts_type_attr = Some((idx, value));
+                        ts_type_attr = Some(value);
                       }
                       _ => {
                         return Err(syn::Error::new(
@@ -148,6 +145,22 @@ fn find_ts_arg_type_and_remove_attribute(
                       }
                     },
                   }
+                } else if meta.path().is_ident("with") {
+                  match meta {
+                    Meta::NameValue(syn::MetaNameValue {
+                      value: syn::Expr::Path(syn::ExprPath { path, .. }),
+                      ..
+                    }) => {
+                      found = true;
+                      with_attr = Some(path);
+                    }
+                    _ => {
+                      return Err(syn::Error::new(
+                        meta.path().span(),
+                        "Expects an assignment (with = MyTypeDef)",
+                      ));
+                    }
+                  }
                 }
               }
 
@@ -158,17 +171,23 @@ fn find_ts_arg_type_and_remove_attribute(
           if !found {
             bail_span!(attr, "Expects a 'ts_arg_type'");
           }
+          if let (Some((ts_args_type, _)), Some(_)) = (ts_args_type, &ts_type_attr) {
+            bail_span!(
+              attr,
+              "Found a 'ts_args_type'=\"{}\" override. Cannot use 'ts_arg_type' at the same time since they are mutually exclusive.",
+              ts_args_type
+            );
+          }
+          napi_attr_idx = Some(idx);
         }
       }
     }
   }
 
-  if let Some((idx, value)) = ts_type_attr {
+  if let Some(idx) = napi_attr_idx {
     p.attrs.remove(idx);
-    Ok(Some(value))
-  } else {
-    Ok(None)
   }
+  Ok((ts_type_attr, with_attr))
 }
 
 fn find_enum_value_and_remove_attribute(v: &mut syn::Variant) -> BindgenResult<Option<String>> {
@@ -597,14 +616,22 @@ fn napi_fn_from_decl(
     .iter_mut()
     .filter_map(|arg| match arg {
       syn::FnArg::Typed(ref mut p) => {
-        let ts_arg_type = find_ts_arg_type_and_remove_attribute(p, opts.ts_args_type().as_ref())
-          .unwrap_or_else(|e| {
-            errors.push(e);
-            None
-          });
+        let (ts_arg_type, with) =
+          find_arg_attrs_and_remove_attribute(p, opts.ts_args_type().as_ref()).unwrap_or_else(
+            |e| {
+              errors.push(e);
+              (None, None)
+            },
+          );
 
         let ty_str = p.ty.to_token_stream().to_string();
         if let Some(path_arguments) = callback_traits.get(&ty_str) {
+          if let Some(with) = &with {
+            errors.push(err_span!(
+              with,
+              "#[napi(with = ...)] can't be used on a callback argument"
+            ));
+          }
           match extract_callback_trait_types(path_arguments) {
             Ok((fn_args, fn_ret)) => Some(NapiFnArg {
               kind: NapiFnArgKind::Callback(Box::new(CallbackArg {
@@ -613,6 +640,7 @@ fn napi_fn_from_decl(
                 ret: fn_ret,
               })),
               ts_arg_type,
+              with: None,
             }),
             Err(e) => {
               errors.push(e);
@@ -625,6 +653,7 @@ fn napi_fn_from_decl(
           Some(NapiFnArg {
             kind: NapiFnArgKind::PatType(Box::new(p.clone())),
             ts_arg_type,
+            with,
           })
         }
       }
@@ -860,6 +889,36 @@ fn napi_fn_from_decl(
       bail_span!(sig.ident, "Constructor don't support asynchronous function");
     }
 
+    let well_known_symbol = match (opts.dispose(), opts.async_dispose()) {
+      (Some(_), Some(_)) => bail_span!(
+        sig.ident,
+        "#[napi(dispose)] and #[napi(async_dispose)] can't be applied to the same fn"
+      ),
+      (Some(_), None) if asyncness.is_some() => bail_span!(
+        sig.ident,
+        "#[napi(dispose)] can't be applied to async fn, use #[napi(async_dispose)] instead"
+      ),
+      (Some(_), None) => Some("dispose"),
+      (None, Some(_)) => Some("asyncDispose"),
+      (None, None) => None,
+    };
+
+    if let Some(return_with) = opts.return_with() {
+      if ret.is_none() || matches!(kind, FnKind::Constructor | FnKind::Factory) {
+        bail_span!(
+          return_with,
+          "#[napi(return_with = ...)] can only be applied to fn returning a value"
+        );
+      }
+    }
+
+    if well_known_symbol.is_some() && (fn_self.is_none() || !matches!(kind, FnKind::Normal)) {
+      bail_span!(
+        sig.ident,
+        "#[napi(dispose)] and #[napi(async_dispose)] can only be applied to methods with `&self` or `&mut self`"
+      );
+    }
+
     Ok(NapiFn {
       name: ident.clone(),
       js_name,
@@ -883,6 +942,7 @@ fn napi_fn_from_decl(
       ts_generic_types: opts.ts_generic_types().map(|(m, _)| m.to_owned()),
       ts_args_type: opts.ts_args_type().map(|(m, _)| m.to_owned()),
       ts_return_type: opts.ts_return_type().map(|(m, _)| m.to_owned()),
+      return_with: opts.return_with().cloned(),
       skip_typescript: opts.skip_typescript().is_some(),
       parent_is_generator,
       parent_is_async_generator,
@@ -893,6 +953,7 @@ fn napi_fn_from_decl(
       unsafe_: sig.unsafety.is_some(),
       register_name: get_register_ident(ident.to_string().as_str()),
       no_export: opts.no_export().is_some(),
+      well_known_symbol: well_known_symbol.map(|s| s.to_owned()),
     })
   })
 }
@@ -906,9 +967,10 @@ impl ParseNapi for syn::Item {
       syn::Item::Enum(e) => e.parse_napi(tokens, opts),
       syn::Item::Const(c) => c.parse_napi(tokens, opts),
       syn::Item::Type(c) => c.parse_napi(tokens, opts),
+      syn::Item::Trait(t) => t.parse_napi(tokens, opts),
       _ => bail_span!(
         self,
-        "#[napi] can only be applied to a function, struct, enum, const, mod or impl."
+        "#[napi] can only be applied to a function, struct, enum, const, mod, trait or impl."
       ),
     }
   }
@@ -1046,7 +1108,12 @@ impl ParseNapi for syn::ItemEnum {
       );
     }
     let napi = self.convert_to_ast(opts);
-    self.to_tokens(tokens);
+    match &napi {
+      Ok(Napi {
+        item: NapiItem::Enum(napi_enum),
+      }) if napi_enum.is_flags => flags_to_tokens(self, napi_enum, tokens),
+      _ => self.to_tokens(tokens),
+    }
 
     napi
   }
@@ -1122,6 +1189,43 @@ impl ParseNapi for syn::ItemType {
   }
 }
 
+impl ParseNapi for syn::ItemTrait {
+  fn parse_napi(&mut self, tokens: &mut TokenStream, opts: &BindgenAttrs) -> BindgenResult<Napi> {
+    if opts.ts_args_type().is_some()
+      || opts.ts_return_type().is_some()
+      || opts.ts_type().is_some()
+      || opts.custom_finalize().is_some()
+    {
+      bail_span!(
+        self,
+        "#[napi] can't be applied to a trait with #[napi(ts_args_type)], #[napi(ts_return_type)] or #[napi(ts_type)] or #[napi(custom_finalize)]"
+      );
+    }
+    if opts.return_if_invalid().is_some() {
+      bail_span!(
+        self,
+        "#[napi(return_if_invalid)] can only be applied to a function or method."
+      );
+    }
+    if opts.catch_unwind().is_some() {
+      bail_span!(
+        self,
+        "#[napi(catch_unwind)] can only be applied to a function or method."
+      );
+    }
+    if opts.no_export().is_some() {
+      bail_span!(
+        self,
+        "#[napi(no_export)] can only be applied to a function."
+      );
+    }
+    // #[napi] attributes will be removed from the trait methods after converted to ast
+    let napi = self.convert_to_ast(opts);
+    self.to_tokens(tokens);
+    napi
+  }
+}
+
 fn fn_kind(opts: &BindgenAttrs) -> FnKind {
   let mut kind = FnKind::Normal;
 
@@ -1161,9 +1265,27 @@ impl ConvertToAST for syn::ItemFn {
   }
 }
 
+const CASE_NAMES: &str = r#""lowercase", "UPPERCASE", "PascalCase", "camelCase", "snake_case", "UPPER_SNAKE", "kebab-case", or "UPPER-KEBAB-CASE""#;
+
+/// Parse the case names used by `string_enum`, `discriminant_case` and `rename_all`
+fn parse_case(case: &str) -> Option<Case<'static>> {
+  match case {
+    "lowercase" => Some(Case::Flat),
+    "UPPERCASE" => Some(Case::UpperFlat),
+    "PascalCase" => Some(Case::Pascal),
+    "camelCase" => Some(Case::Camel),
+    "snake_case" => Some(Case::Snake),
+    "UPPER_SNAKE" => Some(Case::UpperSnake),
+    "kebab-case" => Some(Case::Kebab),
+    "UPPER-KEBAB-CASE" => Some(Case::UpperKebab),
+    _ => None,
+  }
+}
+
 fn convert_fields(
   fields: &mut syn::Fields,
   check_vis: bool,
+  rename_all: Case<'static>,
 ) -> BindgenResult<(Vec<NapiStructField>, bool)> {
   let mut napi_fields = vec![];
   let is_tuple = matches!(fields, syn::Fields::Unnamed(_));
@@ -1177,7 +1299,7 @@ fn convert_fields(
     let (js_name, name) = match &field.ident {
       Some(ident) => (
         field_opts.js_name().map_or_else(
-          || to_case(ident.unraw().to_string(), Case::Camel),
+          || to_case(ident.unraw().to_string(), rename_all),
           |(js_name, _)| js_name.to_owned(),
         ),
         syn::Member::Named(ident.clone()),
@@ -1197,6 +1319,12 @@ fn convert_fields(
     let configurable = field_opts.configurable();
     let skip_typescript = field_opts.skip_typescript().is_some();
     let ts_type = field_opts.ts_type().map(|e| e.0.to_string());
+    let remote_getter = field_opts.getter().cloned().flatten();
+    let default = field_opts.field_default().map(|default| {
+      default
+        .clone()
+        .unwrap_or_else(|| syn::parse_quote!(::core::default::Default::default()))
+    });
 
     let mut ty = field.ty.clone();
 
@@ -1238,23 +1366,221 @@ fn convert_fields(
       skip_typescript,
       ts_type,
       has_lifetime,
+      remote_getter,
+      default,
+      flatten: field_opts.flatten().is_some(),
+      with: field_opts.with().cloned(),
     })
   }
   Ok((napi_fields, is_tuple))
 }
 
+/// `#[napi(with = ...)]` on a field is only supported in `#[napi(object)]` structs
+fn check_fields_without_with(fields: &[NapiStructField]) -> BindgenResult<()> {
+  if let Some(with) = fields.iter().find_map(|field| field.with.as_ref()) {
+    bail_span!(
+      with,
+      "#[napi(with = ...)] on a field is only supported in #[napi(object)] structs"
+    );
+  }
+  Ok(())
+}
+
+/// `#[napi(error)]` on a struct or an enum, `variants` are the converted variants of the enum or the struct itself
+fn convert_error(
+  ident: &Ident,
+  generics: &syn::Generics,
+  attrs: &[syn::Attribute],
+  opts: &BindgenAttrs,
+  variants: Vec<NapiErrorVariant>,
+  is_enum: bool,
+) -> BindgenResult<Napi> {
+  if !generics.params.is_empty() {
+    bail_span!(generics, "#[napi(error)] doesn't support generics");
+  }
+  if opts.object().is_some()
+    || opts.transparent().is_some()
+    || opts.array().is_some()
+    || opts.string_enum().is_some()
+  {
+    bail_span!(
+      ident,
+      "#[napi(error)] can't be used with #[napi(object)], #[napi(transparent)], #[napi(array)] or #[napi(string_enum)]"
+    );
+  }
+  let mut errors = vec![];
+  for field in variants.iter().flat_map(|variant| variant.fields.iter()) {
+    if field.default.is_some() || field.flatten {
+      errors.push(err_span!(
+        field.name,
+        "#[napi(default)] and #[napi(flatten)] are not supported in #[napi(error)]"
+      ));
+    }
+  }
+  Diagnostic::from_vec(errors)?;
+
+  Ok(Napi {
+    item: NapiItem::Error(NapiError {
+      name: ident.clone(),
+      js_name: opts
+        .js_name()
+        .map_or_else(|| ident.to_string(), |(js_name, _)| js_name.to_owned()),
+      js_mod: opts.namespace().map(|(m, _)| m.to_owned()),
+      comments: extract_doc_comments(attrs),
+      register_name: get_register_ident(format!("{ident}_error").as_str()),
+      variants,
+      is_enum,
+    }),
+  })
+}
+
 impl ConvertToAST for syn::ItemStruct {
   fn convert_to_ast(&mut self, opts: &BindgenAttrs) -> BindgenResult<Napi> {
+    if opts.error().is_some() {
+      let (fields, is_tuple) = convert_fields(&mut self.fields, true, Case::Camel)?;
+      check_fields_without_with(&fields)?;
+      let variant = NapiErrorVariant {
+        name: None,
+        js_name: opts
+          .js_name()
+          .map_or_else(|| self.ident.to_string(), |(js_name, _)| js_name.to_owned()),
+        code: opts.code().map_or_else(
+          || to_case(self.ident.unraw().to_string(), Case::UpperSnake),
+          |(code, _)| code.to_owned(),
+        ),
+        fields,
+        is_tuple,
+        comments: vec![],
+      };
+      return convert_error(
+        &self.ident,
+        &self.generics,
+        &self.attrs,
+        opts,
+        vec![variant],
+        false,
+      );
+    }
+
     let mut errors = vec![];
 
     let rust_struct_ident: Ident = self.ident.clone();
+    let remote = opts.remote().cloned();
+    // the mirror of a remote type is named after the remote type in TypeScript
     let final_js_name_for_struct = opts.js_name().map_or_else(
-      || to_case(self.ident.to_string(), Case::Pascal),
+      || {
+        remote
+          .as_ref()
+          .and_then(|remote| remote.segments.last())
+          .map_or_else(
+            || to_case(self.ident.to_string(), Case::Pascal),
+            |segment| segment.ident.to_string(),
+          )
+      },
       |(attr_js_name, _span)| attr_js_name.to_owned(),
     );
 
     let use_nullable = opts.use_nullable();
-    let (fields, is_tuple) = convert_fields(&mut self.fields, true)?;
+    let rename_all = match opts.rename_all() {
+      Some((case, span)) => {
+        if opts.object().is_none() {
+          bail_span!(
+            self.ident,
+            "#[napi(rename_all = ...)] can only be used together with #[napi(object)]"
+          );
+        }
+        match parse_case(case) {
+          Some(case) => case,
+          None => {
+            return Err(Diagnostic::span_error(
+              span,
+              format!("Unknown rename_all case. Possible values are {CASE_NAMES}"),
+            ))
+          }
+        }
+      }
+      None => Case::Camel,
+    };
+    let (fields, is_tuple) = convert_fields(&mut self.fields, true, rename_all)?;
+
+    if let Some(span) = opts.collect_errors() {
+      if opts.object().is_none() || !opts.object_from_js() {
+        return Err(Diagnostic::span_error(
+          *span,
+          "#[napi(collect_errors)] can only be used together with #[napi(object)] converted from JavaScript",
+        ));
+      }
+    }
+
+    if opts.object().is_none() || opts.transparent().is_some() || opts.array().is_some() {
+      check_fields_without_with(&fields)?;
+      if let Some(field) = fields.iter().find(|field| field.default.is_some()) {
+        bail_span!(
+          field.name,
+          "#[napi(default)] on a field is only supported in #[napi(object)] structs"
+        );
+      }
+      if let Some(field) = fields.iter().find(|field| field.flatten) {
+        bail_span!(
+          field.name,
+          "#[napi(flatten)] on a field is only supported in #[napi(object)] structs and structured enums"
+        );
+      }
+    }
+    if let Some(field) = fields
+      .iter()
+      .find(|field| field.flatten && field.default.is_some())
+    {
+      bail_span!(
+        field.name,
+        "#[napi(flatten)] and #[napi(default)] can't be used on the same field"
+      );
+    }
+    if let Some(field) = fields
+      .iter()
+      .find(|field| field.flatten && field.with.is_some())
+    {
+      bail_span!(
+        field.name,
+        "#[napi(flatten)] and #[napi(with)] can't be used on the same field"
+      );
+    }
+
+    if let Some(remote) = &remote {
+      if opts.object().is_none() {
+        bail_span!(
+          remote,
+          "#[napi(remote = ...)] can only be used together with #[napi(object)]"
+        );
+      }
+      if !self.generics.params.is_empty() {
+        bail_span!(
+          self.generics,
+          "#[napi(object, remote = ...)] can't be applied to a struct with generics or lifetimes"
+        );
+      }
+      if !opts.object_from_js() || !opts.object_to_js() {
+        bail_span!(
+          remote,
+          "#[napi(object, remote = ...)] can't be used with `object_from_js = false` or `object_to_js = false`"
+        );
+      }
+      if let Some(field) = self
+        .fields
+        .iter()
+        .find(|field| !matches!(field.vis, Visibility::Public(_)))
+      {
+        bail_span!(
+          field,
+          "All the fields of a #[napi(object, remote = ...)] struct must be public"
+        );
+      }
+    } else if let Some(field) = fields.iter().find(|field| field.remote_getter.is_some()) {
+      bail_span!(
+        field.name,
+        "#[napi(getter = ...)] on a field is only supported in #[napi(object, remote = ...)] structs"
+      );
+    }
 
     record_struct(&rust_struct_ident, final_js_name_for_struct.clone(), opts);
     let namespace = opts.namespace().map(|(m, _)| m.to_owned());
@@ -1332,6 +1658,8 @@ impl ConvertToAST for syn::ItemStruct {
         object_from_js: opts.object_from_js(),
         object_to_js: opts.object_to_js(),
         is_tuple,
+        remote,
+        collect_errors: opts.collect_errors().is_some(),
       })
     } else {
       // field lifetime check, JsValue types with lifetime can't be assigned to a field of napi class struct
@@ -1358,6 +1686,87 @@ impl ConvertToAST for syn::ItemStruct {
           }
         }
       }
+      let extends = opts
+        .extends()
+        .map(|base| -> Result<_, Diagnostic> {
+          let segments = |path: &Path| {
+            path
+              .segments
+              .iter()
+              .map(|s| s.ident.to_string())
+              .collect::<Vec<_>>()
+          };
+          let base_segments = segments(base);
+          // the fields with the declared path, or with a path naming the same type relative to another module,
+          // e.g. `extends = Shape` and a field of type `crate::Shape`
+          let candidates = self
+            .fields
+            .iter()
+            .enumerate()
+            .filter_map(|(index, field)| match &field.ty {
+              syn::Type::Path(syn::TypePath { qself: None, path }) => {
+                let field_segments = segments(path);
+                let exact = field_segments == base_segments;
+                let relative = field_segments.ends_with(&base_segments)
+                  || base_segments.ends_with(&field_segments);
+                relative.then(|| {
+                  (
+                    exact,
+                    field.ident.clone().map_or_else(
+                      || syn::Member::Unnamed(syn::Index::from(index)),
+                      syn::Member::Named,
+                    ),
+                  )
+                })
+              }
+              _ => None,
+            })
+            .collect::<Vec<_>>();
+          let exact = candidates
+            .iter()
+            .filter(|(exact, _)| *exact)
+            .map(|(_, field)| field)
+            .collect::<Vec<_>>();
+          let fields = if exact.is_empty() {
+            candidates.iter().map(|(_, field)| field).collect()
+          } else {
+            exact
+          };
+          match fields.as_slice() {
+            [field] => Ok(NapiClassExtends {
+              base: base.clone(),
+              field: (*field).clone(),
+            }),
+            [] => bail_span!(
+              base,
+              "#[napi(extends = {})] requires a field of type `{}` in the struct",
+              base.to_token_stream(),
+              base.to_token_stream(),
+            ),
+            fields => bail_span!(
+              base,
+              "#[napi(extends = {})] is ambiguous, the fields {} all have the type `{}`",
+              base.to_token_stream(),
+              fields
+                .iter()
+                .map(|field| format!("`{}`", field.to_token_stream()))
+                .collect::<Vec<_>>()
+                .join(", "),
+              base.to_token_stream(),
+            ),
+          }
+        })
+        .transpose()?;
+      let post_finalize = match opts.custom_finalize().flatten() {
+        Some((mode, _)) if mode == "post" => true,
+        Some((_, span)) => {
+          return Err(Diagnostic::span_error(
+            *span,
+            "Unknown custom_finalize mode. Possible value is \"post\"",
+          ))
+        }
+        None => false,
+      };
       NapiStructKind::Class(NapiClass {
         fields,
         ctor: opts.constructor().is_some(),
@@ -1365,6 +1774,8 @@ impl ConvertToAST for syn::ItemStruct {
         implement_async_iterator,
         is_tuple,
         use_custom_finalize: opts.custom_finalize().is_some(),
+        post_finalize,
+        extends,
       })
     };
 
@@ -1438,6 +1849,7 @@ impl ConvertToAST for syn::ItemImpl {
       };
     let mut items = vec![];
     let mut task_output_type = None;
+    let mut task_progress_type = None;
     let mut iterator_yield_type = None;
     let mut iterator_next_type = None;
     let mut iterator_return_type = None;
@@ -1452,6 +1864,12 @@ impl ConvertToAST for syn::ItemImpl {
             if let Some(PathSegment { ident, .. }) = t.segments.last() {
               if (ident == "Task" || ident == "ScopedTask") && m.ident == "JsValue" {
                 task_output_type = Some(m.ty.clone());
+              } else if ident == "ProgressTask" {
+                if m.ident == "JsValue" {
+                  task_output_type = Some(m.ty.clone());
+                } else if m.ident == "Progress" {
+                  task_progress_type = Some(m.ty.clone());
+                }
               } else if ident == "Generator" || ident == "ScopedGenerator" {
                 if let Type::Path(_) = &m.ty {
                   if m.ident == "Yield" {
@@ -1522,6 +1940,7 @@ impl ConvertToAST for syn::ItemImpl {
         js_name: struct_js_name,
         items,
         task_output_type,
+        task_progress_type,
         iterator_yield_type,
         iterator_next_type,
         iterator_return_type,
@@ -1548,34 +1967,80 @@ impl ConvertToAST for syn::ItemEnum {
       .js_name()
       .map_or_else(|| self.ident.to_string(), |(s, _)| s.to_string());
     let is_string_enum = opts.string_enum().is_some();
+    let is_flags = opts.flags().is_some();
 
-    if self
-      .variants
-      .iter()
-      .any(|v| !matches!(v.fields, syn::Fields::Unit))
+    if is_flags && (is_string_enum || opts.error().is_some()) {
+      bail_span!(
+        self,
+        "#[napi(flags)] can't be used with #[napi(string_enum)] or #[napi(error)]"
+      );
+    }
+
+    if opts.error().is_some() {
+      let mut variants = vec![];
+      for variant in self.variants.iter_mut() {
+        let variant_opts = BindgenAttrs::find(&mut variant.attrs)?;
+        let (fields, is_tuple) = convert_fields(&mut variant.fields, false, Case::Camel)?;
+        check_fields_without_with(&fields)?;
+        variants.push(NapiErrorVariant {
+          name: Some(variant.ident.clone()),
+          js_name: variant_opts.js_name().map_or_else(
+            || format!("{js_name}{}", variant.ident),
+            |(js_name, _)| js_name.to_owned(),
+          ),
+          code: variant_opts.code().map_or_else(
+            || to_case(variant.ident.unraw().to_string(), Case::UpperSnake),
+            |(code, _)| code.to_owned(),
+          ),
+          fields,
+          is_tuple,
+          comments: extract_doc_comments(&variant.attrs),
+        });
+        variant_opts.check_used()?;
+      }
+      return convert_error(
+        &self.ident,
+        &self.generics,
+        &self.attrs,
+        opts,
+        variants,
+        true,
+      );
+    }
+
+    if !is_flags
+      && self
+        .variants
+        .iter()
+        .any(|v| !matches!(v.fields, syn::Fields::Unit))
     {
       let discriminant = opts.discriminant().map_or("type", |(s, _)| s);
-      let discriminant_case = opts.discriminant_case().map(|c|
-        Ok::<Case, Diagnostic>(match c.0 {
-          "lowercase" => Case::Flat,
-          "UPPERCASE" => Case::UpperFlat,
-          "PascalCase" => Case::Pascal,
-          "camelCase" => Case::Camel,
-          "snake_case" => Case::Snake,
-          "UPPER_SNAKE" => Case::UpperSnake,
-          "kebab-case" => Case::Kebab,
-          "UPPER-KEBAB-CASE" => Case::UpperKebab,
-          _ => {
-            bail_span!(self, "Unknown discriminant case. Possible values are \"lowercase\", \"UPPERCASE\", \"PascalCase\", \"camelCase\", \"snake_case\", \"UPPER_SNAKE\", \"kebab-case\", or \"UPPER-KEBAB-CASE\"")
+      let discriminant_case = opts
+        .discriminant_case()
+        .map(|c| match parse_case(c.0) {
+          Some(case) => Ok::<Case, Diagnostic>(case),
+          None => {
+            bail_span!(
+              self,
+              "Unknown discriminant case. Possible values are {}",
+              CASE_NAMES
+            )
           }
         })
-      ).transpose()?;
+        .transpose()?;
 
       let mut errors = vec![];
       let mut variants = vec![];
       for variant in self.variants.iter_mut() {
-        let (fields, is_tuple) = convert_fields(&mut variant.fields, false)?;
+        let (fields, is_tuple) = convert_fields(&mut variant.fields, false, Case::Camel)?;
+        check_fields_without_with(&fields)?;
         for field in fields.iter() {
+          if field.default.is_some() {
+            errors.push(err_span!(
+              field.name,
+              "#[napi(default)] on a field is only supported in #[napi(object)] structs"
+            ));
+          }
           if field.js_name == discriminant {
             errors.push(err_span!(
               field.name,
@@ -1615,20 +2080,24 @@ impl ConvertToAST for syn::ItemEnum {
     }
 
     let variants = match opts.string_enum() {
+      _ if is_flags => self
+        .variants
+        .iter()
+        .map(convert_flag)
+        .collect::<BindgenResult<Vec<NapiEnumVariant>>>()?,
       Some(case) => {
-        let case = case.map(|c| Ok::<Case, Diagnostic>(match c.0.as_str() {
-          "lowercase" => Case::Flat,
-          "UPPERCASE" => Case::UpperFlat,
-          "PascalCase" => Case::Pascal,
-          "camelCase" => Case::Camel,
-          "snake_case" => Case::Snake,
-          "UPPER_SNAKE" => Case::UpperSnake,
-          "kebab-case" => Case::Kebab,
-          "UPPER-KEBAB-CASE" => Case::UpperKebab,
-          _ => {
-            bail_span!(self, "Unknown string enum case. Possible values are \"lowercase\", \"UPPERCASE\", \"PascalCase\", \"camelCase\", \"snake_case\", \"UPPER_SNAKE\", \"kebab-case\", or \"UPPER-KEBAB-CASE\"")
-          }
-        })).transpose()?;
+        let case = case
+          .map(|c| match parse_case(&c.0) {
+            Some(case) => Ok::<Case, Diagnostic>(case),
+            None => {
+              bail_span!(
+                self,
+                "Unknown string enum case. Possible values are {}",
+                CASE_NAMES
+              )
+            }
+          })
+          .transpose()?;
 
         self
           .variants
@@ -1730,6 +2199,7 @@ impl ConvertToAST for syn::ItemEnum {
         skip_typescript: opts.skip_typescript().is_some(),
         register_name: get_register_ident(self.ident.to_string().as_str()),
         is_string_enum,
+        is_flags,
         object_from_js: opts.object_from_js(),
         object_to_js: opts.object_to_js(),
       }),
@@ -1737,6 +2207,92 @@ impl ConvertToAST for syn::ItemEnum {
   }
 }
 
+/// A variant of a `#[napi(flags)]` enum, its value is an integer literal or a shift like `1 << 2`
+fn convert_flag(v: &syn::Variant) -> BindgenResult<NapiEnumVariant> {
+  if !matches!(v.fields, syn::Fields::Unit) {
+    bail_span!(
+      v.fields,
+      "Structured enum is not supported with #[napi(flags)]"
+    )
+  }
+  let Some((_, expr)) = &v.discriminant else {
+    bail_span!(
+      v,
+      "flags of #[napi(flags)] must have a value, like `{} = 1 << 0`",
+      v.ident
+    )
+  };
+
+  fn bits(expr: &syn::Expr) -> BindgenResult<u32> {
+    match get_expr(expr) {
+      syn::Expr::Lit(syn::ExprLit {
+        lit: syn::Lit::Int(int_lit),
+        ..
+      }) => int_lit.base10_parse::<u32>().map_err(|_| {
+        err_span!(
+          int_lit,
+          "flags of #[napi(flags)] must be represented as u32"
+        )
+      }),
+      syn::Expr::Binary(syn::ExprBinary {
+        left,
+        op: syn::BinOp::Shl(_),
+        right,
+        ..
+      }) => {
+        let (left, right) = (bits(left)?, bits(right)?);
+        left
+          .checked_shl(right)
+          .filter(|shifted| shifted >> right == left)
+          .ok_or_else(|| err_span!(expr, "flags of #[napi(flags)] must be represented as u32"))
+      }
+      _ => bail_span!(
+        expr,
+        "flags of #[napi(flags)] may only have number literal values or shifts of them"
+      ),
+    }
+  }
+
+  Ok(NapiEnumVariant {
+    name: v.ident.clone(),
+    val: NapiEnumValue::Bits(bits(expr)?),
+    comments: extract_doc_comments(&v.attrs),
+  })
+}
+
+/// Expand a `#[napi(flags)]` enum to a `bitflags` struct with the flags as associated constants
+fn flags_to_tokens(item: &syn::ItemEnum, napi_enum: &NapiEnum, tokens: &mut TokenStream) {
+  let attrs = item
+    .attrs
+    .iter()
+    .filter(|attr| !attr.path().is_ident("repr"));
+  let vis = &item.vis;
+  let name = &item.ident;
+  let flags = item
+    .variants
+    .iter()
+    .zip(&napi_enum.variants)
+    .map(|(v, flag)| {
+      let v_attrs = &v.attrs;
+      let flag_name = &flag.name;
+      let bits: proc_macro2::Literal = (&flag.val).into();
+      quote! {
+        #(#v_attrs)*
+        #[allow(non_upper_case_globals)]
+        const #flag_name = #bits;
+      }
+    });
+  quote! {
+    napi::bitflags::bitflags! {
+      #(#attrs)*
+      #vis struct #name: u32 {
+        #(#flags)*
+      }
+    }
+  }
+  .to_tokens(tokens);
+}
+
 impl ConvertToAST for syn::ItemConst {
   fn convert_to_ast(&mut self, opts: &BindgenAttrs) -> BindgenResult<Napi> {
     match self.vis {
@@ -1794,3 +2350,97 @@ impl ConvertToAST for syn::ItemType {
     }
   }
 }
+
+impl ConvertToAST for syn::ItemTrait {
+  fn convert_to_ast(&mut self, opts: &BindgenAttrs) -> BindgenResult<Napi> {
+    if !self.generics.params.is_empty() {
+      bail_span!(self.generics, "#[napi] trait can't be generic");
+    }
+    if !self.supertraits.is_empty() {
+      bail_span!(self.supertraits, "#[napi] trait can't have supertraits");
+    }
+
+    let mut methods = vec![];
+    for item in self.items.iter_mut() {
+      let method = match item {
+        syn::TraitItem::Fn(method) => method,
+        _ => bail_span!(item, "#[napi] trait only supports methods"),
+      };
+      let method_opts = BindgenAttrs::find(&mut method.attrs)?;
+      // methods with a default implementation are not part of the JavaScript interface
+      if method.default.is_some() {
+        continue;
+      }
+      let sig = &method.sig;
+      if sig.asyncness.is_some() {
+        bail_span!(
+          sig.asyncness,
+          "async method is not supported in #[napi] trait"
+        );
+      }
+      if !sig.generics.params.is_empty() {
+        bail_span!(
+          sig.generics,
+          "generic method is not supported in #[napi] trait"
+        );
+      }
+      let mut receiver = None;
+      let mut args = vec![];
+      for (i, input) in sig.inputs.iter().enumerate() {
+        match input {
+          syn::FnArg::Receiver(r) => receiver = Some(r.clone()),
+          syn::FnArg::Typed(PatType { pat, ty, .. }) => {
+            let name = match &**pat {
+              syn::Pat::Ident(pat_ident) => pat_ident.ident.clone(),
+              _ => Ident::new(&format!("arg{i}"), Span::call_site()),
+            };
+            args.push((name, *ty.clone()));
+          }
+        }
+      }
+      let Some(receiver) = receiver else {
+        bail_span!(
+          sig.ident,
+          "method of #[napi] trait must take `self`, associated function is not supported"
+        );
+      };
+      let ret = match &sig.output {
+        syn::ReturnType::Type(_, ty) => extract_result_ty(ty)?,
+        syn::ReturnType::Default => None,
+      };
+      let Some(ret) = ret else {
+        bail_span!(
+          sig,
+          "method of #[napi] trait must return `Result<T>`, the JavaScript implementation may throw"
+        );
+      };
+      methods.push(NapiTraitMethod {
+        name: sig.ident.clone(),
+        js_name: method_opts.js_name().map_or_else(
+          || to_case(sig.ident.unraw().to_string(), Case::Camel),
+          |(js_name, _)| js_name.to_owned(),
+        ),
+        receiver,
+        args,
+        ret,
+        ts_type: method_opts.ts_return_type().map(|(ty, _)| ty.to_owned()),
+        comments: extract_doc_comments(&method.attrs),
+      });
+    }
+
+    Ok(Napi {
+      item: NapiItem::Trait(NapiTrait {
+        name: self.ident.clone(),
+        js_name: opts
+          .js_name()
+          .map_or_else(|| self.ident.to_string(), |(js_name, _)| js_name.to_owned()),
+        vis: self.vis.clone(),
+        methods,
+        js_mod: opts.namespace().map(|(m, _)| m.to_owned()),
+        comments: extract_doc_comments(&self.attrs),
+        skip_typescript: opts.skip_typescript().is_some(),
+        threadsafe: opts.threadsafe().is_some(),
+      }),
+    })
+  }
+}
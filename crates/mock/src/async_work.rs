@@ -0,0 +1,339 @@
+//! Async works and threadsafe functions
+//!
+//! Async works are executed on their own threads, the completions and the calls of threadsafe
+//! functions are sent back as jobs which are run by `MockEnv::run_pending_jobs`.
+
+use std::ffi::c_void;
+use std::sync::{Arc, Mutex};
+
+use napi_sys as sys;
+
+use crate::env::{with_env, write, EnvState, JobQueue, SendPtr};
+use crate::value::Value;
+
+struct AsyncWork {
+  execute: unsafe extern "C" fn(sys::napi_env, *mut c_void),
+  complete: sys::napi_async_complete_callback,
+  data: *mut c_void,
+  queued: bool,
+}
+
+pub(crate) unsafe extern "C" fn napi_create_async_work(
+  env: sys::napi_env,
+  _async_resource: sys::napi_value,
+  _async_resource_name: sys::napi_value,
+  execute: sys::napi_async_execute_callback,
+  complete: sys::napi_async_complete_callback,
+  data: *mut c_void,
+  result: *mut sys::napi_async_work,
+) -> sys::napi_status {
+  with_env(env, |_| {
+    let execute = execute.ok_or(sys::Status::napi_invalid_arg)?;
+    let work = Box::new(AsyncWork {
+      execute,
+      complete,
+      data,
+      queued: false,
+    });
+    write(result, Box::into_raw(work).cast())
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_delete_async_work(
+  env: sys::napi_env,
+  work: sys::napi_async_work,
+) -> sys::napi_status {
+  with_env(env, |_| {
+    if work.is_null() {
+      return Err(sys::Status::napi_invalid_arg);
+    }
+    drop(unsafe { Box::from_raw(work.cast::<AsyncWork>()) });
+    Ok(())
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_queue_async_work(
+  env: sys::napi_env,
+  work: sys::napi_async_work,
+) -> sys::napi_status {
+  with_env(env, |state| {
+    if work.is_null() {
+      return Err(sys::Status::napi_invalid_arg);
+    }
+    let work = unsafe { &mut *work.cast::<AsyncWork>() };
+    if work.queued {
+      return Err(sys::Status::napi_generic_failure);
+    }
+    work.queued = true;
+    let (execute, complete) = (work.execute, work.complete);
+    let (env, data) = (SendPtr(env.cast::<c_void>()), SendPtr(work.data));
+    let queue = state.queue.clone();
+    EnvState::set_pending(&queue, 1);
+    let worker = std::thread::spawn(move || {
+      let (env, data) = (env, data);
+      unsafe { execute(env.0.cast(), data.0) };
+      let data = SendPtr(data.0);
+      EnvState::send_job(
+        &queue,
+        Box::new(move |env| {
+          let data = data;
+          if let Some(complete) = complete {
+            unsafe { complete(env, sys::Status::napi_ok, data.0) };
+          }
+        }),
+      );
+      EnvState::set_pending(&queue, -1);
+    });
+    state.workers.borrow_mut().push(worker);
+    Ok(())
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_cancel_async_work(
+  env: sys::napi_env,
+  work: sys::napi_async_work,
+) -> sys::napi_status {
+  // The works are started as soon as they are queued, so they can't be cancelled anymore
+  with_env(env, |_| {
+    if work.is_null() {
+      return Err(sys::Status::napi_invalid_arg);
+    }
+    Err(sys::Status::napi_generic_failure)
+  })
+}
+
+/// A value only accessed on the JavaScript thread
+struct JsThreadOnly<T>(T);
+
+unsafe impl<T> Send for JsThreadOnly<T> {}
+unsafe impl<T> Sync for JsThreadOnly<T> {}
+
+pub(crate) struct ThreadsafeFunction {
+  func: JsThreadOnly<Option<Value>>,
+  context: *mut c_void,
+  call_js_cb: sys::napi_threadsafe_function_call_js,
+  finalize_cb: sys::napi_finalize,
+  finalize_data: *mut c_void,
+  queue: Arc<JobQueue>,
+  state: Mutex<ThreadsafeFunctionState>,
+}
+
+unsafe impl Send for ThreadsafeFunction {}
+unsafe impl Sync for ThreadsafeFunction {}
+
+#[derive(Default)]
+struct ThreadsafeFunctionState {
+  threads: usize,
+  referenced: bool,
+  aborted: bool,
+  closing: bool,
+}
+
+pub(crate) unsafe extern "C" fn napi_create_threadsafe_function(
+  env: sys::napi_env,
+  func: sys::napi_value,
+  _async_resource: sys::napi_value,
+  _async_resource_name: sys::napi_value,
+  _max_queue_size: usize,
+  initial_thread_count: usize,
+  thread_finalize_data: *mut c_void,
+  thread_finalize_cb: sys::napi_finalize,
+  context: *mut c_void,
+  call_js_cb: sys::napi_threadsafe_function_call_js,
+  result: *mut sys::napi_threadsafe_function,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let func = if func.is_null() {
+      if call_js_cb.is_none() {
+        return Err(sys::Status::napi_invalid_arg);
+      }
+      None
+    } else {
+      Some(Value::Object(env.function(func)?))
+    };
+    if initial_thread_count == 0 {
+      return Err(sys::Status::napi_invalid_arg);
+    }
+    let tsfn = Box::into_raw(Box::new(ThreadsafeFunction {
+      func: JsThreadOnly(func),
+      context,
+      call_js_cb,
+      finalize_cb: thread_finalize_cb,
+      finalize_data: thread_finalize_data,
+      queue: env.queue.clone(),
+      state: Mutex::new(ThreadsafeFunctionState {
+        threads: initial_thread_count,
+        referenced: true,
+        ..Default::default()
+      }),
+    }));
+    EnvState::set_pending(&env.queue, 1);
+    env.threadsafe_functions.borrow_mut().push(tsfn);
+    write(result, tsfn.cast())
+  })
+}
+
+unsafe fn threadsafe_function<'f>(
+  func: sys::napi_threadsafe_function,
+) -> Result<&'f ThreadsafeFunction, sys::napi_status> {
+  if func.is_null() {
+    return Err(sys::Status::napi_invalid_arg);
+  }
+  Ok(unsafe { &*func.cast::<ThreadsafeFunction>() })
+}
+
+fn status(result: Result<(), sys::napi_status>) -> sys::napi_status {
+  match result {
+    Ok(()) => sys::Status::napi_ok,
+    Err(status) => status,
+  }
+}
+
+/// Call the finalizer and free the threadsafe function, on the JavaScript thread
+pub(crate) fn finalize_threadsafe_function(env: &EnvState, tsfn: *mut ThreadsafeFunction) {
+  let mut threadsafe_functions = env.threadsafe_functions.borrow_mut();
+  let Some(index) = threadsafe_functions.iter().position(|f| *f == tsfn) else {
+    return;
+  };
+  threadsafe_functions.swap_remove(index);
+  drop(threadsafe_functions);
+  let tsfn = unsafe { Box::from_raw(tsfn) };
+  if tsfn.state.lock().unwrap().referenced {
+    EnvState::set_pending(&tsfn.queue, -1);
+  }
+  if let Some(finalize_cb) = tsfn.finalize_cb {
+    unsafe { finalize_cb(env.raw(), tsfn.finalize_data, tsfn.context) };
+  }
+}
+
+fn send_finalize(tsfn: &ThreadsafeFunction) {
+  let pointer = SendPtr((tsfn as *const ThreadsafeFunction).cast_mut());
+  EnvState::send_job(
+    &tsfn.queue,
+    Box::new(move |env| {
+      let pointer = pointer;
+      finalize_threadsafe_function(unsafe { EnvState::from_raw(env) }, pointer.0)
+    }),
+  );
+}
+
+pub(crate) unsafe extern "C" fn napi_call_threadsafe_function(
+  func: sys::napi_threadsafe_function,
+  data: *mut c_void,
+  _is_blocking: sys::napi_threadsafe_function_call_mode,
+) -> sys::napi_status {
+  status((|| {
+    let tsfn = unsafe { threadsafe_function(func) }?;
+    if tsfn.state.lock().unwrap().closing {
+      return Err(sys::Status::napi_closing);
+    }
+    let pointer = SendPtr(func.cast::<ThreadsafeFunction>());
+    let data = SendPtr(data);
+    EnvState::send_job(
+      &tsfn.queue,
+      Box::new(move |env| {
+        let (pointer, data) = (pointer, data);
+        let tsfn = unsafe { &*pointer.0 };
+        let state = unsafe { EnvState::from_raw(env) };
+        let aborted = tsfn.state.lock().unwrap().aborted;
+        match (tsfn.call_js_cb, &tsfn.func.0) {
+          (Some(call_js_cb), _) if aborted => unsafe {
+            call_js_cb(
+              std::ptr::null_mut(),
+              std::ptr::null_mut(),
+              tsfn.context,
+              data.0,
+            )
+          },
+          (Some(call_js_cb), func) => {
+            let func = func
+              .clone()
+              .map_or(std::ptr::null_mut(), |func| state.handle(func));
+            unsafe { call_js_cb(env, func, tsfn.context, data.0) }
+          }
+          (None, Some(Value::Object(func))) if !aborted => {
+            let _ = state.call(func, Value::Undefined, Vec::new(), None);
+          }
+          (None, _) => {}
+        }
+        if let Some(exception) = state.pending_exception.take() {
+          state.uncaught_exceptions.borrow_mut().push(exception);
+        }
+      }),
+    );
+    Ok(())
+  })())
+}
+
+pub(crate) unsafe extern "C" fn napi_acquire_threadsafe_function(
+  func: sys::napi_threadsafe_function,
+) -> sys::napi_status {
+  status((|| {
+    let tsfn = unsafe { threadsafe_function(func) }?;
+    let mut state = tsfn.state.lock().unwrap();
+    if state.closing {
+      return Err(sys::Status::napi_closing);
+    }
+    state.threads += 1;
+    Ok(())
+  })())
+}
+
+pub(crate) unsafe extern "C" fn napi_release_threadsafe_function(
+  func: sys::napi_threadsafe_function,
+  mode: sys::napi_threadsafe_function_release_mode,
+) -> sys::napi_status {
+  status((|| {
+    let tsfn = unsafe { threadsafe_function(func) }?;
+    let mut state = tsfn.state.lock().unwrap();
+    if state.threads == 0 {
+      return Err(sys::Status::napi_invalid_arg);
+    }
+    state.threads -= 1;
+    if mode == sys::ThreadsafeFunctionReleaseMode::abort {
+      state.aborted = true;
+    }
+    if (state.threads == 0 || state.aborted) && !state.closing {
+      state.closing = true;
+      drop(state);
+      send_finalize(tsfn);
+    }
+    Ok(())
+  })())
+}
+
+fn set_referenced(func: sys::napi_threadsafe_function, referenced: bool) -> sys::napi_status {
+  status((|| {
+    let tsfn = unsafe { threadsafe_function(func) }?;
+    let mut state = tsfn.state.lock().unwrap();
+    if state.referenced != referenced {
+      state.referenced = referenced;
+      EnvState::set_pending(&tsfn.queue, if referenced { 1 } else { -1 });
+    }
+    Ok(())
+  })())
+}
+
+pub(crate) unsafe extern "C" fn napi_ref_threadsafe_function(
+  _env: sys::napi_env,
+  func: sys::napi_threadsafe_function,
+) -> sys::napi_status {
+  set_referenced(func, true)
+}
+
+pub(crate) unsafe extern "C" fn napi_unref_threadsafe_function(
+  _env: sys::napi_env,
+  func: sys::napi_threadsafe_function,
+) -> sys::napi_status {
+  set_referenced(func, false)
+}
+
+pub(crate) unsafe extern "C" fn napi_get_threadsafe_function_context(
+  func: sys::napi_threadsafe_function,
+  result: *mut *mut c_void,
+) -> sys::napi_status {
+  status((|| {
+    let tsfn = unsafe { threadsafe_function(func) }?;
+    write(result, tsfn.context)
+  })())
+}
@@ -0,0 +1,552 @@
+//! ArrayBuffers, TypedArrays and Buffers
+
+use std::ffi::c_void;
+
+use napi_sys as sys;
+
+use crate::env::{with_env, write, EnvState, Finalizer, Status};
+use crate::value::{ArrayBufferData, Object, ObjectKind, TypedArrayData, Value};
+
+fn element_size(ty: sys::napi_typedarray_type) -> Option<usize> {
+  Some(match ty {
+    sys::TypedarrayType::int8_array
+    | sys::TypedarrayType::uint8_array
+    | sys::TypedarrayType::uint8_clamped_array => 1,
+    sys::TypedarrayType::int16_array | sys::TypedarrayType::uint16_array => 2,
+    sys::TypedarrayType::int32_array
+    | sys::TypedarrayType::uint32_array
+    | sys::TypedarrayType::float32_array => 4,
+    sys::TypedarrayType::float64_array
+    | sys::TypedarrayType::bigint64_array
+    | sys::TypedarrayType::biguint64_array => 8,
+    _ => return None,
+  })
+}
+
+fn new_arraybuffer(env: &EnvState, owned: Box<[u8]>) -> Object {
+  let mut owned = owned;
+  let data = ArrayBufferData {
+    data: owned.as_mut_ptr(),
+    len: owned.len(),
+    owned: Some(owned),
+    detached: false,
+  };
+  Object::new(
+    ObjectKind::ArrayBuffer(data),
+    Some(env.object_prototype.clone()),
+  )
+}
+
+fn new_external_arraybuffer(
+  env: &EnvState,
+  data: *mut c_void,
+  len: usize,
+  finalize_cb: sys::napi_finalize,
+  finalize_hint: *mut c_void,
+) -> Object {
+  if let Some(callback) = finalize_cb {
+    env.add_finalizer(Finalizer {
+      callback,
+      data,
+      hint: finalize_hint,
+    });
+  }
+  let data = ArrayBufferData {
+    data: data.cast(),
+    len,
+    owned: None,
+    detached: false,
+  };
+  Object::new(
+    ObjectKind::ArrayBuffer(data),
+    Some(env.object_prototype.clone()),
+  )
+}
+
+fn new_typedarray(env: &EnvState, data: TypedArrayData) -> Object {
+  Object::new(
+    ObjectKind::TypedArray(data),
+    Some(env.object_prototype.clone()),
+  )
+}
+
+fn new_buffer(env: &EnvState, buffer: Object, byte_offset: usize, length: usize) -> Object {
+  new_typedarray(
+    env,
+    TypedArrayData {
+      ty: sys::TypedarrayType::uint8_array,
+      buffer,
+      byte_offset,
+      length,
+    },
+  )
+}
+
+/// The data pointer and byte length of an ArrayBuffer, they are null and 0 once detached
+fn arraybuffer_info(object: &Object) -> Result<(*mut u8, usize), Status> {
+  match &object.borrow().kind {
+    ObjectKind::ArrayBuffer(data) if data.detached => Ok((std::ptr::null_mut(), 0)),
+    ObjectKind::ArrayBuffer(data) => Ok((data.data, data.len)),
+    _ => Err(sys::Status::napi_arraybuffer_expected),
+  }
+}
+
+fn write_data(data: *mut *mut c_void, value: *mut u8) {
+  if !data.is_null() {
+    unsafe { data.write(value.cast()) };
+  }
+}
+
+pub(crate) unsafe extern "C" fn napi_create_arraybuffer(
+  env: sys::napi_env,
+  byte_length: usize,
+  data: *mut *mut c_void,
+  result: *mut sys::napi_value,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let arraybuffer = new_arraybuffer(env, vec![0; byte_length].into_boxed_slice());
+    write_data(data, arraybuffer_info(&arraybuffer)?.0);
+    write(result, env.handle(Value::Object(arraybuffer)))
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_create_external_arraybuffer(
+  env: sys::napi_env,
+  external_data: *mut c_void,
+  byte_length: usize,
+  finalize_cb: sys::napi_finalize,
+  finalize_hint: *mut c_void,
+  result: *mut sys::napi_value,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let arraybuffer =
+      new_external_arraybuffer(env, external_data, byte_length, finalize_cb, finalize_hint);
+    write(result, env.handle(Value::Object(arraybuffer)))
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_get_arraybuffer_info(
+  env: sys::napi_env,
+  arraybuffer: sys::napi_value,
+  data: *mut *mut c_void,
+  byte_length: *mut usize,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let (pointer, len) = arraybuffer_info(&env.object(arraybuffer)?)?;
+    write_data(data, pointer);
+    if !byte_length.is_null() {
+      unsafe { byte_length.write(len) };
+    }
+    Ok(())
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_is_arraybuffer(
+  env: sys::napi_env,
+  value: sys::napi_value,
+  result: *mut bool,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let is_arraybuffer = matches!(
+      env.value(value)?,
+      Value::Object(object) if matches!(object.borrow().kind, ObjectKind::ArrayBuffer(_))
+    );
+    write(result, is_arraybuffer)
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_detach_arraybuffer(
+  env: sys::napi_env,
+  arraybuffer: sys::napi_value,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    match &mut env.object(arraybuffer)?.borrow_mut().kind {
+      ObjectKind::ArrayBuffer(data) => {
+        data.detached = true;
+        data.owned = None;
+        Ok(())
+      }
+      _ => Err(sys::Status::napi_detachable_arraybuffer_expected),
+    }
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_is_detached_arraybuffer(
+  env: sys::napi_env,
+  value: sys::napi_value,
+  result: *mut bool,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let detached = matches!(
+      env.value(value)?,
+      Value::Object(object)
+        if matches!(&object.borrow().kind, ObjectKind::ArrayBuffer(data) if data.detached)
+    );
+    write(result, detached)
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_create_typedarray(
+  env: sys::napi_env,
+  type_: sys::napi_typedarray_type,
+  length: usize,
+  arraybuffer: sys::napi_value,
+  byte_offset: usize,
+  result: *mut sys::napi_value,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let buffer = env.object(arraybuffer)?;
+    let (_, byte_length) = arraybuffer_info(&buffer)?;
+    let size = element_size(type_).ok_or(sys::Status::napi_invalid_arg)?;
+    if !byte_offset.is_multiple_of(size) {
+      return Err(env.throw_error(
+        2,
+        &format!("start offset of TypedArray should be a multiple of {size}"),
+      ));
+    }
+    if byte_offset + length * size > byte_length {
+      return Err(env.throw_error(2, "Invalid typed array length"));
+    }
+    let typedarray = new_typedarray(
+      env,
+      TypedArrayData {
+        ty: type_,
+        buffer,
+        byte_offset,
+        length,
+      },
+    );
+    write(result, env.handle(Value::Object(typedarray)))
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_get_typedarray_info(
+  env: sys::napi_env,
+  typedarray: sys::napi_value,
+  type_: *mut sys::napi_typedarray_type,
+  length: *mut usize,
+  data: *mut *mut c_void,
+  arraybuffer: *mut sys::napi_value,
+  byte_offset: *mut usize,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let object = env.object(typedarray)?;
+    let object = object.borrow();
+    let ObjectKind::TypedArray(typed) = &object.kind else {
+      return Err(sys::Status::napi_invalid_arg);
+    };
+    let (pointer, _) = arraybuffer_info(&typed.buffer)?;
+    if !type_.is_null() {
+      unsafe { type_.write(typed.ty) };
+    }
+    if !length.is_null() {
+      unsafe { length.write(typed.length) };
+    }
+    write_data(data, pointer.wrapping_add(typed.byte_offset));
+    if !arraybuffer.is_null() {
+      unsafe { arraybuffer.write(env.handle(Value::Object(typed.buffer.clone()))) };
+    }
+    if !byte_offset.is_null() {
+      unsafe { byte_offset.write(typed.byte_offset) };
+    }
+    Ok(())
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_is_typedarray(
+  env: sys::napi_env,
+  value: sys::napi_value,
+  result: *mut bool,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let is_typedarray = matches!(
+      env.value(value)?,
+      Value::Object(object) if matches!(object.borrow().kind, ObjectKind::TypedArray(_))
+    );
+    write(result, is_typedarray)
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_create_buffer(
+  env: sys::napi_env,
+  length: usize,
+  data: *mut *mut c_void,
+  result: *mut sys::napi_value,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let arraybuffer = new_arraybuffer(env, vec![0; length].into_boxed_slice());
+    write_data(data, arraybuffer_info(&arraybuffer)?.0);
+    let buffer = new_buffer(env, arraybuffer, 0, length);
+    write(result, env.handle(Value::Object(buffer)))
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_create_buffer_copy(
+  env: sys::napi_env,
+  length: usize,
+  data: *const c_void,
+  result_data: *mut *mut c_void,
+  result: *mut sys::napi_value,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let bytes: Box<[u8]> = if length == 0 {
+      Box::default()
+    } else if data.is_null() {
+      return Err(sys::Status::napi_invalid_arg);
+    } else {
+      unsafe { std::slice::from_raw_parts(data.cast::<u8>(), length) }.into()
+    };
+    let arraybuffer = new_arraybuffer(env, bytes);
+    write_data(result_data, arraybuffer_info(&arraybuffer)?.0);
+    let buffer = new_buffer(env, arraybuffer, 0, length);
+    write(result, env.handle(Value::Object(buffer)))
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_create_external_buffer(
+  env: sys::napi_env,
+  length: usize,
+  data: *mut c_void,
+  finalize_cb: sys::napi_finalize,
+  finalize_hint: *mut c_void,
+  result: *mut sys::napi_value,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let arraybuffer = new_external_arraybuffer(env, data, length, finalize_cb, finalize_hint);
+    let buffer = new_buffer(env, arraybuffer, 0, length);
+    write(result, env.handle(Value::Object(buffer)))
+  })
+}
+
+pub(crate) unsafe extern "C" fn node_api_create_buffer_from_arraybuffer(
+  env: sys::napi_env,
+  arraybuffer: sys::napi_value,
+  byte_offset: usize,
+  byte_length: usize,
+  result: *mut sys::napi_value,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let arraybuffer = env.object(arraybuffer)?;
+    let (_, len) = arraybuffer_info(&arraybuffer)?;
+    if byte_offset + byte_length > len {
+      return Err(env.throw_error(2, "The byte offset + length is out of range"));
+    }
+    let buffer = new_buffer(env, arraybuffer, byte_offset, byte_length);
+    write(result, env.handle(Value::Object(buffer)))
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_get_buffer_info(
+  env: sys::napi_env,
+  value: sys::napi_value,
+  data: *mut *mut c_void,
+  length: *mut usize,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let object = env.object(value)?;
+    let object = object.borrow();
+    let ObjectKind::TypedArray(typed) = &object.kind else {
+      return Err(sys::Status::napi_invalid_arg);
+    };
+    let (pointer, _) = arraybuffer_info(&typed.buffer)?;
+    write_data(data, pointer.wrapping_add(typed.byte_offset));
+    if !length.is_null() {
+      unsafe { length.write(typed.length * element_size(typed.ty).unwrap_or(1)) };
+    }
+    Ok(())
+  })
+}
+
+/// Node.js accepts all the `Uint8Array`s as `Buffer`s since 22
+pub(crate) unsafe extern "C" fn napi_is_buffer(
+  env: sys::napi_env,
+  value: sys::napi_value,
+  result: *mut bool,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let is_buffer = matches!(
+      env.value(value)?,
+      Value::Object(object) if matches!(object.borrow().kind, ObjectKind::TypedArray(_))
+    );
+    write(result, is_buffer)
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use std::ptr;
+  use std::sync::atomic::{AtomicU32, Ordering};
+
+  use super::*;
+  use crate::MockEnv;
+
+  fn create_arraybuffer(env: &MockEnv, len: usize) -> (sys::napi_value, *mut u8) {
+    let mut data = ptr::null_mut();
+    let mut arraybuffer = ptr::null_mut();
+    let status = unsafe { napi_create_arraybuffer(env.raw(), len, &mut data, &mut arraybuffer) };
+    assert_eq!(status, sys::Status::napi_ok);
+    (arraybuffer, data.cast())
+  }
+
+  fn arraybuffer_info(env: &MockEnv, arraybuffer: sys::napi_value) -> (*mut u8, usize) {
+    let mut data = ptr::null_mut();
+    let mut len = 0;
+    let status = unsafe { napi_get_arraybuffer_info(env.raw(), arraybuffer, &mut data, &mut len) };
+    assert_eq!(status, sys::Status::napi_ok);
+    (data.cast(), len)
+  }
+
+  fn buffer_info(env: &MockEnv, buffer: sys::napi_value) -> (*mut u8, usize) {
+    let mut data = ptr::null_mut();
+    let mut len = 0;
+    let status = unsafe { napi_get_buffer_info(env.raw(), buffer, &mut data, &mut len) };
+    assert_eq!(status, sys::Status::napi_ok);
+    (data.cast(), len)
+  }
+
+  unsafe extern "C" fn count_finalize(_env: sys::napi_env, _data: *mut c_void, hint: *mut c_void) {
+    unsafe { &*hint.cast::<AtomicU32>() }.fetch_add(1, Ordering::SeqCst);
+  }
+
+  #[test]
+  fn test_arraybuffer() {
+    let env = MockEnv::new();
+    let (arraybuffer, data) = create_arraybuffer(&env, 4);
+    assert_eq!(arraybuffer_info(&env, arraybuffer), (data, 4));
+    unsafe { std::slice::from_raw_parts_mut(data, 4) }.copy_from_slice(&[1, 2, 3, 4]);
+
+    let mut typedarray = ptr::null_mut();
+    let status = unsafe {
+      napi_create_typedarray(
+        env.raw(),
+        sys::TypedarrayType::uint16_array,
+        1,
+        arraybuffer,
+        2,
+        &mut typedarray,
+      )
+    };
+    assert_eq!(status, sys::Status::napi_ok);
+    let mut ty = sys::TypedarrayType::int8_array;
+    let mut length = 0;
+    let mut typed_data = ptr::null_mut();
+    let mut byte_offset = 0;
+    let status = unsafe {
+      napi_get_typedarray_info(
+        env.raw(),
+        typedarray,
+        &mut ty,
+        &mut length,
+        &mut typed_data,
+        ptr::null_mut(),
+        &mut byte_offset,
+      )
+    };
+    assert_eq!(status, sys::Status::napi_ok);
+    assert_eq!(ty, sys::TypedarrayType::uint16_array);
+    assert_eq!((length, byte_offset), (1, 2));
+    assert_eq!(typed_data.cast::<u8>(), data.wrapping_add(2));
+
+    assert!(!env.state.has_pending_exception());
+    let status = unsafe {
+      napi_create_typedarray(
+        env.raw(),
+        sys::TypedarrayType::uint16_array,
+        1,
+        arraybuffer,
+        1,
+        &mut typedarray,
+      )
+    };
+    assert_eq!(status, sys::Status::napi_pending_exception);
+    assert!(env.state.pending_exception.take().is_some());
+    let status = unsafe {
+      napi_create_typedarray(
+        env.raw(),
+        sys::TypedarrayType::uint32_array,
+        2,
+        arraybuffer,
+        0,
+        &mut typedarray,
+      )
+    };
+    assert_eq!(status, sys::Status::napi_pending_exception);
+    assert!(env.state.pending_exception.take().is_some());
+  }
+
+  #[test]
+  fn test_detach_arraybuffer() {
+    let env = MockEnv::new();
+    let (arraybuffer, _) = create_arraybuffer(&env, 4);
+    let mut detached = true;
+    unsafe { napi_is_detached_arraybuffer(env.raw(), arraybuffer, &mut detached) };
+    assert!(!detached);
+    let status = unsafe { napi_detach_arraybuffer(env.raw(), arraybuffer) };
+    assert_eq!(status, sys::Status::napi_ok);
+    unsafe { napi_is_detached_arraybuffer(env.raw(), arraybuffer, &mut detached) };
+    assert!(detached);
+    assert_eq!(arraybuffer_info(&env, arraybuffer), (ptr::null_mut(), 0));
+  }
+
+  #[test]
+  fn test_buffers() {
+    let env = MockEnv::new();
+    let bytes = *b"abc";
+    let mut copied_data = ptr::null_mut();
+    let mut buffer = ptr::null_mut();
+    let status = unsafe {
+      napi_create_buffer_copy(
+        env.raw(),
+        bytes.len(),
+        bytes.as_ptr().cast(),
+        &mut copied_data,
+        &mut buffer,
+      )
+    };
+    assert_eq!(status, sys::Status::napi_ok);
+    let (data, len) = buffer_info(&env, buffer);
+    assert_eq!(data, copied_data.cast());
+    assert_ne!(data, bytes.as_ptr().cast_mut());
+    assert_eq!(unsafe { std::slice::from_raw_parts(data, len) }, b"abc");
+    let mut is_buffer = false;
+    unsafe { napi_is_buffer(env.raw(), buffer, &mut is_buffer) };
+    assert!(is_buffer);
+
+    let (arraybuffer, arraybuffer_data) = create_arraybuffer(&env, 4);
+    let status =
+      unsafe { node_api_create_buffer_from_arraybuffer(env.raw(), arraybuffer, 1, 2, &mut buffer) };
+    assert_eq!(status, sys::Status::napi_ok);
+    assert_eq!(
+      buffer_info(&env, buffer),
+      (arraybuffer_data.wrapping_add(1), 2)
+    );
+    let status =
+      unsafe { node_api_create_buffer_from_arraybuffer(env.raw(), arraybuffer, 3, 2, &mut buffer) };
+    assert_eq!(status, sys::Status::napi_pending_exception);
+    assert!(env.state.pending_exception.take().is_some());
+  }
+
+  #[test]
+  fn test_external_buffer() {
+    let finalized = AtomicU32::new(0);
+    let mut bytes = *b"external";
+    let env = MockEnv::new();
+    let mut buffer = ptr::null_mut();
+    let status = unsafe {
+      napi_create_external_buffer(
+        env.raw(),
+        bytes.len(),
+        bytes.as_mut_ptr().cast(),
+        Some(count_finalize),
+        ptr::from_ref(&finalized).cast_mut().cast(),
+        &mut buffer,
+      )
+    };
+    assert_eq!(status, sys::Status::napi_ok);
+    // the external data is not copied
+    assert_eq!(buffer_info(&env, buffer), (bytes.as_mut_ptr(), bytes.len()));
+    assert_eq!(finalized.load(Ordering::SeqCst), 0);
+    drop(env);
+    assert_eq!(finalized.load(Ordering::SeqCst), 1);
+  }
+}
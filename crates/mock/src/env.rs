@@ -0,0 +1,500 @@
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, VecDeque};
+use std::ffi::{c_void, CStr};
+use std::os::raw::c_char;
+use std::ptr;
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::JoinHandle;
+
+use napi_sys as sys;
+
+use crate::async_work::ThreadsafeFunction;
+use crate::value::{
+  bigint_to_string, number_to_string, Key, NativeFunction, Object, ObjectKind, Property, Symbol,
+  Value, DEFAULT_ATTRIBUTES,
+};
+
+pub(crate) type Status = sys::napi_status;
+
+/// The state behind a `napi_env` of the mock host
+pub(crate) struct EnvState {
+  /// `napi_value`s are the indices in it plus one, they are valid until the env is dropped
+  handles: RefCell<Vec<Value>>,
+  pub(crate) pending_exception: RefCell<Option<Value>>,
+  pub(crate) uncaught_exceptions: RefCell<Vec<Value>>,
+  pub(crate) last_error: RefCell<sys::napi_extended_error_info>,
+  pub(crate) global: Object,
+  pub(crate) object_prototype: Object,
+  pub(crate) function_prototype: Object,
+  pub(crate) array_prototype: Object,
+  pub(crate) promise_prototype: Object,
+  /// `Error`, `TypeError`, `RangeError` and `SyntaxError`
+  pub(crate) error_prototypes: [Object; 4],
+  pub(crate) symbol_registry: RefCell<HashMap<String, Value>>,
+  pub(crate) finalizers: RefCell<Vec<Option<Finalizer>>>,
+  pub(crate) cleanup_hooks: RefCell<Vec<(unsafe extern "C" fn(*mut c_void), *mut c_void)>>,
+  pub(crate) instance_data: Cell<Option<(*mut c_void, Option<Finalizer>)>>,
+  pub(crate) external_memory: Cell<i64>,
+  pub(crate) queue: Arc<JobQueue>,
+  pub(crate) workers: RefCell<Vec<JoinHandle<()>>>,
+  /// The threadsafe functions which are not finalized yet
+  pub(crate) threadsafe_functions: RefCell<Vec<*mut ThreadsafeFunction>>,
+}
+
+/// A finalize callback, it's called when the env is dropped if it's not removed before
+#[derive(Clone, Copy)]
+pub(crate) struct Finalizer {
+  pub(crate) callback: unsafe extern "C" fn(sys::napi_env, *mut c_void, *mut c_void),
+  pub(crate) data: *mut c_void,
+  pub(crate) hint: *mut c_void,
+}
+
+pub(crate) type Job = Box<dyn FnOnce(sys::napi_env) + Send>;
+
+/// The jobs sent to the JavaScript thread from the other threads
+#[derive(Default)]
+pub(crate) struct JobQueue {
+  pub(crate) state: Mutex<JobQueueState>,
+  pub(crate) ready: Condvar,
+}
+
+#[derive(Default)]
+pub(crate) struct JobQueueState {
+  pub(crate) jobs: VecDeque<Job>,
+  /// The async works and referenced threadsafe functions which may still send jobs
+  pub(crate) pending: usize,
+}
+
+// The jobs are `Send`, the raw pointers in them are only dereferenced on the JavaScript thread
+pub(crate) struct SendPtr<T>(pub(crate) *mut T);
+
+unsafe impl<T> Send for SendPtr<T> {}
+
+pub(crate) struct CallbackInfo {
+  pub(crate) this: sys::napi_value,
+  pub(crate) args: Vec<sys::napi_value>,
+  pub(crate) new_target: sys::napi_value,
+  pub(crate) data: *mut c_void,
+}
+
+impl EnvState {
+  pub(crate) fn new() -> Box<Self> {
+    let object_prototype = Object::new(ObjectKind::Ordinary, None);
+    let function_prototype = Object::new(ObjectKind::Ordinary, Some(object_prototype.clone()));
+    let array_prototype = Object::new(ObjectKind::Ordinary, Some(object_prototype.clone()));
+    let promise_prototype = Object::new(ObjectKind::Ordinary, Some(object_prototype.clone()));
+    let error_prototype = Object::new(ObjectKind::Ordinary, Some(object_prototype.clone()));
+    let error_prototypes = ["Error", "TypeError", "RangeError", "SyntaxError"].map(|name| {
+      let prototype = if name == "Error" {
+        error_prototype.clone()
+      } else {
+        Object::new(ObjectKind::Ordinary, Some(error_prototype.clone()))
+      };
+      define_hidden(&prototype, "name", Value::String(name.into()));
+      define_hidden(&prototype, "message", Value::String("".into()));
+      prototype
+    });
+    let global = Object::new(ObjectKind::Ordinary, Some(object_prototype.clone()));
+    Box::new(Self {
+      handles: RefCell::new(Vec::new()),
+      pending_exception: RefCell::new(None),
+      uncaught_exceptions: RefCell::new(Vec::new()),
+      last_error: RefCell::new(sys::napi_extended_error_info {
+        error_message: ptr::null(),
+        engine_reserved: ptr::null_mut(),
+        engine_error_code: 0,
+        error_code: sys::Status::napi_ok,
+      }),
+      global,
+      object_prototype,
+      function_prototype,
+      array_prototype,
+      promise_prototype,
+      error_prototypes,
+      symbol_registry: RefCell::new(HashMap::new()),
+      finalizers: RefCell::new(Vec::new()),
+      cleanup_hooks: RefCell::new(Vec::new()),
+      instance_data: Cell::new(None),
+      external_memory: Cell::new(0),
+      queue: Arc::default(),
+      workers: RefCell::new(Vec::new()),
+      threadsafe_functions: RefCell::new(Vec::new()),
+    })
+  }
+
+  /// # Safety
+  ///
+  /// `env` must be created by `MockEnv` and not dropped.
+  pub(crate) unsafe fn from_raw<'env>(env: sys::napi_env) -> &'env Self {
+    unsafe { &*env.cast::<EnvState>() }
+  }
+
+  pub(crate) fn raw(&self) -> sys::napi_env {
+    (self as *const Self).cast_mut().cast()
+  }
+
+  pub(crate) fn handle(&self, value: Value) -> sys::napi_value {
+    let mut handles = self.handles.borrow_mut();
+    handles.push(value);
+    handles.len() as sys::napi_value
+  }
+
+  pub(crate) fn value(&self, handle: sys::napi_value) -> Result<Value, Status> {
+    let index = (handle as usize)
+      .checked_sub(1)
+      .ok_or(sys::Status::napi_invalid_arg)?;
+    self
+      .handles
+      .borrow()
+      .get(index)
+      .cloned()
+      .ok_or(sys::Status::napi_invalid_arg)
+  }
+
+  pub(crate) fn object(&self, handle: sys::napi_value) -> Result<Object, Status> {
+    match self.value(handle)? {
+      Value::Object(object) => Ok(object),
+      _ => Err(sys::Status::napi_object_expected),
+    }
+  }
+
+  pub(crate) fn function(&self, handle: sys::napi_value) -> Result<Object, Status> {
+    match self.value(handle)? {
+      Value::Object(object) if object.is_function() => Ok(object),
+      _ => Err(sys::Status::napi_function_expected),
+    }
+  }
+
+  pub(crate) fn new_object(&self) -> Object {
+    Object::new(ObjectKind::Ordinary, Some(self.object_prototype.clone()))
+  }
+
+  pub(crate) fn new_array(&self, elements: Vec<Value>) -> Object {
+    Object::new(
+      ObjectKind::Array(elements),
+      Some(self.array_prototype.clone()),
+    )
+  }
+
+  pub(crate) fn new_function(&self, name: &str, function: NativeFunction) -> Object {
+    let object = Object::new(
+      ObjectKind::Function(function),
+      Some(self.function_prototype.clone()),
+    );
+    define_hidden(&object, "name", Value::String(name.into()));
+    object
+  }
+
+  /// Create an error with the prototype of `kind` in `error_prototypes`
+  pub(crate) fn new_error(&self, kind: usize, code: Option<Value>, message: Value) -> Object {
+    let error = Object::new(ObjectKind::Error, Some(self.error_prototypes[kind].clone()));
+    define_hidden(&error, "message", message);
+    if let Some(code) = code {
+      error.define(
+        Key::from_str("code"),
+        Property::Data {
+          value: code,
+          attributes: DEFAULT_ATTRIBUTES,
+        },
+      );
+    }
+    error
+  }
+
+  pub(crate) fn throw(&self, error: Value) {
+    self.pending_exception.replace(Some(error));
+  }
+
+  pub(crate) fn throw_error(&self, kind: usize, message: &str) -> Status {
+    let error = self.new_error(kind, None, Value::String(message.into()));
+    self.throw(Value::Object(error));
+    sys::Status::napi_pending_exception
+  }
+
+  pub(crate) fn has_pending_exception(&self) -> bool {
+    self.pending_exception.borrow().is_some()
+  }
+
+  pub(crate) fn add_finalizer(&self, finalizer: Finalizer) -> usize {
+    let mut finalizers = self.finalizers.borrow_mut();
+    finalizers.push(Some(finalizer));
+    finalizers.len() - 1
+  }
+
+  pub(crate) fn remove_finalizer(&self, id: usize) {
+    if let Some(finalizer) = self.finalizers.borrow_mut().get_mut(id) {
+      *finalizer = None;
+    }
+  }
+
+  pub(crate) fn to_key(&self, key: &Value) -> Result<Key, Status> {
+    match key {
+      Value::String(name) => Ok(Key::String(name.clone())),
+      Value::Symbol(symbol) => Ok(Key::Symbol(symbol.clone())),
+      Value::Object(_) => Err(sys::Status::napi_name_expected),
+      _ => Ok(Key::String(self.to_string(key)?.into())),
+    }
+  }
+
+  /// `ToString`, objects are formatted without calling into their methods
+  pub(crate) fn to_string(&self, value: &Value) -> Result<String, Status> {
+    Ok(match value {
+      Value::Undefined => "undefined".to_owned(),
+      Value::Null => "null".to_owned(),
+      Value::Boolean(value) => value.to_string(),
+      Value::Number(value) => number_to_string(*value),
+      Value::String(value) => value.to_string(),
+      Value::BigInt(value) => bigint_to_string(value),
+      Value::Symbol(_) => {
+        self.throw_error(1, "Cannot convert a Symbol value to a string");
+        return Err(sys::Status::napi_pending_exception);
+      }
+      Value::Object(object) => {
+        let is_error = matches!(object.borrow().kind, ObjectKind::Error)
+          || object.has_in_chain(&self.error_prototypes[0]);
+        let kind = match &object.borrow().kind {
+          ObjectKind::Array(elements) => Some(elements.clone()),
+          _ => None,
+        };
+        if let Some(elements) = kind {
+          elements
+            .iter()
+            .map(|element| match element {
+              Value::Undefined | Value::Null => Ok(String::new()),
+              element => self.to_string(element),
+            })
+            .collect::<Result<Vec<_>, _>>()?
+            .join(",")
+        } else if is_error {
+          let name = self.to_string(&self.get(value, &Key::from_str("name"))?)?;
+          let message = self.to_string(&self.get(value, &Key::from_str("message"))?)?;
+          match (name.is_empty(), message.is_empty()) {
+            (_, true) => name,
+            (true, false) => message,
+            (false, false) => format!("{name}: {message}"),
+          }
+        } else if object.is_function() {
+          let name = self.to_string(&self.get(value, &Key::from_str("name"))?)?;
+          format!("function {name}() {{ [native code] }}")
+        } else {
+          "[object Object]".to_owned()
+        }
+      }
+    })
+  }
+
+  /// `target[key]`, the getters are called
+  pub(crate) fn get(&self, target: &Value, key: &Key) -> Result<Value, Status> {
+    let object = match target {
+      Value::Object(object) => object,
+      Value::Undefined | Value::Null => return Err(sys::Status::napi_object_expected),
+      Value::String(value) if *key == Key::from_str("length") => {
+        return Ok(Value::Number(value.encode_utf16().count() as f64));
+      }
+      _ => return Ok(Value::Undefined),
+    };
+    match object.find_property(key) {
+      Some(Property::Data { value, .. }) => Ok(value),
+      Some(Property::Accessor {
+        getter: Some(getter),
+        ..
+      }) => self.call(&getter, target.clone(), Vec::new(), None),
+      _ => Ok(Value::Undefined),
+    }
+  }
+
+  /// `target[key] = value`, the setters are called
+  pub(crate) fn set(&self, target: &Object, key: Key, value: Value) -> Result<(), Status> {
+    match target.find_property(&key) {
+      Some(Property::Accessor { setter, .. }) => {
+        if let Some(setter) = setter {
+          self.call(&setter, Value::Object(target.clone()), vec![value], None)?;
+        }
+        Ok(())
+      }
+      Some(Property::Data { attributes, .. })
+        if attributes & sys::PropertyAttributes::writable == 0 =>
+      {
+        Ok(())
+      }
+      _ => {
+        let attributes = match target.own_property(&key) {
+          Some(property) => property.attributes(),
+          None => DEFAULT_ATTRIBUTES,
+        };
+        target.define(key, Property::Data { value, attributes });
+        Ok(())
+      }
+    }
+  }
+
+  /// Call the native function `function`, `new_target` is the constructor for `new` calls
+  pub(crate) fn call(
+    &self,
+    function: &Object,
+    this: Value,
+    args: Vec<Value>,
+    new_target: Option<Value>,
+  ) -> Result<Value, Status> {
+    let native = match &function.borrow().kind {
+      ObjectKind::Function(native) => native.clone(),
+      _ => return Err(sys::Status::napi_function_expected),
+    };
+    let info = CallbackInfo {
+      this: self.handle(this),
+      args: args.into_iter().map(|arg| self.handle(arg)).collect(),
+      new_target: new_target.map_or(ptr::null_mut(), |target| self.handle(target)),
+      data: native.data,
+    };
+    let result =
+      unsafe { (native.callback)(self.raw(), (&info as *const CallbackInfo).cast_mut().cast()) };
+    if self.has_pending_exception() {
+      return Err(sys::Status::napi_pending_exception);
+    }
+    if result.is_null() {
+      Ok(Value::Undefined)
+    } else {
+      self.value(result)
+    }
+  }
+
+  /// `new constructor(...args)`
+  pub(crate) fn construct(&self, constructor: &Object, args: Vec<Value>) -> Result<Value, Status> {
+    let prototype = match self.get(
+      &Value::Object(constructor.clone()),
+      &Key::from_str("prototype"),
+    )? {
+      Value::Object(prototype) => Some(prototype),
+      _ => Some(self.object_prototype.clone()),
+    };
+    let this = Value::Object(Object::new(ObjectKind::Ordinary, prototype));
+    let result = self.call(
+      constructor,
+      this.clone(),
+      args,
+      Some(Value::Object(constructor.clone())),
+    )?;
+    Ok(match result {
+      Value::Object(_) => result,
+      _ => this,
+    })
+  }
+
+  pub(crate) fn symbol(&self, description: Option<String>) -> Value {
+    Value::Symbol(Symbol { description }.into())
+  }
+
+  /// Run the jobs sent from the other threads, returns whether any job is run
+  pub(crate) fn run_jobs(&self) -> bool {
+    let mut ran = false;
+    loop {
+      let job = self.queue.state.lock().unwrap().jobs.pop_front();
+      match job {
+        Some(job) => {
+          job(self.raw());
+          ran = true;
+        }
+        None => return ran,
+      }
+    }
+  }
+
+  /// Wait for a job, returns `false` if no job could come anymore
+  pub(crate) fn wait_job(&self) -> bool {
+    let state = self.queue.state.lock().unwrap();
+    let state = self
+      .queue
+      .ready
+      .wait_while(state, |state| state.jobs.is_empty() && state.pending > 0)
+      .unwrap();
+    !state.jobs.is_empty()
+  }
+
+  pub(crate) fn send_job(queue: &JobQueue, job: Job) {
+    queue.state.lock().unwrap().jobs.push_back(job);
+    queue.ready.notify_all();
+  }
+
+  pub(crate) fn set_pending(queue: &JobQueue, change: isize) {
+    let mut state = queue.state.lock().unwrap();
+    state.pending = state.pending.saturating_add_signed(change);
+    queue.ready.notify_all();
+  }
+}
+
+pub(crate) fn define_hidden(object: &Object, name: &str, value: Value) {
+  object.define(
+    Key::from_str(name),
+    Property::Data {
+      value,
+      attributes: sys::PropertyAttributes::writable | sys::PropertyAttributes::configurable,
+    },
+  );
+}
+
+/// Run `f` with the env state, the returned status is recorded for `napi_get_last_error_info`
+pub(crate) fn with_env(
+  env: sys::napi_env,
+  f: impl FnOnce(&EnvState) -> Result<(), Status>,
+) -> Status {
+  if env.is_null() {
+    return sys::Status::napi_invalid_arg;
+  }
+  let state = unsafe { EnvState::from_raw(env) };
+  let status = match f(state) {
+    Ok(()) => sys::Status::napi_ok,
+    Err(status) => status,
+  };
+  state.last_error.borrow_mut().error_code = status;
+  status
+}
+
+/// Write `value` into the out parameter `result`
+pub(crate) fn write<T>(result: *mut T, value: T) -> Result<(), Status> {
+  if result.is_null() {
+    return Err(sys::Status::napi_invalid_arg);
+  }
+  unsafe { result.write(value) };
+  Ok(())
+}
+
+/// Read a string argument, `length` is `NAPI_AUTO_LENGTH` (-1) for nul terminated strings
+pub(crate) unsafe fn read_str<'a>(value: *const c_char, length: isize) -> Result<&'a [u8], Status> {
+  if value.is_null() {
+    return if length == 0 {
+      Ok(&[])
+    } else {
+      Err(sys::Status::napi_invalid_arg)
+    };
+  }
+  Ok(if length < 0 {
+    unsafe { CStr::from_ptr(value) }.to_bytes()
+  } else {
+    unsafe { std::slice::from_raw_parts(value.cast(), length as usize) }
+  })
+}
+
+pub(crate) unsafe fn read_utf16<'a>(value: *const u16, length: isize) -> Result<&'a [u16], Status> {
+  if value.is_null() {
+    return if length == 0 {
+      Ok(&[])
+    } else {
+      Err(sys::Status::napi_invalid_arg)
+    };
+  }
+  let length = if length < 0 {
+    (0..).take_while(|i| unsafe { *value.add(*i) } != 0).count()
+  } else {
+    length as usize
+  };
+  Ok(unsafe { std::slice::from_raw_parts(value, length) })
+}
+
+pub(crate) unsafe fn read_name(name: *const c_char) -> Result<Key, Status> {
+  if name.is_null() {
+    return Err(sys::Status::napi_invalid_arg);
+  }
+  Ok(Key::String(
+    String::from_utf8_lossy(unsafe { CStr::from_ptr(name) }.to_bytes()).into(),
+  ))
+}
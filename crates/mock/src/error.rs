@@ -0,0 +1,208 @@
+//! Errors and exceptions
+
+use std::os::raw::c_char;
+
+use napi_sys as sys;
+
+use crate::env::{read_str, with_env, write, EnvState, Status};
+use crate::value::{ObjectKind, Value};
+
+/// The messages of the statuses, in the order of the status codes
+const ERROR_MESSAGES: [&std::ffi::CStr; 23] = [
+  c"",
+  c"Invalid argument",
+  c"An object was expected",
+  c"A string was expected",
+  c"A string or symbol was expected",
+  c"A function was expected",
+  c"A number was expected",
+  c"A boolean was expected",
+  c"An array was expected",
+  c"Unknown failure",
+  c"An exception is pending",
+  c"The async work item was cancelled",
+  c"napi_escape_handle already called on scope",
+  c"Invalid handle scope usage",
+  c"Invalid callback scope usage",
+  c"Thread-safe function queue is full",
+  c"Thread-safe function handle is closing",
+  c"A bigint was expected",
+  c"A date was expected",
+  c"An arraybuffer was expected",
+  c"A detachable arraybuffer was expected",
+  c"Main thread would deadlock",
+  c"External buffers are not allowed",
+];
+
+fn create_error(
+  env: &EnvState,
+  kind: usize,
+  code: sys::napi_value,
+  msg: sys::napi_value,
+  result: *mut sys::napi_value,
+) -> Result<(), Status> {
+  let message = env.value(msg)?;
+  if !matches!(message, Value::String(_)) {
+    return Err(sys::Status::napi_string_expected);
+  }
+  let code = if code.is_null() {
+    None
+  } else {
+    match env.value(code)? {
+      code @ Value::String(_) => Some(code),
+      _ => return Err(sys::Status::napi_string_expected),
+    }
+  };
+  let error = env.new_error(kind, code, message);
+  write(result, env.handle(Value::Object(error)))
+}
+
+pub(crate) unsafe extern "C" fn napi_create_error(
+  env: sys::napi_env,
+  code: sys::napi_value,
+  msg: sys::napi_value,
+  result: *mut sys::napi_value,
+) -> sys::napi_status {
+  with_env(env, |env| create_error(env, 0, code, msg, result))
+}
+
+pub(crate) unsafe extern "C" fn napi_create_type_error(
+  env: sys::napi_env,
+  code: sys::napi_value,
+  msg: sys::napi_value,
+  result: *mut sys::napi_value,
+) -> sys::napi_status {
+  with_env(env, |env| create_error(env, 1, code, msg, result))
+}
+
+pub(crate) unsafe extern "C" fn napi_create_range_error(
+  env: sys::napi_env,
+  code: sys::napi_value,
+  msg: sys::napi_value,
+  result: *mut sys::napi_value,
+) -> sys::napi_status {
+  with_env(env, |env| create_error(env, 2, code, msg, result))
+}
+
+pub(crate) unsafe extern "C" fn node_api_create_syntax_error(
+  env: sys::napi_env,
+  code: sys::napi_value,
+  msg: sys::napi_value,
+  result: *mut sys::napi_value,
+) -> sys::napi_status {
+  with_env(env, |env| create_error(env, 3, code, msg, result))
+}
+
+pub(crate) unsafe extern "C" fn napi_throw(
+  env: sys::napi_env,
+  error: sys::napi_value,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    env.throw(env.value(error)?);
+    Ok(())
+  })
+}
+
+unsafe fn throw_error(
+  env: sys::napi_env,
+  kind: usize,
+  code: *const c_char,
+  msg: *const c_char,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let string = |value: *const c_char| -> Result<Value, Status> {
+      let value = unsafe { read_str(value, -1) }?;
+      Ok(Value::String(String::from_utf8_lossy(value).into()))
+    };
+    let code = if code.is_null() {
+      None
+    } else {
+      Some(string(code)?)
+    };
+    let error = env.new_error(kind, code, string(msg)?);
+    env.throw(Value::Object(error));
+    Ok(())
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_throw_error(
+  env: sys::napi_env,
+  code: *const c_char,
+  msg: *const c_char,
+) -> sys::napi_status {
+  unsafe { throw_error(env, 0, code, msg) }
+}
+
+pub(crate) unsafe extern "C" fn napi_throw_type_error(
+  env: sys::napi_env,
+  code: *const c_char,
+  msg: *const c_char,
+) -> sys::napi_status {
+  unsafe { throw_error(env, 1, code, msg) }
+}
+
+pub(crate) unsafe extern "C" fn napi_throw_range_error(
+  env: sys::napi_env,
+  code: *const c_char,
+  msg: *const c_char,
+) -> sys::napi_status {
+  unsafe { throw_error(env, 2, code, msg) }
+}
+
+pub(crate) unsafe extern "C" fn node_api_throw_syntax_error(
+  env: sys::napi_env,
+  code: *const c_char,
+  msg: *const c_char,
+) -> sys::napi_status {
+  unsafe { throw_error(env, 3, code, msg) }
+}
+
+pub(crate) unsafe extern "C" fn napi_is_error(
+  env: sys::napi_env,
+  value: sys::napi_value,
+  result: *mut bool,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let is_error = match env.value(value)? {
+      Value::Object(object) => {
+        matches!(object.borrow().kind, ObjectKind::Error)
+          || object.has_in_chain(&env.error_prototypes[0])
+      }
+      _ => false,
+    };
+    write(result, is_error)
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_is_exception_pending(
+  env: sys::napi_env,
+  result: *mut bool,
+) -> sys::napi_status {
+  with_env(env, |env| write(result, env.has_pending_exception()))
+}
+
+pub(crate) unsafe extern "C" fn napi_get_and_clear_last_exception(
+  env: sys::napi_env,
+  result: *mut sys::napi_value,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let exception = env.pending_exception.take().unwrap_or(Value::Undefined);
+    write(result, env.handle(exception))
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_get_last_error_info(
+  env: sys::napi_env,
+  result: *mut *const sys::napi_extended_error_info,
+) -> sys::napi_status {
+  if env.is_null() || result.is_null() {
+    return sys::Status::napi_invalid_arg;
+  }
+  let env = unsafe { EnvState::from_raw(env) };
+  let mut last_error = env.last_error.borrow_mut();
+  last_error.error_message = ERROR_MESSAGES
+    .get(last_error.error_code as usize)
+    .map_or(std::ptr::null(), |message| message.as_ptr());
+  unsafe { result.write(&*last_error) };
+  sys::Status::napi_ok
+}
@@ -0,0 +1,300 @@
+//! Functions, callbacks and classes
+
+use std::ffi::c_void;
+use std::os::raw::c_char;
+
+use napi_sys as sys;
+
+use crate::env::{read_str, with_env, write, CallbackInfo, EnvState, Status};
+use crate::object::{descriptor_property, descriptors};
+use crate::value::{Key, NativeFunction, Object, Property, Value};
+
+unsafe fn function_name(utf8name: *const c_char, length: isize) -> Result<String, Status> {
+  if utf8name.is_null() {
+    return Ok(String::new());
+  }
+  Ok(String::from_utf8_lossy(unsafe { read_str(utf8name, length) }?).into_owned())
+}
+
+fn args(env: &EnvState, argc: usize, argv: *const sys::napi_value) -> Result<Vec<Value>, Status> {
+  if argc > 0 && argv.is_null() {
+    return Err(sys::Status::napi_invalid_arg);
+  }
+  (0..argc)
+    .map(|i| env.value(unsafe { *argv.add(i) }))
+    .collect()
+}
+
+pub(crate) unsafe extern "C" fn napi_create_function(
+  env: sys::napi_env,
+  utf8name: *const c_char,
+  length: isize,
+  cb: sys::napi_callback,
+  data: *mut c_void,
+  result: *mut sys::napi_value,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let callback = cb.ok_or(sys::Status::napi_invalid_arg)?;
+    let name = unsafe { function_name(utf8name, length) }?;
+    let function = env.new_function(&name, NativeFunction { callback, data });
+    write(result, env.handle(Value::Object(function)))
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_call_function(
+  env: sys::napi_env,
+  recv: sys::napi_value,
+  func: sys::napi_value,
+  argc: usize,
+  argv: *const sys::napi_value,
+  result: *mut sys::napi_value,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    if env.has_pending_exception() {
+      return Err(sys::Status::napi_pending_exception);
+    }
+    let this = env.value(recv)?;
+    let function = env.function(func)?;
+    let value = env.call(&function, this, args(env, argc, argv)?, None)?;
+    if !result.is_null() {
+      unsafe { result.write(env.handle(value)) };
+    }
+    Ok(())
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_new_instance(
+  env: sys::napi_env,
+  constructor: sys::napi_value,
+  argc: usize,
+  argv: *const sys::napi_value,
+  result: *mut sys::napi_value,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    if env.has_pending_exception() {
+      return Err(sys::Status::napi_pending_exception);
+    }
+    let constructor = env.function(constructor)?;
+    let instance = env.construct(&constructor, args(env, argc, argv)?)?;
+    write(result, env.handle(instance))
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_get_cb_info(
+  env: sys::napi_env,
+  cbinfo: sys::napi_callback_info,
+  argc: *mut usize,
+  argv: *mut sys::napi_value,
+  this_arg: *mut sys::napi_value,
+  data: *mut *mut c_void,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    if cbinfo.is_null() {
+      return Err(sys::Status::napi_invalid_arg);
+    }
+    let info = unsafe { &*cbinfo.cast::<CallbackInfo>() };
+    if !argc.is_null() {
+      let capacity = unsafe { *argc };
+      if capacity > 0 && argv.is_null() {
+        return Err(sys::Status::napi_invalid_arg);
+      }
+      for i in 0..capacity {
+        let arg = match info.args.get(i) {
+          Some(arg) => *arg,
+          None => env.handle(Value::Undefined),
+        };
+        unsafe { argv.add(i).write(arg) };
+      }
+      unsafe { argc.write(info.args.len()) };
+    }
+    if !this_arg.is_null() {
+      unsafe { this_arg.write(info.this) };
+    }
+    if !data.is_null() {
+      unsafe { data.write(info.data) };
+    }
+    Ok(())
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_get_new_target(
+  env: sys::napi_env,
+  cbinfo: sys::napi_callback_info,
+  result: *mut sys::napi_value,
+) -> sys::napi_status {
+  with_env(env, |_| {
+    if cbinfo.is_null() {
+      return Err(sys::Status::napi_invalid_arg);
+    }
+    let info = unsafe { &*cbinfo.cast::<CallbackInfo>() };
+    write(result, info.new_target)
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_define_class(
+  env: sys::napi_env,
+  utf8name: *const c_char,
+  length: isize,
+  constructor: sys::napi_callback,
+  data: *mut c_void,
+  property_count: usize,
+  properties: *const sys::napi_property_descriptor,
+  result: *mut sys::napi_value,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let callback = constructor.ok_or(sys::Status::napi_invalid_arg)?;
+    let name = unsafe { function_name(utf8name, length) }?;
+    let class = env.new_function(&name, NativeFunction { callback, data });
+    let prototype = env.new_object();
+    prototype.define(
+      Key::from_str("constructor"),
+      Property::Data {
+        value: Value::Object(class.clone()),
+        attributes: sys::PropertyAttributes::writable | sys::PropertyAttributes::configurable,
+      },
+    );
+    class.define(
+      Key::from_str("prototype"),
+      Property::Data {
+        value: Value::Object(prototype.clone()),
+        attributes: sys::PropertyAttributes::writable,
+      },
+    );
+    for descriptor in unsafe { descriptors(property_count, properties) }? {
+      let (key, property) = descriptor_property(env, descriptor)?;
+      if descriptor.attributes & sys::PropertyAttributes::static_ != 0 {
+        class.define(key, property);
+      } else {
+        prototype.define(key, property);
+      }
+    }
+    write(result, env.handle(Value::Object(class)))
+  })
+}
+
+/// `Object.setPrototypeOf`, class inheritance of napi-rs uses it
+unsafe extern "C" fn set_prototype_of(
+  env: sys::napi_env,
+  info: sys::napi_callback_info,
+) -> sys::napi_value {
+  let state = unsafe { EnvState::from_raw(env) };
+  let info = unsafe { &*info.cast::<CallbackInfo>() };
+  let arg = |i: usize| {
+    info
+      .args
+      .get(i)
+      .map_or(Ok(Value::Undefined), |arg| state.value(*arg))
+  };
+  let (Ok(target), Ok(prototype)) = (arg(0), arg(1)) else {
+    return std::ptr::null_mut();
+  };
+  match (&target, prototype) {
+    (Value::Object(object), Value::Object(prototype)) => {
+      object.borrow_mut().prototype = Some(prototype);
+    }
+    (Value::Object(object), Value::Null) => object.borrow_mut().prototype = None,
+    _ => {
+      state.throw_error(1, "Object prototype may only be an Object or null");
+      return std::ptr::null_mut();
+    }
+  }
+  state.handle(target)
+}
+
+/// `Error.captureStackTrace`, there are no stack traces in the mock host
+unsafe extern "C" fn capture_stack_trace(
+  env: sys::napi_env,
+  _info: sys::napi_callback_info,
+) -> sys::napi_value {
+  unsafe { EnvState::from_raw(env) }.handle(Value::Undefined)
+}
+
+/// Unsupported constructors of the globals, they throw a `TypeError`
+unsafe extern "C" fn unsupported(
+  env: sys::napi_env,
+  _info: sys::napi_callback_info,
+) -> sys::napi_value {
+  unsafe { EnvState::from_raw(env) }.throw_error(1, "Not supported by the mock host");
+  std::ptr::null_mut()
+}
+
+/// Define the globals napi-rs looks up, `Object`, `Symbol`, the errors and `globalThis`
+pub(crate) fn define_globals(env: &EnvState) {
+  let global = &env.global;
+  let constructor = |name: &str, prototype: &Object| {
+    let function = env.new_function(
+      name,
+      NativeFunction {
+        callback: unsupported,
+        data: std::ptr::null_mut(),
+      },
+    );
+    function.define(
+      Key::from_str("prototype"),
+      Property::Data {
+        value: Value::Object(prototype.clone()),
+        attributes: 0,
+      },
+    );
+    crate::env::define_hidden(prototype, "constructor", Value::Object(function.clone()));
+    crate::env::define_hidden(global, name, Value::Object(function.clone()));
+    function
+  };
+  let method = |target: &Object, name: &str, callback: NativeFunction| {
+    crate::env::define_hidden(
+      target,
+      name,
+      Value::Object(env.new_function(name, callback)),
+    );
+  };
+  let native = |callback| NativeFunction {
+    callback,
+    data: std::ptr::null_mut(),
+  };
+
+  let object = constructor("Object", &env.object_prototype);
+  method(&object, "setPrototypeOf", native(set_prototype_of));
+  constructor("Function", &env.function_prototype);
+  constructor("Array", &env.array_prototype);
+  constructor("Promise", &env.promise_prototype);
+  for (prototype, name) in
+    env
+      .error_prototypes
+      .iter()
+      .zip(["Error", "TypeError", "RangeError", "SyntaxError"])
+  {
+    let error = constructor(name, prototype);
+    if name == "Error" {
+      method(&error, "captureStackTrace", native(capture_stack_trace));
+    }
+  }
+
+  let symbol_prototype = env.new_object();
+  let symbol = constructor("Symbol", &symbol_prototype);
+  for name in [
+    "asyncIterator",
+    "hasInstance",
+    "isConcatSpreadable",
+    "iterator",
+    "match",
+    "matchAll",
+    "replace",
+    "search",
+    "species",
+    "split",
+    "toPrimitive",
+    "toStringTag",
+    "unscopables",
+  ] {
+    let value = env.symbol(Some(format!("Symbol.{name}")));
+    symbol.define(
+      Key::from_str(name),
+      Property::Data {
+        value,
+        attributes: 0,
+      },
+    );
+  }
+
+  crate::env::define_hidden(global, "globalThis", Value::Object(global.clone()));
+}
@@ -0,0 +1,375 @@
+//! An in-process mock N-API host for testing napi-rs addons with `cargo test`
+//!
+//! `MockEnv` registers the `#[napi]` exports of the crate under test like Node.js does when the
+//! addon is imported, then they can be called through the napi-rs APIs:
+//!
+//! ```rust,ignore
+//! let env = napi_mock::MockEnv::new();
+//! let plus: Function<FnArgs<(i32, i32)>, i32> = env.get_export("plus")?;
+//! assert_eq!(plus.call((1, 2).into())?, 3);
+//! ```
+//!
+//! There is no JavaScript engine behind it, the values only live in Rust and nothing is garbage
+//! collected before the `MockEnv` is dropped.
+
+use std::ffi::c_void;
+use std::sync::Once;
+
+use napi::bindgen_prelude::{
+  FromNapiValue, JsObjectValue, Object, ToNapiValue, Unknown, ValidateNapiValue,
+};
+use napi::{Env, Error, JsValue, Result, Status};
+use napi_sys as sys;
+
+use crate::env::EnvState;
+use crate::value::{ObjectKind, PromiseState, Value};
+
+mod async_work;
+mod buffer;
+mod env;
+mod error;
+mod function;
+mod lifecycle;
+mod object;
+mod promise;
+mod value;
+mod values;
+
+macro_rules! resolve {
+  ($name:expr, $($module:ident::$function:ident,)*) => {
+    match $name {
+      $(stringify!($function) => Some($module::$function as *const c_void),)*
+      _ => None,
+    }
+  };
+}
+
+fn resolve(name: &str) -> Option<*const c_void> {
+  resolve!(
+    name,
+    values::napi_get_undefined,
+    values::napi_get_null,
+    values::napi_get_global,
+    values::napi_get_boolean,
+    values::napi_create_double,
+    values::napi_create_int32,
+    values::napi_create_uint32,
+    values::napi_create_int64,
+    values::napi_get_value_double,
+    values::napi_get_value_int32,
+    values::napi_get_value_uint32,
+    values::napi_get_value_int64,
+    values::napi_get_value_bool,
+    values::napi_create_string_latin1,
+    values::napi_create_string_utf8,
+    values::napi_create_string_utf16,
+    values::node_api_create_property_key_latin1,
+    values::node_api_create_property_key_utf8,
+    values::node_api_create_property_key_utf16,
+    values::node_api_create_external_string_latin1,
+    values::node_api_create_external_string_utf16,
+    values::napi_get_value_string_latin1,
+    values::napi_get_value_string_utf8,
+    values::napi_get_value_string_utf16,
+    values::napi_create_symbol,
+    values::node_api_symbol_for,
+    values::napi_typeof,
+    values::napi_coerce_to_bool,
+    values::napi_coerce_to_number,
+    values::napi_coerce_to_string,
+    values::napi_coerce_to_object,
+    values::napi_create_bigint_int64,
+    values::napi_create_bigint_uint64,
+    values::napi_create_bigint_words,
+    values::napi_get_value_bigint_int64,
+    values::napi_get_value_bigint_uint64,
+    values::napi_get_value_bigint_words,
+    values::napi_create_date,
+    values::napi_is_date,
+    values::napi_get_date_value,
+    object::napi_create_object,
+    object::napi_create_array,
+    object::napi_create_array_with_length,
+    object::napi_create_object_with_properties,
+    object::napi_set_property,
+    object::napi_get_property,
+    object::napi_has_property,
+    object::napi_has_own_property,
+    object::napi_delete_property,
+    object::napi_set_named_property,
+    object::napi_get_named_property,
+    object::napi_has_named_property,
+    object::napi_set_element,
+    object::napi_get_element,
+    object::napi_has_element,
+    object::napi_delete_element,
+    object::napi_define_properties,
+    object::napi_is_array,
+    object::napi_get_array_length,
+    object::napi_get_property_names,
+    object::napi_get_all_property_names,
+    object::napi_get_prototype,
+    object::napi_strict_equals,
+    object::napi_instanceof,
+    object::napi_object_freeze,
+    object::napi_object_seal,
+    object::napi_type_tag_object,
+    object::napi_check_object_type_tag,
+    function::napi_create_function,
+    function::napi_call_function,
+    function::napi_new_instance,
+    function::napi_get_cb_info,
+    function::napi_get_new_target,
+    function::napi_define_class,
+    lifecycle::napi_create_reference,
+    lifecycle::napi_delete_reference,
+    lifecycle::napi_reference_ref,
+    lifecycle::napi_reference_unref,
+    lifecycle::napi_get_reference_value,
+    lifecycle::napi_wrap,
+    lifecycle::napi_unwrap,
+    lifecycle::napi_remove_wrap,
+    lifecycle::napi_create_external,
+    lifecycle::napi_get_value_external,
+    lifecycle::napi_add_finalizer,
+    lifecycle::node_api_post_finalizer,
+    lifecycle::napi_open_handle_scope,
+    lifecycle::napi_close_handle_scope,
+    lifecycle::napi_open_escapable_handle_scope,
+    lifecycle::napi_close_escapable_handle_scope,
+    lifecycle::napi_escape_handle,
+    lifecycle::napi_open_callback_scope,
+    lifecycle::napi_close_callback_scope,
+    lifecycle::napi_async_init,
+    lifecycle::napi_async_destroy,
+    lifecycle::napi_make_callback,
+    lifecycle::napi_set_instance_data,
+    lifecycle::napi_get_instance_data,
+    lifecycle::napi_add_env_cleanup_hook,
+    lifecycle::napi_remove_env_cleanup_hook,
+    lifecycle::napi_adjust_external_memory,
+    lifecycle::napi_get_version,
+    lifecycle::napi_get_node_version,
+    lifecycle::napi_fatal_error,
+    lifecycle::napi_fatal_exception,
+    error::napi_create_error,
+    error::napi_create_type_error,
+    error::napi_create_range_error,
+    error::node_api_create_syntax_error,
+    error::napi_throw,
+    error::napi_throw_error,
+    error::napi_throw_type_error,
+    error::napi_throw_range_error,
+    error::node_api_throw_syntax_error,
+    error::napi_is_error,
+    error::napi_is_exception_pending,
+    error::napi_get_and_clear_last_exception,
+    error::napi_get_last_error_info,
+    promise::napi_create_promise,
+    promise::napi_resolve_deferred,
+    promise::napi_reject_deferred,
+    promise::napi_is_promise,
+    buffer::napi_create_arraybuffer,
+    buffer::napi_create_external_arraybuffer,
+    buffer::napi_get_arraybuffer_info,
+    buffer::napi_is_arraybuffer,
+    buffer::napi_detach_arraybuffer,
+    buffer::napi_is_detached_arraybuffer,
+    buffer::napi_create_typedarray,
+    buffer::napi_get_typedarray_info,
+    buffer::napi_is_typedarray,
+    buffer::napi_create_buffer,
+    buffer::napi_create_buffer_copy,
+    buffer::napi_create_external_buffer,
+    buffer::node_api_create_buffer_from_arraybuffer,
+    buffer::napi_get_buffer_info,
+    buffer::napi_is_buffer,
+    async_work::napi_create_async_work,
+    async_work::napi_delete_async_work,
+    async_work::napi_queue_async_work,
+    async_work::napi_cancel_async_work,
+    async_work::napi_create_threadsafe_function,
+    async_work::napi_call_threadsafe_function,
+    async_work::napi_acquire_threadsafe_function,
+    async_work::napi_release_threadsafe_function,
+    async_work::napi_ref_threadsafe_function,
+    async_work::napi_unref_threadsafe_function,
+    async_work::napi_get_threadsafe_function_context,
+  )
+}
+
+static INSTALL: Once = Once::new();
+
+/// Route the N-API functions of `napi-sys` to the mock host
+fn install() {
+  INSTALL.call_once(|| unsafe { sys::setup_with(resolve) });
+}
+
+/// A mock N-API env with the `#[napi]` exports registered
+///
+/// Only one `MockEnv` should be alive on a thread at a time, the class constructors registered
+/// by napi-rs are thread local.
+pub struct MockEnv {
+  state: Box<EnvState>,
+  exports: sys::napi_value,
+}
+
+impl MockEnv {
+  /// Create an env and register the exports into it
+  ///
+  /// Panics if an exception is thrown while registering the exports.
+  pub fn new() -> Self {
+    install();
+    let state = EnvState::new();
+    function::define_globals(&state);
+    let exports = state.handle(Value::Object(state.new_object()));
+    #[cfg(not(feature = "noop"))]
+    let exports = unsafe { napi::bindgen_prelude::napi_register_module_v1(state.raw(), exports) };
+    let env = Self { state, exports };
+    if let Some(exception) = env.state.pending_exception.take() {
+      panic!(
+        "Failed to register the exports: {}",
+        env.state.to_string(&exception).unwrap_or_default()
+      );
+    }
+    env
+  }
+
+  /// The `Env` to create values and call into the exports with
+  pub fn env(&self) -> Env {
+    Env::from_raw(self.raw())
+  }
+
+  /// The raw `napi_env`, it's valid until the `MockEnv` is dropped
+  pub fn raw(&self) -> sys::napi_env {
+    self.state.raw()
+  }
+
+  /// The `exports` object of the addon
+  pub fn exports(&self) -> Object<'_> {
+    unsafe { Object::from_napi_value(self.raw(), self.exports) }
+      .expect("The exports should be an object")
+  }
+
+  /// Get the export `name` as `T`
+  pub fn get_export<T: FromNapiValue + ValidateNapiValue>(&self, name: &str) -> Result<T> {
+    self.exports().get_named_property(name)
+  }
+
+  /// Convert `value` into a JavaScript value and back, like passing it to JavaScript and
+  /// receiving it back from it
+  pub fn round_trip<T: ToNapiValue, R: FromNapiValue>(&self, value: T) -> Result<R> {
+    unsafe {
+      let raw = T::to_napi_value(self.raw(), value)?;
+      R::from_napi_value(self.raw(), raw)
+    }
+  }
+
+  /// Run the completions of the async works and the calls of the threadsafe functions, until no
+  /// more could come
+  ///
+  /// The threadsafe functions which are referenced keep it waiting until they are released.
+  pub fn run_pending_jobs(&self) {
+    loop {
+      self.state.run_jobs();
+      if !self.state.wait_job() {
+        break;
+      }
+    }
+  }
+
+  /// Run the pending jobs until `promise` is settled, then get its result
+  pub fn await_promise<T: FromNapiValue>(&self, promise: Unknown<'_>) -> Result<T> {
+    let object = self
+      .state
+      .object(promise.raw())
+      .map_err(|_| Error::new(Status::ObjectExpected, "Expected a Promise"))?;
+    loop {
+      let state = match &object.borrow().kind {
+        ObjectKind::Promise(state) => state.clone(),
+        _ => return Err(Error::new(Status::InvalidArg, "Expected a Promise")),
+      };
+      match state {
+        PromiseState::Pending => {
+          if !self.state.run_jobs() && !self.state.wait_job() {
+            return Err(Error::new(
+              Status::GenericFailure,
+              "The Promise is never settled",
+            ));
+          }
+        }
+        PromiseState::Fulfilled(value) => {
+          return unsafe { T::from_napi_value(self.raw(), self.state.handle(value)) };
+        }
+        PromiseState::Rejected(reason) => {
+          let reason = self.state.handle(reason);
+          return Err(Error::from(unsafe {
+            Unknown::from_raw_unchecked(self.raw(), reason)
+          }));
+        }
+      }
+    }
+  }
+
+  /// Take the exceptions thrown by the calls of the threadsafe functions, and the ones passed to
+  /// `napi_fatal_exception`, formatted with `String(exception)`
+  pub fn take_uncaught_exceptions(&self) -> Vec<String> {
+    self
+      .state
+      .uncaught_exceptions
+      .take()
+      .iter()
+      .map(|exception| self.state.to_string(exception).unwrap_or_default())
+      .collect()
+  }
+}
+
+impl Default for MockEnv {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl Drop for MockEnv {
+  fn drop(&mut self) {
+    let state = &self.state;
+    loop {
+      let workers = state.workers.take();
+      if workers.is_empty() && !state.run_jobs() {
+        break;
+      }
+      for worker in workers {
+        let _ = worker.join();
+      }
+    }
+    loop {
+      let tsfn = state.threadsafe_functions.borrow().last().copied();
+      match tsfn {
+        Some(tsfn) => async_work::finalize_threadsafe_function(state, tsfn),
+        None => break,
+      }
+    }
+    state.run_jobs();
+    loop {
+      let hook = state.cleanup_hooks.borrow_mut().pop();
+      match hook {
+        Some((hook, arg)) => unsafe { hook(arg) },
+        None => break,
+      }
+    }
+    loop {
+      let finalizers = state.finalizers.take();
+      if finalizers.is_empty() {
+        break;
+      }
+      for finalizer in finalizers.into_iter().flatten() {
+        unsafe { (finalizer.callback)(state.raw(), finalizer.data, finalizer.hint) };
+      }
+      state.run_jobs();
+    }
+    if let Some((data, Some(finalizer))) = state.instance_data.take() {
+      unsafe { (finalizer.callback)(state.raw(), data, finalizer.hint) };
+    }
+    state.run_jobs();
+  }
+}
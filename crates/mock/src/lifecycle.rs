@@ -0,0 +1,647 @@
+//! References, wrapping, externals, scopes and the env lifecycle
+//!
+//! Nothing is garbage collected by the mock host, so references never become empty and all the
+//! finalizers are called when the `MockEnv` is dropped.
+
+use std::ffi::c_void;
+use std::os::raw::c_char;
+use std::ptr::{self, NonNull};
+
+use napi_sys as sys;
+
+use crate::env::{read_str, with_env, write, EnvState, Finalizer, SendPtr, Status};
+use crate::value::{Object, ObjectKind, Value, Wrap};
+
+/// The data behind a `napi_ref`
+pub(crate) struct Reference {
+  value: Value,
+  count: u32,
+}
+
+fn new_reference(value: Value, count: u32) -> sys::napi_ref {
+  Box::into_raw(Box::new(Reference { value, count })).cast()
+}
+
+unsafe fn reference<'r>(reference: sys::napi_ref) -> Result<&'r mut Reference, Status> {
+  if reference.is_null() {
+    return Err(sys::Status::napi_invalid_arg);
+  }
+  Ok(unsafe { &mut *reference.cast::<Reference>() })
+}
+
+fn finalizer(
+  finalize_cb: sys::napi_finalize,
+  data: *mut c_void,
+  hint: *mut c_void,
+) -> Option<Finalizer> {
+  finalize_cb.map(|callback| Finalizer {
+    callback,
+    data,
+    hint,
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_create_reference(
+  env: sys::napi_env,
+  value: sys::napi_value,
+  initial_refcount: u32,
+  result: *mut sys::napi_ref,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let value = env.value(value)?;
+    write(result, new_reference(value, initial_refcount))
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_delete_reference(
+  env: sys::napi_env,
+  ref_: sys::napi_ref,
+) -> sys::napi_status {
+  with_env(env, |_| {
+    if ref_.is_null() {
+      return Err(sys::Status::napi_invalid_arg);
+    }
+    drop(unsafe { Box::from_raw(ref_.cast::<Reference>()) });
+    Ok(())
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_reference_ref(
+  env: sys::napi_env,
+  ref_: sys::napi_ref,
+  result: *mut u32,
+) -> sys::napi_status {
+  with_env(env, |_| {
+    let reference = unsafe { reference(ref_) }?;
+    reference.count += 1;
+    if !result.is_null() {
+      unsafe { result.write(reference.count) };
+    }
+    Ok(())
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_reference_unref(
+  env: sys::napi_env,
+  ref_: sys::napi_ref,
+  result: *mut u32,
+) -> sys::napi_status {
+  with_env(env, |_| {
+    let reference = unsafe { reference(ref_) }?;
+    reference.count = reference
+      .count
+      .checked_sub(1)
+      .ok_or(sys::Status::napi_generic_failure)?;
+    if !result.is_null() {
+      unsafe { result.write(reference.count) };
+    }
+    Ok(())
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_get_reference_value(
+  env: sys::napi_env,
+  ref_: sys::napi_ref,
+  result: *mut sys::napi_value,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let value = unsafe { reference(ref_) }?.value.clone();
+    write(result, env.handle(value))
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_wrap(
+  env: sys::napi_env,
+  js_object: sys::napi_value,
+  native_object: *mut c_void,
+  finalize_cb: sys::napi_finalize,
+  finalize_hint: *mut c_void,
+  result: *mut sys::napi_ref,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let object = env.object(js_object)?;
+    if object.borrow().wrap.is_some() {
+      return Err(sys::Status::napi_invalid_arg);
+    }
+    let finalizer = finalizer(finalize_cb, native_object, finalize_hint)
+      .map(|finalizer| env.add_finalizer(finalizer));
+    object.borrow_mut().wrap = Some(Wrap {
+      data: native_object,
+      finalizer,
+    });
+    if !result.is_null() {
+      unsafe { result.write(new_reference(Value::Object(object), 0)) };
+    }
+    Ok(())
+  })
+}
+
+fn wrapped(env: &EnvState, js_object: sys::napi_value) -> Result<Object, Status> {
+  let object = env.object(js_object)?;
+  if object.borrow().wrap.is_none() {
+    return Err(sys::Status::napi_invalid_arg);
+  }
+  Ok(object)
+}
+
+pub(crate) unsafe extern "C" fn napi_unwrap(
+  env: sys::napi_env,
+  js_object: sys::napi_value,
+  result: *mut *mut c_void,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let object = wrapped(env, js_object)?;
+    let data = object.borrow().wrap.as_ref().map(|wrap| wrap.data);
+    write(result, data.unwrap_or(ptr::null_mut()))
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_remove_wrap(
+  env: sys::napi_env,
+  js_object: sys::napi_value,
+  result: *mut *mut c_void,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let object = wrapped(env, js_object)?;
+    let Some(wrap) = object.borrow_mut().wrap.take() else {
+      return Err(sys::Status::napi_invalid_arg);
+    };
+    if let Some(finalizer) = wrap.finalizer {
+      env.remove_finalizer(finalizer);
+    }
+    if !result.is_null() {
+      unsafe { result.write(wrap.data) };
+    }
+    Ok(())
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_create_external(
+  env: sys::napi_env,
+  data: *mut c_void,
+  finalize_cb: sys::napi_finalize,
+  finalize_hint: *mut c_void,
+  result: *mut sys::napi_value,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    if let Some(finalizer) = finalizer(finalize_cb, data, finalize_hint) {
+      env.add_finalizer(finalizer);
+    }
+    let external = Object::new(ObjectKind::External(data), None);
+    write(result, env.handle(Value::Object(external)))
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_get_value_external(
+  env: sys::napi_env,
+  value: sys::napi_value,
+  result: *mut *mut c_void,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let data = match env.object(value)?.borrow().kind {
+      ObjectKind::External(data) => data,
+      _ => return Err(sys::Status::napi_invalid_arg),
+    };
+    write(result, data)
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_add_finalizer(
+  env: sys::napi_env,
+  js_object: sys::napi_value,
+  native_object: *mut c_void,
+  finalize_cb: sys::napi_finalize,
+  finalize_hint: *mut c_void,
+  result: *mut sys::napi_ref,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let object = env.object(js_object)?;
+    let finalizer =
+      finalizer(finalize_cb, native_object, finalize_hint).ok_or(sys::Status::napi_invalid_arg)?;
+    env.add_finalizer(finalizer);
+    if !result.is_null() {
+      unsafe { result.write(new_reference(Value::Object(object), 0)) };
+    }
+    Ok(())
+  })
+}
+
+pub(crate) unsafe extern "C" fn node_api_post_finalizer(
+  env: sys::node_api_basic_env,
+  finalize_cb: sys::napi_finalize,
+  finalize_data: *mut c_void,
+  finalize_hint: *mut c_void,
+) -> sys::napi_status {
+  with_env(env.cast(), |env| {
+    let finalizer =
+      finalizer(finalize_cb, finalize_data, finalize_hint).ok_or(sys::Status::napi_invalid_arg)?;
+    let (data, hint) = (SendPtr(finalizer.data), SendPtr(finalizer.hint));
+    let callback = finalizer.callback;
+    EnvState::send_job(
+      &env.queue,
+      Box::new(move |env| {
+        let (data, hint) = (data, hint);
+        unsafe { callback(env, data.0, hint.0) }
+      }),
+    );
+    Ok(())
+  })
+}
+
+/// Handle scopes are not needed, the handles live as long as the env
+fn scope<T>() -> *mut T {
+  NonNull::dangling().as_ptr()
+}
+
+pub(crate) unsafe extern "C" fn napi_open_handle_scope(
+  env: sys::napi_env,
+  result: *mut sys::napi_handle_scope,
+) -> sys::napi_status {
+  with_env(env, |_| write(result, scope()))
+}
+
+pub(crate) unsafe extern "C" fn napi_close_handle_scope(
+  env: sys::napi_env,
+  scope: sys::napi_handle_scope,
+) -> sys::napi_status {
+  with_env(env, |_| {
+    if scope.is_null() {
+      return Err(sys::Status::napi_invalid_arg);
+    }
+    Ok(())
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_open_escapable_handle_scope(
+  env: sys::napi_env,
+  result: *mut sys::napi_escapable_handle_scope,
+) -> sys::napi_status {
+  with_env(env, |_| write(result, scope()))
+}
+
+pub(crate) unsafe extern "C" fn napi_close_escapable_handle_scope(
+  env: sys::napi_env,
+  scope: sys::napi_escapable_handle_scope,
+) -> sys::napi_status {
+  with_env(env, |_| {
+    if scope.is_null() {
+      return Err(sys::Status::napi_invalid_arg);
+    }
+    Ok(())
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_escape_handle(
+  env: sys::napi_env,
+  scope: sys::napi_escapable_handle_scope,
+  escapee: sys::napi_value,
+  result: *mut sys::napi_value,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    if scope.is_null() {
+      return Err(sys::Status::napi_invalid_arg);
+    }
+    env.value(escapee)?;
+    write(result, escapee)
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_open_callback_scope(
+  env: sys::napi_env,
+  _resource_object: sys::napi_value,
+  _context: sys::napi_async_context,
+  result: *mut sys::napi_callback_scope,
+) -> sys::napi_status {
+  with_env(env, |_| write(result, scope()))
+}
+
+pub(crate) unsafe extern "C" fn napi_close_callback_scope(
+  env: sys::napi_env,
+  _scope: sys::napi_callback_scope,
+) -> sys::napi_status {
+  with_env(env, |_| Ok(()))
+}
+
+pub(crate) unsafe extern "C" fn napi_async_init(
+  env: sys::napi_env,
+  _async_resource: sys::napi_value,
+  _async_resource_name: sys::napi_value,
+  result: *mut sys::napi_async_context,
+) -> sys::napi_status {
+  with_env(env, |_| write(result, scope()))
+}
+
+pub(crate) unsafe extern "C" fn napi_async_destroy(
+  env: sys::napi_env,
+  _async_context: sys::napi_async_context,
+) -> sys::napi_status {
+  with_env(env, |_| Ok(()))
+}
+
+pub(crate) unsafe extern "C" fn napi_make_callback(
+  env: sys::napi_env,
+  _async_context: sys::napi_async_context,
+  recv: sys::napi_value,
+  func: sys::napi_value,
+  argc: usize,
+  argv: *const sys::napi_value,
+  result: *mut sys::napi_value,
+) -> sys::napi_status {
+  unsafe { crate::function::napi_call_function(env, recv, func, argc, argv, result) }
+}
+
+pub(crate) unsafe extern "C" fn napi_set_instance_data(
+  env: sys::napi_env,
+  data: *mut c_void,
+  finalize_cb: sys::napi_finalize,
+  finalize_hint: *mut c_void,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    env
+      .instance_data
+      .set(Some((data, finalizer(finalize_cb, data, finalize_hint))));
+    Ok(())
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_get_instance_data(
+  env: sys::napi_env,
+  data: *mut *mut c_void,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let instance_data = env.instance_data.get();
+    write(
+      data,
+      instance_data.map_or(ptr::null_mut(), |(data, _)| data),
+    )
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_add_env_cleanup_hook(
+  env: sys::napi_env,
+  fun: Option<unsafe extern "C" fn(arg: *mut c_void)>,
+  arg: *mut c_void,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let fun = fun.ok_or(sys::Status::napi_invalid_arg)?;
+    env.cleanup_hooks.borrow_mut().push((fun, arg));
+    Ok(())
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_remove_env_cleanup_hook(
+  env: sys::napi_env,
+  fun: Option<unsafe extern "C" fn(arg: *mut c_void)>,
+  arg: *mut c_void,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let fun = fun.ok_or(sys::Status::napi_invalid_arg)?;
+    let mut hooks = env.cleanup_hooks.borrow_mut();
+    if let Some(index) = hooks
+      .iter()
+      .rposition(|(hook, hook_arg)| *hook as usize == fun as usize && *hook_arg == arg)
+    {
+      hooks.remove(index);
+    }
+    Ok(())
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_adjust_external_memory(
+  env: sys::napi_env,
+  change_in_bytes: i64,
+  adjusted_value: *mut i64,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let memory = env.external_memory.get() + change_in_bytes;
+    env.external_memory.set(memory);
+    if !adjusted_value.is_null() {
+      unsafe { adjusted_value.write(memory) };
+    }
+    Ok(())
+  })
+}
+
+/// The N-API version of the mock host
+pub(crate) const NAPI_VERSION: u32 = 10;
+
+pub(crate) unsafe extern "C" fn napi_get_version(
+  env: sys::napi_env,
+  result: *mut u32,
+) -> sys::napi_status {
+  with_env(env, |_| write(result, NAPI_VERSION))
+}
+
+struct NodeVersion(sys::napi_node_version);
+
+// The release name is a static string
+unsafe impl Sync for NodeVersion {}
+
+static NODE_VERSION: NodeVersion = NodeVersion(sys::napi_node_version {
+  major: 22,
+  minor: 0,
+  patch: 0,
+  release: c"node".as_ptr(),
+});
+
+pub(crate) unsafe extern "C" fn napi_get_node_version(
+  env: sys::napi_env,
+  version: *mut *const sys::napi_node_version,
+) -> sys::napi_status {
+  with_env(env, |_| write(version, &NODE_VERSION.0 as *const _))
+}
+
+pub(crate) unsafe extern "C" fn napi_fatal_error(
+  location: *const c_char,
+  location_len: isize,
+  message: *const c_char,
+  message_len: isize,
+) {
+  let read = |value, length| unsafe {
+    read_str(value, length).map_or_else(
+      |_| String::new(),
+      |value| String::from_utf8_lossy(value).into_owned(),
+    )
+  };
+  panic!(
+    "FATAL ERROR: {} {}",
+    read(location, location_len),
+    read(message, message_len)
+  );
+}
+
+pub(crate) unsafe extern "C" fn napi_fatal_exception(
+  env: sys::napi_env,
+  err: sys::napi_value,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let error = env.value(err)?;
+    env.uncaught_exceptions.borrow_mut().push(error);
+    Ok(())
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use std::sync::atomic::{AtomicU32, Ordering};
+
+  use super::*;
+  use crate::object::{napi_create_object, napi_strict_equals};
+  use crate::MockEnv;
+
+  fn create_object(env: &MockEnv) -> sys::napi_value {
+    let mut object = ptr::null_mut();
+    assert_eq!(
+      unsafe { napi_create_object(env.raw(), &mut object) },
+      sys::Status::napi_ok
+    );
+    object
+  }
+
+  fn reference_value(env: &MockEnv, reference: sys::napi_ref) -> sys::napi_value {
+    let mut value = ptr::null_mut();
+    let status = unsafe { napi_get_reference_value(env.raw(), reference, &mut value) };
+    assert_eq!(status, sys::Status::napi_ok);
+    value
+  }
+
+  fn strict_equals(env: &MockEnv, lhs: sys::napi_value, rhs: sys::napi_value) -> bool {
+    let mut equals = false;
+    assert_eq!(
+      unsafe { napi_strict_equals(env.raw(), lhs, rhs, &mut equals) },
+      sys::Status::napi_ok
+    );
+    equals
+  }
+
+  unsafe extern "C" fn count_finalize(_env: sys::napi_env, _data: *mut c_void, hint: *mut c_void) {
+    unsafe { &*hint.cast::<AtomicU32>() }.fetch_add(1, Ordering::SeqCst);
+  }
+
+  #[test]
+  fn test_reference_count() {
+    let env = MockEnv::new();
+    let object = create_object(&env);
+    let mut reference = ptr::null_mut();
+    let status = unsafe { napi_create_reference(env.raw(), object, 1, &mut reference) };
+    assert_eq!(status, sys::Status::napi_ok);
+
+    let mut count = 0;
+    unsafe { napi_reference_ref(env.raw(), reference, &mut count) };
+    assert_eq!(count, 2);
+    unsafe { napi_reference_unref(env.raw(), reference, &mut count) };
+    unsafe { napi_reference_unref(env.raw(), reference, &mut count) };
+    assert_eq!(count, 0);
+    // the count can't go below 0
+    let status = unsafe { napi_reference_unref(env.raw(), reference, &mut count) };
+    assert_eq!(status, sys::Status::napi_generic_failure);
+
+    // nothing is garbage collected, a weak reference still points to the value
+    assert!(strict_equals(
+      &env,
+      reference_value(&env, reference),
+      object
+    ));
+    let status = unsafe { napi_delete_reference(env.raw(), reference) };
+    assert_eq!(status, sys::Status::napi_ok);
+    let status = unsafe { napi_delete_reference(env.raw(), ptr::null_mut()) };
+    assert_eq!(status, sys::Status::napi_invalid_arg);
+  }
+
+  #[test]
+  fn test_wrap() {
+    let finalized = AtomicU32::new(0);
+    let env = MockEnv::new();
+    let object = create_object(&env);
+    let mut native = 42_u32;
+    let native_ptr: *mut c_void = ptr::from_mut(&mut native).cast();
+    let hint = ptr::from_ref(&finalized).cast_mut().cast();
+    let mut reference = ptr::null_mut();
+    let status = unsafe {
+      napi_wrap(
+        env.raw(),
+        object,
+        native_ptr,
+        Some(count_finalize),
+        hint,
+        &mut reference,
+      )
+    };
+    assert_eq!(status, sys::Status::napi_ok);
+    assert!(strict_equals(
+      &env,
+      reference_value(&env, reference),
+      object
+    ));
+    unsafe { napi_delete_reference(env.raw(), reference) };
+
+    // an object is only wrapped once
+    let status = unsafe {
+      napi_wrap(
+        env.raw(),
+        object,
+        native_ptr,
+        None,
+        ptr::null_mut(),
+        ptr::null_mut(),
+      )
+    };
+    assert_eq!(status, sys::Status::napi_invalid_arg);
+
+    let mut unwrapped = ptr::null_mut();
+    unsafe { napi_unwrap(env.raw(), object, &mut unwrapped) };
+    assert_eq!(unwrapped, native_ptr);
+
+    drop(env);
+    assert_eq!(finalized.load(Ordering::SeqCst), 1);
+  }
+
+  #[test]
+  fn test_remove_wrap() {
+    let finalized = AtomicU32::new(0);
+    let env = MockEnv::new();
+    let object = create_object(&env);
+    let mut native = 42_u32;
+    let native_ptr: *mut c_void = ptr::from_mut(&mut native).cast();
+    let hint = ptr::from_ref(&finalized).cast_mut().cast();
+    let status = unsafe {
+      napi_wrap(
+        env.raw(),
+        object,
+        native_ptr,
+        Some(count_finalize),
+        hint,
+        ptr::null_mut(),
+      )
+    };
+    assert_eq!(status, sys::Status::napi_ok);
+
+    let mut removed = ptr::null_mut();
+    let status = unsafe { napi_remove_wrap(env.raw(), object, &mut removed) };
+    assert_eq!(status, sys::Status::napi_ok);
+    assert_eq!(removed, native_ptr);
+
+    let mut unwrapped = ptr::null_mut();
+    let status = unsafe { napi_unwrap(env.raw(), object, &mut unwrapped) };
+    assert_eq!(status, sys::Status::napi_invalid_arg);
+    let status = unsafe { napi_remove_wrap(env.raw(), object, &mut removed) };
+    assert_eq!(status, sys::Status::napi_invalid_arg);
+
+    // the object can be wrapped again once the native object is removed
+    let status = unsafe {
+      napi_wrap(
+        env.raw(),
+        object,
+        native_ptr,
+        None,
+        ptr::null_mut(),
+        ptr::null_mut(),
+      )
+    };
+    assert_eq!(status, sys::Status::napi_ok);
+
+    // the finalizer of the removed wrap is never called
+    drop(env);
+    assert_eq!(finalized.load(Ordering::SeqCst), 0);
+  }
+}
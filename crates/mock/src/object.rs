@@ -0,0 +1,545 @@
+//! Objects, properties and arrays
+
+use std::os::raw::c_char;
+
+use napi_sys as sys;
+
+use crate::env::{read_name, with_env, write, EnvState, Status};
+use crate::value::{Key, NativeFunction, Object, ObjectKind, Property, Value};
+
+pub(crate) unsafe extern "C" fn napi_create_object(
+  env: sys::napi_env,
+  result: *mut sys::napi_value,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    write(result, env.handle(Value::Object(env.new_object())))
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_create_array(
+  env: sys::napi_env,
+  result: *mut sys::napi_value,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    write(result, env.handle(Value::Object(env.new_array(Vec::new()))))
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_create_array_with_length(
+  env: sys::napi_env,
+  length: usize,
+  result: *mut sys::napi_value,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let array = env.new_array(vec![Value::Undefined; length]);
+    write(result, env.handle(Value::Object(array)))
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_create_object_with_properties(
+  env: sys::napi_env,
+  prototype_or_null: sys::napi_value,
+  property_names: *const sys::napi_value,
+  property_values: *const sys::napi_value,
+  property_count: usize,
+  result: *mut sys::napi_value,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let prototype = if prototype_or_null.is_null() {
+      Some(env.object_prototype.clone())
+    } else {
+      match env.value(prototype_or_null)? {
+        Value::Object(prototype) => Some(prototype),
+        Value::Null => None,
+        _ => return Err(sys::Status::napi_invalid_arg),
+      }
+    };
+    let object = Object::new(ObjectKind::Ordinary, prototype);
+    for i in 0..property_count {
+      let key = env.to_key(&env.value(unsafe { *property_names.add(i) })?)?;
+      let value = env.value(unsafe { *property_values.add(i) })?;
+      env.set(&object, key, value)?;
+    }
+    write(result, env.handle(Value::Object(object)))
+  })
+}
+
+fn key(env: &EnvState, key: sys::napi_value) -> Result<Key, Status> {
+  env.to_key(&env.value(key)?)
+}
+
+fn index_key(index: u32) -> Key {
+  Key::from_str(&index.to_string())
+}
+
+pub(crate) unsafe extern "C" fn napi_set_property(
+  env: sys::napi_env,
+  object: sys::napi_value,
+  key: sys::napi_value,
+  value: sys::napi_value,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let object = env.object(object)?;
+    env.set(&object, self::key(env, key)?, env.value(value)?)
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_get_property(
+  env: sys::napi_env,
+  object: sys::napi_value,
+  key: sys::napi_value,
+  result: *mut sys::napi_value,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let value = env.get(&env.value(object)?, &self::key(env, key)?)?;
+    write(result, env.handle(value))
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_has_property(
+  env: sys::napi_env,
+  object: sys::napi_value,
+  key: sys::napi_value,
+  result: *mut bool,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let object = env.object(object)?;
+    write(
+      result,
+      object.find_property(&self::key(env, key)?).is_some(),
+    )
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_has_own_property(
+  env: sys::napi_env,
+  object: sys::napi_value,
+  key: sys::napi_value,
+  result: *mut bool,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let object = env.object(object)?;
+    let key = match env.value(key)? {
+      key @ (Value::String(_) | Value::Symbol(_)) => env.to_key(&key)?,
+      _ => return Err(sys::Status::napi_name_expected),
+    };
+    write(result, object.own_property(&key).is_some())
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_delete_property(
+  env: sys::napi_env,
+  object: sys::napi_value,
+  key: sys::napi_value,
+  result: *mut bool,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let object = env.object(object)?;
+    let deleted = object.delete(&self::key(env, key)?);
+    if !result.is_null() {
+      unsafe { result.write(deleted) };
+    }
+    Ok(())
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_set_named_property(
+  env: sys::napi_env,
+  object: sys::napi_value,
+  utf8name: *const c_char,
+  value: sys::napi_value,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let object = env.object(object)?;
+    env.set(&object, unsafe { read_name(utf8name) }?, env.value(value)?)
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_get_named_property(
+  env: sys::napi_env,
+  object: sys::napi_value,
+  utf8name: *const c_char,
+  result: *mut sys::napi_value,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let value = env.get(&env.value(object)?, &unsafe { read_name(utf8name) }?)?;
+    write(result, env.handle(value))
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_has_named_property(
+  env: sys::napi_env,
+  object: sys::napi_value,
+  utf8name: *const c_char,
+  result: *mut bool,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let object = env.object(object)?;
+    let key = unsafe { read_name(utf8name) }?;
+    write(result, object.find_property(&key).is_some())
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_set_element(
+  env: sys::napi_env,
+  object: sys::napi_value,
+  index: u32,
+  value: sys::napi_value,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let object = env.object(object)?;
+    env.set(&object, index_key(index), env.value(value)?)
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_get_element(
+  env: sys::napi_env,
+  object: sys::napi_value,
+  index: u32,
+  result: *mut sys::napi_value,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let value = env.get(&env.value(object)?, &index_key(index))?;
+    write(result, env.handle(value))
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_has_element(
+  env: sys::napi_env,
+  object: sys::napi_value,
+  index: u32,
+  result: *mut bool,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let object = env.object(object)?;
+    write(result, object.find_property(&index_key(index)).is_some())
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_delete_element(
+  env: sys::napi_env,
+  object: sys::napi_value,
+  index: u32,
+  result: *mut bool,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let deleted = env.object(object)?.delete(&index_key(index));
+    if !result.is_null() {
+      unsafe { result.write(deleted) };
+    }
+    Ok(())
+  })
+}
+
+/// Create the property of a descriptor of `napi_define_properties` or `napi_define_class`
+pub(crate) fn descriptor_property(
+  env: &EnvState,
+  descriptor: &sys::napi_property_descriptor,
+) -> Result<(Key, Property), Status> {
+  let key = if descriptor.utf8name.is_null() {
+    let name = env.value(descriptor.name)?;
+    match name {
+      Value::String(_) | Value::Symbol(_) => env.to_key(&name)?,
+      _ => return Err(sys::Status::napi_name_expected),
+    }
+  } else {
+    unsafe { read_name(descriptor.utf8name) }?
+  };
+  let name = match &key {
+    Key::String(name) => name.to_string(),
+    Key::Symbol(symbol) => format!("[{}]", symbol.description.as_deref().unwrap_or_default()),
+  };
+  let function = |callback: sys::napi_callback| {
+    callback.map(|callback| {
+      env.new_function(
+        &name,
+        NativeFunction {
+          callback,
+          data: descriptor.data,
+        },
+      )
+    })
+  };
+  let attributes = descriptor.attributes
+    & (sys::PropertyAttributes::writable
+      | sys::PropertyAttributes::enumerable
+      | sys::PropertyAttributes::configurable);
+  let property = if descriptor.getter.is_some() || descriptor.setter.is_some() {
+    Property::Accessor {
+      getter: function(descriptor.getter),
+      setter: function(descriptor.setter),
+      attributes,
+    }
+  } else if let Some(method) = function(descriptor.method) {
+    Property::Data {
+      value: Value::Object(method),
+      attributes,
+    }
+  } else {
+    Property::Data {
+      value: env.value(descriptor.value)?,
+      attributes,
+    }
+  };
+  Ok((key, property))
+}
+
+pub(crate) unsafe fn descriptors<'a>(
+  count: usize,
+  properties: *const sys::napi_property_descriptor,
+) -> Result<&'a [sys::napi_property_descriptor], Status> {
+  if count == 0 {
+    return Ok(&[]);
+  }
+  if properties.is_null() {
+    return Err(sys::Status::napi_invalid_arg);
+  }
+  Ok(unsafe { std::slice::from_raw_parts(properties, count) })
+}
+
+pub(crate) unsafe extern "C" fn napi_define_properties(
+  env: sys::napi_env,
+  object: sys::napi_value,
+  property_count: usize,
+  properties: *const sys::napi_property_descriptor,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let object = env.object(object)?;
+    for descriptor in unsafe { descriptors(property_count, properties) }? {
+      let (key, property) = descriptor_property(env, descriptor)?;
+      object.define(key, property);
+    }
+    Ok(())
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_is_array(
+  env: sys::napi_env,
+  value: sys::napi_value,
+  result: *mut bool,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let is_array = matches!(
+      env.value(value)?,
+      Value::Object(object) if matches!(object.borrow().kind, ObjectKind::Array(_))
+    );
+    write(result, is_array)
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_get_array_length(
+  env: sys::napi_env,
+  value: sys::napi_value,
+  result: *mut u32,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let length = match &env.object(value)?.borrow().kind {
+      ObjectKind::Array(elements) => elements.len() as u32,
+      _ => return Err(sys::Status::napi_array_expected),
+    };
+    write(result, length)
+  })
+}
+
+/// The keys of `object` like `napi_get_all_property_names`
+fn property_names(
+  env: &EnvState,
+  object: &Object,
+  own_only: bool,
+  filter: sys::napi_key_filter,
+) -> Vec<Value> {
+  let mut keys: Vec<Key> = Vec::new();
+  let mut current = Some(object.clone());
+  while let Some(object) = current {
+    for (key, enumerable) in object.own_keys() {
+      let skipped = match key {
+        Key::String(_) => filter & sys::KeyFilter::skip_strings != 0,
+        Key::Symbol(_) => filter & sys::KeyFilter::skip_symbols != 0,
+      };
+      let attributes = object
+        .own_property(&key)
+        .map_or(0, |property| property.attributes());
+      let filtered = [
+        sys::KeyFilter::writable,
+        sys::KeyFilter::enumerable,
+        sys::KeyFilter::configurable,
+      ]
+      .into_iter()
+      .any(|bit| filter & bit != 0 && attributes & bit == 0);
+      if !skipped
+        && !filtered
+        && (enumerable || filter & sys::KeyFilter::enumerable == 0)
+        && !keys.contains(&key)
+      {
+        keys.push(key);
+      }
+    }
+    if own_only {
+      break;
+    }
+    current = object.borrow().prototype.clone();
+  }
+  let _ = env;
+  keys.iter().map(Key::to_value).collect()
+}
+
+pub(crate) unsafe extern "C" fn napi_get_property_names(
+  env: sys::napi_env,
+  object: sys::napi_value,
+  result: *mut sys::napi_value,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let object = env.object(object)?;
+    let names = property_names(
+      env,
+      &object,
+      false,
+      sys::KeyFilter::enumerable | sys::KeyFilter::skip_symbols,
+    );
+    write(result, env.handle(Value::Object(env.new_array(names))))
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_get_all_property_names(
+  env: sys::napi_env,
+  object: sys::napi_value,
+  key_mode: sys::napi_key_collection_mode,
+  key_filter: sys::napi_key_filter,
+  key_conversion: sys::napi_key_conversion,
+  result: *mut sys::napi_value,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let object = env.object(object)?;
+    let own_only = key_mode == sys::KeyCollectionMode::own_only;
+    let mut names = property_names(env, &object, own_only, key_filter);
+    if key_conversion == sys::KeyConversion::keep_numbers {
+      for name in &mut names {
+        if let Some(index) = env.to_key(name)?.as_index() {
+          *name = Value::Number(index as f64);
+        }
+      }
+    }
+    write(result, env.handle(Value::Object(env.new_array(names))))
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_get_prototype(
+  env: sys::napi_env,
+  object: sys::napi_value,
+  result: *mut sys::napi_value,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let prototype = env.object(object)?.borrow().prototype.clone();
+    write(
+      result,
+      env.handle(prototype.map_or(Value::Null, Value::Object)),
+    )
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_strict_equals(
+  env: sys::napi_env,
+  lhs: sys::napi_value,
+  rhs: sys::napi_value,
+  result: *mut bool,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    write(result, env.value(lhs)?.strict_equals(&env.value(rhs)?))
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_instanceof(
+  env: sys::napi_env,
+  object: sys::napi_value,
+  constructor: sys::napi_value,
+  result: *mut bool,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let constructor = match env.function(constructor) {
+      Ok(constructor) => constructor,
+      Err(status) => {
+        env.throw_error(1, "Constructor must be a function");
+        return Err(status);
+      }
+    };
+    let prototype = env.get(&Value::Object(constructor), &Key::from_str("prototype"))?;
+    let is_instance = match (env.value(object)?, prototype) {
+      (Value::Object(object), Value::Object(prototype)) => object.has_in_chain(&prototype),
+      _ => false,
+    };
+    write(result, is_instance)
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_object_freeze(
+  env: sys::napi_env,
+  object: sys::napi_value,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    restrict(&env.object(object)?, true);
+    Ok(())
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_object_seal(
+  env: sys::napi_env,
+  object: sys::napi_value,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    restrict(&env.object(object)?, false);
+    Ok(())
+  })
+}
+
+fn restrict(object: &Object, freeze: bool) {
+  let mut data = object.borrow_mut();
+  data.extensible = false;
+  for (_, property) in &mut data.properties {
+    match property {
+      Property::Data { attributes, .. } => {
+        *attributes &= !sys::PropertyAttributes::configurable;
+        if freeze {
+          *attributes &= !sys::PropertyAttributes::writable;
+        }
+      }
+      Property::Accessor { attributes, .. } => {
+        *attributes &= !sys::PropertyAttributes::configurable;
+      }
+    }
+  }
+}
+
+pub(crate) unsafe extern "C" fn napi_type_tag_object(
+  env: sys::napi_env,
+  value: sys::napi_value,
+  type_tag: *const sys::napi_type_tag,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let object = env.object(value)?;
+    if type_tag.is_null() {
+      return Err(sys::Status::napi_invalid_arg);
+    }
+    let mut data = object.borrow_mut();
+    if data.type_tag.is_some() {
+      return Err(sys::Status::napi_invalid_arg);
+    }
+    data.type_tag = Some(unsafe { *type_tag });
+    Ok(())
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_check_object_type_tag(
+  env: sys::napi_env,
+  value: sys::napi_value,
+  type_tag: *const sys::napi_type_tag,
+  result: *mut bool,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let object = env.object(value)?;
+    if type_tag.is_null() {
+      return Err(sys::Status::napi_invalid_arg);
+    }
+    let matched = object.borrow().type_tag == Some(unsafe { *type_tag });
+    write(result, matched)
+  })
+}
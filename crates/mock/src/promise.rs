@@ -0,0 +1,155 @@
+//! Promises, they are settled by the deferreds only
+
+use napi_sys as sys;
+
+use crate::env::{with_env, write};
+use crate::value::{Object, ObjectKind, PromiseState, Value};
+
+pub(crate) unsafe extern "C" fn napi_create_promise(
+  env: sys::napi_env,
+  deferred: *mut sys::napi_deferred,
+  promise: *mut sys::napi_value,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    if deferred.is_null() {
+      return Err(sys::Status::napi_invalid_arg);
+    }
+    let object = Object::new(
+      ObjectKind::Promise(PromiseState::Pending),
+      Some(env.promise_prototype.clone()),
+    );
+    write(promise, env.handle(Value::Object(object.clone())))?;
+    unsafe { deferred.write(Box::into_raw(Box::new(object)).cast()) };
+    Ok(())
+  })
+}
+
+unsafe fn settle(
+  env: sys::napi_env,
+  deferred: sys::napi_deferred,
+  value: sys::napi_value,
+  settled: fn(Value) -> PromiseState,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    if deferred.is_null() {
+      return Err(sys::Status::napi_invalid_arg);
+    }
+    let value = env.value(value)?;
+    // The deferred is released once the promise is settled
+    let promise = unsafe { Box::from_raw(deferred.cast::<Object>()) };
+    promise.borrow_mut().kind = ObjectKind::Promise(settled(value));
+    Ok(())
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_resolve_deferred(
+  env: sys::napi_env,
+  deferred: sys::napi_deferred,
+  resolution: sys::napi_value,
+) -> sys::napi_status {
+  unsafe { settle(env, deferred, resolution, PromiseState::Fulfilled) }
+}
+
+pub(crate) unsafe extern "C" fn napi_reject_deferred(
+  env: sys::napi_env,
+  deferred: sys::napi_deferred,
+  rejection: sys::napi_value,
+) -> sys::napi_status {
+  unsafe { settle(env, deferred, rejection, PromiseState::Rejected) }
+}
+
+pub(crate) unsafe extern "C" fn napi_is_promise(
+  env: sys::napi_env,
+  value: sys::napi_value,
+  is_promise: *mut bool,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let promise = matches!(
+      env.value(value)?,
+      Value::Object(object) if matches!(object.borrow().kind, ObjectKind::Promise(_))
+    );
+    write(is_promise, promise)
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use std::ptr;
+
+  use napi::bindgen_prelude::{FromNapiValue, Unknown};
+  use napi::Status;
+
+  use super::*;
+  use crate::values::napi_create_string_utf8;
+  use crate::MockEnv;
+
+  fn create_promise(env: &MockEnv) -> (sys::napi_deferred, sys::napi_value) {
+    let mut deferred = ptr::null_mut();
+    let mut promise = ptr::null_mut();
+    let status = unsafe { napi_create_promise(env.raw(), &mut deferred, &mut promise) };
+    assert_eq!(status, sys::Status::napi_ok);
+    (deferred, promise)
+  }
+
+  fn create_string(env: &MockEnv, value: &str) -> sys::napi_value {
+    let mut string = ptr::null_mut();
+    let status = unsafe {
+      napi_create_string_utf8(
+        env.raw(),
+        value.as_ptr().cast(),
+        value.len() as isize,
+        &mut string,
+      )
+    };
+    assert_eq!(status, sys::Status::napi_ok);
+    string
+  }
+
+  #[test]
+  fn test_reject_promise() {
+    let env = MockEnv::new();
+    let (deferred, promise) = create_promise(&env);
+    let mut is_promise = false;
+    unsafe { napi_is_promise(env.raw(), promise, &mut is_promise) };
+    assert!(is_promise);
+    assert!(matches!(
+      env.state.object(promise).unwrap().borrow().kind,
+      ObjectKind::Promise(PromiseState::Pending)
+    ));
+
+    let reason = create_string(&env, "rejected");
+    let status = unsafe { napi_reject_deferred(env.raw(), deferred, reason) };
+    assert_eq!(status, sys::Status::napi_ok);
+    assert!(matches!(
+      &env.state.object(promise).unwrap().borrow().kind,
+      ObjectKind::Promise(PromiseState::Rejected(Value::String(reason))) if &**reason == "rejected"
+    ));
+
+    let promise = unsafe { Unknown::from_napi_value(env.raw(), promise) }.unwrap();
+    let err = env.await_promise::<()>(promise).unwrap_err();
+    assert_eq!(err.status, Status::GenericFailure);
+    assert_eq!(err.reason, "rejected");
+  }
+
+  #[test]
+  fn test_resolve_promise() {
+    let env = MockEnv::new();
+    let (deferred, promise) = create_promise(&env);
+    let resolution = create_string(&env, "resolved");
+    let status = unsafe { napi_resolve_deferred(env.raw(), deferred, resolution) };
+    assert_eq!(status, sys::Status::napi_ok);
+    let promise = unsafe { Unknown::from_napi_value(env.raw(), promise) }.unwrap();
+    assert_eq!(env.await_promise::<String>(promise).unwrap(), "resolved");
+  }
+
+  #[test]
+  fn test_null_deferred() {
+    let env = MockEnv::new();
+    let mut promise = ptr::null_mut();
+    let status = unsafe { napi_create_promise(env.raw(), ptr::null_mut(), &mut promise) };
+    assert_eq!(status, sys::Status::napi_invalid_arg);
+    let reason = create_string(&env, "rejected");
+    let status = unsafe { napi_reject_deferred(env.raw(), ptr::null_mut(), reason) };
+    assert_eq!(status, sys::Status::napi_invalid_arg);
+  }
+}
@@ -0,0 +1,442 @@
+use std::cell::{Ref, RefCell, RefMut};
+use std::ffi::c_void;
+use std::rc::Rc;
+
+use napi_sys as sys;
+
+/// A JavaScript value of the mock host
+#[derive(Clone)]
+pub(crate) enum Value {
+  Undefined,
+  Null,
+  Boolean(bool),
+  Number(f64),
+  String(Rc<str>),
+  Symbol(Rc<Symbol>),
+  BigInt(Rc<BigInt>),
+  Object(Object),
+}
+
+/// Symbols are compared by identity
+pub(crate) struct Symbol {
+  pub(crate) description: Option<String>,
+}
+
+/// The magnitude is in little endian words, without leading zero words
+#[derive(PartialEq, Eq)]
+pub(crate) struct BigInt {
+  pub(crate) negative: bool,
+  pub(crate) words: Vec<u64>,
+}
+
+impl BigInt {
+  pub(crate) fn new(negative: bool, mut words: Vec<u64>) -> Self {
+    while words.last() == Some(&0) {
+      words.pop();
+    }
+    Self {
+      negative: negative && !words.is_empty(),
+      words,
+    }
+  }
+
+  /// The lowest 64 bits in two's complement
+  pub(crate) fn low_bits(&self) -> u64 {
+    let low = self.words.first().copied().unwrap_or(0);
+    if self.negative {
+      low.wrapping_neg()
+    } else {
+      low
+    }
+  }
+}
+
+#[derive(Clone)]
+pub(crate) struct Object(Rc<RefCell<ObjectData>>);
+
+pub(crate) struct ObjectData {
+  pub(crate) kind: ObjectKind,
+  pub(crate) prototype: Option<Object>,
+  pub(crate) properties: Vec<(Key, Property)>,
+  pub(crate) wrap: Option<Wrap>,
+  pub(crate) type_tag: Option<sys::napi_type_tag>,
+  pub(crate) extensible: bool,
+}
+
+pub(crate) enum ObjectKind {
+  Ordinary,
+  Array(Vec<Value>),
+  Function(NativeFunction),
+  External(*mut c_void),
+  Error,
+  Promise(PromiseState),
+  Date(f64),
+  ArrayBuffer(ArrayBufferData),
+  TypedArray(TypedArrayData),
+}
+
+#[derive(Clone)]
+pub(crate) struct NativeFunction {
+  pub(crate) callback:
+    unsafe extern "C" fn(sys::napi_env, sys::napi_callback_info) -> sys::napi_value,
+  pub(crate) data: *mut c_void,
+}
+
+#[derive(Clone)]
+pub(crate) enum PromiseState {
+  Pending,
+  Fulfilled(Value),
+  Rejected(Value),
+}
+
+pub(crate) struct Wrap {
+  pub(crate) data: *mut c_void,
+  pub(crate) finalizer: Option<usize>,
+}
+
+pub(crate) struct ArrayBufferData {
+  pub(crate) data: *mut u8,
+  pub(crate) len: usize,
+  /// `None` if the memory is external, it's released by the finalizer
+  pub(crate) owned: Option<Box<[u8]>>,
+  pub(crate) detached: bool,
+}
+
+pub(crate) struct TypedArrayData {
+  pub(crate) ty: sys::napi_typedarray_type,
+  pub(crate) buffer: Object,
+  pub(crate) byte_offset: usize,
+  pub(crate) length: usize,
+}
+
+#[derive(Clone)]
+pub(crate) enum Key {
+  String(Rc<str>),
+  Symbol(Rc<Symbol>),
+}
+
+impl PartialEq for Key {
+  fn eq(&self, other: &Self) -> bool {
+    match (self, other) {
+      (Key::String(a), Key::String(b)) => a == b,
+      (Key::Symbol(a), Key::Symbol(b)) => Rc::ptr_eq(a, b),
+      _ => false,
+    }
+  }
+}
+
+impl Key {
+  pub(crate) fn from_str(name: &str) -> Self {
+    Key::String(name.into())
+  }
+
+  /// The index if the key is a canonical array index
+  pub(crate) fn as_index(&self) -> Option<usize> {
+    match self {
+      Key::String(name) => name
+        .parse::<u32>()
+        .ok()
+        .filter(|index| *index != u32::MAX && index.to_string() == **name)
+        .map(|index| index as usize),
+      Key::Symbol(_) => None,
+    }
+  }
+
+  pub(crate) fn to_value(&self) -> Value {
+    match self {
+      Key::String(name) => Value::String(name.clone()),
+      Key::Symbol(symbol) => Value::Symbol(symbol.clone()),
+    }
+  }
+}
+
+#[derive(Clone)]
+pub(crate) enum Property {
+  Data {
+    value: Value,
+    attributes: sys::napi_property_attributes,
+  },
+  Accessor {
+    getter: Option<Object>,
+    setter: Option<Object>,
+    attributes: sys::napi_property_attributes,
+  },
+}
+
+impl Property {
+  pub(crate) fn attributes(&self) -> sys::napi_property_attributes {
+    match self {
+      Property::Data { attributes, .. } | Property::Accessor { attributes, .. } => *attributes,
+    }
+  }
+
+  pub(crate) fn is_enumerable(&self) -> bool {
+    self.attributes() & sys::PropertyAttributes::enumerable != 0
+  }
+}
+
+/// The attributes of properties created by assignment
+pub(crate) const DEFAULT_ATTRIBUTES: sys::napi_property_attributes =
+  sys::PropertyAttributes::writable
+    | sys::PropertyAttributes::enumerable
+    | sys::PropertyAttributes::configurable;
+
+impl Object {
+  pub(crate) fn new(kind: ObjectKind, prototype: Option<Object>) -> Self {
+    Self(Rc::new(RefCell::new(ObjectData {
+      kind,
+      prototype,
+      properties: Vec::new(),
+      wrap: None,
+      type_tag: None,
+      extensible: true,
+    })))
+  }
+
+  pub(crate) fn borrow(&self) -> Ref<'_, ObjectData> {
+    self.0.borrow()
+  }
+
+  pub(crate) fn borrow_mut(&self) -> RefMut<'_, ObjectData> {
+    self.0.borrow_mut()
+  }
+
+  pub(crate) fn ptr_eq(&self, other: &Object) -> bool {
+    Rc::ptr_eq(&self.0, &other.0)
+  }
+
+  pub(crate) fn is_function(&self) -> bool {
+    matches!(self.borrow().kind, ObjectKind::Function(_))
+  }
+
+  /// The own property of `key`, the elements and `length` of arrays are included
+  pub(crate) fn own_property(&self, key: &Key) -> Option<Property> {
+    let data = self.borrow();
+    if let ObjectKind::Array(elements) = &data.kind {
+      if let Some(index) = key.as_index() {
+        return elements.get(index).map(|value| Property::Data {
+          value: value.clone(),
+          attributes: DEFAULT_ATTRIBUTES,
+        });
+      }
+      if *key == Key::from_str("length") {
+        return Some(Property::Data {
+          value: Value::Number(elements.len() as f64),
+          attributes: sys::PropertyAttributes::writable,
+        });
+      }
+    }
+    data
+      .properties
+      .iter()
+      .find(|(k, _)| k == key)
+      .map(|(_, property)| property.clone())
+  }
+
+  /// Look up `key` through the prototype chain
+  pub(crate) fn find_property(&self, key: &Key) -> Option<Property> {
+    let mut current = Some(self.clone());
+    while let Some(object) = current {
+      if let Some(property) = object.own_property(key) {
+        return Some(property);
+      }
+      current = object.borrow().prototype.clone();
+    }
+    None
+  }
+
+  /// Define or replace the own property of `key`
+  pub(crate) fn define(&self, key: Key, property: Property) {
+    let mut data = self.borrow_mut();
+    if let ObjectKind::Array(elements) = &mut data.kind {
+      if let (Some(index), Property::Data { value, .. }) = (key.as_index(), &property) {
+        if index >= elements.len() {
+          elements.resize(index + 1, Value::Undefined);
+        }
+        elements[index] = value.clone();
+        return;
+      }
+      if key == Key::from_str("length") {
+        if let Property::Data {
+          value: Value::Number(len),
+          ..
+        } = &property
+        {
+          elements.resize(*len as usize, Value::Undefined);
+        }
+        return;
+      }
+    }
+    if let Some((_, existing)) = data.properties.iter_mut().find(|(k, _)| *k == key) {
+      *existing = property;
+    } else if data.extensible {
+      data.properties.push((key, property));
+    }
+  }
+
+  pub(crate) fn delete(&self, key: &Key) -> bool {
+    let mut data = self.borrow_mut();
+    if let (ObjectKind::Array(elements), Some(index)) = (&mut data.kind, key.as_index()) {
+      if let Some(element) = elements.get_mut(index) {
+        *element = Value::Undefined;
+      }
+      return true;
+    }
+    match data.properties.iter().position(|(k, _)| k == key) {
+      Some(position) => {
+        if data.properties[position].1.attributes() & sys::PropertyAttributes::configurable == 0 {
+          return false;
+        }
+        data.properties.remove(position);
+        true
+      }
+      None => true,
+    }
+  }
+
+  /// The own keys in the order of JavaScript, the array indices go first
+  pub(crate) fn own_keys(&self) -> Vec<(Key, bool)> {
+    let data = self.borrow();
+    let mut keys = Vec::new();
+    if let ObjectKind::Array(elements) = &data.kind {
+      keys.extend((0..elements.len()).map(|index| (Key::from_str(&index.to_string()), true)));
+    }
+    let mut indices = Vec::new();
+    let mut names = Vec::new();
+    let mut symbols = Vec::new();
+    for (key, property) in &data.properties {
+      let entry = (key.clone(), property.is_enumerable());
+      match key {
+        Key::Symbol(_) => symbols.push(entry),
+        Key::String(_) if key.as_index().is_some() => indices.push(entry),
+        Key::String(_) => names.push(entry),
+      }
+    }
+    indices.sort_by_key(|(key, _)| key.as_index());
+    keys.extend(indices);
+    keys.extend(names);
+    keys.extend(symbols);
+    keys
+  }
+
+  pub(crate) fn has_in_chain(&self, prototype: &Object) -> bool {
+    let mut current = self.borrow().prototype.clone();
+    while let Some(object) = current {
+      if object.ptr_eq(prototype) {
+        return true;
+      }
+      current = object.borrow().prototype.clone();
+    }
+    false
+  }
+}
+
+impl Value {
+  pub(crate) fn type_of(&self) -> sys::napi_valuetype {
+    match self {
+      Value::Undefined => sys::ValueType::napi_undefined,
+      Value::Null => sys::ValueType::napi_null,
+      Value::Boolean(_) => sys::ValueType::napi_boolean,
+      Value::Number(_) => sys::ValueType::napi_number,
+      Value::String(_) => sys::ValueType::napi_string,
+      Value::Symbol(_) => sys::ValueType::napi_symbol,
+      Value::BigInt(_) => sys::ValueType::napi_bigint,
+      Value::Object(object) => match object.borrow().kind {
+        ObjectKind::Function(_) => sys::ValueType::napi_function,
+        ObjectKind::External(_) => sys::ValueType::napi_external,
+        _ => sys::ValueType::napi_object,
+      },
+    }
+  }
+
+  pub(crate) fn strict_equals(&self, other: &Value) -> bool {
+    match (self, other) {
+      (Value::Undefined, Value::Undefined) | (Value::Null, Value::Null) => true,
+      (Value::Boolean(a), Value::Boolean(b)) => a == b,
+      (Value::Number(a), Value::Number(b)) => a == b,
+      (Value::String(a), Value::String(b)) => a == b,
+      (Value::Symbol(a), Value::Symbol(b)) => Rc::ptr_eq(a, b),
+      (Value::BigInt(a), Value::BigInt(b)) => a == b,
+      (Value::Object(a), Value::Object(b)) => a.ptr_eq(b),
+      _ => false,
+    }
+  }
+
+  pub(crate) fn to_boolean(&self) -> bool {
+    match self {
+      Value::Undefined | Value::Null => false,
+      Value::Boolean(value) => *value,
+      Value::Number(value) => *value != 0.0 && !value.is_nan(),
+      Value::String(value) => !value.is_empty(),
+      Value::BigInt(value) => !value.words.is_empty(),
+      Value::Symbol(_) | Value::Object(_) => true,
+    }
+  }
+
+  pub(crate) fn to_number(&self) -> f64 {
+    match self {
+      Value::Undefined => f64::NAN,
+      Value::Null => 0.0,
+      Value::Boolean(value) => f64::from(u8::from(*value)),
+      Value::Number(value) => *value,
+      Value::String(value) => {
+        let value = value.trim();
+        match value {
+          "" => 0.0,
+          "Infinity" | "+Infinity" => f64::INFINITY,
+          "-Infinity" => f64::NEG_INFINITY,
+          _ if value.contains(|c: char| c.is_ascii_alphabetic() && c != 'e' && c != 'E') => {
+            f64::NAN
+          }
+          _ => value.parse().unwrap_or(f64::NAN),
+        }
+      }
+      Value::Object(object) => match object.borrow().kind {
+        ObjectKind::Date(time) => time,
+        _ => f64::NAN,
+      },
+      Value::Symbol(_) | Value::BigInt(_) => f64::NAN,
+    }
+  }
+}
+
+/// Format a number like `Number.prototype.toString`
+pub(crate) fn number_to_string(value: f64) -> String {
+  if value.is_nan() {
+    "NaN".to_owned()
+  } else if value.is_infinite() {
+    if value > 0.0 { "Infinity" } else { "-Infinity" }.to_owned()
+  } else if value == 0.0 {
+    "0".to_owned()
+  } else if value.fract() == 0.0 && value.abs() < 1e21 {
+    format!("{value:.0}")
+  } else {
+    value.to_string()
+  }
+}
+
+/// Format a BigInt in decimal
+pub(crate) fn bigint_to_string(value: &BigInt) -> String {
+  let mut words = value.words.clone();
+  let mut digits = Vec::new();
+  while !words.is_empty() {
+    let mut remainder = 0u128;
+    for word in words.iter_mut().rev() {
+      let current = (remainder << 64) | u128::from(*word);
+      *word = (current / 10) as u64;
+      remainder = current % 10;
+    }
+    digits.push(b'0' + remainder as u8);
+    while words.last() == Some(&0) {
+      words.pop();
+    }
+  }
+  if digits.is_empty() {
+    digits.push(b'0');
+  }
+  if value.negative {
+    digits.push(b'-');
+  }
+  digits.reverse();
+  String::from_utf8(digits).unwrap_or_default()
+}
@@ -0,0 +1,731 @@
+//! Primitive values, strings, symbols, BigInts and dates
+
+use std::ffi::c_void;
+use std::os::raw::{c_char, c_int};
+
+use napi_sys as sys;
+
+use crate::env::{read_str, read_utf16, with_env, write, EnvState, Status};
+use crate::value::{BigInt, Object, ObjectKind, Value};
+
+pub(crate) unsafe extern "C" fn napi_get_undefined(
+  env: sys::napi_env,
+  result: *mut sys::napi_value,
+) -> sys::napi_status {
+  with_env(env, |env| write(result, env.handle(Value::Undefined)))
+}
+
+pub(crate) unsafe extern "C" fn napi_get_null(
+  env: sys::napi_env,
+  result: *mut sys::napi_value,
+) -> sys::napi_status {
+  with_env(env, |env| write(result, env.handle(Value::Null)))
+}
+
+pub(crate) unsafe extern "C" fn napi_get_global(
+  env: sys::napi_env,
+  result: *mut sys::napi_value,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    write(result, env.handle(Value::Object(env.global.clone())))
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_get_boolean(
+  env: sys::napi_env,
+  value: bool,
+  result: *mut sys::napi_value,
+) -> sys::napi_status {
+  with_env(env, |env| write(result, env.handle(Value::Boolean(value))))
+}
+
+pub(crate) unsafe extern "C" fn napi_create_double(
+  env: sys::napi_env,
+  value: f64,
+  result: *mut sys::napi_value,
+) -> sys::napi_status {
+  with_env(env, |env| write(result, env.handle(Value::Number(value))))
+}
+
+pub(crate) unsafe extern "C" fn napi_create_int32(
+  env: sys::napi_env,
+  value: i32,
+  result: *mut sys::napi_value,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    write(result, env.handle(Value::Number(f64::from(value))))
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_create_uint32(
+  env: sys::napi_env,
+  value: u32,
+  result: *mut sys::napi_value,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    write(result, env.handle(Value::Number(f64::from(value))))
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_create_int64(
+  env: sys::napi_env,
+  value: i64,
+  result: *mut sys::napi_value,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    write(result, env.handle(Value::Number(value as f64)))
+  })
+}
+
+fn number(env: &EnvState, value: sys::napi_value) -> Result<f64, Status> {
+  match env.value(value)? {
+    Value::Number(value) => Ok(value),
+    _ => Err(sys::Status::napi_number_expected),
+  }
+}
+
+/// `ToUint32`, the non-finite numbers are 0
+fn to_uint32(value: f64) -> u32 {
+  if value.is_finite() {
+    value.trunc().rem_euclid(4294967296.0) as u32
+  } else {
+    0
+  }
+}
+
+pub(crate) unsafe extern "C" fn napi_get_value_double(
+  env: sys::napi_env,
+  value: sys::napi_value,
+  result: *mut f64,
+) -> sys::napi_status {
+  with_env(env, |env| write(result, number(env, value)?))
+}
+
+pub(crate) unsafe extern "C" fn napi_get_value_int32(
+  env: sys::napi_env,
+  value: sys::napi_value,
+  result: *mut i32,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    write(result, to_uint32(number(env, value)?) as i32)
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_get_value_uint32(
+  env: sys::napi_env,
+  value: sys::napi_value,
+  result: *mut u32,
+) -> sys::napi_status {
+  with_env(env, |env| write(result, to_uint32(number(env, value)?)))
+}
+
+pub(crate) unsafe extern "C" fn napi_get_value_int64(
+  env: sys::napi_env,
+  value: sys::napi_value,
+  result: *mut i64,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let value = number(env, value)?;
+    write(result, if value.is_finite() { value as i64 } else { 0 })
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_get_value_bool(
+  env: sys::napi_env,
+  value: sys::napi_value,
+  result: *mut bool,
+) -> sys::napi_status {
+  with_env(env, |env| match env.value(value)? {
+    Value::Boolean(value) => write(result, value),
+    _ => Err(sys::Status::napi_boolean_expected),
+  })
+}
+
+fn create_string(
+  env: sys::napi_env,
+  value: Result<String, Status>,
+  result: *mut sys::napi_value,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    write(result, env.handle(Value::String(value?.into())))
+  })
+}
+
+fn latin1(bytes: &[u8]) -> String {
+  bytes.iter().map(|byte| char::from(*byte)).collect()
+}
+
+pub(crate) unsafe extern "C" fn napi_create_string_latin1(
+  env: sys::napi_env,
+  str_: *const c_char,
+  length: isize,
+  result: *mut sys::napi_value,
+) -> sys::napi_status {
+  create_string(env, unsafe { read_str(str_, length) }.map(latin1), result)
+}
+
+pub(crate) unsafe extern "C" fn napi_create_string_utf8(
+  env: sys::napi_env,
+  str_: *const c_char,
+  length: isize,
+  result: *mut sys::napi_value,
+) -> sys::napi_status {
+  create_string(
+    env,
+    unsafe { read_str(str_, length) }.map(|bytes| String::from_utf8_lossy(bytes).into_owned()),
+    result,
+  )
+}
+
+pub(crate) unsafe extern "C" fn napi_create_string_utf16(
+  env: sys::napi_env,
+  str_: *const u16,
+  length: isize,
+  result: *mut sys::napi_value,
+) -> sys::napi_status {
+  create_string(
+    env,
+    unsafe { read_utf16(str_, length) }.map(String::from_utf16_lossy),
+    result,
+  )
+}
+
+pub(crate) unsafe extern "C" fn node_api_create_property_key_latin1(
+  env: sys::napi_env,
+  str_: *const c_char,
+  length: isize,
+  result: *mut sys::napi_value,
+) -> sys::napi_status {
+  unsafe { napi_create_string_latin1(env, str_, length, result) }
+}
+
+pub(crate) unsafe extern "C" fn node_api_create_property_key_utf8(
+  env: sys::napi_env,
+  str_: *const c_char,
+  length: isize,
+  result: *mut sys::napi_value,
+) -> sys::napi_status {
+  unsafe { napi_create_string_utf8(env, str_, length, result) }
+}
+
+pub(crate) unsafe extern "C" fn node_api_create_property_key_utf16(
+  env: sys::napi_env,
+  str_: *const u16,
+  length: isize,
+  result: *mut sys::napi_value,
+) -> sys::napi_status {
+  unsafe { napi_create_string_utf16(env, str_, length, result) }
+}
+
+/// The external strings are always copied, so `finalize` is called before returning
+unsafe fn finalize_copied(
+  env: sys::napi_env,
+  status: sys::napi_status,
+  data: *mut c_void,
+  finalize: sys::node_api_basic_finalize,
+  hint: *mut c_void,
+  copied: *mut bool,
+) -> sys::napi_status {
+  if status != sys::Status::napi_ok {
+    return status;
+  }
+  if !copied.is_null() {
+    unsafe { copied.write(true) };
+  }
+  if let Some(finalize) = finalize {
+    unsafe { finalize(env.cast(), data, hint) };
+  }
+  status
+}
+
+pub(crate) unsafe extern "C" fn node_api_create_external_string_latin1(
+  env: sys::napi_env,
+  str_: *const c_char,
+  length: isize,
+  finalize: sys::node_api_basic_finalize,
+  finalize_hint: *mut c_void,
+  result: *mut sys::napi_value,
+  copied: *mut bool,
+) -> sys::napi_status {
+  let status = unsafe { napi_create_string_latin1(env, str_, length, result) };
+  unsafe {
+    finalize_copied(
+      env,
+      status,
+      str_.cast_mut().cast(),
+      finalize,
+      finalize_hint,
+      copied,
+    )
+  }
+}
+
+pub(crate) unsafe extern "C" fn node_api_create_external_string_utf16(
+  env: sys::napi_env,
+  str_: *const u16,
+  length: isize,
+  finalize: sys::node_api_basic_finalize,
+  finalize_hint: *mut c_void,
+  result: *mut sys::napi_value,
+  copied: *mut bool,
+) -> sys::napi_status {
+  let status = unsafe { napi_create_string_utf16(env, str_, length, result) };
+  unsafe {
+    finalize_copied(
+      env,
+      status,
+      str_.cast_mut().cast(),
+      finalize,
+      finalize_hint,
+      copied,
+    )
+  }
+}
+
+fn string(env: &EnvState, value: sys::napi_value) -> Result<std::rc::Rc<str>, Status> {
+  match env.value(value)? {
+    Value::String(value) => Ok(value),
+    _ => Err(sys::Status::napi_string_expected),
+  }
+}
+
+/// Copy `units` into `buf` of `bufsize` units with a nul terminator like V8,
+/// `len` is the number of units of the whole string if `buf` is null
+unsafe fn copy_units<T: Copy + Default>(
+  units: &[T],
+  buf: *mut T,
+  bufsize: usize,
+  result: *mut usize,
+  boundary: impl Fn(usize) -> bool,
+) -> Result<(), Status> {
+  if buf.is_null() {
+    return write(result, units.len());
+  }
+  if bufsize == 0 {
+    if !result.is_null() {
+      unsafe { result.write(0) };
+    }
+    return Ok(());
+  }
+  let mut copied = units.len().min(bufsize - 1);
+  while !boundary(copied) {
+    copied -= 1;
+  }
+  unsafe {
+    std::ptr::copy_nonoverlapping(units.as_ptr(), buf, copied);
+    buf.add(copied).write(T::default());
+  }
+  if !result.is_null() {
+    unsafe { result.write(copied) };
+  }
+  Ok(())
+}
+
+pub(crate) unsafe extern "C" fn napi_get_value_string_latin1(
+  env: sys::napi_env,
+  value: sys::napi_value,
+  buf: *mut c_char,
+  bufsize: usize,
+  result: *mut usize,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let bytes = string(env, value)?
+      .chars()
+      .map(|c| c as u32 as u8)
+      .collect::<Vec<_>>();
+    unsafe { copy_units(&bytes, buf.cast::<u8>(), bufsize, result, |_| true) }
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_get_value_string_utf8(
+  env: sys::napi_env,
+  value: sys::napi_value,
+  buf: *mut c_char,
+  bufsize: usize,
+  result: *mut usize,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let value = string(env, value)?;
+    unsafe {
+      copy_units(value.as_bytes(), buf.cast::<u8>(), bufsize, result, |len| {
+        value.is_char_boundary(len)
+      })
+    }
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_get_value_string_utf16(
+  env: sys::napi_env,
+  value: sys::napi_value,
+  buf: *mut u16,
+  bufsize: usize,
+  result: *mut usize,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let units = string(env, value)?.encode_utf16().collect::<Vec<_>>();
+    unsafe { copy_units(&units, buf, bufsize, result, |_| true) }
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_create_symbol(
+  env: sys::napi_env,
+  description: sys::napi_value,
+  result: *mut sys::napi_value,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let description = if description.is_null() {
+      None
+    } else {
+      Some(string(env, description)?.to_string())
+    };
+    write(result, env.handle(env.symbol(description)))
+  })
+}
+
+pub(crate) unsafe extern "C" fn node_api_symbol_for(
+  env: sys::napi_env,
+  utf8name: *const c_char,
+  length: isize,
+  result: *mut sys::napi_value,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let name = String::from_utf8_lossy(unsafe { read_str(utf8name, length) }?).into_owned();
+    let symbol = env
+      .symbol_registry
+      .borrow_mut()
+      .entry(name.clone())
+      .or_insert_with(|| env.symbol(Some(name)))
+      .clone();
+    write(result, env.handle(symbol))
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_typeof(
+  env: sys::napi_env,
+  value: sys::napi_value,
+  result: *mut sys::napi_valuetype,
+) -> sys::napi_status {
+  with_env(env, |env| write(result, env.value(value)?.type_of()))
+}
+
+pub(crate) unsafe extern "C" fn napi_coerce_to_bool(
+  env: sys::napi_env,
+  value: sys::napi_value,
+  result: *mut sys::napi_value,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let value = env.value(value)?.to_boolean();
+    write(result, env.handle(Value::Boolean(value)))
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_coerce_to_number(
+  env: sys::napi_env,
+  value: sys::napi_value,
+  result: *mut sys::napi_value,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let value = match env.value(value)? {
+      Value::Symbol(_) | Value::BigInt(_) => {
+        return Err(env.throw_error(1, "Cannot convert the value to a number"));
+      }
+      value => value.to_number(),
+    };
+    write(result, env.handle(Value::Number(value)))
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_coerce_to_string(
+  env: sys::napi_env,
+  value: sys::napi_value,
+  result: *mut sys::napi_value,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let value = env.to_string(&env.value(value)?)?;
+    write(result, env.handle(Value::String(value.into())))
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_coerce_to_object(
+  env: sys::napi_env,
+  value: sys::napi_value,
+  result: *mut sys::napi_value,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let object = match env.value(value)? {
+      Value::Undefined | Value::Null => {
+        return Err(env.throw_error(1, "Cannot convert undefined or null to object"));
+      }
+      Value::Object(object) => object,
+      primitive => {
+        // The wrapper objects only keep the primitive value for `valueOf` like properties
+        let object = env.new_object();
+        crate::env::define_hidden(&object, "__primitive__", primitive);
+        object
+      }
+    };
+    write(result, env.handle(Value::Object(object)))
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_create_bigint_int64(
+  env: sys::napi_env,
+  value: i64,
+  result: *mut sys::napi_value,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let value = BigInt::new(value < 0, vec![value.unsigned_abs()]);
+    write(result, env.handle(Value::BigInt(value.into())))
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_create_bigint_uint64(
+  env: sys::napi_env,
+  value: u64,
+  result: *mut sys::napi_value,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let value = BigInt::new(false, vec![value]);
+    write(result, env.handle(Value::BigInt(value.into())))
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_create_bigint_words(
+  env: sys::napi_env,
+  sign_bit: c_int,
+  word_count: usize,
+  words: *const u64,
+  result: *mut sys::napi_value,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    if word_count > i32::MAX as usize || (words.is_null() && word_count > 0) {
+      return Err(sys::Status::napi_invalid_arg);
+    }
+    let words = if word_count == 0 {
+      Vec::new()
+    } else {
+      unsafe { std::slice::from_raw_parts(words, word_count) }.to_vec()
+    };
+    let value = BigInt::new(sign_bit != 0, words);
+    write(result, env.handle(Value::BigInt(value.into())))
+  })
+}
+
+fn bigint(env: &EnvState, value: sys::napi_value) -> Result<std::rc::Rc<BigInt>, Status> {
+  match env.value(value)? {
+    Value::BigInt(value) => Ok(value),
+    _ => Err(sys::Status::napi_bigint_expected),
+  }
+}
+
+pub(crate) unsafe extern "C" fn napi_get_value_bigint_int64(
+  env: sys::napi_env,
+  value: sys::napi_value,
+  result: *mut i64,
+  lossless: *mut bool,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let value = bigint(env, value)?;
+    let bits = value.low_bits() as i64;
+    write(result, bits)?;
+    let is_lossless = value.words.len() <= 1 && (bits < 0) == value.negative;
+    write(lossless, is_lossless)
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_get_value_bigint_uint64(
+  env: sys::napi_env,
+  value: sys::napi_value,
+  result: *mut u64,
+  lossless: *mut bool,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let value = bigint(env, value)?;
+    write(result, value.low_bits())?;
+    write(lossless, value.words.len() <= 1 && !value.negative)
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_get_value_bigint_words(
+  env: sys::napi_env,
+  value: sys::napi_value,
+  sign_bit: *mut c_int,
+  word_count: *mut usize,
+  words: *mut u64,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let value = bigint(env, value)?;
+    if word_count.is_null() {
+      return Err(sys::Status::napi_invalid_arg);
+    }
+    if !words.is_null() {
+      let capacity = unsafe { *word_count };
+      let copied = capacity.min(value.words.len());
+      unsafe { std::ptr::copy_nonoverlapping(value.words.as_ptr(), words, copied) };
+      if !sign_bit.is_null() {
+        unsafe { sign_bit.write(c_int::from(value.negative)) };
+      }
+    }
+    write(word_count, value.words.len())
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_create_date(
+  env: sys::napi_env,
+  time: f64,
+  result: *mut sys::napi_value,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let date = Object::new(ObjectKind::Date(time), Some(env.object_prototype.clone()));
+    write(result, env.handle(Value::Object(date)))
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_is_date(
+  env: sys::napi_env,
+  value: sys::napi_value,
+  is_date: *mut bool,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let value = env.value(value)?;
+    let is = matches!(&value, Value::Object(object) if matches!(object.borrow().kind, ObjectKind::Date(_)));
+    write(is_date, is)
+  })
+}
+
+pub(crate) unsafe extern "C" fn napi_get_date_value(
+  env: sys::napi_env,
+  value: sys::napi_value,
+  result: *mut f64,
+) -> sys::napi_status {
+  with_env(env, |env| {
+    let time = match &env.object(value)?.borrow().kind {
+      ObjectKind::Date(time) => *time,
+      _ => return Err(sys::Status::napi_date_expected),
+    };
+    write(result, time)
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use std::ptr;
+
+  use super::*;
+  use crate::MockEnv;
+
+  fn create_latin1(env: &MockEnv, bytes: &[u8], length: isize) -> sys::napi_value {
+    let mut value = ptr::null_mut();
+    let status =
+      unsafe { napi_create_string_latin1(env.raw(), bytes.as_ptr().cast(), length, &mut value) };
+    assert_eq!(status, sys::Status::napi_ok);
+    value
+  }
+
+  fn create_utf16(env: &MockEnv, units: &[u16]) -> sys::napi_value {
+    let mut value = ptr::null_mut();
+    let status = unsafe {
+      napi_create_string_utf16(env.raw(), units.as_ptr(), units.len() as isize, &mut value)
+    };
+    assert_eq!(status, sys::Status::napi_ok);
+    value
+  }
+
+  /// Read the string into a buffer of `bufsize` bytes, and check the nul terminator
+  fn get_utf8(env: &MockEnv, value: sys::napi_value, bufsize: usize) -> String {
+    let mut buf = vec![0xff_u8; bufsize];
+    let mut len = usize::MAX;
+    let status = unsafe {
+      napi_get_value_string_utf8(env.raw(), value, buf.as_mut_ptr().cast(), bufsize, &mut len)
+    };
+    assert_eq!(status, sys::Status::napi_ok);
+    if bufsize > 0 {
+      assert_eq!(buf[len], 0);
+    }
+    String::from_utf8(buf[..len].to_vec()).unwrap()
+  }
+
+  fn utf8_len(env: &MockEnv, value: sys::napi_value) -> usize {
+    let mut len = 0;
+    let status =
+      unsafe { napi_get_value_string_utf8(env.raw(), value, ptr::null_mut(), 0, &mut len) };
+    assert_eq!(status, sys::Status::napi_ok);
+    len
+  }
+
+  #[test]
+  fn test_latin1_strings() {
+    let env = MockEnv::new();
+    let value = create_latin1(&env, b"caf\xe9", 4);
+    assert_eq!(get_utf8(&env, value, 16), "café");
+    assert_eq!(utf8_len(&env, value), 5);
+
+    let mut buf = [0_u8; 8];
+    let mut len = 0;
+    let status = unsafe {
+      napi_get_value_string_latin1(
+        env.raw(),
+        value,
+        buf.as_mut_ptr().cast(),
+        buf.len(),
+        &mut len,
+      )
+    };
+    assert_eq!(status, sys::Status::napi_ok);
+    assert_eq!(&buf[..=len], b"caf\xe9\0");
+
+    let nul_terminated = create_latin1(&env, b"caf\xe9\0ignored", -1);
+    assert_eq!(get_utf8(&env, nul_terminated, 16), "café");
+  }
+
+  #[test]
+  fn test_utf8_truncation() {
+    let env = MockEnv::new();
+    let value = create_latin1(&env, b"h\xe9llo", 5);
+    assert_eq!(utf8_len(&env, value), 6);
+    assert_eq!(get_utf8(&env, value, 7), "héllo");
+    // one byte is kept for the nul terminator
+    assert_eq!(get_utf8(&env, value, 6), "héll");
+    // `é` doesn't fit and isn't split
+    assert_eq!(get_utf8(&env, value, 3), "h");
+    assert_eq!(get_utf8(&env, value, 4), "hé");
+    assert_eq!(get_utf8(&env, value, 0), "");
+  }
+
+  #[test]
+  fn test_utf16_strings() {
+    let env = MockEnv::new();
+    let units = "a😀b".encode_utf16().collect::<Vec<_>>();
+    let value = create_utf16(&env, &units);
+    assert_eq!(get_utf8(&env, value, 16), "a😀b");
+
+    let mut len = 0;
+    let status =
+      unsafe { napi_get_value_string_utf16(env.raw(), value, ptr::null_mut(), 0, &mut len) };
+    assert_eq!(status, sys::Status::napi_ok);
+    assert_eq!(len, 4);
+
+    // code units are copied like V8 does, a surrogate pair may be split
+    let mut buf = [0xffff_u16; 3];
+    let status = unsafe {
+      napi_get_value_string_utf16(env.raw(), value, buf.as_mut_ptr(), buf.len(), &mut len)
+    };
+    assert_eq!(status, sys::Status::napi_ok);
+    assert_eq!(len, 2);
+    assert_eq!(buf, [units[0], units[1], 0]);
+  }
+
+  #[test]
+  fn test_string_expected() {
+    let env = MockEnv::new();
+    let mut number = ptr::null_mut();
+    assert_eq!(
+      unsafe { napi_create_int32(env.raw(), 1, &mut number) },
+      sys::Status::napi_ok
+    );
+    let mut len = 0;
+    let status =
+      unsafe { napi_get_value_string_utf8(env.raw(), number, ptr::null_mut(), 0, &mut len) };
+    assert_eq!(status, sys::Status::napi_string_expected);
+  }
+}
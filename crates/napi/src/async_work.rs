@@ -1,10 +1,12 @@
-use std::cell::Cell;
 use std::marker::PhantomData;
 use std::mem;
 use std::os::raw::c_void;
 use std::panic::UnwindSafe;
 use std::ptr;
-use std::rc::Rc;
+use std::sync::{
+  atomic::{AtomicU8, Ordering},
+  Arc,
+};
 
 use crate::bindgen_runtime::JsObjectValue;
 use crate::{
@@ -17,7 +19,7 @@ struct AsyncWork<'task, T: ScopedTask<'task>> {
   deferred: sys::napi_deferred,
   value: mem::MaybeUninit<Result<T::Output>>,
   napi_async_work: sys::napi_async_work,
-  status: Rc<Cell<u8>>,
+  status: Arc<AtomicU8>,
 }
 
 pub struct AsyncWorkPromise<T> {
@@ -28,7 +30,7 @@ pub struct AsyncWorkPromise<T> {
   /// 0: not started
   /// 1: completed
   /// 2: canceled
-  pub(crate) status: Rc<Cell<u8>>,
+  pub(crate) status: Arc<AtomicU8>,
   _phantom: PhantomData<T>,
 }
 
@@ -42,7 +44,7 @@ impl<T> AsyncWorkPromise<T> {
 
   pub fn cancel(&mut self) -> Result<()> {
     // must be happened in the main thread, relaxed is enough
-    self.status.set(2);
+    self.status.store(2, Ordering::Relaxed);
     check_status!(
       unsafe { sys::napi_cancel_async_work(self.env, self.napi_async_work) },
       "Cancel async work failed"
@@ -53,7 +55,7 @@ impl<T> AsyncWorkPromise<T> {
 pub fn run<'task, T: ScopedTask<'task>>(
   env: sys::napi_env,
   task: T,
-  abort_status: Option<Rc<Cell<u8>>>,
+  abort_status: Option<Arc<AtomicU8>>,
 ) -> Result<AsyncWorkPromise<T::JsValue>> {
   let mut undefined = ptr::null_mut();
   check_status!(
@@ -66,7 +68,7 @@ pub fn run<'task, T: ScopedTask<'task>>(
     unsafe { sys::napi_create_promise(env, &mut deferred, &mut raw_promise) },
     "Create promise failed in async_work::run"
   )?;
-  let task_status = abort_status.unwrap_or_else(|| Rc::new(Cell::new(0)));
+  let task_status = abort_status.unwrap_or_else(|| Arc::new(AtomicU8::new(0)));
   let result = Box::leak(Box::new(AsyncWork {
     inner_task: task,
     deferred,
@@ -101,6 +103,16 @@ pub fn run<'task, T: ScopedTask<'task>>(
   })
 }
 
+/// Create the `AbortError` used to reject the `Promise` of an aborted `AsyncTask` or `async fn`
+pub(crate) fn create_abort_error(env: sys::napi_env) -> Result<sys::napi_value> {
+  const ABORT_ERROR_NAME: &str = "AbortError";
+  let wrapped_env = Env::from_raw(env);
+  let mut error =
+    wrapped_env.create_error(Error::new(Status::Cancelled, ABORT_ERROR_NAME.to_owned()))?;
+  error.set_named_property("name", ABORT_ERROR_NAME)?;
+  Ok(error.0.value)
+}
+
 unsafe impl<'task, T: ScopedTask<'task> + Send> Send for AsyncWork<'task, T> {}
 unsafe impl<'task, T: ScopedTask<'task> + Sync> Sync for AsyncWork<'task, T> {}
 
@@ -132,13 +144,9 @@ fn complete_impl<'task, T: ScopedTask<'task>>(
   let napi_async_work = mem::replace(&mut work.napi_async_work, ptr::null_mut());
   let deferred = mem::replace(&mut work.deferred, ptr::null_mut());
   if status == sys::Status::napi_cancelled {
-    const ABORT_ERROR_NAME: &str = "AbortError";
-    let wrapped_env = Env::from_raw(env);
-    let mut error =
-      wrapped_env.create_error(Error::new(Status::Cancelled, ABORT_ERROR_NAME.to_owned()))?;
-    error.set_named_property("name", ABORT_ERROR_NAME)?;
+    let error = create_abort_error(env)?;
     check_status!(
-      unsafe { sys::napi_reject_deferred(env, deferred, error.0.value) },
+      unsafe { sys::napi_reject_deferred(env, deferred, error) },
       "Reject AbortError failed"
     )?;
   } else {
@@ -155,7 +163,7 @@ fn complete_impl<'task, T: ScopedTask<'task>>(
         e,
       ),
     };
-    if work.status.get() != 2 {
+    if work.status.load(Ordering::Relaxed) != 2 {
       match check_status!(status)
         .and_then(move |_| value)
         .and_then(|v| unsafe { ToNapiValue::to_napi_value(env, v) })
@@ -174,7 +182,7 @@ fn complete_impl<'task, T: ScopedTask<'task>>(
         }
       };
     }
-    work.status.set(1);
+    work.status.store(1, Ordering::Relaxed);
   }
   work.inner_task.finally(Env::from_raw(env))?;
   check_status!(
@@ -1,5 +1,4 @@
 use std::cell::Cell;
-use std::ffi::c_void;
 use std::ptr;
 use std::rc::Rc;
 
@@ -115,6 +114,7 @@ impl<const N: usize> CallbackInfo<N> {
         ),
         "Failed to initialize class `{js_name}`",
       )?;
+      tag_class_instance::<T>(self.env, this)?;
     };
 
     Reference::<T>::add_ref(
@@ -248,6 +248,7 @@ impl<const N: usize> CallbackInfo<N> {
       "Failed to initialize class `{}`",
       js_name,
     )?;
+    unsafe { tag_class_instance::<T>(self.env, instance) }?;
 
     Reference::<T>::add_ref(
       self.env,
@@ -278,16 +279,6 @@ impl<const N: usize> CallbackInfo<N> {
   where
     T: TypeName,
   {
-    let mut wrapped_val: *mut c_void = std::ptr::null_mut();
-
-    unsafe {
-      check_status!(
-        sys::napi_unwrap(self.env, self.this, &mut wrapped_val),
-        "Failed to unwrap exclusive reference of `{}` type from napi value",
-        T::type_name(),
-      )?;
-
-      Ok(wrapped_val.cast())
-    }
+    unsafe { unwrap_class_instance::<T>(self.env, self.this) }.map(|wrapped_val| wrapped_val.cast())
   }
 }
@@ -1,3 +1,5 @@
+use crate::{Error, JsError};
+
 #[doc(hidden)]
 #[macro_export]
 macro_rules! check_status_or_throw {
@@ -8,3 +10,87 @@ macro_rules! check_status_or_throw {
     }
   };
 }
+
+/// Pick the conversion of the `Err` returned by a `#[napi]` function with autoref specialization.
+///
+/// `(&&ErrorProbe(&err)).napi_error_kind()` returns the tag of the first conversion `err` has:
+/// 1. `Into<JsError<S>>`, like `Error` and the `#[napi(error)]` types
+/// 2. `Into<Error>`, like `anyhow::Error`
+/// 3. `std::error::Error`, the `source()` chain becomes the `cause` chain, see `Error::from_error_chain`
+#[doc(hidden)]
+pub struct ErrorProbe<'a, E>(pub &'a E);
+
+#[doc(hidden)]
+pub struct IntoJsErrorTag;
+
+#[doc(hidden)]
+pub struct IntoErrorTag;
+
+#[doc(hidden)]
+pub struct StdErrorTag;
+
+#[doc(hidden)]
+pub trait IntoJsErrorKind<S> {
+  #[inline]
+  fn napi_error_kind(&self) -> IntoJsErrorTag {
+    IntoJsErrorTag
+  }
+}
+
+impl<S: AsRef<str>, E: Into<JsError<S>>> IntoJsErrorKind<S> for &ErrorProbe<'_, E> {}
+
+#[doc(hidden)]
+pub trait IntoErrorKind {
+  #[inline]
+  fn napi_error_kind(&self) -> IntoErrorTag {
+    IntoErrorTag
+  }
+}
+
+impl<E: Into<Error>> IntoErrorKind for &&ErrorProbe<'_, E> {}
+
+#[doc(hidden)]
+pub trait StdErrorKind {
+  #[inline]
+  fn napi_error_kind(&self) -> StdErrorTag {
+    StdErrorTag
+  }
+}
+
+impl<E: std::error::Error + 'static> StdErrorKind for ErrorProbe<'_, E> {}
+
+impl IntoJsErrorTag {
+  #[inline]
+  pub fn into_js_error<S: AsRef<str>, E: Into<JsError<S>>>(self, err: E) -> JsError<S> {
+    err.into()
+  }
+
+  #[inline]
+  pub fn into_error<E: Into<Error>>(self, err: E) -> Error {
+    err.into()
+  }
+}
+
+impl IntoErrorTag {
+  #[inline]
+  pub fn into_js_error<E: Into<Error>>(self, err: E) -> JsError {
+    JsError::from(err.into())
+  }
+
+  #[inline]
+  pub fn into_error<E: Into<Error>>(self, err: E) -> Error {
+    err.into()
+  }
+}
+
+impl StdErrorTag {
+  #[inline]
+  pub fn into_js_error<E: std::error::Error + 'static>(self, err: E) -> JsError {
+    JsError::from(self.into_error(err))
+  }
+
+  #[inline]
+  pub fn into_error<E: std::error::Error + 'static>(self, err: E) -> Error {
+    Error::from_error_chain(&err)
+  }
+}
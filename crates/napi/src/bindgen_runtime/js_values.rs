@@ -1,4 +1,5 @@
 use std::{
+  marker::PhantomData,
   ptr,
   rc::Rc,
   sync::{Arc, Mutex},
@@ -16,14 +17,17 @@ mod class;
 #[cfg(all(feature = "chrono_date", feature = "napi5"))]
 mod date;
 mod either;
+mod error_class;
 mod external;
 mod function;
 mod map;
 mod nil;
 mod number;
 mod object;
+mod object_proxy;
 mod promise;
 mod promise_raw;
+mod property_key;
 mod scope;
 #[cfg(feature = "serde-json")]
 mod serde;
@@ -33,6 +37,10 @@ mod stream;
 mod string;
 mod symbol;
 mod task;
+#[cfg(feature = "napi5")]
+mod task_progress;
+mod time;
+mod validation;
 mod value_ref;
 
 pub use crate::js_values::Unknown;
@@ -45,18 +53,29 @@ pub use bigint::*;
 pub use buffer::*;
 pub use class::*;
 pub use either::*;
+pub use error_class::*;
 pub use external::*;
 pub use function::*;
+pub use map::JsMap;
+#[cfg(feature = "serde-json")]
+pub(crate) use map::{create_js_map, for_each_js_map_entry, is_js_map};
 pub use nil::*;
 pub use object::*;
+pub use object_proxy::*;
 pub use promise::*;
 pub use promise_raw::*;
+pub use property_key::*;
 pub use scope::*;
 #[cfg(feature = "web_stream")]
 pub use stream::*;
 pub use string::*;
 pub use symbol::*;
 pub use task::*;
+#[cfg(feature = "napi5")]
+pub use task_progress::*;
+#[cfg(feature = "napi6")]
+pub use time::DurationNanos;
+pub use validation::*;
 pub use value_ref::*;
 
 pub trait TypeName {
@@ -102,6 +121,20 @@ pub trait FromNapiValue: Sized {
   /// this function called to convert napi values to native rust values
   unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self>;
 
+  #[doc(hidden)]
+  /// # Safety
+  ///
+  /// Convert the value in a `#[napi(object, collect_errors)]` struct, the errors are recorded into `errors`.
+  /// Types that can report more than one error, like `Vec<T>` and the `collect_errors` structs, override it.
+  unsafe fn from_napi_value_collecting(
+    env: sys::napi_env,
+    napi_val: sys::napi_value,
+    errors: &mut ValidationErrors,
+  ) -> Result<Self> {
+    let _ = errors;
+    unsafe { Self::from_napi_value(env, napi_val) }
+  }
+
   fn from_unknown(value: Unknown) -> Result<Self> {
     unsafe { Self::from_napi_value(value.0.env, value.0.value) }
   }
@@ -164,6 +197,111 @@ pub trait ValidateNapiValue: TypeName {
   }
 }
 
+/// Conversions of a type defined in another crate, implemented by `#[napi(object, remote = Type)]`.
+///
+/// `Def` is the local mirror struct the attribute is applied to,
+/// it makes the implementation for the foreign type allowed by the orphan rules.
+pub trait RemoteNapiValue<Def>: Sized {
+  /// # Safety
+  ///
+  /// this function called to convert the remote rust value to napi values
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value>;
+
+  /// # Safety
+  ///
+  /// this function called to convert napi values to the remote rust value
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self>;
+
+  /// # Safety
+  ///
+  /// this function called to validate whether napi value passed to rust is valid type
+  unsafe fn validate(env: sys::napi_env, napi_val: sys::napi_value) -> Result<sys::napi_value>;
+}
+
+impl<Def: TypeName, T: RemoteNapiValue<Def>> RemoteNapiValue<Def> for Option<T> {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    unsafe { Option::<RemoteValue<T, Def>>::to_napi_value(env, val.map(RemoteValue::new)) }
+  }
+
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    Ok(
+      unsafe { Option::<RemoteValue<T, Def>>::from_napi_value(env, napi_val) }?
+        .map(RemoteValue::into_inner),
+    )
+  }
+
+  unsafe fn validate(env: sys::napi_env, napi_val: sys::napi_value) -> Result<sys::napi_value> {
+    unsafe { Option::<RemoteValue<T, Def>>::validate(env, napi_val) }
+  }
+}
+
+impl<Def: TypeName, T: RemoteNapiValue<Def>> RemoteNapiValue<Def> for Vec<T> {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    let mut arr = Array::new(env, val.len() as u32)?;
+    for (i, v) in val.into_iter().enumerate() {
+      arr.set(i as u32, RemoteValue::<T, Def>::new(v))?;
+    }
+    unsafe { Array::to_napi_value(env, arr) }
+  }
+
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    Ok(
+      unsafe { Vec::<RemoteValue<T, Def>>::from_napi_value(env, napi_val) }?
+        .into_iter()
+        .map(RemoteValue::into_inner)
+        .collect(),
+    )
+  }
+
+  unsafe fn validate(env: sys::napi_env, napi_val: sys::napi_value) -> Result<sys::napi_value> {
+    unsafe { Vec::<RemoteValue<T, Def>>::validate(env, napi_val) }
+  }
+}
+
+/// A value of a type from another crate converted through its `#[napi(object, remote = Type)]` mirror `Def`.
+///
+/// The `#[napi(with = Def)]` fields of `#[napi(object)]` are converted with it.
+#[repr(transparent)]
+pub struct RemoteValue<T, Def>(T, PhantomData<fn() -> Def>);
+
+impl<T, Def> RemoteValue<T, Def> {
+  pub fn new(value: T) -> Self {
+    Self(value, PhantomData)
+  }
+
+  pub fn into_inner(self) -> T {
+    self.0
+  }
+}
+
+impl<T, Def: TypeName> TypeName for RemoteValue<T, Def> {
+  fn type_name() -> &'static str {
+    Def::type_name()
+  }
+
+  fn value_type() -> ValueType {
+    Def::value_type()
+  }
+}
+
+impl<Def: TypeName, T: RemoteNapiValue<Def>> ValidateNapiValue for RemoteValue<T, Def> {
+  unsafe fn validate(env: sys::napi_env, napi_val: sys::napi_value) -> Result<sys::napi_value> {
+    unsafe { T::validate(env, napi_val) }
+  }
+}
+
+impl<Def, T: RemoteNapiValue<Def>> FromNapiValue for RemoteValue<T, Def> {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    Ok(Self::new(unsafe { T::from_napi_value(env, napi_val) }?))
+  }
+}
+
+impl<Def, T: RemoteNapiValue<Def>> ToNapiValue for RemoteValue<T, Def> {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    unsafe { T::to_napi_value(env, val.0) }
+  }
+}
+
 impl<T: TypeName> TypeName for Option<T> {
   fn type_name() -> &'static str {
     T::type_name()
@@ -217,6 +355,26 @@ where
       _ => Ok(Some(unsafe { T::from_napi_value(env, napi_val)? })),
     }
   }
+
+  unsafe fn from_napi_value_collecting(
+    env: sys::napi_env,
+    napi_val: sys::napi_value,
+    errors: &mut ValidationErrors,
+  ) -> Result<Self> {
+    let mut val_type = 0;
+
+    check_status!(
+      unsafe { sys::napi_typeof(env, napi_val, &mut val_type) },
+      "Failed to convert napi value into rust type `Option<T>`",
+    )?;
+
+    match val_type {
+      sys::ValueType::napi_undefined | sys::ValueType::napi_null => Ok(None),
+      _ => Ok(Some(unsafe {
+        T::from_napi_value_collecting(env, napi_val, errors)?
+      })),
+    }
+  }
 }
 
 impl<T> ToNapiValue for Option<T>
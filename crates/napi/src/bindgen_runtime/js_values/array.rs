@@ -284,19 +284,49 @@ where
     let arr = unsafe { Array::from_napi_value(env, napi_val)? };
     let mut vec = Vec::with_capacity(arr.len() as usize);
 
-    for i in 0..arr.len() {
-      if let Some(val) = arr.get::<T>(i)? {
-        vec.push(val);
-      } else {
-        return Err(Error::new(
+    let get_item = |i| {
+      arr.get::<T>(i)?.ok_or_else(|| {
+        Error::new(
           Status::InvalidArg,
           "Found inconsistent data type in Array<T> when converting to Rust Vec<T>".to_owned(),
-        ));
-      }
+        )
+      })
+    };
+    for i in 0..arr.len() {
+      vec.push(get_item(i)?);
     }
 
     Ok(vec)
   }
+
+  /// Validate all the items, and return the first error after that
+  unsafe fn from_napi_value_collecting(
+    env: sys::napi_env,
+    napi_val: sys::napi_value,
+    errors: &mut ValidationErrors,
+  ) -> Result<Self> {
+    let arr = unsafe { Array::from_napi_value(env, napi_val)? };
+    let mut vec = Vec::with_capacity(arr.len() as usize);
+    let mut first_error = None;
+    for i in 0..arr.len() {
+      let item = errors.validate_path(i, |errors| {
+        let mut item = ptr::null_mut();
+        check_status!(
+          unsafe { sys::napi_get_element(env, arr.inner, i, &mut item) },
+          "Failed to get element with index `{}`",
+          i,
+        )?;
+        unsafe { T::from_napi_value_collecting(env, item, errors) }
+      });
+      match item {
+        Ok(val) => vec.push(val),
+        Err(err) => {
+          first_error.get_or_insert(err);
+        }
+      }
+    }
+    first_error.map_or(Ok(vec), Err)
+  }
 }
 
 impl<T> ValidateNapiValue for Vec<T>
@@ -1,8 +1,10 @@
 use std::any::type_name;
+use std::collections::HashMap;
 use std::ffi::CString;
 use std::marker::PhantomData;
 use std::ops::{Deref, DerefMut};
 use std::ptr;
+use std::sync::{LazyLock, RwLock};
 
 use crate::{
   bindgen_runtime::{
@@ -194,16 +196,10 @@ where
 
 impl<'env, T: 'env> FromNapiValue for ClassInstance<'env, T> {
   unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> crate::Result<Self> {
-    let mut value = ptr::null_mut();
-    check_status!(
-      unsafe { sys::napi_unwrap(env, napi_val, &mut value) },
-      "Unwrap value [{}] from class failed",
-      type_name::<T>(),
-    )?;
-    let value = unsafe { Box::from_raw(value as *mut T) };
+    let value = unsafe { unwrap_class_instance::<T>(env, napi_val) }?;
     Ok(Self {
       value: napi_val,
-      inner: Box::leak(value),
+      inner: value.cast(),
       env,
       _phantom: &PhantomData,
     })
@@ -277,6 +273,7 @@ pub unsafe fn new_instance<T: 'static + ObjectFinalize>(
     "Failed to wrap native object of class `{}`",
     type_name::<T>(),
   )?;
+  tag_class_instance::<T>(env, result)?;
   Reference::<T>::add_ref(
     env,
     wrapped_value,
@@ -284,3 +281,212 @@ pub unsafe fn new_instance<T: 'static + ObjectFinalize>(
   );
   Ok(result)
 }
+
+/// Seed of the class type tags, its address is different in every loaded copy of the addon
+#[cfg(feature = "napi8")]
+static CLASS_TYPE_TAG_SEED: u8 = 0;
+
+/// Type tag of the class named `type_name`, unique per Rust type and per loaded copy of the addon
+#[cfg(feature = "napi8")]
+fn class_type_tag(type_name: &str) -> sys::napi_type_tag {
+  const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+  const FNV_PRIME: u64 = 0x0100_0000_01b3;
+  let fnv1a = |basis: u64| {
+    type_name.bytes().fold(basis, |hash, byte| {
+      (hash ^ byte as u64).wrapping_mul(FNV_PRIME)
+    })
+  };
+  let seed = ptr::addr_of!(CLASS_TYPE_TAG_SEED) as u64;
+  sys::napi_type_tag {
+    lower: fnv1a(FNV_OFFSET_BASIS),
+    upper: fnv1a(FNV_OFFSET_BASIS ^ seed),
+  }
+}
+
+#[cfg(feature = "napi8")]
+unsafe fn is_tagged_with_class(
+  env: sys::napi_env,
+  value: sys::napi_value,
+  type_name: &str,
+) -> Result<bool> {
+  let type_tag = class_type_tag(type_name);
+  let mut is_tagged = false;
+  check_status!(
+    unsafe { sys::napi_check_object_type_tag(env, value, &type_tag, &mut is_tagged) },
+    "Failed to check type tag of class `{}`",
+    type_name,
+  )?;
+  Ok(is_tagged)
+}
+
+/// # Safety
+///
+/// Tag the newly created instance of class `T` with `napi_type_tag_object`,
+/// so that `check_class_type_tag` could reject objects which were not created by this class.
+#[doc(hidden)]
+pub unsafe fn tag_class_instance<T: ?Sized>(
+  env: sys::napi_env,
+  instance: sys::napi_value,
+) -> Result<()> {
+  #[cfg(feature = "napi8")]
+  {
+    let type_tag = class_type_tag(type_name::<T>());
+    check_status!(
+      unsafe { sys::napi_type_tag_object(env, instance, &type_tag) },
+      "Failed to tag instance of class `{}`",
+      type_name::<T>(),
+    )?;
+  }
+  #[cfg(not(feature = "napi8"))]
+  {
+    let _ = (env, instance);
+  }
+  Ok(())
+}
+
+/// # Safety
+///
+/// Check that the object was created by the class `T` of this addon before unwrapping it.
+/// Without `napi8` this is a no-op.
+#[doc(hidden)]
+pub unsafe fn check_class_type_tag<T: ?Sized>(
+  env: sys::napi_env,
+  value: sys::napi_value,
+) -> Result<()> {
+  #[cfg(feature = "napi8")]
+  {
+    if !unsafe { is_tagged_with_class(env, value, type_name::<T>()) }? {
+      return Err(crate::Error::new(
+        crate::Status::InvalidArg,
+        format!("Value is not an instance of class `{}`", type_name::<T>()),
+      ));
+    }
+  }
+  #[cfg(not(feature = "napi8"))]
+  {
+    let _ = (env, value);
+  }
+  Ok(())
+}
+
+/// Convert the pointer wrapped in an instance of the subclass into the pointer of its base class
+pub type ClassUpcast = fn(*mut std::ffi::c_void) -> *mut std::ffi::c_void;
+
+struct ClassSubtype {
+  type_name: &'static str,
+  #[cfg_attr(feature = "napi8", allow(unused))]
+  js_name: &'static str,
+  upcast: ClassUpcast,
+}
+
+/// Base class type name => classes declared with `#[napi(extends = Base)]`
+static CLASS_SUBTYPES: LazyLock<RwLock<HashMap<&'static str, Vec<ClassSubtype>>>> =
+  LazyLock::new(Default::default);
+
+pub(crate) fn register_class_subtype<T: ?Sized, Base: ?Sized>(
+  js_name: &'static str,
+  upcast: ClassUpcast,
+) {
+  CLASS_SUBTYPES
+    .write()
+    .expect("Write CLASS_SUBTYPES failed")
+    .entry(type_name::<Base>())
+    .or_default()
+    .push(ClassSubtype {
+      type_name: type_name::<T>(),
+      js_name,
+      upcast,
+    });
+}
+
+/// Find the pointer of `type_name` in the value wrapped by `value`, walking down the subclasses.
+/// Returns `None` if the `value` is not an instance of `type_name` or any of its subclasses.
+unsafe fn resolve_class_instance(
+  env: sys::napi_env,
+  value: sys::napi_value,
+  type_name: &str,
+  wrapped: *mut std::ffi::c_void,
+) -> Result<Option<*mut std::ffi::c_void>> {
+  #[cfg(feature = "napi8")]
+  if unsafe { is_tagged_with_class(env, value, type_name) }? {
+    return Ok(Some(wrapped));
+  }
+  let subtypes = CLASS_SUBTYPES
+    .read()
+    .expect("Read CLASS_SUBTYPES failed")
+    .get(type_name)
+    .map(|subtypes| {
+      subtypes
+        .iter()
+        .map(|subtype| (subtype.type_name, subtype.js_name, subtype.upcast))
+        .collect::<Vec<_>>()
+    })
+    .unwrap_or_default();
+  for (subtype_name, _subtype_js_name, upcast) in subtypes {
+    // without type tags, the most derived class is found by `instanceof`
+    #[cfg(not(feature = "napi8"))]
+    if !unsafe { is_instance_of_class(env, value, _subtype_js_name) }? {
+      continue;
+    }
+    if let Some(subtype_wrapped) =
+      unsafe { resolve_class_instance(env, value, subtype_name, wrapped) }?
+    {
+      return Ok(Some(upcast(subtype_wrapped)));
+    }
+  }
+  #[cfg(feature = "napi8")]
+  {
+    Ok(None)
+  }
+  #[cfg(not(feature = "napi8"))]
+  {
+    Ok(Some(wrapped))
+  }
+}
+
+#[cfg(not(feature = "napi8"))]
+unsafe fn is_instance_of_class(
+  env: sys::napi_env,
+  value: sys::napi_value,
+  js_name: &'static str,
+) -> Result<bool> {
+  let Some(ctor_ref) = crate::bindgen_runtime::get_class_constructor(js_name) else {
+    return Ok(false);
+  };
+  let mut ctor = ptr::null_mut();
+  check_status!(
+    unsafe { sys::napi_get_reference_value(env, ctor_ref, &mut ctor) },
+    "Failed to get constructor reference of class `{}`",
+    js_name,
+  )?;
+  let mut is_instance_of = false;
+  check_status!(
+    unsafe { sys::napi_instanceof(env, value, ctor, &mut is_instance_of) },
+    "Failed to run instanceof for class `{}`",
+    js_name,
+  )?;
+  Ok(is_instance_of)
+}
+
+/// # Safety
+///
+/// Unwrap the native value of class `T` from `value`.
+/// If `value` is an instance of a class declared with `#[napi(extends = T)]`, the pointer to its `T` field is returned.
+#[doc(hidden)]
+pub unsafe fn unwrap_class_instance<T: ?Sized>(
+  env: sys::napi_env,
+  value: sys::napi_value,
+) -> Result<*mut std::ffi::c_void> {
+  let mut wrapped = ptr::null_mut();
+  check_status!(
+    unsafe { sys::napi_unwrap(env, value, &mut wrapped) },
+    "Failed to recover `{}` type from napi value",
+    type_name::<T>(),
+  )?;
+  unsafe { resolve_class_instance(env, value, type_name::<T>(), wrapped) }?.ok_or_else(|| {
+    crate::Error::new(
+      crate::Status::InvalidArg,
+      format!("Value is not an instance of class `{}`", type_name::<T>()),
+    )
+  })
+}
@@ -0,0 +1,279 @@
+use std::cell::LazyCell;
+use std::collections::HashMap;
+#[cfg(feature = "napi3")]
+use std::ffi::c_void;
+use std::ptr;
+
+use rustc_hash::FxBuildHasher;
+
+use crate::{
+  bindgen_prelude::*,
+  bindgen_runtime::{module_register::inherit_class, PersistedPerInstanceHashMap},
+  check_status, sys, ValueType,
+};
+
+/// The JavaScript `Error` subclass of a `#[napi(error)]` type, or of a variant of a `#[napi(error)]` enum
+///
+/// The class is defined on its first use in an env, so a variant class can be created before its parent is exported.
+#[doc(hidden)]
+pub struct ErrorClass {
+  js_name: &'static str,
+  parent: Option<&'static ErrorClass>,
+}
+
+type ErrorClasses =
+  HashMap</* class address */ usize, /* constructor */ sys::napi_ref, FxBuildHasher>;
+
+thread_local! {
+  static ERROR_CLASSES: LazyCell<PersistedPerInstanceHashMap<sys::napi_env, ErrorClasses, FxBuildHasher>> =
+    LazyCell::new(Default::default);
+}
+
+impl ErrorClass {
+  /// `parent` is the class extended by this class, the global `Error` if it's `None`
+  #[cfg(feature = "napi3")]
+  pub const fn new(js_name: &'static str, parent: Option<&'static ErrorClass>) -> Self {
+    Self { js_name, parent }
+  }
+
+  /// The class is cached per env and the reference is released by an env cleanup hook,
+  /// without the hook a new env allocated at the same address would read a stale reference,
+  /// and defining the class on every use would break `instanceof` against the exported class.
+  #[cfg(not(feature = "napi3"))]
+  pub const fn new(_js_name: &'static str, _parent: Option<&'static ErrorClass>) -> Self {
+    panic!("#[napi(error)] requires the `napi3` feature of napi")
+  }
+
+  /// Get the constructor of the class in `env`, it's defined if this is the first time
+  ///
+  /// # Safety
+  ///
+  /// `env` must be a valid env of the current thread.
+  pub unsafe fn constructor(&'static self, env: sys::napi_env) -> Result<sys::napi_value> {
+    let key = self as *const Self as usize;
+    let cached = ERROR_CLASSES.with(|cell| {
+      cell.borrow_mut(|envs| {
+        envs
+          .get(&env)
+          .and_then(|classes| classes.get(&key).copied())
+      })
+    });
+    let mut ctor = ptr::null_mut();
+    if let Some(ctor_ref) = cached {
+      check_status!(
+        unsafe { sys::napi_get_reference_value(env, ctor_ref, &mut ctor) },
+        "Failed to get the constructor of error class `{}`",
+        self.js_name
+      )?;
+      return Ok(ctor);
+    }
+
+    ctor = unsafe { self.define(env) }?;
+    let mut ctor_ref = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_create_reference(env, ctor, 1, &mut ctor_ref) },
+      "Failed to create reference for error class `{}`",
+      self.js_name
+    )?;
+    #[cfg_attr(not(feature = "napi3"), allow(unused_variables))]
+    let is_first_class = ERROR_CLASSES.with(|cell| {
+      cell.borrow_mut(|envs| {
+        let is_first_class = !envs.contains_key(&env);
+        envs.entry(env).or_default().insert(key, ctor_ref);
+        is_first_class
+      })
+    });
+    #[cfg(feature = "napi3")]
+    if is_first_class {
+      check_status!(
+        unsafe { sys::napi_add_env_cleanup_hook(env, Some(remove_error_classes), env.cast()) },
+        "Failed to add the cleanup hook of error classes"
+      )?;
+    }
+    Ok(ctor)
+  }
+
+  /// Create an instance of the class with `message`, the `code` and the other properties are set by the caller
+  ///
+  /// # Safety
+  ///
+  /// `env` must be a valid env of the current thread.
+  pub unsafe fn new_instance<'env>(
+    &'static self,
+    env: sys::napi_env,
+    message: &str,
+  ) -> Result<Object<'env>> {
+    let ctor = unsafe { self.constructor(env) }?;
+    let message = unsafe { <&str>::to_napi_value(env, message) }?;
+    let mut instance = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_new_instance(env, ctor, 1, &message, &mut instance) },
+      "Failed to create an instance of error class `{}`",
+      self.js_name
+    )?;
+    Ok(Object::from_raw(env, instance))
+  }
+
+  unsafe fn define(&'static self, env: sys::napi_env) -> Result<sys::napi_value> {
+    let name = unsafe { <&str>::to_napi_value(env, self.js_name) }?;
+    // `name` is on the prototype and not enumerable, like `Error.prototype.name`
+    let properties = [sys::napi_property_descriptor {
+      utf8name: c"name".as_ptr(),
+      name: ptr::null_mut(),
+      method: None,
+      getter: None,
+      setter: None,
+      value: name,
+      attributes: sys::PropertyAttributes::writable | sys::PropertyAttributes::configurable,
+      data: ptr::null_mut(),
+    }];
+    let mut class = ptr::null_mut();
+    check_status!(
+      unsafe {
+        sys::napi_define_class(
+          env,
+          self.js_name.as_ptr().cast(),
+          self.js_name.len() as isize,
+          Some(construct_error),
+          ptr::null_mut(),
+          properties.len(),
+          properties.as_ptr(),
+          &mut class,
+        )
+      },
+      "Failed to define error class `{}`",
+      self.js_name
+    )?;
+
+    let parent = match self.parent {
+      Some(parent) => unsafe { parent.constructor(env) }?,
+      None => unsafe { get_global_error(env) }?,
+    };
+    unsafe { inherit_class(env, class, parent) }?;
+    Ok(class)
+  }
+}
+
+unsafe fn get_global_error(env: sys::napi_env) -> Result<sys::napi_value> {
+  let mut global = ptr::null_mut();
+  check_status!(
+    unsafe { sys::napi_get_global(env, &mut global) },
+    "Failed to get global object"
+  )?;
+  let mut error = ptr::null_mut();
+  check_status!(
+    unsafe { sys::napi_get_named_property(env, global, c"Error".as_ptr(), &mut error) },
+    "Failed to get global Error"
+  )?;
+  Ok(error)
+}
+
+unsafe extern "C" fn construct_error(
+  env: sys::napi_env,
+  info: sys::napi_callback_info,
+) -> sys::napi_value {
+  match unsafe { init_error(env, info) } {
+    Ok(this) => this,
+    Err(err) => {
+      unsafe { JsError::from(err).throw_into(env) };
+      ptr::null_mut()
+    }
+  }
+}
+
+/// Do what the `Error` constructor does, set `message` and `cause` from the arguments and capture the stack trace
+unsafe fn init_error(env: sys::napi_env, info: sys::napi_callback_info) -> Result<sys::napi_value> {
+  let mut argc = 2;
+  let mut argv = [ptr::null_mut(); 2];
+  let mut this = ptr::null_mut();
+  check_status!(
+    unsafe {
+      sys::napi_get_cb_info(
+        env,
+        info,
+        &mut argc,
+        argv.as_mut_ptr(),
+        &mut this,
+        ptr::null_mut(),
+      )
+    },
+    "Failed to get the arguments of error class constructor"
+  )?;
+  let mut new_target = ptr::null_mut();
+  check_status!(
+    unsafe { sys::napi_get_new_target(env, info, &mut new_target) },
+    "Failed to get new.target of error class constructor"
+  )?;
+  if new_target.is_null() {
+    return Err(Error::new(
+      Status::GenericFailure,
+      "Error class constructor cannot be invoked without 'new'",
+    ));
+  }
+
+  let mut properties = vec![];
+  let attributes = sys::PropertyAttributes::writable | sys::PropertyAttributes::configurable;
+  if argc > 0 && type_of!(env, argv[0])? != ValueType::Undefined {
+    let mut message = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_coerce_to_string(env, argv[0], &mut message) },
+      "Failed to convert the message of error into string"
+    )?;
+    properties.push((c"message", message));
+  }
+  if argc > 1 && type_of!(env, argv[1])? == ValueType::Object {
+    let options = Object::from_raw(env, argv[1]);
+    if options.has_named_property("cause")? {
+      let cause: Unknown = options.get_named_property_unchecked("cause")?;
+      properties.push((c"cause", cause.0.value));
+    }
+  }
+  let properties = properties
+    .into_iter()
+    .map(|(name, value)| sys::napi_property_descriptor {
+      utf8name: name.as_ptr(),
+      name: ptr::null_mut(),
+      method: None,
+      getter: None,
+      setter: None,
+      value,
+      attributes,
+      data: ptr::null_mut(),
+    })
+    .collect::<Vec<_>>();
+  check_status!(
+    unsafe { sys::napi_define_properties(env, this, properties.len(), properties.as_ptr()) },
+    "Failed to set the message of error"
+  )?;
+
+  // `Error.captureStackTrace` is not standard, it's skipped if the engine doesn't have it.
+  // The constructor is not passed to it, it's not in the stack when the instance is created by `new_instance`
+  let error = Object::from_raw(env, unsafe { get_global_error(env) }?);
+  let capture_stack_trace: Unknown = error.get_named_property_unchecked("captureStackTrace")?;
+  if capture_stack_trace.get_type()? == ValueType::Function {
+    check_status!(
+      unsafe {
+        sys::napi_call_function(
+          env,
+          error.0.value,
+          capture_stack_trace.0.value,
+          1,
+          &this,
+          ptr::null_mut(),
+        )
+      },
+      "Failed to capture the stack trace of error"
+    )?;
+  }
+  Ok(this)
+}
+
+#[cfg(feature = "napi3")]
+unsafe extern "C" fn remove_error_classes(env: *mut c_void) {
+  let env: sys::napi_env = env.cast();
+  if let Some(classes) = ERROR_CLASSES.with(|cell| cell.borrow_mut(|envs| envs.remove(&env))) {
+    for ctor_ref in classes.into_values() {
+      unsafe { sys::napi_delete_reference(env, ctor_ref) };
+    }
+  }
+}
@@ -6,11 +6,12 @@ use std::{
 };
 
 use crate::{
+  bindgen_runtime::post_finalize,
   bindgen_runtime::{
     sys, Env, FromNapiMutRef, FromNapiRef, FromNapiValue, Result, Status, ToNapiValue, TypeName,
     Unknown, ValidateNapiValue,
   },
-  check_status, check_status_or_throw, Error, JsExternal,
+  check_status, check_status_or_throw, Error, JsError, JsExternal,
 };
 
 #[repr(C)]
@@ -19,6 +20,7 @@ pub struct External<T: 'static> {
   obj: T,
   size_hint: usize,
   pub adjusted_size: i64,
+  finalize: Option<fn(Env, T) -> Result<()>>,
 }
 
 impl<T: 'static> TypeName for &External<T> {
@@ -58,6 +60,7 @@ impl<T: 'static> External<T> {
       obj: value,
       size_hint: 0,
       adjusted_size: 0,
+      finalize: None,
     }
   }
 
@@ -104,6 +107,21 @@ impl<T: 'static> External<T> {
       obj: value,
       size_hint,
       adjusted_size: 0,
+      finalize: None,
+    }
+  }
+
+  /// `finalize` is called with the value when the `External` object is garbage collected
+  ///
+  /// It's run after the GC callback with `node_api_post_finalizer`, so it's able to call into JavaScript.
+  /// Use `External::new` instead if the value only needs to be dropped, it's dropped in the GC callback.
+  pub fn new_with_finalize(value: T, finalize: fn(Env, T) -> Result<()>) -> Self {
+    Self {
+      type_id: TypeId::of::<T>(),
+      obj: value,
+      size_hint: 0,
+      adjusted_size: 0,
+      finalize: Some(finalize),
     }
   }
 
@@ -133,7 +151,7 @@ impl<T: 'static> External<T> {
         sys::napi_create_external(
           env,
           obj_ptr.cast(),
-          Some(crate::raw_finalize::<External<T>>),
+          Some(finalize_external::<T>),
           size_hint_ptr.cast(),
           &mut napi_value,
         )
@@ -166,6 +184,27 @@ impl<T: 'static> External<T> {
   }
 }
 
+unsafe extern "C" fn finalize_external<T: 'static>(
+  env: sys::napi_env,
+  finalize_data: *mut c_void,
+  finalize_hint: *mut c_void,
+) {
+  let external = finalize_data.cast::<External<T>>();
+  let Some(finalize) = (unsafe { (*external).finalize }) else {
+    unsafe { crate::raw_finalize::<External<T>>(env, finalize_data, finalize_hint) };
+    return;
+  };
+  let External { obj, .. } = *unsafe { Box::from_raw(external) };
+  unsafe { crate::adjust_finalized_external_memory(env, finalize_hint) };
+  let finalize = Box::new(move |env: Env| {
+    if let Err(err) = finalize(env, obj) {
+      let e: JsError = err.into();
+      unsafe { e.throw_into(env.raw()) };
+    }
+  });
+  unsafe { post_finalize(env, finalize) };
+}
+
 impl<T: 'static> FromNapiMutRef for External<T> {
   unsafe fn from_napi_mut_ref(
     env: sys::napi_env,
@@ -50,6 +50,12 @@ impl<T> From<T> for FnArgs<T> {
   }
 }
 
+impl JsValuesTupleIntoVec for FnArgs<()> {
+  fn into_vec(self, _env: sys::napi_env) -> Result<Vec<sys::napi_value>> {
+    Ok(vec![])
+  }
+}
+
 macro_rules! impl_tuple_conversion {
   ($($ident:ident),*) => {
     impl<$($ident: ToNapiValue),*> JsValuesTupleIntoVec for FnArgs<($($ident,)*)> {
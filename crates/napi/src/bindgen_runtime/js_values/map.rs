@@ -1,5 +1,6 @@
 use std::collections::{BTreeMap, HashMap};
 use std::hash::{BuildHasher, Hash};
+use std::ptr;
 
 #[cfg(feature = "object_indexmap")]
 use indexmap::IndexMap;
@@ -25,39 +26,7 @@ where
 {
   #[cfg(not(feature = "noop"))]
   unsafe fn to_napi_value(raw_env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
-    let env = Env::from(raw_env);
-    #[cfg_attr(feature = "napi10", allow(unused_mut))]
-    let mut obj = Object::new(&env)?;
-    #[cfg(all(
-      feature = "napi10",
-      feature = "node_version_detect",
-      feature = "dyn-symbols",
-      not(feature = "noop"),
-    ))]
-    let node_version = NODE_VERSION.get().unwrap();
-    for (k, v) in val.into_iter() {
-      #[cfg(all(
-        feature = "napi10",
-        feature = "node_version_detect",
-        feature = "dyn-symbols",
-        not(feature = "noop"),
-      ))]
-      {
-        if node_version.major >= 20 && node_version.minor >= 18 {
-          fast_set_property(raw_env, obj.0.value, k, v)?;
-        } else {
-          obj.set(k.as_ref(), v)?;
-        }
-      }
-      #[cfg(not(all(
-        feature = "napi10",
-        feature = "node_version_detect",
-        feature = "dyn-symbols"
-      )))]
-      obj.set(k.as_ref(), v)?;
-    }
-
-    unsafe { Object::to_napi_value(raw_env, obj) }
+    unsafe { entries_to_object(raw_env, val) }
   }
 
   #[cfg(feature = "noop")]
@@ -105,39 +74,7 @@ where
 {
   #[cfg(not(feature = "noop"))]
   unsafe fn to_napi_value(raw_env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
-    let env = Env::from(raw_env);
-    #[cfg_attr(feature = "napi10", allow(unused_mut))]
-    let mut obj = Object::new(&env)?;
-    #[cfg(all(
-      feature = "napi10",
-      feature = "node_version_detect",
-      feature = "dyn-symbols",
-      not(feature = "noop"),
-    ))]
-    let node_version = NODE_VERSION.get().unwrap();
-    for (k, v) in val.into_iter() {
-      #[cfg(all(
-        feature = "napi10",
-        feature = "node_version_detect",
-        feature = "dyn-symbols",
-        not(feature = "noop"),
-      ))]
-      {
-        if node_version.major >= 20 && node_version.minor >= 18 {
-          fast_set_property(raw_env, obj.0.value, k, v)?;
-        } else {
-          obj.set(k.as_ref(), v)?;
-        }
-      }
-      #[cfg(not(all(
-        feature = "napi10",
-        feature = "node_version_detect",
-        feature = "dyn-symbols"
-      )))]
-      obj.set(k.as_ref(), v)?;
-    }
-
-    unsafe { Object::to_napi_value(raw_env, obj) }
+    unsafe { entries_to_object(raw_env, val) }
   }
 
   #[cfg(feature = "noop")]
@@ -187,39 +124,7 @@ where
 {
   #[cfg(not(feature = "noop"))]
   unsafe fn to_napi_value(raw_env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
-    let env = Env::from(raw_env);
-    #[cfg_attr(feature = "napi10", allow(unused_mut))]
-    let mut obj = Object::new(&env)?;
-    #[cfg(all(
-      feature = "napi10",
-      feature = "node_version_detect",
-      feature = "dyn-symbols",
-      not(feature = "noop"),
-    ))]
-    let node_version = NODE_VERSION.get().unwrap();
-    for (k, v) in val.into_iter() {
-      #[cfg(all(
-        feature = "napi10",
-        feature = "node_version_detect",
-        feature = "dyn-symbols",
-        not(feature = "noop"),
-      ))]
-      {
-        if node_version.major >= 20 && node_version.minor >= 18 {
-          fast_set_property(raw_env, obj.0.value, k, v)?;
-        } else {
-          obj.set(k.as_ref(), v)?;
-        }
-      }
-      #[cfg(not(all(
-        feature = "experimental",
-        feature = "node_version_detect",
-        feature = "dyn-symbols"
-      )))]
-      obj.set(k.as_ref(), v)?;
-    }
-
-    unsafe { Object::to_napi_value(raw_env, obj) }
+    unsafe { entries_to_object(raw_env, val) }
   }
 
   #[cfg(feature = "noop")]
@@ -248,12 +153,264 @@ where
   }
 }
 
-#[cfg(all(
-  feature = "napi10",
-  feature = "node_version_detect",
-  feature = "dyn-symbols",
-  not(feature = "noop"),
-))]
+/// Convert the entries of a Rust map into a plain JavaScript `Object`
+#[cfg(not(feature = "noop"))]
+unsafe fn entries_to_object<K: AsRef<str>, V: ToNapiValue, I: IntoIterator<Item = (K, V)>>(
+  raw_env: sys::napi_env,
+  entries: I,
+) -> Result<sys::napi_value> {
+  let env = Env::from(raw_env);
+  if can_create_object_with_properties(raw_env) {
+    return Ok(Object::from_entries(&env, entries)?.0.value);
+  }
+
+  #[cfg_attr(feature = "napi10", allow(unused_mut))]
+  let mut obj = Object::new(&env)?;
+  #[cfg(feature = "napi10")]
+  let fast = can_create_property_key(raw_env);
+  for (k, v) in entries.into_iter() {
+    #[cfg(feature = "napi10")]
+    if fast {
+      fast_set_property(raw_env, obj.0.value, k, v)?;
+      continue;
+    }
+    obj.set(k.as_ref(), v)?;
+  }
+
+  unsafe { Object::to_napi_value(raw_env, obj) }
+}
+
+/// Convert the Rust map into a JavaScript `Map` rather than a plain `Object`.
+///
+/// Unlike the plain `Object` conversion, the keys are not required to be strings,
+/// so numbers, `BigInt` and objects can be used as keys, and the insertion order is kept.
+///
+/// ```no_run
+/// use std::collections::HashMap;
+///
+/// use napi::bindgen_prelude::*;
+///
+/// #[napi]
+/// fn get_scores() -> JsMap<HashMap<u32, String>> {
+///   JsMap(HashMap::from([(1, "a".to_owned())]))
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JsMap<M>(pub M);
+
+impl<M> JsMap<M> {
+  pub fn into_inner(self) -> M {
+    self.0
+  }
+}
+
+impl<M> From<M> for JsMap<M> {
+  fn from(value: M) -> Self {
+    Self(value)
+  }
+}
+
+impl<M> std::ops::Deref for JsMap<M> {
+  type Target = M;
+
+  fn deref(&self) -> &Self::Target {
+    &self.0
+  }
+}
+
+impl<M> std::ops::DerefMut for JsMap<M> {
+  fn deref_mut(&mut self) -> &mut Self::Target {
+    &mut self.0
+  }
+}
+
+impl<M> TypeName for JsMap<M> {
+  fn type_name() -> &'static str {
+    "Map"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Object
+  }
+}
+
+impl<M> ValidateNapiValue for JsMap<M>
+where
+  JsMap<M>: FromNapiValue,
+{
+  unsafe fn validate(env: sys::napi_env, napi_val: sys::napi_value) -> Result<sys::napi_value> {
+    expect_js_map(env, napi_val)?;
+    Ok(ptr::null_mut())
+  }
+}
+
+/// Create a JavaScript `Map` from the entries, the insertion order is kept
+///
+/// The entries are added by `Map.prototype.set` on an empty `Map`, no intermediate `[key, value]` arrays are created.
+pub(crate) fn create_js_map<K, V, I>(raw_env: sys::napi_env, entries: I) -> Result<sys::napi_value>
+where
+  K: ToNapiValue,
+  V: ToNapiValue,
+  I: IntoIterator<Item = (K, V)>,
+{
+  let env = Env::from(raw_env);
+  let map_class = env
+    .get_global()?
+    .get_named_property_unchecked::<Unknown>("Map")?;
+  let mut map = ptr::null_mut();
+  check_status!(
+    unsafe { sys::napi_new_instance(raw_env, map_class.raw(), 0, ptr::null(), &mut map) },
+    "Failed to create Map"
+  )?;
+  let mut set = ptr::null_mut();
+  check_status!(
+    unsafe { sys::napi_get_named_property(raw_env, map, c"set".as_ptr(), &mut set) },
+    "Failed to get Map.prototype.set"
+  )?;
+  for (key, value) in entries {
+    let args = unsafe {
+      [
+        K::to_napi_value(raw_env, key)?,
+        V::to_napi_value(raw_env, value)?,
+      ]
+    };
+    check_status!(
+      unsafe { sys::napi_call_function(raw_env, map, set, 2, args.as_ptr(), ptr::null_mut()) },
+      "Failed to set Map entry"
+    )?;
+  }
+
+  Ok(map)
+}
+
+/// Check if the value is an instance of the JavaScript `Map`
+pub(crate) fn is_js_map(raw_env: sys::napi_env, napi_val: sys::napi_value) -> Result<bool> {
+  let env = Env::from(raw_env);
+  let map_class = env
+    .get_global()?
+    .get_named_property_unchecked::<Unknown>("Map")?;
+  let mut is_map = false;
+  check_status!(
+    unsafe { sys::napi_instanceof(raw_env, napi_val, map_class.raw(), &mut is_map) },
+    "Failed to check if the value is a Map"
+  )?;
+  Ok(is_map)
+}
+
+fn expect_js_map(env: sys::napi_env, napi_val: sys::napi_value) -> Result<()> {
+  if !is_js_map(env, napi_val)? {
+    return Err(Error::new(Status::InvalidArg, "Expected a Map".to_owned()));
+  }
+  Ok(())
+}
+
+/// Iterate the entries of a JavaScript `Map`
+pub(crate) fn for_each_js_map_entry<K, V, F>(
+  env: sys::napi_env,
+  napi_val: sys::napi_value,
+  mut f: F,
+) -> Result<()>
+where
+  K: FromNapiValue,
+  V: FromNapiValue,
+  F: FnMut(K, V),
+{
+  let obj = unsafe { Object::from_napi_value(env, napi_val)? };
+  let iter_creator: Function<'_, (), Object> = obj.get_named_property("entries")?;
+  let iter = iter_creator.apply(obj, ())?;
+  let next: Function<'_, (), Object> = iter.get_named_property("next")?;
+  while {
+    let o: Object = next.apply(iter, ())?;
+    let done: bool = o.get_named_property("done")?;
+    if !done {
+      let (k, v) = o.get_named_property_unchecked::<(K, V)>("value")?;
+      f(k, v);
+    }
+    !done
+  } {}
+  Ok(())
+}
+
+impl<K, V, S> ToNapiValue for JsMap<HashMap<K, V, S>>
+where
+  K: ToNapiValue,
+  V: ToNapiValue,
+{
+  unsafe fn to_napi_value(raw_env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    create_js_map(raw_env, val.0)
+  }
+}
+
+impl<K, V, S> FromNapiValue for JsMap<HashMap<K, V, S>>
+where
+  K: FromNapiValue + Eq + Hash,
+  V: FromNapiValue,
+  S: Default + BuildHasher,
+{
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    let mut map = HashMap::default();
+    expect_js_map(env, napi_val)?;
+    for_each_js_map_entry(env, napi_val, |k, v| {
+      map.insert(k, v);
+    })?;
+    Ok(JsMap(map))
+  }
+}
+
+impl<K, V> ToNapiValue for JsMap<BTreeMap<K, V>>
+where
+  K: ToNapiValue,
+  V: ToNapiValue,
+{
+  unsafe fn to_napi_value(raw_env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    create_js_map(raw_env, val.0)
+  }
+}
+
+impl<K, V> FromNapiValue for JsMap<BTreeMap<K, V>>
+where
+  K: FromNapiValue + Ord,
+  V: FromNapiValue,
+{
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    let mut map = BTreeMap::default();
+    expect_js_map(env, napi_val)?;
+    for_each_js_map_entry(env, napi_val, |k, v| {
+      map.insert(k, v);
+    })?;
+    Ok(JsMap(map))
+  }
+}
+
+#[cfg(feature = "object_indexmap")]
+impl<K, V, S> ToNapiValue for JsMap<IndexMap<K, V, S>>
+where
+  K: ToNapiValue,
+  V: ToNapiValue,
+{
+  unsafe fn to_napi_value(raw_env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    create_js_map(raw_env, val.0)
+  }
+}
+
+#[cfg(feature = "object_indexmap")]
+impl<K, V, S> FromNapiValue for JsMap<IndexMap<K, V, S>>
+where
+  K: FromNapiValue + Hash + Eq,
+  V: FromNapiValue,
+  S: Default + BuildHasher,
+{
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    let mut map = IndexMap::default();
+    expect_js_map(env, napi_val)?;
+    for_each_js_map_entry(env, napi_val, |k, v| {
+      map.insert(k, v);
+    })?;
+    Ok(JsMap(map))
+  }
+}
+
+#[cfg(all(feature = "napi10", not(feature = "noop")))]
 fn fast_set_property<K: AsRef<str>, V: ToNapiValue>(
   raw_env: sys::napi_env,
   obj: sys::napi_value,
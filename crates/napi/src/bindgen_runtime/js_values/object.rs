@@ -732,6 +732,54 @@ impl Object<'_> {
     ))
   }
 
+  /// create a new `Object` with the properties from the `entries`
+  ///
+  /// The object is created together with all the properties in one call if the Node.js supports it,
+  /// which is faster than creating an empty `Object` and setting the properties one by one.
+  pub fn from_entries<K, V, I>(env: &Env, entries: I) -> Result<Self>
+  where
+    K: AsRef<str>,
+    V: ToNapiValue,
+    I: IntoIterator<Item = (K, V)>,
+  {
+    unsafe { Self::create_from_entries(env, ptr::null_mut(), entries) }
+  }
+
+  /// create a new `Object` with the `prototype` and the properties from the `entries`, see [`Object::from_entries`]
+  pub fn from_entries_with_prototype<K, V, I>(
+    env: &Env,
+    prototype: &Object,
+    entries: I,
+  ) -> Result<Self>
+  where
+    K: AsRef<str>,
+    V: ToNapiValue,
+    I: IntoIterator<Item = (K, V)>,
+  {
+    unsafe { Self::create_from_entries(env, prototype.0.value, entries) }
+  }
+
+  unsafe fn create_from_entries<K, V, I>(
+    env: &Env,
+    prototype: sys::napi_value,
+    entries: I,
+  ) -> Result<Self>
+  where
+    K: AsRef<str>,
+    V: ToNapiValue,
+    I: IntoIterator<Item = (K, V)>,
+  {
+    let entries = entries.into_iter();
+    let mut names = Vec::with_capacity(entries.size_hint().0);
+    let mut values = Vec::with_capacity(entries.size_hint().0);
+    for (k, v) in entries {
+      names.push(unsafe { create_property_key(env.0, k.as_ref()) }?);
+      values.push(unsafe { V::to_napi_value(env.0, v) }?);
+    }
+    let value = unsafe { create_object_from_entries(env.0, prototype, &names, &values) }?;
+    Ok(Self::from_raw(env.0, value))
+  }
+
   /// Get the property value from the `Object`, if the property is not found, `None` is returned
   pub fn get<V: FromNapiValue>(&self, field: &str) -> Result<Option<V>> {
     unsafe {
@@ -742,6 +790,34 @@ impl Object<'_> {
     }
   }
 
+  #[doc(hidden)]
+  /// Get the property value from the `Object` with the property key interned by [`intern_property_key`]
+  pub fn get_with_property_key<V: FromNapiValue>(&self, field: &'static str) -> Result<Option<V>> {
+    unsafe {
+      let property_key = intern_property_key(self.0.env, field)?;
+      self
+        .get_property_inner(property_key, field)?
+        .map(|v| V::from_napi_value(self.0.env, v))
+        .transpose()
+    }
+  }
+
+  #[doc(hidden)]
+  /// Like [`Object::get_with_property_key`], but the value is converted in a `#[napi(object, collect_errors)]` struct
+  pub fn get_with_property_key_collecting<V: FromNapiValue>(
+    &self,
+    field: &'static str,
+    errors: &mut ValidationErrors,
+  ) -> Result<Option<V>> {
+    unsafe {
+      let property_key = intern_property_key(self.0.env, field)?;
+      self
+        .get_property_inner(property_key, field)?
+        .map(|v| V::from_napi_value_collecting(self.0.env, v, errors))
+        .transpose()
+    }
+  }
+
   fn get_inner(&self, field: &str) -> Result<Option<sys::napi_value>> {
     unsafe {
       let mut property_key = std::ptr::null_mut();
@@ -755,6 +831,16 @@ impl Object<'_> {
         "Failed to create property key with `{field}`"
       )?;
 
+      self.get_property_inner(property_key, field)
+    }
+  }
+
+  fn get_property_inner(
+    &self,
+    property_key: sys::napi_value,
+    field: &str,
+  ) -> Result<Option<sys::napi_value>> {
+    unsafe {
       let mut ret = ptr::null_mut();
 
       check_status!(
@@ -777,6 +863,19 @@ impl Object<'_> {
     unsafe { self.set_inner(field.as_ref(), V::to_napi_value(self.0.env, val)?) }
   }
 
+  #[doc(hidden)]
+  /// Set the property value to the `Object` with the property key interned by [`intern_property_key`]
+  pub fn set_with_property_key<V: ToNapiValue>(
+    &mut self,
+    field: &'static str,
+    val: V,
+  ) -> Result<()> {
+    unsafe {
+      let property_key = intern_property_key(self.0.env, field)?;
+      self.set_property_inner(property_key, field, V::to_napi_value(self.0.env, val)?)
+    }
+  }
+
   unsafe fn set_inner(&mut self, field: &str, napi_val: sys::napi_value) -> Result<()> {
     let mut property_key = std::ptr::null_mut();
     check_status!(
@@ -791,6 +890,15 @@ impl Object<'_> {
       "Failed to create property key with `{field}`"
     )?;
 
+    unsafe { self.set_property_inner(property_key, field, napi_val) }
+  }
+
+  unsafe fn set_property_inner(
+    &mut self,
+    property_key: sys::napi_value,
+    field: &str,
+    napi_val: sys::napi_value,
+  ) -> Result<()> {
     check_status!(
       unsafe { sys::napi_set_property(self.0.env, self.0.value, property_key, napi_val) },
       "Failed to set property with field `{field}`"
@@ -831,6 +939,13 @@ impl Object<'_> {
   }
 }
 
+#[doc(hidden)]
+/// Set the fields of a `#[napi(object)]` struct on an existing `Object`,
+/// it's used by the `#[napi(flatten)]` fields to write into the object of their parent
+pub trait ToNapiObjectFields {
+  fn set_object_fields(obj: &mut Object, val: Self) -> Result<()>;
+}
+
 /// A reference to a JavaScript object.
 ///
 /// You must call the `unref` method to release the reference, or the object under the hood will be leaked forever.
@@ -0,0 +1,118 @@
+use std::ffi::CString;
+use std::ptr;
+
+use crate::{
+  bindgen_prelude::*, check_pending_exception, check_status, sys, Error, Result, Status, ValueType,
+};
+
+/// A handle to a JavaScript object whose methods are called from Rust.
+///
+/// This is what the proxy type generated by `#[napi] trait` wraps: every trait method is forwarded to
+/// the method with the same (camelCase) name on the object.
+///
+/// The object is kept alive by a reference which is released when the `ObjectProxy` is dropped,
+/// so it must be dropped on the JavaScript thread.
+pub struct ObjectProxy {
+  env: sys::napi_env,
+  inner: sys::napi_ref,
+}
+
+impl ObjectProxy {
+  /// Call the method `name` of the underlying object, `this` in the JavaScript method will be the object
+  pub fn call_method<Args: JsValuesTupleIntoVec, Return: FromNapiValue>(
+    &self,
+    name: &str,
+    args: Args,
+  ) -> Result<Return> {
+    let object = unsafe { <&ObjectProxy as ToNapiValue>::to_napi_value(self.env, self) }?;
+    let raw_return = unsafe { call_object_method(self.env, object, name, args) }?;
+    unsafe { Return::from_napi_value(self.env, raw_return) }
+  }
+}
+
+/// Call the method `name` of `object` with `this` bound to the object
+pub(crate) unsafe fn call_object_method<Args: JsValuesTupleIntoVec>(
+  env: sys::napi_env,
+  object: sys::napi_value,
+  name: &str,
+  args: Args,
+) -> Result<sys::napi_value> {
+  let key = CString::new(name)?;
+  let mut method = ptr::null_mut();
+  check_status!(
+    unsafe { sys::napi_get_named_property(env, object, key.as_ptr(), &mut method) },
+    "Failed to get method `{}` of object",
+    name
+  )?;
+  let mut value_type = 0;
+  check_status!(
+    unsafe { sys::napi_typeof(env, method, &mut value_type) },
+    "Failed to get the type of method `{}`",
+    name
+  )?;
+  if ValueType::from(value_type) != ValueType::Function {
+    return Err(Error::new(
+      Status::FunctionExpected,
+      format!("`{name}` is not a function"),
+    ));
+  }
+  let args_ptr = args.into_vec(env)?;
+  let mut raw_return = ptr::null_mut();
+  check_pending_exception!(
+    env,
+    unsafe {
+      sys::napi_call_function(
+        env,
+        object,
+        method,
+        args_ptr.len(),
+        args_ptr.as_ptr(),
+        &mut raw_return,
+      )
+    },
+    "Call method `{}` failed",
+    name
+  )?;
+  Ok(raw_return)
+}
+
+impl Drop for ObjectProxy {
+  fn drop(&mut self) {
+    unsafe { sys::napi_delete_reference(self.env, self.inner) };
+  }
+}
+
+impl TypeName for ObjectProxy {
+  fn type_name() -> &'static str {
+    "ObjectProxy"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Object
+  }
+}
+
+impl ValidateNapiValue for ObjectProxy {}
+
+impl FromNapiValue for ObjectProxy {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    unsafe { Self::validate(env, napi_val) }?;
+    let mut inner = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_create_reference(env, napi_val, 1, &mut inner) },
+      "Failed to create reference"
+    )?;
+    Ok(Self { env, inner })
+  }
+}
+
+impl ToNapiValue for &ObjectProxy {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    let mut result = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_get_reference_value(env, val.inner, &mut result) },
+      "Failed to get reference value"
+    )?;
+    Ok(result)
+  }
+}
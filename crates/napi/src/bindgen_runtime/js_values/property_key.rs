@@ -0,0 +1,152 @@
+#[cfg(feature = "napi3")]
+use std::cell::LazyCell;
+#[cfg(feature = "napi3")]
+use std::collections::HashMap;
+#[cfg(feature = "napi3")]
+use std::ffi::c_void;
+use std::ptr;
+
+#[cfg(feature = "napi3")]
+use rustc_hash::FxBuildHasher;
+
+#[cfg(feature = "napi3")]
+use crate::bindgen_runtime::PersistedPerInstanceHashMap;
+use crate::{check_status, sys, Result};
+
+/// The interned property keys of an env, references can't be created to strings unless the module declares Node-API 10,
+/// so the keys are stored in a JavaScript `Array` and looked up by their index.
+#[cfg(feature = "napi3")]
+struct PropertyKeys {
+  keys: sys::napi_ref,
+  indices: HashMap<Box<str>, u32, FxBuildHasher>,
+}
+
+#[cfg(feature = "napi3")]
+thread_local! {
+  static PROPERTY_KEYS: LazyCell<PersistedPerInstanceHashMap<sys::napi_env, PropertyKeys, FxBuildHasher>> =
+    LazyCell::new(Default::default);
+}
+
+/// Get the JavaScript string for the property key `key`, it's created once per env and then reused.
+///
+/// With `napi10` it's created by `node_api_create_property_key_utf8` when the host supports it,
+/// which makes it an internalized string that is faster to look up properties with.
+/// The keys of an env are released by an env cleanup hook, so they're only interned with `napi3`,
+/// without the hook a new env allocated at the same address would read the stale keys.
+#[doc(hidden)]
+#[cfg(feature = "napi3")]
+pub unsafe fn intern_property_key(env: sys::napi_env, key: &str) -> Result<sys::napi_value> {
+  let cached = PROPERTY_KEYS.with(|cell| {
+    cell.borrow_mut(|envs| {
+      envs
+        .get(&env)
+        .map(|keys| (keys.keys, keys.indices.get(key).copied()))
+    })
+  });
+
+  let mut keys = ptr::null_mut();
+  let mut value = ptr::null_mut();
+  match cached {
+    Some((keys_ref, index)) => {
+      check_status!(
+        unsafe { sys::napi_get_reference_value(env, keys_ref, &mut keys) },
+        "Failed to get the interned property keys"
+      )?;
+      if let Some(index) = index {
+        check_status!(
+          unsafe { sys::napi_get_element(env, keys, index, &mut value) },
+          "Failed to get the interned property key `{}`",
+          key
+        )?;
+        return Ok(value);
+      }
+    }
+    None => {
+      check_status!(
+        unsafe { sys::napi_create_array(env, &mut keys) },
+        "Failed to create the property keys Array"
+      )?;
+      let mut keys_ref = ptr::null_mut();
+      check_status!(
+        unsafe { sys::napi_create_reference(env, keys, 1, &mut keys_ref) },
+        "Failed to create reference for the property keys Array"
+      )?;
+      check_status!(
+        unsafe { sys::napi_add_env_cleanup_hook(env, Some(remove_property_keys), env.cast()) },
+        "Failed to add the cleanup hook of property keys"
+      )?;
+      PROPERTY_KEYS.with(|cell| {
+        cell.borrow_mut(|envs| {
+          envs.insert(
+            env,
+            PropertyKeys {
+              keys: keys_ref,
+              indices: Default::default(),
+            },
+          )
+        })
+      });
+    }
+  }
+
+  value = unsafe { create_property_key(env, key) }?;
+  let index = PROPERTY_KEYS.with(|cell| {
+    cell.borrow_mut(|envs| {
+      let property_keys = envs
+        .get_mut(&env)
+        .expect("Property keys of env must be created");
+      let index = property_keys.indices.len() as u32;
+      property_keys.indices.insert(key.into(), index);
+      index
+    })
+  });
+  check_status!(
+    unsafe { sys::napi_set_element(env, keys, index, value) },
+    "Failed to intern property key `{}`",
+    key
+  )?;
+  Ok(value)
+}
+
+/// Get the JavaScript string for the property key `key`, it's created on every call without `napi3`.
+#[doc(hidden)]
+#[cfg(not(feature = "napi3"))]
+pub unsafe fn intern_property_key(env: sys::napi_env, key: &str) -> Result<sys::napi_value> {
+  unsafe { create_property_key(env, key) }
+}
+
+pub(crate) unsafe fn create_property_key(env: sys::napi_env, key: &str) -> Result<sys::napi_value> {
+  let mut value = ptr::null_mut();
+  #[cfg(feature = "napi10")]
+  if crate::bindgen_runtime::can_create_property_key(env) {
+    check_status!(
+      unsafe {
+        sys::node_api_create_property_key_utf8(
+          env,
+          key.as_ptr().cast(),
+          key.len() as isize,
+          &mut value,
+        )
+      },
+      "Failed to create property key `{}`",
+      key
+    )?;
+    return Ok(value);
+  }
+  check_status!(
+    unsafe {
+      sys::napi_create_string_utf8(env, key.as_ptr().cast(), key.len() as isize, &mut value)
+    },
+    "Failed to create property key `{}`",
+    key
+  )?;
+  Ok(value)
+}
+
+#[cfg(feature = "napi3")]
+unsafe extern "C" fn remove_property_keys(env: *mut c_void) {
+  let env: sys::napi_env = env.cast();
+  if let Some(keys) = PROPERTY_KEYS.with(|cell| cell.borrow_mut(|envs| envs.remove(&env))) {
+    unsafe { sys::napi_delete_reference(env, keys.keys) };
+  }
+}
@@ -615,6 +615,7 @@ impl<T: FromNapiValue + 'static> futures_core::Stream for Reader<T> {
               cause: None,
               maybe_raw: error_ref,
               maybe_env: cx.env.0,
+              extra: None,
             });
             Ok(())
           })?
@@ -1,9 +1,13 @@
-use std::cell::RefCell;
 use std::ffi::c_void;
 use std::marker::PhantomData;
+use std::mem::ManuallyDrop;
+use std::panic::UnwindSafe;
 use std::ptr;
-use std::rc::Rc;
-use std::{cell::Cell, panic::UnwindSafe};
+use std::sync::{
+  atomic::{AtomicBool, AtomicPtr, AtomicU8, Ordering},
+  Arc, Mutex,
+};
+use std::thread::{self, ThreadId};
 
 use crate::{
   async_work,
@@ -51,18 +55,80 @@ impl<T: for<'task> ScopedTask<'task>> AsyncTask<T> {
   }
 }
 
-type AbortCallback = Rc<RefCell<Vec<Box<dyn Fn()>>>>;
+enum AbortCallback {
+  Send(Box<dyn Fn() + Send>),
+  /// Registered by [`AbortSignal::on_abort`] on the JavaScript thread, it's only called and dropped there
+  Local(ThreadId, ManuallyDrop<Box<dyn Fn()>>),
+}
+
+// the `Local` callbacks are never called or dropped on another thread
+unsafe impl Send for AbortCallback {}
+
+impl AbortCallback {
+  fn call(&self) {
+    match self {
+      AbortCallback::Send(cb) => cb(),
+      AbortCallback::Local(_, cb) => cb(),
+    }
+  }
+}
+
+impl Drop for AbortCallback {
+  fn drop(&mut self) {
+    if let AbortCallback::Local(thread, cb) = self {
+      // leaked if the last clone of the signal is dropped on another thread
+      if *thread == thread::current().id() {
+        unsafe { ManuallyDrop::drop(cb) };
+      }
+    }
+  }
+}
 
 /// <https://developer.mozilla.org/zh-CN/docs/Web/API/AbortController>
+///
+/// It can be passed to both `AsyncTask` and `#[napi] async fn`.
+/// In `async fn`, the spawned future will be dropped and the returned `Promise` will be rejected with `AbortError` once the signal is aborted.
+#[derive(Clone)]
 pub struct AbortSignal {
-  raw_work: Rc<Cell<sys::napi_async_work>>,
-  status: Rc<Cell<u8>>,
-  abort: AbortCallback,
+  raw_work: Arc<AtomicPtr<sys::napi_async_work__>>,
+  status: Arc<AtomicU8>,
+  aborted: Arc<AtomicBool>,
+  abort: Arc<Mutex<Vec<AbortCallback>>>,
+  js_thread: ThreadId,
 }
 
 impl AbortSignal {
+  /// The callback will be called in the JavaScript main thread when the signal is aborted
+  ///
+  /// It must be registered on the JavaScript main thread, use [`AbortSignal::on_abort_send`] from other threads.
   pub fn on_abort<F: Fn() + 'static>(&self, cb: F) {
-    self.abort.borrow_mut().push(Box::new(cb));
+    let thread = thread::current().id();
+    assert!(
+      thread == self.js_thread,
+      "AbortSignal::on_abort must be called on the JavaScript thread, use AbortSignal::on_abort_send instead"
+    );
+    self.push_callback(AbortCallback::Local(
+      thread,
+      ManuallyDrop::new(Box::new(cb)),
+    ));
+  }
+
+  /// Like [`AbortSignal::on_abort`], but the callback can be registered from any thread
+  pub fn on_abort_send<F: Fn() + Send + 'static>(&self, cb: F) {
+    self.push_callback(AbortCallback::Send(Box::new(cb)));
+  }
+
+  fn push_callback(&self, cb: AbortCallback) {
+    self
+      .abort
+      .lock()
+      .expect("AbortSignal callbacks lock poisoned")
+      .push(cb);
+  }
+
+  /// Whether the `AbortController` has been aborted
+  pub fn aborted(&self) -> bool {
+    self.aborted.load(Ordering::Acquire)
   }
 }
 
@@ -82,13 +148,13 @@ impl FromNapiValue for AbortSignal {
       },
       PhantomData,
     );
-    let async_work_inner: Rc<Cell<sys::napi_async_work>> = Rc::new(Cell::new(ptr::null_mut()));
-    let task_status = Rc::new(Cell::new(0));
-    let abort_cbs = Rc::new(RefCell::new(vec![]));
+    let aborted = signal.get::<bool>("aborted")?.unwrap_or(false);
     let abort_signal = AbortSignal {
-      raw_work: async_work_inner.clone(),
-      status: task_status.clone(),
-      abort: abort_cbs.clone(),
+      raw_work: Arc::new(AtomicPtr::new(ptr::null_mut())),
+      status: Arc::new(AtomicU8::new(0)),
+      aborted: Arc::new(AtomicBool::new(aborted)),
+      abort: Arc::new(Mutex::new(vec![])),
+      js_thread: thread::current().id(),
     };
     let js_env = Env::from_raw(env);
 
@@ -97,9 +163,9 @@ impl FromNapiValue for AbortSignal {
     let unwrap_status = unsafe { sys::napi_remove_wrap(env, signal.0.value, &mut maybe_stack) };
     if unwrap_status == sys::Status::napi_ok {
       stack = unsafe { Box::from_raw(maybe_stack as *mut AbortSignalStack) };
-      stack.0.push(abort_signal);
+      stack.0.push(abort_signal.clone());
     } else {
-      stack = Box::new(AbortSignalStack(vec![abort_signal]));
+      stack = Box::new(AbortSignalStack(vec![abort_signal.clone()]));
     }
     let mut signal_ref = ptr::null_mut();
     check_status!(
@@ -120,11 +186,7 @@ impl FromNapiValue for AbortSignal {
       js_env.create_function::<(), Unknown>("onabort", on_abort)?,
     )?;
 
-    Ok(AbortSignal {
-      raw_work: async_work_inner,
-      status: task_status,
-      abort: abort_cbs,
-    })
+    Ok(abort_signal)
   }
 }
 
@@ -166,23 +228,34 @@ fn on_abort_impl(
     )?;
     let abort_controller_stack = Box::leak(Box::from_raw(async_task as *mut AbortSignalStack));
     for abort_controller in abort_controller_stack.0.iter() {
-      // call abort callback
-      for cb in abort_controller.abort.borrow().iter() {
-        cb();
+      abort_controller.aborted.store(true, Ordering::Release);
+      // call abort callback, out of the lock so they can register other callbacks
+      let callbacks = std::mem::take(
+        &mut *abort_controller
+          .abort
+          .lock()
+          .expect("AbortSignal callbacks lock poisoned"),
+      );
+      for cb in callbacks.iter() {
+        cb.call();
       }
 
-      // Task Completed, return now
-      if abort_controller.status.get() == 1 {
-        return Ok(ptr::null_mut());
+      // Task Completed, skip it
+      if abort_controller.status.load(Ordering::Relaxed) == 1 {
+        continue;
+      }
+      let raw_async_work = abort_controller.raw_work.load(Ordering::Relaxed);
+      // Not bound to an `AsyncTask`, e.g. passed to `async fn`
+      if raw_async_work.is_null() {
+        continue;
       }
-      let raw_async_work = abort_controller.raw_work.get();
       let status = sys::napi_cancel_async_work(env, raw_async_work);
       // async work is already started, so we can't cancel it
       if status != sys::Status::napi_ok {
-        abort_controller.status.set(0);
+        abort_controller.status.store(0, Ordering::Relaxed);
       } else {
         // abort function must be called from JavaScript main thread, so Relaxed Ordering is ok.
-        abort_controller.status.set(2);
+        abort_controller.status.store(2, Ordering::Relaxed);
       }
     }
     let mut undefined = ptr::null_mut();
@@ -198,7 +271,9 @@ impl<T: for<'task> ScopedTask<'task>> ToNapiValue for AsyncTask<T> {
   unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> crate::Result<sys::napi_value> {
     if let Some(abort_signal) = val.abort_signal {
       let async_promise = async_work::run(env, val.inner, Some(abort_signal.status.clone()))?;
-      abort_signal.raw_work.set(async_promise.napi_async_work);
+      abort_signal
+        .raw_work
+        .store(async_promise.napi_async_work, Ordering::Relaxed);
       Ok(async_promise.promise_object().inner)
     } else {
       let async_promise = async_work::run(env, val.inner, None)?;
@@ -0,0 +1,435 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::ffi::c_void;
+use std::mem;
+use std::ptr;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+
+use crate::{
+  bindgen_prelude::{
+    AbortSignal, AsyncTask, FunctionRef, JsObjectValue, JsValue, Object, ToNapiValue, Unknown,
+  },
+  check_status, sys, Env, Error, JsError, ProgressTask, Result, Task,
+};
+
+/// The channel handed to `ProgressTask::compute` to report progress to JavaScript
+///
+/// The values are delivered in the JavaScript thread. If it falls behind, a value which is not delivered yet
+/// is replaced by the newer one, so JavaScript always receives the latest progress.
+pub struct TaskProgress<P> {
+  state: Arc<Mutex<ProgressState<P>>>,
+  tsfn: sys::napi_threadsafe_function,
+}
+
+unsafe impl<P: Send> Send for TaskProgress<P> {}
+unsafe impl<P: Send> Sync for TaskProgress<P> {}
+
+impl<P> Clone for TaskProgress<P> {
+  fn clone(&self) -> Self {
+    Self {
+      state: self.state.clone(),
+      tsfn: self.tsfn,
+    }
+  }
+}
+
+struct ProgressState<P> {
+  // The latest value not delivered yet, its delivery is scheduled if it's `Some`
+  pending: Option<P>,
+  // The task is finished and the threadsafe function is released
+  closed: bool,
+}
+
+impl<P: Send> TaskProgress<P> {
+  /// Report `value` to JavaScript, it's ignored once the task is finished
+  pub fn report(&self, value: P) {
+    let mut state = self.state.lock().expect("TaskProgress state lock poisoned");
+    if state.closed {
+      return;
+    }
+    if state.pending.replace(value).is_none() {
+      unsafe {
+        sys::napi_call_threadsafe_function(
+          self.tsfn,
+          ptr::null_mut(),
+          sys::ThreadsafeFunctionCallMode::nonblocking,
+        )
+      };
+    }
+  }
+}
+
+/// Run a `ProgressTask` in the libuv thread pool, like `AsyncTask` does for a `Task`
+///
+/// The returned `Promise` has a `progress` property, an async iterable of the reported values which ends when the task is finished.
+pub struct AsyncProgressTask<T: ProgressTask> {
+  inner: T,
+  abort_signal: Option<AbortSignal>,
+  callback: Option<FunctionRef<T::Progress, ()>>,
+}
+
+impl<T: ProgressTask> AsyncProgressTask<T> {
+  pub fn new(task: T) -> Self {
+    Self {
+      inner: task,
+      abort_signal: None,
+      callback: None,
+    }
+  }
+
+  pub fn with_signal(task: T, signal: AbortSignal) -> Self {
+    Self {
+      inner: task,
+      abort_signal: Some(signal),
+      callback: None,
+    }
+  }
+
+  pub fn with_optional_signal(task: T, signal: Option<AbortSignal>) -> Self {
+    Self {
+      inner: task,
+      abort_signal: signal,
+      callback: None,
+    }
+  }
+
+  /// Call `callback` with every delivered progress value
+  pub fn on_progress(mut self, callback: FunctionRef<T::Progress, ()>) -> Self {
+    self.callback = Some(callback);
+    self
+  }
+}
+
+/// The receivers of the progress in the JavaScript thread
+struct ProgressListeners<P: ToNapiValue> {
+  env: sys::napi_env,
+  callback: Option<FunctionRef<P, ()>>,
+  // `next()` calls of the `progress` async iterator waiting for a value
+  waiting: VecDeque<sys::napi_deferred>,
+  // An object keeping the latest value not read by the `progress` async iterator in its `value` property,
+  // a primitive value can't be referenced directly
+  unread: sys::napi_ref,
+  has_unread: bool,
+  done: bool,
+}
+
+impl<P: ToNapiValue> ProgressListeners<P> {
+  fn new(env: sys::napi_env, callback: Option<FunctionRef<P, ()>>) -> Result<Self> {
+    let holder = Object::new(&Env::from_raw(env))?;
+    let mut unread = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_create_reference(env, holder.raw(), 1, &mut unread) },
+      "Failed to create the reference of task progress"
+    )?;
+    Ok(Self {
+      env,
+      callback,
+      waiting: VecDeque::new(),
+      unread,
+      has_unread: false,
+      done: false,
+    })
+  }
+
+  fn unread_holder(&self) -> Result<Object<'static>> {
+    let mut holder = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_get_reference_value(self.env, self.unread, &mut holder) },
+      "Failed to get the unread task progress"
+    )?;
+    Ok(Object::from_raw(self.env, holder))
+  }
+}
+
+impl<P: ToNapiValue> Drop for ProgressListeners<P> {
+  fn drop(&mut self) {
+    let status = unsafe { sys::napi_delete_reference(self.env, self.unread) };
+    debug_assert_eq!(
+      status,
+      sys::Status::napi_ok,
+      "Delete the reference of task progress failed"
+    );
+  }
+}
+
+type Listeners<P> = Rc<RefCell<ProgressListeners<P>>>;
+
+/// The context of the threadsafe function
+struct ProgressContext<P: ToNapiValue> {
+  state: Arc<Mutex<ProgressState<P>>>,
+  listeners: Listeners<P>,
+}
+
+/// The `Task` run by `AsyncTask`, it closes the progress channel when the task is finished
+struct ProgressTaskRunner<T: ProgressTask> {
+  task: T,
+  progress: TaskProgress<T::Progress>,
+  listeners: JsThreadOnly<Listeners<T::Progress>>,
+}
+
+// `Rc` is only cloned and dropped in the JavaScript thread, `compute` doesn't touch it
+struct JsThreadOnly<T>(T);
+
+unsafe impl<T> Send for JsThreadOnly<T> {}
+
+impl<T: ProgressTask> Task for ProgressTaskRunner<T> {
+  type Output = T::Output;
+  type JsValue = T::JsValue;
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    self.task.compute(&self.progress)
+  }
+
+  fn resolve(&mut self, env: Env, output: Self::Output) -> Result<Self::JsValue> {
+    self.task.resolve(env, output)
+  }
+
+  fn reject(&mut self, env: Env, err: Error) -> Result<Self::JsValue> {
+    self.task.reject(env, err)
+  }
+
+  fn finally(self, env: Env) -> Result<()> {
+    unsafe { close(env.raw(), &self.progress, &self.listeners.0) }?;
+    self.task.finally(env)
+  }
+}
+
+impl<T: ProgressTask> ToNapiValue for AsyncProgressTask<T> {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    let state = Arc::new(Mutex::new(ProgressState {
+      pending: None,
+      closed: false,
+    }));
+    let listeners = Rc::new(RefCell::new(ProgressListeners::new(env, val.callback)?));
+    let iterator = create_progress_iterator(env, listeners.clone())?;
+    let tsfn = unsafe { create_progress_tsfn(env, state.clone(), listeners.clone()) }?;
+    let runner = ProgressTaskRunner {
+      task: val.inner,
+      progress: TaskProgress { state, tsfn },
+      listeners: JsThreadOnly(listeners),
+    };
+    let promise = unsafe {
+      AsyncTask::to_napi_value(
+        env,
+        AsyncTask::with_optional_signal(runner, val.abort_signal),
+      )
+    }?;
+    Object::from_raw(env, promise).set_named_property("progress", iterator)?;
+    Ok(promise)
+  }
+}
+
+unsafe fn create_progress_tsfn<P: ToNapiValue>(
+  env: sys::napi_env,
+  state: Arc<Mutex<ProgressState<P>>>,
+  listeners: Listeners<P>,
+) -> Result<sys::napi_threadsafe_function> {
+  let context = Box::into_raw(Box::new(ProgressContext { state, listeners }));
+  let resource_name = unsafe { <&str>::to_napi_value(env, "napi_rs_task_progress") }?;
+  let mut tsfn = ptr::null_mut();
+  check_status!(
+    unsafe {
+      sys::napi_create_threadsafe_function(
+        env,
+        ptr::null_mut(),
+        ptr::null_mut(),
+        resource_name,
+        0,
+        1,
+        context.cast(),
+        Some(finalize_progress_context::<P>),
+        context.cast(),
+        Some(deliver_pending::<P>),
+        &mut tsfn,
+      )
+    },
+    "Failed to create the threadsafe function of task progress"
+  )?;
+  // The async work keeps the event loop alive, the progress doesn't need to
+  check_status!(
+    unsafe { sys::napi_unref_threadsafe_function(env, tsfn) },
+    "Failed to unref the threadsafe function of task progress"
+  )?;
+  Ok(tsfn)
+}
+
+fn create_progress_iterator<P: ToNapiValue + 'static>(
+  env: sys::napi_env,
+  listeners: Listeners<P>,
+) -> Result<Object<'static>> {
+  let js_env = Env::from_raw(env);
+  let mut iterator = Object::new(&js_env)?;
+  let next = js_env.create_function_from_closure::<(), Unknown, _>("next", move |ctx| {
+    let env = ctx.env.raw();
+    let mut deferred = ptr::null_mut();
+    let mut promise = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_create_promise(env, &mut deferred, &mut promise) },
+      "Failed to create the Promise of task progress"
+    )?;
+    let mut listeners = listeners.borrow_mut();
+    if listeners.has_unread {
+      listeners.has_unread = false;
+      let mut holder = listeners.unread_holder()?;
+      let value: Unknown = holder.get_named_property_unchecked("value")?;
+      holder.set_named_property("value", ())?;
+      unsafe { resolve_iterator_result(env, deferred, value.raw(), false) }?;
+    } else if listeners.done {
+      unsafe { resolve_iterator_result(env, deferred, ptr::null_mut(), true) }?;
+    } else {
+      listeners.waiting.push_back(deferred);
+    }
+    Ok(unsafe { Unknown::from_raw_unchecked(env, promise) })
+  })?;
+  iterator.set_named_property("next", next)?;
+
+  let async_iterator = js_env
+    .create_function_from_closure::<(), Unknown, _>("[Symbol.asyncIterator]", |ctx| {
+      ctx.this::<Unknown>()
+    })?;
+  let symbol: Object = js_env
+    .get_global()?
+    .get_named_property_unchecked("Symbol")?;
+  let async_iterator_symbol: Unknown = symbol.get_named_property_unchecked("asyncIterator")?;
+  iterator.set_property(async_iterator_symbol, async_iterator)?;
+  Ok(iterator)
+}
+
+/// Resolve `deferred` with `{ value, done }`, `value` is `undefined` if it's null
+unsafe fn resolve_iterator_result(
+  env: sys::napi_env,
+  deferred: sys::napi_deferred,
+  value: sys::napi_value,
+  done: bool,
+) -> Result<()> {
+  let mut result = Object::new(&Env::from_raw(env))?;
+  if !value.is_null() {
+    result.set_named_property("value", unsafe { Unknown::from_raw_unchecked(env, value) })?;
+  }
+  result.set_named_property("done", done)?;
+  check_status!(
+    unsafe { sys::napi_resolve_deferred(env, deferred, result.raw()) },
+    "Failed to resolve the Promise of task progress"
+  )
+}
+
+/// Deliver `value` to the first waiting `next()` of the async iterator, or keep it as the unread value, and call the callback
+unsafe fn deliver<P: ToNapiValue>(
+  env: sys::napi_env,
+  listeners: &Listeners<P>,
+  value: P,
+) -> Result<()> {
+  let value = unsafe { P::to_napi_value(env, value) }?;
+  let (deferred, callback) = {
+    let mut listeners = listeners.borrow_mut();
+    let deferred = listeners.waiting.pop_front();
+    if deferred.is_none() {
+      let value = unsafe { Unknown::from_raw_unchecked(env, value) };
+      listeners
+        .unread_holder()?
+        .set_named_property("value", value)?;
+      listeners.has_unread = true;
+    }
+    let callback = match &listeners.callback {
+      Some(callback) => {
+        let mut callback_value = ptr::null_mut();
+        check_status!(
+          unsafe { sys::napi_get_reference_value(env, callback.inner, &mut callback_value) },
+          "Failed to get the task progress callback"
+        )?;
+        Some(callback_value)
+      }
+      None => None,
+    };
+    (deferred, callback)
+  };
+  // the listeners are not borrowed any more, the callback may call `next()`
+  if let Some(deferred) = deferred {
+    unsafe { resolve_iterator_result(env, deferred, value, false) }?;
+  }
+  if let Some(callback) = callback {
+    let mut undefined = ptr::null_mut();
+    check_status!(unsafe { sys::napi_get_undefined(env, &mut undefined) })?;
+    let status =
+      unsafe { sys::napi_call_function(env, undefined, callback, 1, &value, ptr::null_mut()) };
+    if status == sys::Status::napi_pending_exception {
+      let mut exception = ptr::null_mut();
+      unsafe { sys::napi_get_and_clear_last_exception(env, &mut exception) };
+      unsafe { sys::napi_fatal_exception(env, exception) };
+    } else {
+      check_status!(status, "Failed to call the task progress callback")?;
+    }
+  }
+  Ok(())
+}
+
+/// Deliver the last value, end the async iterator and release the threadsafe function
+unsafe fn close<P: ToNapiValue>(
+  env: sys::napi_env,
+  progress: &TaskProgress<P>,
+  listeners: &Listeners<P>,
+) -> Result<()> {
+  let pending = {
+    let mut state = progress
+      .state
+      .lock()
+      .expect("TaskProgress state lock poisoned");
+    state.closed = true;
+    state.pending.take()
+  };
+  check_status!(
+    unsafe {
+      sys::napi_release_threadsafe_function(
+        progress.tsfn,
+        sys::ThreadsafeFunctionReleaseMode::release,
+      )
+    },
+    "Failed to release the threadsafe function of task progress"
+  )?;
+  if let Some(value) = pending {
+    unsafe { deliver(env, listeners, value) }?;
+  }
+  let waiting = {
+    let mut listeners = listeners.borrow_mut();
+    listeners.done = true;
+    listeners.callback = None;
+    mem::take(&mut listeners.waiting)
+  };
+  for deferred in waiting {
+    unsafe { resolve_iterator_result(env, deferred, ptr::null_mut(), true) }?;
+  }
+  Ok(())
+}
+
+unsafe extern "C" fn deliver_pending<P: ToNapiValue>(
+  env: sys::napi_env,
+  _js_callback: sys::napi_value,
+  context: *mut c_void,
+  _data: *mut c_void,
+) {
+  // the threadsafe function is being torn down
+  if env.is_null() {
+    return;
+  }
+  let context = unsafe { &*(context as *const ProgressContext<P>) };
+  let pending = context
+    .state
+    .lock()
+    .expect("TaskProgress state lock poisoned")
+    .pending
+    .take();
+  if let Some(value) = pending {
+    if let Err(err) = unsafe { deliver(env, &context.listeners, value) } {
+      unsafe { sys::napi_fatal_exception(env, JsError::from(err).into_value(env)) };
+    }
+  }
+}
+
+unsafe extern "C" fn finalize_progress_context<P: ToNapiValue>(
+  _env: sys::napi_env,
+  finalize_data: *mut c_void,
+  _finalize_hint: *mut c_void,
+) {
+  drop(unsafe { Box::from_raw(finalize_data as *mut ProgressContext<P>) });
+}
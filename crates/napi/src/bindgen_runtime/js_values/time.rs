@@ -0,0 +1,259 @@
+//! `std::time` conversions
+//!
+//! - `Duration` is converted to a `number` of milliseconds, with the fractional part kept.
+//! - `DurationNanos` is converted to a `bigint` of nanoseconds, it's lossless.
+//! - `SystemTime` and `Instant` are converted to a `Date`, `Instant` is mapped through the current time.
+
+use std::ptr;
+use std::time::Duration;
+#[cfg(feature = "napi5")]
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use crate::{bindgen_prelude::*, check_status, sys, ValueType};
+
+const MILLIS_PER_SEC: f64 = 1000.0;
+
+impl TypeName for Duration {
+  fn type_name() -> &'static str {
+    "Duration"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Number
+  }
+}
+
+impl ValidateNapiValue for Duration {}
+
+impl ToNapiValue for Duration {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Duration) -> Result<sys::napi_value> {
+    let mut ptr = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_create_double(env, val.as_secs_f64() * MILLIS_PER_SEC, &mut ptr) },
+      "Failed to convert rust type `Duration` into napi value",
+    )?;
+    Ok(ptr)
+  }
+}
+
+impl FromNapiValue for Duration {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    let mut millis = 0.0;
+    check_status!(
+      unsafe { sys::napi_get_value_double(env, napi_val, &mut millis) },
+      "Failed to convert napi value into rust type `Duration`",
+    )?;
+    Duration::try_from_secs_f64(millis / MILLIS_PER_SEC).map_err(|err| {
+      Error::new(
+        Status::InvalidArg,
+        format!("Failed to convert {millis} milliseconds into rust type `Duration`, {err}"),
+      )
+    })
+  }
+}
+
+/// A `Duration` converted from/to a `bigint` of nanoseconds.
+///
+/// Use it instead of `Duration` when the sub-millisecond precision matters,
+/// `Duration` is converted to a `number` of milliseconds which may lose precision.
+#[cfg(feature = "napi6")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DurationNanos(pub Duration);
+
+#[cfg(feature = "napi6")]
+impl From<Duration> for DurationNanos {
+  fn from(value: Duration) -> Self {
+    Self(value)
+  }
+}
+
+#[cfg(feature = "napi6")]
+impl From<DurationNanos> for Duration {
+  fn from(value: DurationNanos) -> Self {
+    value.0
+  }
+}
+
+#[cfg(feature = "napi6")]
+impl TypeName for DurationNanos {
+  fn type_name() -> &'static str {
+    "DurationNanos"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::BigInt
+  }
+}
+
+#[cfg(feature = "napi6")]
+impl ValidateNapiValue for DurationNanos {}
+
+#[cfg(feature = "napi6")]
+impl ToNapiValue for DurationNanos {
+  unsafe fn to_napi_value(env: sys::napi_env, val: DurationNanos) -> Result<sys::napi_value> {
+    unsafe { u128::to_napi_value(env, val.0.as_nanos()) }
+  }
+}
+
+#[cfg(feature = "napi6")]
+impl FromNapiValue for DurationNanos {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    let (signed, nanos, lossless) = unsafe { BigInt::from_napi_value(env, napi_val) }?.get_u128();
+    if signed && nanos != 0 {
+      return Err(Error::new(
+        Status::InvalidArg,
+        "Failed to convert a negative BigInt into rust type `Duration`".to_owned(),
+      ));
+    }
+    let secs = (nanos / 1_000_000_000).try_into().ok().filter(|_| lossless);
+    match secs {
+      Some(secs) => Ok(Self(Duration::new(secs, (nanos % 1_000_000_000) as u32))),
+      None => Err(Error::new(
+        Status::InvalidArg,
+        "BigInt is too large to convert into rust type `Duration`".to_owned(),
+      )),
+    }
+  }
+}
+
+#[cfg(feature = "napi5")]
+unsafe fn validate_date(env: sys::napi_env, napi_val: sys::napi_value) -> Result<sys::napi_value> {
+  let mut is_date = false;
+  check_status!(unsafe { sys::napi_is_date(env, napi_val, &mut is_date) })?;
+  if !is_date {
+    return Err(Error::new(
+      Status::InvalidArg,
+      "Expected a Date object".to_owned(),
+    ));
+  }
+
+  Ok(ptr::null_mut())
+}
+
+#[cfg(feature = "napi5")]
+unsafe fn create_date(
+  env: sys::napi_env,
+  time: SystemTime,
+  type_name: &str,
+) -> Result<sys::napi_value> {
+  let millis_since_epoch = match time.duration_since(UNIX_EPOCH) {
+    Ok(since_epoch) => since_epoch.as_secs_f64() * MILLIS_PER_SEC,
+    Err(err) => -err.duration().as_secs_f64() * MILLIS_PER_SEC,
+  };
+  let mut ptr = ptr::null_mut();
+  check_status!(
+    unsafe { sys::napi_create_date(env, millis_since_epoch, &mut ptr) },
+    "Failed to convert rust type `{}` into napi value",
+    type_name,
+  )?;
+  Ok(ptr)
+}
+
+#[cfg(feature = "napi5")]
+unsafe fn get_date_value(
+  env: sys::napi_env,
+  napi_val: sys::napi_value,
+  type_name: &str,
+) -> Result<SystemTime> {
+  let mut millis_since_epoch = 0.0;
+  check_status!(
+    unsafe { sys::napi_get_date_value(env, napi_val, &mut millis_since_epoch) },
+    "Failed to convert napi value into rust type `{}`",
+    type_name,
+  )?;
+  let since_epoch = Duration::try_from_secs_f64(millis_since_epoch.abs() / MILLIS_PER_SEC).ok();
+  let time = if millis_since_epoch >= 0.0 {
+    since_epoch.and_then(|d| UNIX_EPOCH.checked_add(d))
+  } else {
+    since_epoch.and_then(|d| UNIX_EPOCH.checked_sub(d))
+  };
+  time.ok_or_else(|| Error::new(Status::DateExpected, "Found invalid date".to_owned()))
+}
+
+#[cfg(feature = "napi5")]
+impl TypeName for SystemTime {
+  fn type_name() -> &'static str {
+    "SystemTime"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Object
+  }
+}
+
+#[cfg(feature = "napi5")]
+impl ValidateNapiValue for SystemTime {
+  unsafe fn validate(env: sys::napi_env, napi_val: sys::napi_value) -> Result<sys::napi_value> {
+    unsafe { validate_date(env, napi_val) }
+  }
+}
+
+#[cfg(feature = "napi5")]
+impl ToNapiValue for SystemTime {
+  unsafe fn to_napi_value(env: sys::napi_env, val: SystemTime) -> Result<sys::napi_value> {
+    unsafe { create_date(env, val, "SystemTime") }
+  }
+}
+
+#[cfg(feature = "napi5")]
+impl FromNapiValue for SystemTime {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    unsafe { get_date_value(env, napi_val, "SystemTime") }
+  }
+}
+
+#[cfg(feature = "napi5")]
+impl TypeName for Instant {
+  fn type_name() -> &'static str {
+    "Instant"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Object
+  }
+}
+
+#[cfg(feature = "napi5")]
+impl ValidateNapiValue for Instant {
+  unsafe fn validate(env: sys::napi_env, napi_val: sys::napi_value) -> Result<sys::napi_value> {
+    unsafe { validate_date(env, napi_val) }
+  }
+}
+
+/// `Instant` is opaque, it's converted by its distance from `Instant::now()` applied to `SystemTime::now()`
+#[cfg(feature = "napi5")]
+impl ToNapiValue for Instant {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Instant) -> Result<sys::napi_value> {
+    let (now, system_now) = (Instant::now(), SystemTime::now());
+    let time = if val <= now {
+      system_now.checked_sub(now - val)
+    } else {
+      system_now.checked_add(val - now)
+    };
+    let time = time.ok_or_else(|| {
+      Error::new(
+        Status::InvalidArg,
+        "Failed to convert rust type `Instant` into napi value, it's out of range".to_owned(),
+      )
+    })?;
+    unsafe { create_date(env, time, "Instant") }
+  }
+}
+
+#[cfg(feature = "napi5")]
+impl FromNapiValue for Instant {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    let time = unsafe { get_date_value(env, napi_val, "Instant") }?;
+    let (now, system_now) = (Instant::now(), SystemTime::now());
+    let instant = match system_now.duration_since(time) {
+      Ok(elapsed) => now.checked_sub(elapsed),
+      Err(err) => now.checked_add(err.duration()),
+    };
+    instant.ok_or_else(|| {
+      Error::new(
+        Status::InvalidArg,
+        "Date is out of the range of rust type `Instant`".to_owned(),
+      )
+    })
+  }
+}
@@ -0,0 +1,130 @@
+use std::fmt::Write;
+use std::ptr;
+
+use crate::{bindgen_prelude::*, check_status, sys};
+
+/// A problem found when converting a JavaScript value in `#[napi(object, collect_errors)]`
+///
+/// The `errors` property of the thrown `TypeError` is an array of them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+  /// JSON pointer of the invalid value, like `/servers/2/port`
+  pub path: String,
+  pub message: String,
+}
+
+impl ToNapiValue for ValidationError {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    let obj = Object::from_entries(
+      &Env::from_raw(env),
+      [("path", val.path), ("message", val.message)],
+    )?;
+    Ok(obj.0.value)
+  }
+}
+
+/// The errors found by the conversion of a `#[napi(object, collect_errors)]` struct
+///
+/// It's passed to [`FromNapiValue::from_napi_value_collecting`] explicitly,
+/// so conversions running in a nested or re-entrant call never record into it.
+#[doc(hidden)]
+#[derive(Default)]
+pub struct ValidationErrors {
+  path: Vec<String>,
+  errors: Vec<ValidationError>,
+}
+
+impl ValidationErrors {
+  /// Run the conversion of the value at `segment` of the current path
+  ///
+  /// If the conversion fails and the error was not recorded by a nested conversion,
+  /// it's recorded with the path of the value.
+  pub fn validate_path<T, S: ToString>(
+    &mut self,
+    segment: S,
+    convert: impl FnOnce(&mut Self) -> Result<T>,
+  ) -> Result<T> {
+    self.path.push(escape_pointer_segment(&segment.to_string()));
+    let result = self.validate_flattened(convert);
+    self.path.pop();
+    result
+  }
+
+  /// Like [`ValidationErrors::validate_path`], but the value is a `#[napi(flatten)]` field which is at the path of its parent
+  pub fn validate_flattened<T>(
+    &mut self,
+    convert: impl FnOnce(&mut Self) -> Result<T>,
+  ) -> Result<T> {
+    let recorded = self.errors.len();
+    let result = convert(self);
+    if let Err(err) = &result {
+      if self.errors.len() == recorded {
+        let path = self.path.iter().fold(String::new(), |mut path, segment| {
+          let _ = write!(path, "/{segment}");
+          path
+        });
+        self.errors.push(ValidationError {
+          path,
+          message: err.reason.clone(),
+        });
+      }
+    }
+    result
+  }
+}
+
+/// Run the conversion of a `#[napi(object, collect_errors)]` struct from its [`FromNapiValue::from_napi_value`]
+///
+/// The errors recorded into the [`ValidationErrors`] are turned into a single `TypeError`
+/// with an `errors` property listing every [`ValidationError`].
+/// Nested `collect_errors` structs are converted by [`FromNapiValue::from_napi_value_collecting`] with the same collector,
+/// so only the outermost struct creates the `TypeError`.
+#[doc(hidden)]
+pub unsafe fn collect_validation_errors<T>(
+  env: sys::napi_env,
+  type_name: &str,
+  convert: impl FnOnce(&mut ValidationErrors) -> Result<T>,
+) -> Result<T> {
+  let mut errors = ValidationErrors::default();
+  match convert(&mut errors) {
+    Err(_) if !errors.errors.is_empty() => {
+      Err(unsafe { create_validation_error(env, type_name, errors.errors) }?)
+    }
+    result => result,
+  }
+}
+
+unsafe fn create_validation_error(
+  env: sys::napi_env,
+  type_name: &str,
+  errors: Vec<ValidationError>,
+) -> Result<Error> {
+  let mut reason = format!(
+    "Found {} invalid value{} when converting into `{type_name}`",
+    errors.len(),
+    if errors.len() == 1 { "" } else { "s" }
+  );
+  for error in &errors {
+    let _ = write!(reason, "\n  {}: {}", error.path, error.message);
+  }
+
+  let code = unsafe { String::to_napi_value(env, Status::InvalidArg.as_ref().to_owned()) }?;
+  let message = unsafe { String::to_napi_value(env, reason.clone()) }?;
+  let mut type_error = ptr::null_mut();
+  check_status!(
+    unsafe { sys::napi_create_type_error(env, code, message, &mut type_error) },
+    "Failed to create TypeError of the validation errors"
+  )?;
+  let mut error_object = Object::from_raw(env, type_error);
+  error_object.set_named_property("errors", errors)?;
+
+  let mut error = Error::from(unsafe { Unknown::from_raw_unchecked(env, type_error) });
+  error.status = Status::InvalidArg;
+  error.reason = reason;
+  Ok(error)
+}
+
+/// Escape `~` and `/` in the path segment, <https://datatracker.ietf.org/doc/html/rfc6901#section-3>
+fn escape_pointer_segment(segment: &str) -> String {
+  segment.replace('~', "~0").replace('/', "~1")
+}
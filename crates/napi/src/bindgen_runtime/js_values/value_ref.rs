@@ -124,13 +124,17 @@ impl<T: 'static> FromNapiValue for Reference<T> {
     env: crate::sys::napi_env,
     napi_val: crate::sys::napi_value,
   ) -> Result<Self> {
+    // the `T` of an instance of a class declared with `#[napi(extends = T)]` is a field of the wrapped value
+    let instance = unsafe { crate::bindgen_runtime::unwrap_class_instance::<T>(env, napi_val) }?;
     let mut value = ptr::null_mut();
     check_status!(
       unsafe { crate::sys::napi_unwrap(env, napi_val, &mut value) },
       "Unwrap value [{}] from class Reference failed",
       std::any::type_name::<T>(),
     )?;
-    unsafe { Reference::from_value_ptr(value.cast(), env) }
+    let mut reference = unsafe { Reference::<T>::from_value_ptr(value.cast(), env) }?;
+    reference.raw = instance.cast();
+    Ok(reference)
   }
 }
 
@@ -163,7 +167,7 @@ impl<T: 'static> Reference<T> {
     #[allow(unused_variables)] env: Env,
     f: F,
   ) -> Result<SharedReference<T, S>> {
-    let s = f(Box::leak(unsafe { Box::from_raw(self.raw) }))?;
+    let s = f(unsafe { &mut *self.raw })?;
     let s_ptr = Box::into_raw(Box::new(s));
     let prev_drop_fn = unsafe { Box::from_raw(self.finalize_callbacks.get()) };
     let drop_fn = Box::new(move || {
@@ -182,13 +186,13 @@ impl<T: 'static> Deref for Reference<T> {
   type Target = T;
 
   fn deref(&self) -> &Self::Target {
-    unsafe { Box::leak(Box::from_raw(self.raw)) }
+    unsafe { &*self.raw }
   }
 }
 
 impl<T: 'static> DerefMut for Reference<T> {
   fn deref_mut(&mut self) -> &mut Self::Target {
-    unsafe { Box::leak(Box::from_raw(self.raw)) }
+    unsafe { &mut *self.raw }
   }
 }
 
@@ -251,7 +255,7 @@ impl<T: 'static> WeakReference<T> {
     if Weak::strong_count(&self.finalize_callbacks) == 0 {
       None
     } else {
-      Some(unsafe { Box::leak(Box::from_raw(self.raw)) })
+      Some(unsafe { &*self.raw })
     }
   }
 
@@ -259,7 +263,7 @@ impl<T: 'static> WeakReference<T> {
     if Weak::strong_count(&self.finalize_callbacks) == 0 {
       None
     } else {
-      Some(unsafe { Box::leak(Box::from_raw(self.raw)) })
+      Some(unsafe { &mut *self.raw })
     }
   }
 }
@@ -292,7 +296,7 @@ impl<T: 'static, S: 'static> SharedReference<T, S> {
     #[allow(unused_variables)] env: Env,
     f: F,
   ) -> Result<SharedReference<T, U>> {
-    let s = f(Box::leak(unsafe { Box::from_raw(self.raw) }))?;
+    let s = f(unsafe { &mut *self.raw })?;
     let raw = Box::into_raw(Box::new(s));
     let prev_drop_fn = unsafe { Box::from_raw(self.owner.finalize_callbacks.get()) };
     let drop_fn = Box::new(move || {
@@ -322,12 +326,12 @@ impl<T: 'static, S: 'static> Deref for SharedReference<T, S> {
   type Target = S;
 
   fn deref(&self) -> &Self::Target {
-    unsafe { Box::leak(Box::from_raw(self.raw)) }
+    unsafe { &*self.raw }
   }
 }
 
 impl<T: 'static, S: 'static> DerefMut for SharedReference<T, S> {
   fn deref_mut(&mut self) -> &mut Self::Target {
-    unsafe { Box::leak(Box::from_raw(self.raw)) }
+    unsafe { &mut *self.raw }
   }
 }
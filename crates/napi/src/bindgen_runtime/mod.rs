@@ -4,6 +4,7 @@ use std::rc::Rc;
 pub use callback_info::*;
 pub use ctor::ctor;
 pub use env::*;
+pub use error::*;
 pub use iterator::Generator;
 pub use js_values::*;
 pub use module_register::*;
@@ -22,7 +23,18 @@ pub mod iterator;
 mod js_values;
 mod module_register;
 
-pub trait ObjectFinalize: Sized {
+/// When the `finalize` of a class is run, it's implemented by `#[napi]` for every class
+///
+/// With `#[napi(custom_finalize = "post")]` the `finalize` calls into JavaScript,
+/// it's run after the GC callback with `node_api_post_finalizer`.
+/// Otherwise it's run in the GC callback, where it can only drop Rust data.
+/// `finalize` is always run in the GC callback if the `experimental` feature is not enabled.
+#[doc(hidden)]
+pub trait ObjectFinalizeMode {
+  const POST_FINALIZE: bool;
+}
+
+pub trait ObjectFinalize: Sized + ObjectFinalizeMode {
   #[allow(unused)]
   fn finalize(self, env: Env) -> Result<()> {
     Ok(())
@@ -33,17 +45,33 @@ pub trait ObjectFinalize: Sized {
 ///
 /// called when node wrapper objects destroyed
 #[doc(hidden)]
-pub(crate) unsafe extern "C" fn raw_finalize_unchecked<T: ObjectFinalize>(
+pub(crate) unsafe extern "C" fn raw_finalize_unchecked<T: ObjectFinalize + 'static>(
   env: sys::napi_env,
   finalize_data: *mut c_void,
   _finalize_hint: *mut c_void,
 ) {
   let data: Box<T> = unsafe { Box::from_raw(finalize_data.cast()) };
+  if T::POST_FINALIZE {
+    unsafe { remove_reference(env, finalize_data) };
+    let finalize = Box::new(move |env: Env| {
+      if let Err(err) = data.finalize(env) {
+        let e: JsError = err.into();
+        unsafe { e.throw_into(env.raw()) };
+      }
+    });
+    unsafe { post_finalize(env, finalize) };
+    return;
+  }
   if let Err(err) = data.finalize(Env::from_raw(env)) {
     let e: JsError = err.into();
     unsafe { e.throw_into(env) };
     return;
   }
+  unsafe { remove_reference(env, finalize_data) };
+}
+
+/// Run the finalize callbacks of the `Reference`s to the wrapped object and delete its reference
+unsafe fn remove_reference(env: sys::napi_env, finalize_data: *mut c_void) {
   if let Some((_, ref_val, finalize_callbacks_ptr)) =
     REFERENCE_MAP.with(|cell| cell.borrow_mut(|reference_map| reference_map.remove(&finalize_data)))
   {
@@ -70,6 +98,47 @@ pub(crate) unsafe extern "C" fn raw_finalize_unchecked<T: ObjectFinalize>(
   }
 }
 
+/// Run `finalize` out of the GC callback with `node_api_post_finalizer`, so it's able to call into JavaScript
+///
+/// It's run in the GC callback if the `experimental` feature is not enabled, the host doesn't provide `node_api_post_finalizer`, or it failed.
+pub(crate) unsafe fn post_finalize(env: sys::napi_env, finalize: Box<dyn FnOnce(Env)>) {
+  #[cfg(feature = "experimental")]
+  let finalize = if can_post_finalizer(env) {
+    let data = Box::into_raw(Box::new(finalize));
+    let status = unsafe {
+      sys::node_api_post_finalizer(
+        env.cast(),
+        Some(call_post_finalize),
+        data.cast(),
+        std::ptr::null_mut(),
+      )
+    };
+    if status == sys::Status::napi_ok {
+      return;
+    }
+    *unsafe { Box::from_raw(data) }
+  } else {
+    finalize
+  };
+  finalize(Env::from_raw(env));
+}
+
+#[cfg(feature = "experimental")]
+fn can_post_finalizer(env: sys::napi_env) -> bool {
+  static SUPPORTED: std::sync::OnceLock<bool> = std::sync::OnceLock::new();
+  *SUPPORTED.get_or_init(|| crate::Capability::PostFinalizer.is_supported(env))
+}
+
+#[cfg(feature = "experimental")]
+unsafe extern "C" fn call_post_finalize(
+  env: sys::napi_env,
+  finalize_data: *mut c_void,
+  _finalize_hint: *mut c_void,
+) {
+  let finalize: Box<Box<dyn FnOnce(Env)>> = unsafe { Box::from_raw(finalize_data.cast()) };
+  finalize(Env::from_raw(env));
+}
+
 /// # Safety
 ///
 /// called when node buffer is ready for gc
@@ -116,10 +185,133 @@ pub unsafe extern "C" fn drop_buffer_slice(
   }
 }
 
+/// Whether `napi_create_object_with_properties` can be called, it's available since Node.js 25.2.0
+#[cfg(all(
+  feature = "experimental",
+  not(target_family = "wasm"),
+  not(feature = "noop")
+))]
+#[inline]
+pub(crate) fn can_create_object_with_properties(env: sys::napi_env) -> bool {
+  static SUPPORTED: std::sync::OnceLock<bool> = std::sync::OnceLock::new();
+  *SUPPORTED.get_or_init(|| crate::Capability::CreateObjectWithProperties.is_supported(env))
+}
+
+#[cfg(all(
+  not(all(feature = "experimental", not(target_family = "wasm"))),
+  not(feature = "noop")
+))]
+#[inline]
+pub(crate) fn can_create_object_with_properties(_env: sys::napi_env) -> bool {
+  false
+}
+
+/// Whether `node_api_create_property_key_utf8` can be called, it's available since Node.js 20.18.0
+#[cfg(feature = "napi10")]
+#[inline]
+pub(crate) fn can_create_property_key(env: sys::napi_env) -> bool {
+  static SUPPORTED: std::sync::OnceLock<bool> = std::sync::OnceLock::new();
+  *SUPPORTED.get_or_init(|| crate::Capability::CreatePropertyKey.is_supported(env))
+}
+
+/// Create an object with the `prototype` and the properties `names[i]: values[i]`
+///
+/// The properties are plain data properties, like the ones created by assignments.
+/// If `prototype` is a null pointer, `Object.prototype` is used,
+/// it can also be the JavaScript `null` to create an object without prototype.
+///
+/// When the `experimental` feature is enabled and the Node.js supports it,
+/// the object is created by `napi_create_object_with_properties` in a single call.
+/// Otherwise falls back to creating the object and then `napi_define_properties`.
+#[doc(hidden)]
+#[cfg(not(feature = "noop"))]
+pub unsafe fn create_object_from_entries(
+  env: sys::napi_env,
+  prototype: sys::napi_value,
+  names: &[sys::napi_value],
+  values: &[sys::napi_value],
+) -> Result<sys::napi_value> {
+  use crate::check_status;
+
+  debug_assert_eq!(names.len(), values.len());
+
+  #[cfg(all(feature = "experimental", not(target_family = "wasm")))]
+  if can_create_object_with_properties(env) {
+    let mut obj_ptr = std::ptr::null_mut();
+    check_status!(
+      sys::napi_create_object_with_properties(
+        env,
+        prototype,
+        names.as_ptr(),
+        values.as_ptr(),
+        names.len(),
+        &mut obj_ptr,
+      ),
+      "Failed to create object with properties",
+    )?;
+    return Ok(obj_ptr);
+  }
+
+  let obj_ptr = if prototype.is_null() {
+    let mut obj_ptr = std::ptr::null_mut();
+    check_status!(
+      sys::napi_create_object(env, &mut obj_ptr),
+      "Failed to create object",
+    )?;
+    obj_ptr
+  } else {
+    let env = crate::Env::from_raw(env);
+    let object_create = env
+      .get_global()?
+      .get_named_property_unchecked::<Object>("Object")?
+      .get_named_property_unchecked::<Function<'_, Unknown, Object>>("create")?;
+    object_create
+      .call(Unknown::from_raw_unchecked(env.raw(), prototype))?
+      .0
+      .value
+  };
+
+  if !names.is_empty() {
+    let properties = names
+      .iter()
+      .zip(values)
+      .map(|(&name, &value)| sys::napi_property_descriptor {
+        utf8name: std::ptr::null(),
+        name,
+        method: None,
+        getter: None,
+        setter: None,
+        value,
+        attributes: sys::PropertyAttributes::writable
+          | sys::PropertyAttributes::enumerable
+          | sys::PropertyAttributes::configurable,
+        data: std::ptr::null_mut(),
+      })
+      .collect::<Vec<_>>();
+    check_status!(
+      sys::napi_define_properties(env, obj_ptr, properties.len(), properties.as_ptr()),
+      "Failed to define properties",
+    )?;
+  }
+
+  Ok(obj_ptr)
+}
+
+#[doc(hidden)]
+#[cfg(feature = "noop")]
+pub unsafe fn create_object_from_entries(
+  _env: sys::napi_env,
+  _prototype: sys::napi_value,
+  _names: &[sys::napi_value],
+  _values: &[sys::napi_value],
+) -> Result<sys::napi_value> {
+  Ok(std::ptr::null_mut())
+}
+
 /// Create an object with properties
 ///
-/// When the `experimental` feature is enabled, uses `napi_create_object_with_properties`
-/// which creates the object with all properties in a single optimized call.
+/// When all the properties are plain data properties (writable, enumerable and configurable values),
+/// they are created together with the object by [`create_object_from_entries`].
 /// Otherwise falls back to `napi_create_object` + `napi_define_properties`.
 #[doc(hidden)]
 #[cfg(not(feature = "noop"))]
@@ -130,50 +322,40 @@ pub unsafe fn create_object_with_properties(
 ) -> Result<sys::napi_value> {
   use crate::check_status;
 
-  let mut obj_ptr = std::ptr::null_mut();
+  const DATA_PROPERTY: sys::napi_property_attributes = sys::PropertyAttributes::writable
+    | sys::PropertyAttributes::enumerable
+    | sys::PropertyAttributes::configurable;
 
-  #[cfg(all(
-    feature = "experimental",
-    feature = "node_version_detect",
-    not(target_family = "wasm")
-  ))]
+  if can_create_object_with_properties(env)
+    && !properties.is_empty()
+    && properties.iter().all(|prop| {
+      prop.attributes == DATA_PROPERTY
+        && prop.method.is_none()
+        && prop.getter.is_none()
+        && prop.setter.is_none()
+    })
   {
-    let node_version = NODE_VERSION.get().unwrap();
-    if !properties.is_empty()
-      && ((node_version.major == 25 && node_version.minor >= 2) || node_version.major > 25)
-    {
-      // Convert property names from C strings to napi_value
-      let mut names: Vec<sys::napi_value> = Vec::with_capacity(properties.len());
-      let mut values: Vec<sys::napi_value> = Vec::with_capacity(properties.len());
+    let mut names: Vec<sys::napi_value> = Vec::with_capacity(properties.len());
+    let mut values: Vec<sys::napi_value> = Vec::with_capacity(properties.len());
 
-      for prop in properties {
-        let mut name_value = std::ptr::null_mut();
+    for prop in properties {
+      // Convert property names from C strings to napi_value, unless they are already given as napi_value
+      let mut name_value = prop.name;
+      if name_value.is_null() {
         // utf8name is a null-terminated C string, use -1 to auto-detect length
         check_status!(
           sys::napi_create_string_utf8(env, prop.utf8name, -1, &mut name_value),
           "Failed to create property name string",
         )?;
-        names.push(name_value);
-        values.push(prop.value);
       }
-
-      let mut result_obj = std::ptr::null_mut();
-      check_status!(
-        sys::napi_create_object_with_properties(
-          env,
-          std::ptr::null_mut(), // prototype_or_null
-          names.as_ptr(),
-          values.as_ptr(),
-          properties.len(),
-          &mut result_obj,
-        ),
-        "Failed to create object with properties",
-      )?;
-      return Ok(result_obj);
+      names.push(name_value);
+      values.push(prop.value);
     }
+
+    return create_object_from_entries(env, std::ptr::null_mut(), &names, &values);
   }
 
-  // Fallback: create object then define properties
+  let mut obj_ptr = std::ptr::null_mut();
   check_status!(
     sys::napi_create_object(env, &mut obj_ptr),
     "Failed to create object",
@@ -197,3 +379,44 @@ pub unsafe fn create_object_with_properties(
 ) -> Result<sys::napi_value> {
   Ok(std::ptr::null_mut())
 }
+
+/// Freeze an object like `Object.freeze`, the exported objects of the `#[napi(flags)]` enums are frozen
+#[doc(hidden)]
+#[cfg(not(feature = "noop"))]
+pub unsafe fn freeze_object(env: sys::napi_env, obj: sys::napi_value) -> Result<()> {
+  use crate::check_status;
+
+  #[cfg(feature = "napi8")]
+  {
+    check_status!(sys::napi_object_freeze(env, obj), "Failed to freeze object")
+  }
+  #[cfg(not(feature = "napi8"))]
+  {
+    let mut global = std::ptr::null_mut();
+    check_status!(
+      sys::napi_get_global(env, &mut global),
+      "Failed to get global"
+    )?;
+    let mut object = std::ptr::null_mut();
+    check_status!(
+      sys::napi_get_named_property(env, global, c"Object".as_ptr(), &mut object),
+      "Failed to get `Object`"
+    )?;
+    let mut freeze = std::ptr::null_mut();
+    check_status!(
+      sys::napi_get_named_property(env, object, c"freeze".as_ptr(), &mut freeze),
+      "Failed to get `Object.freeze`"
+    )?;
+    let mut frozen = std::ptr::null_mut();
+    check_status!(
+      sys::napi_call_function(env, object, freeze, 1, &obj, &mut frozen),
+      "Failed to freeze object"
+    )
+  }
+}
+
+#[doc(hidden)]
+#[cfg(feature = "noop")]
+pub unsafe fn freeze_object(_env: sys::napi_env, _obj: sys::napi_value) -> Result<()> {
+  Ok(())
+}
@@ -4,10 +4,9 @@ use std::cell::{LazyCell, RefCell};
 #[cfg(not(feature = "noop"))]
 use std::collections::HashSet;
 #[cfg(not(feature = "noop"))]
-use std::ffi::CStr;
+use std::ffi::{CStr, CString};
 #[cfg(all(not(feature = "noop"), feature = "node_version_detect"))]
 use std::mem::MaybeUninit;
-#[cfg(not(feature = "noop"))]
 use std::ptr;
 #[cfg(all(not(feature = "noop"), feature = "node_version_detect"))]
 use std::sync::OnceLock;
@@ -23,8 +22,8 @@ use rustc_hash::FxBuildHasher;
 #[cfg(all(not(feature = "noop"), feature = "node_version_detect"))]
 use crate::NodeVersion;
 #[cfg(not(feature = "noop"))]
-use crate::{check_status, check_status_or_throw, JsError};
-use crate::{sys, Property, Result};
+use crate::{bindgen_runtime::intern_property_key, check_status_or_throw, JsError};
+use crate::{check_status, sys, Property, Result};
 
 // #[napi] fn
 pub type ExportRegisterCallback = unsafe fn(sys::napi_env) -> Result<sys::napi_value>;
@@ -112,6 +111,10 @@ static MODULE_REGISTER_HOOK_CALLBACK: LazyLock<RwLock<Option<ExportRegisterHookC
   LazyLock::new(Default::default);
 #[cfg(not(feature = "noop"))]
 static MODULE_CLASS_PROPERTIES: LazyLock<ModuleClassProperty> = LazyLock::new(Default::default);
+/// Class => base class declared with `#[napi(extends = Base)]`
+#[cfg(not(feature = "noop"))]
+static MODULE_CLASS_PARENTS: LazyLock<RwLock<HashMap<TypeId, TypeId, FxBuildHasher>>> =
+  LazyLock::new(Default::default);
 #[cfg(not(feature = "noop"))]
 static MODULE_COUNT: AtomicUsize = AtomicUsize::new(0);
 #[cfg(not(feature = "noop"))]
@@ -236,6 +239,167 @@ pub fn register_class(
 ) {
 }
 
+#[doc(hidden)]
+pub fn register_class_extends<T: 'static, Base: 'static>(
+  js_name: &'static str,
+  upcast: super::ClassUpcast,
+) {
+  #[cfg(not(feature = "noop"))]
+  MODULE_CLASS_PARENTS
+    .write()
+    .expect("Write MODULE_CLASS_PARENTS failed")
+    .insert(TypeId::of::<T>(), TypeId::of::<Base>());
+  super::register_class_subtype::<T, Base>(js_name, upcast);
+}
+
+/// The module of the class `rust_type_id` extended by a class of `js_mod`.
+///
+/// It's the module of its only registration, or `js_mod` if the base class is registered in several modules.
+#[cfg(not(feature = "noop"))]
+fn base_class_module(
+  classes: &ClassPropertyRegistry,
+  rust_type_id: TypeId,
+  js_mod: Option<&'static str>,
+) -> Result<Option<Option<&'static str>>> {
+  let Some(registrations) = classes.get(&rust_type_id) else {
+    return Ok(None);
+  };
+  if registrations.contains_key(&js_mod) {
+    return Ok(Some(js_mod));
+  }
+  let mut modules = registrations.keys();
+  match (modules.next(), modules.next()) {
+    (Some(module), None) => Ok(Some(*module)),
+    (None, _) => Ok(None),
+    (Some(_), Some(_)) => Err(crate::Error::new(
+      crate::Status::GenericFailure,
+      format!(
+        "The base class `{}` is registered in several modules and none of them is `{}`",
+        registrations
+          .values()
+          .next()
+          .map_or("", |(js_name, _)| js_name.trim_end_matches('\0')),
+        js_mod.map_or("exports", |js_mod| js_mod.trim_end_matches('\0')),
+      ),
+    )),
+  }
+}
+
+/// Collect the instance properties of all the base classes of `rust_type_id`,
+/// skipping the ones overridden by the subclass.
+///
+/// Methods defined by `napi_define_class` can only be called on the instances of the same class,
+/// so the subclass has to define its own copies of the inherited properties.
+#[cfg(not(feature = "noop"))]
+fn inherited_class_properties(
+  classes: &ClassPropertyRegistry,
+  parents: &HashMap<TypeId, TypeId, FxBuildHasher>,
+  rust_type_id: TypeId,
+  js_mod: Option<&'static str>,
+  own_props: &[&Property],
+) -> Result<Vec<Property>> {
+  let mut names: HashSet<String> = own_props.iter().filter_map(|prop| prop.key()).collect();
+  let mut inherited = vec![];
+  let mut current = (rust_type_id, js_mod);
+  while let Some(parent) = parents.get(&current.0) {
+    let Some(parent_mod) = base_class_module(classes, *parent, current.1)? else {
+      break;
+    };
+    let (_, parent_props) = &classes[parent][&parent_mod];
+    for prop in parent_props {
+      if prop.is_ctor || prop.is_static() {
+        continue;
+      }
+      if let Some(key) = prop.key() {
+        if !names.insert(key) {
+          continue;
+        }
+      }
+      inherited.push(prop.clone());
+    }
+    current = (*parent, parent_mod);
+  }
+  Ok(inherited)
+}
+
+/// `Symbol[symbol]`, `None` if the current runtime doesn't provide it, for example `Symbol.dispose` before Node.js 18.18
+#[cfg(not(feature = "noop"))]
+unsafe fn get_well_known_symbol(env: sys::napi_env, symbol: &str) -> Option<sys::napi_value> {
+  let symbol = CString::new(symbol).ok()?;
+  let mut global = ptr::null_mut();
+  let mut symbol_ctor = ptr::null_mut();
+  let mut value = ptr::null_mut();
+  let mut value_type = 0;
+  unsafe {
+    (sys::napi_get_global(env, &mut global) == sys::Status::napi_ok
+      && sys::napi_get_named_property(env, global, c"Symbol".as_ptr(), &mut symbol_ctor)
+        == sys::Status::napi_ok
+      && sys::napi_get_named_property(env, symbol_ctor, symbol.as_ptr(), &mut value)
+        == sys::Status::napi_ok
+      && sys::napi_typeof(env, value, &mut value_type) == sys::Status::napi_ok
+      && value_type == sys::ValueType::napi_symbol)
+      .then_some(value)
+  }
+}
+
+/// `Object.setPrototypeOf(Derived.prototype, Base.prototype)` and `Object.setPrototypeOf(Derived, Base)`
+pub(crate) unsafe fn inherit_class(
+  env: sys::napi_env,
+  derived: sys::napi_value,
+  base: sys::napi_value,
+) -> Result<()> {
+  let mut global = ptr::null_mut();
+  check_status!(
+    unsafe { sys::napi_get_global(env, &mut global) },
+    "Failed to get global object"
+  )?;
+  let mut object = ptr::null_mut();
+  check_status!(
+    unsafe { sys::napi_get_named_property(env, global, c"Object".as_ptr(), &mut object) },
+    "Failed to get global Object"
+  )?;
+  let mut set_prototype_of = ptr::null_mut();
+  check_status!(
+    unsafe {
+      sys::napi_get_named_property(
+        env,
+        object,
+        c"setPrototypeOf".as_ptr(),
+        &mut set_prototype_of,
+      )
+    },
+    "Failed to get Object.setPrototypeOf"
+  )?;
+  let mut derived_prototype = ptr::null_mut();
+  check_status!(
+    unsafe {
+      sys::napi_get_named_property(env, derived, c"prototype".as_ptr(), &mut derived_prototype)
+    },
+    "Failed to get prototype of class"
+  )?;
+  let mut base_prototype = ptr::null_mut();
+  check_status!(
+    unsafe { sys::napi_get_named_property(env, base, c"prototype".as_ptr(), &mut base_prototype) },
+    "Failed to get prototype of base class"
+  )?;
+  for args in [[derived_prototype, base_prototype], [derived, base]] {
+    check_status!(
+      unsafe {
+        sys::napi_call_function(
+          env,
+          object,
+          set_prototype_of,
+          2,
+          args.as_ptr(),
+          ptr::null_mut(),
+        )
+      },
+      "Failed to set prototype of class"
+    )?;
+  }
+  Ok(())
+}
+
 #[cfg(all(target_family = "wasm", not(feature = "noop")))]
 #[no_mangle]
 unsafe extern "C" fn napi_register_wasm_v1(
@@ -373,9 +537,13 @@ pub unsafe extern "C" fn napi_register_module_v1(
   }
 
   let mut registered_classes = HashMap::default();
+  let mut class_values = HashMap::<(TypeId, Option<&'static str>), sys::napi_value>::default();
+  let class_parents = MODULE_CLASS_PARENTS
+    .read()
+    .expect("Read MODULE_CLASS_PARENTS failed");
 
   MODULE_CLASS_PROPERTIES.borrow(|inner| {
-    inner.iter().for_each(|(_, js_mods)| {
+    inner.iter().for_each(|(rust_type_id, js_mods)| {
       for (js_mod, (js_name, props)) in js_mods {
         let mut exports_js_mod = ptr::null_mut();
         unsafe {
@@ -415,7 +583,34 @@ pub unsafe extern "C" fn napi_register_module_v1(
             .first()
             .map(|c| c.raw().method.unwrap())
             .unwrap_or(noop);
-          let raw_props: Vec<_> = props.iter().map(|prop| prop.raw()).collect();
+          let inherited_props =
+            match inherited_class_properties(inner, &class_parents, *rust_type_id, *js_mod, &props)
+            {
+              Ok(inherited_props) => inherited_props,
+              Err(e) => {
+                JsError::from(e).throw_into(env);
+                return;
+              }
+            };
+          let raw_props: Vec<_> = props
+            .iter()
+            .copied()
+            .chain(inherited_props.iter())
+            .filter_map(|prop| {
+              let mut raw_prop = prop.raw();
+              if let Some(symbol) = prop.well_known_symbol {
+                raw_prop.name = get_well_known_symbol(env, symbol)?;
+              } else if let Some(key) = prop
+                .utf8_name()
+                .and_then(|name| intern_property_key(env, name).ok())
+              {
+                // getters, setters and methods share the keys with the generated object conversions
+                raw_prop.name = key;
+                raw_prop.utf8name = ptr::null();
+              }
+              Some(raw_prop)
+            })
+            .collect();
 
           let js_class_name = CStr::from_bytes_with_nul_unchecked(js_name.as_bytes());
           let mut class_ptr = ptr::null_mut();
@@ -440,6 +635,7 @@ pub unsafe extern "C" fn napi_register_module_v1(
           sys::napi_create_reference(env, class_ptr, 1, &mut ctor_ref);
 
           registered_classes.insert(js_name.to_string(), ctor_ref);
+          class_values.insert((*rust_type_id, *js_mod), class_ptr);
 
           check_status_or_throw!(
             env,
@@ -461,6 +657,25 @@ pub unsafe extern "C" fn napi_register_module_v1(
     });
   });
 
+  MODULE_CLASS_PROPERTIES.borrow(|inner| {
+    for ((rust_type_id, js_mod), derived) in class_values.iter() {
+      let Some(parent_type_id) = class_parents.get(rust_type_id) else {
+        continue;
+      };
+      let result =
+        base_class_module(inner, *parent_type_id, *js_mod).and_then(|parent_mod| match parent_mod
+          .and_then(|parent_mod| class_values.get(&(*parent_type_id, parent_mod)))
+        {
+          Some(base) => unsafe { inherit_class(env, *derived, *base) },
+          None => Ok(()),
+        });
+      if let Err(e) = result {
+        unsafe { JsError::from(e).throw_into(env) };
+      }
+    }
+  });
+  drop(class_parents);
+
   REGISTERED_CLASSES.with(|cell| {
     cell.borrow_mut(|map| {
       *map = registered_classes;
@@ -0,0 +1,106 @@
+use std::ptr;
+
+use crate::sys;
+
+/// A part of Node-API which may be missing from the host, see [`Env::supports`](crate::Env::supports).
+///
+/// With `dyn-symbols` the capabilities are detected from the symbols the host actually provides,
+/// the missing ones are replaced by stubs which fail when called.
+/// When the symbols are linked with the module, the Node.js version of the host is checked instead.
+/// The capabilities gated by the `napiN` and `experimental` features which are not enabled are never supported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Capability {
+  /// All the functions of this Node-API version and the versions before it, reported by `napi_get_version`
+  NapiVersion(u32),
+  /// `node_api_create_property_key_utf8`, `node_api_create_property_key_latin1` and `node_api_create_property_key_utf16`, needs the `napi10` feature
+  CreatePropertyKey,
+  /// `node_api_create_external_string_latin1` and `node_api_create_external_string_utf16`, needs the `napi10` feature
+  CreateExternalString,
+  /// `node_api_create_buffer_from_arraybuffer`, needs the `experimental` feature
+  CreateBufferFromArrayBuffer,
+  /// `node_api_post_finalizer`, needs the `experimental` feature
+  PostFinalizer,
+  /// `napi_create_object_with_properties`, needs the `experimental` feature
+  CreateObjectWithProperties,
+}
+
+impl Capability {
+  fn symbols(self) -> &'static [&'static str] {
+    match self {
+      Capability::NapiVersion(_) => &[],
+      Capability::CreatePropertyKey => &[
+        "node_api_create_property_key_utf8",
+        "node_api_create_property_key_latin1",
+        "node_api_create_property_key_utf16",
+      ],
+      Capability::CreateExternalString => &[
+        "node_api_create_external_string_latin1",
+        "node_api_create_external_string_utf16",
+      ],
+      Capability::CreateBufferFromArrayBuffer => &["node_api_create_buffer_from_arraybuffer"],
+      Capability::PostFinalizer => &["node_api_post_finalizer"],
+      Capability::CreateObjectWithProperties => &["napi_create_object_with_properties"],
+    }
+  }
+
+  /// The `(major, minor)` Node.js versions which added the symbols, one per release line in ascending order
+  fn node_versions(self) -> &'static [(u32, u32)] {
+    match self {
+      Capability::NapiVersion(_) => &[],
+      Capability::CreatePropertyKey => &[(20, 18), (22, 9)],
+      Capability::CreateExternalString => &[(18, 18), (20, 4)],
+      Capability::CreateBufferFromArrayBuffer => &[(22, 12), (23, 0)],
+      Capability::PostFinalizer => &[(18, 19), (20, 10), (21, 0)],
+      Capability::CreateObjectWithProperties => &[(25, 2)],
+    }
+  }
+
+  fn is_enabled(self) -> bool {
+    match self {
+      Capability::NapiVersion(_) => true,
+      Capability::CreatePropertyKey | Capability::CreateExternalString => cfg!(feature = "napi10"),
+      Capability::CreateBufferFromArrayBuffer
+      | Capability::PostFinalizer
+      | Capability::CreateObjectWithProperties => cfg!(feature = "experimental"),
+    }
+  }
+
+  pub(crate) fn is_supported(self, env: sys::napi_env) -> bool {
+    match self {
+      Capability::NapiVersion(version) => {
+        sys::is_version_loaded(version)
+          && host_napi_version(env).is_some_and(|host| host >= version)
+      }
+      capability if !capability.is_enabled() => false,
+      capability if sys::SYMBOLS_RESOLVED_AT_RUNTIME => {
+        capability.symbols().iter().all(|name| sys::is_loaded(name))
+      }
+      capability => host_node_version(env).is_some_and(|(major, minor)| {
+        let versions = capability.node_versions();
+        versions
+          .iter()
+          .any(|&(since_major, since_minor)| major == since_major && minor >= since_minor)
+          || versions
+            .last()
+            .is_some_and(|&(since_major, _)| major > since_major)
+      }),
+    }
+  }
+}
+
+fn host_napi_version(env: sys::napi_env) -> Option<u32> {
+  let mut version = 0;
+  let status = unsafe { sys::napi_get_version(env, &mut version) };
+  (status == sys::Status::napi_ok).then_some(version)
+}
+
+fn host_node_version(env: sys::napi_env) -> Option<(u32, u32)> {
+  let mut version = ptr::null();
+  let status = unsafe { sys::napi_get_node_version(env, &mut version) };
+  if status != sys::Status::napi_ok || version.is_null() {
+    return None;
+  }
+  let version = unsafe { &*version };
+  Some((version.major, version.minor))
+}
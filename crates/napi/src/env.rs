@@ -32,7 +32,7 @@ use crate::bindgen_runtime::{
 #[cfg(feature = "napi3")]
 use crate::cleanup_env::{CleanupEnvHook, CleanupEnvHookData};
 #[cfg(feature = "serde-json")]
-use crate::js_values::{De, Ser};
+use crate::js_values::{De, Ser, SerdeOptions};
 #[cfg(all(feature = "napi4", feature = "compat-mode"))]
 use crate::threadsafe_function::{ThreadsafeCallContext, ThreadsafeFunction};
 #[cfg(feature = "napi3")]
@@ -42,7 +42,8 @@ use crate::{
   bindgen_runtime::JsObjectValue,
   check_status,
   js_values::*,
-  sys, Error, ExtendedErrorInfo, NodeVersion, Result, ScopedTask, Status, ValueType,
+  sys, Capability, Error, ExtendedErrorInfo, JsAggregateError, NodeVersion, Result, ScopedTask,
+  Status, ValueType,
 };
 
 pub type Callback = unsafe extern "C" fn(sys::napi_env, sys::napi_callback_info) -> sys::napi_value;
@@ -1037,6 +1038,17 @@ impl Env {
     Ok(Object::from_raw(self.0, result))
   }
 
+  /// Create a JavaScript `AggregateError` object from `errors` and `message`
+  pub fn create_aggregate_error<M: ToString>(
+    &self,
+    errors: Vec<Error>,
+    message: M,
+  ) -> Result<Object<'_>> {
+    let error = JsAggregateError::new(errors, message);
+    let value = unsafe { ToNapiValue::to_napi_value(self.0, error) }?;
+    Ok(Object::from_raw(self.0, value))
+  }
+
   /// Run [Task](./trait.Task.html) in libuv thread pool, return [AsyncWorkPromise](./struct.AsyncWorkPromise.html)
   pub fn spawn<'env, T: 'env + ScopedTask<'env>>(
     &self,
@@ -1425,7 +1437,38 @@ impl Env {
   where
     T: Serialize,
   {
-    let s = Ser(self);
+    self.to_js_value_with_options(node, SerdeOptions::default())
+  }
+
+  /// ### Serialize `Rust Struct` into `JavaScript Value` with [`SerdeOptions`]
+  ///
+  /// ```
+  /// #[derive(Serialize)]
+  /// struct Telemetry {
+  ///     #[serde(with = "serde_bytes")]
+  ///     payload: Vec<u8>,
+  ///     timestamp: u64,
+  ///     counters: HashMap<u32, u64>,
+  /// }
+  ///
+  /// // `payload` becomes an `Uint8Array`, `timestamp` a `BigInt` and `counters` a `Map<number, bigint>`
+  /// let options = SerdeOptions::new()
+  ///     .with_bytes_as_uint8_array(true)
+  ///     .with_bigint(true)
+  ///     .with_map(true);
+  /// env.to_js_value_with_options(&telemetry, options)
+  /// ```
+  #[cfg(feature = "serde-json")]
+  #[allow(clippy::wrong_self_convention)]
+  pub fn to_js_value_with_options<'env, T>(
+    &self,
+    node: &T,
+    options: SerdeOptions,
+  ) -> Result<Unknown<'env>>
+  where
+    T: Serialize,
+  {
+    let s = Ser(self, options);
     node
       .serialize(s)
       .map(|v| Unknown(v, std::marker::PhantomData))
@@ -1449,6 +1492,19 @@ impl Env {
   ///
   #[cfg(feature = "serde-json")]
   pub fn from_js_value<'v, T, V>(&self, value: V) -> Result<T>
+  where
+    T: DeserializeOwned,
+    V: JsValue<'v>,
+  {
+    self.from_js_value_with_options(value, SerdeOptions::default())
+  }
+
+  /// ### Deserialize data from `JsValue` with [`SerdeOptions`]
+  ///
+  /// Use the same options used to serialize the value so that it round-trips,
+  /// e.g. with the `map` option JavaScript `Map` are deserialized as maps.
+  #[cfg(feature = "serde-json")]
+  pub fn from_js_value_with_options<'v, T, V>(&self, value: V, options: SerdeOptions) -> Result<T>
   where
     T: DeserializeOwned,
     V: JsValue<'v>,
@@ -1458,7 +1514,7 @@ impl Env {
       value: value.raw(),
       value_type: ValueType::Unknown,
     };
-    let mut de = De(&value);
+    let mut de = De(&value, options);
     T::deserialize(&mut de)
   }
 
@@ -1480,6 +1536,21 @@ impl Env {
     version.try_into()
   }
 
+  /// Whether the host provides the `capability`
+  ///
+  /// ```rust
+  /// use napi::{Capability, Env};
+  ///
+  /// fn create_key(env: &Env) {
+  ///   if env.supports(Capability::CreatePropertyKey) {
+  ///     // `node_api_create_property_key_utf8` can be called
+  ///   }
+  /// }
+  /// ```
+  pub fn supports(&self, capability: Capability) -> bool {
+    capability.is_supported(self.0)
+  }
+
   /// get raw env ptr
   pub fn raw(&self) -> sys::napi_env {
     self.0
@@ -1503,7 +1574,6 @@ unsafe extern "C" fn drop_buffer(
   mem::drop(unsafe { Vec::from_raw_parts(finalize_data as *mut u8, length, cap) });
 }
 
-#[cfg_attr(target_family = "wasm", allow(unused_variables))]
 pub(crate) unsafe extern "C" fn raw_finalize<T>(
   env: sys::napi_env,
   finalize_data: *mut c_void,
@@ -1511,6 +1581,15 @@ pub(crate) unsafe extern "C" fn raw_finalize<T>(
 ) {
   let tagged_object = finalize_data as *mut T;
   drop(unsafe { Box::from_raw(tagged_object) });
+  unsafe { adjust_finalized_external_memory(env, finalize_hint) };
+}
+
+/// Release the external memory of `finalize_hint`, which is the boxed `size_hint` of the finalized object
+#[cfg_attr(target_family = "wasm", allow(unused_variables))]
+pub(crate) unsafe fn adjust_finalized_external_memory(
+  env: sys::napi_env,
+  finalize_hint: *mut c_void,
+) {
   #[cfg(not(target_family = "wasm"))]
   if !finalize_hint.is_null() {
     let size_hint = unsafe { *Box::from_raw(finalize_hint as *mut i64) };
@@ -12,10 +12,13 @@ use serde::{de, ser};
 #[cfg(feature = "serde-json")]
 use serde_json::Error as SerdeJSONError;
 
-use crate::bindgen_runtime::JsObjectValue;
+use crate::bindgen_runtime::{JsObjectValue, Object};
 #[cfg(target_family = "wasm")]
 use crate::ValueType;
-use crate::{bindgen_runtime::ToNapiValue, check_status, sys, Env, JsValue, Status, Unknown};
+use crate::{
+  bindgen_runtime::{FromNapiValue, ToNapiValue},
+  check_status, sys, Env, JsValue, Status, Unknown,
+};
 
 pub type Result<T, S = Status> = std::result::Result<T, Error<S>>;
 
@@ -29,8 +32,20 @@ pub struct Error<S: AsRef<str> = Status> {
   // Convert raw `JsError` into Error
   pub(crate) maybe_raw: sys::napi_ref,
   pub(crate) maybe_env: sys::napi_env,
+  // Rarely set data, boxed to keep `Error` small
+  pub(crate) extra: Option<Box<ErrorExtra>>,
 }
 
+#[derive(Default)]
+pub(crate) struct ErrorExtra {
+  // Create the JavaScript value thrown for this Error, see `Error::with_value`
+  value: Option<ErrorValueFn>,
+  // Appended to the `stack` of the JavaScript Error, see `Error::set_backtrace`
+  backtrace: Option<String>,
+}
+
+type ErrorValueFn = Box<dyn FnOnce(sys::napi_env) -> Result<sys::napi_value> + Send>;
+
 #[cfg(not(feature = "noop"))]
 impl<S: AsRef<str>> Drop for Error<S> {
   fn drop(&mut self) {
@@ -56,6 +71,21 @@ impl<S: AsRef<str>> Error<S> {
   pub fn set_cause(&mut self, cause: Error) {
     self.cause = Some(Box::new(cause));
   }
+
+  /// Append `backtrace` to the `stack` of the thrown JavaScript Error, after the JavaScript frames.
+  ///
+  /// `anyhow::Error` captured with `RUST_BACKTRACE=1` carries its backtrace with it.
+  pub fn set_backtrace<B: ToString>(&mut self, backtrace: B) {
+    self.extra.get_or_insert_with(Default::default).backtrace = Some(backtrace.to_string());
+  }
+
+  pub(crate) fn take_value(&mut self) -> Option<ErrorValueFn> {
+    self.extra.as_mut().and_then(|extra| extra.value.take())
+  }
+
+  pub(crate) fn take_backtrace(&mut self) -> Option<String> {
+    self.extra.as_mut().and_then(|extra| extra.backtrace.take())
+  }
 }
 
 impl<S: AsRef<str>> std::fmt::Debug for Error<S> {
@@ -159,6 +189,7 @@ impl From<Unknown<'_>> for Error {
         cause: maybe_cause,
         maybe_raw: result,
         maybe_env,
+        extra: None,
       };
     }
 
@@ -168,6 +199,7 @@ impl From<Unknown<'_>> for Error {
       cause: maybe_cause,
       maybe_raw: result,
       maybe_env,
+      extra: None,
     }
   }
 }
@@ -221,6 +253,7 @@ impl From<Unknown<'_>> for Error {
         cause: maybe_cause,
         maybe_raw: ptr::null_mut(),
         maybe_env: ptr::null_mut(),
+        extra: None,
       };
     }
 
@@ -230,14 +263,21 @@ impl From<Unknown<'_>> for Error {
       cause: maybe_cause,
       maybe_raw: ptr::null_mut(),
       maybe_env: ptr::null_mut(),
+      extra: None,
     }
   }
 }
 
 #[cfg(feature = "anyhow")]
 impl From<anyhow::Error> for Error {
+  /// The contexts and the sources of `value` become the `cause` chain, its backtrace is kept if it was captured
   fn from(value: anyhow::Error) -> Self {
-    Error::new(Status::GenericFailure, format!("{:?}", value))
+    let mut error = Error::from_error_chain(value.as_ref());
+    let backtrace = value.backtrace();
+    if backtrace.status() == std::backtrace::BacktraceStatus::Captured {
+      error.set_backtrace(backtrace);
+    }
+    error
   }
 }
 
@@ -259,6 +299,7 @@ impl<S: AsRef<str>> Error<S> {
       cause: None,
       maybe_raw: ptr::null_mut(),
       maybe_env: ptr::null_mut(),
+      extra: None,
     }
   }
 
@@ -269,6 +310,7 @@ impl<S: AsRef<str>> Error<S> {
       cause: None,
       maybe_raw: ptr::null_mut(),
       maybe_env: ptr::null_mut(),
+      extra: None,
     }
   }
 }
@@ -287,11 +329,33 @@ impl<S: AsRef<str> + Clone> Error<S> {
       cause: None,
       maybe_raw: self.maybe_raw,
       maybe_env: self.maybe_env,
+      extra: None,
     })
   }
 }
 
 impl Error {
+  /// Create an `Error` thrown as the JavaScript value of `value`, instead of an `Error` with `reason` as message.
+  ///
+  /// The `#[napi(error)]` types are converted into `Error` with it.
+  pub fn with_value<R: ToString, V: ToNapiValue + Send + 'static>(reason: R, value: V) -> Self {
+    let mut error = Error::new(Status::GenericFailure, reason);
+    error.extra.get_or_insert_with(Default::default).value =
+      Some(Box::new(move |env| unsafe { V::to_napi_value(env, value) }));
+    error
+  }
+
+  /// Convert `err` and its `source()` chain into `Error`s linked by `cause`, each one keeps the message of its level.
+  ///
+  /// The `Err` of `Result<T, E: std::error::Error>` returned by a `#[napi]` function is converted with it.
+  pub fn from_error_chain(err: &(dyn error::Error + 'static)) -> Self {
+    let mut error = Error::new(Status::GenericFailure, err);
+    if let Some(source) = err.source() {
+      error.set_cause(Error::from_error_chain(source));
+    }
+    error
+  }
+
   pub fn from_reason<T: Into<String>>(reason: T) -> Self {
     Error {
       status: Status::GenericFailure,
@@ -299,6 +363,7 @@ impl Error {
       cause: None,
       maybe_raw: ptr::null_mut(),
       maybe_env: ptr::null_mut(),
+      extra: None,
     }
   }
 }
@@ -311,6 +376,7 @@ impl From<std::ffi::NulError> for Error {
       cause: None,
       maybe_raw: ptr::null_mut(),
       maybe_env: ptr::null_mut(),
+      extra: None,
     }
   }
 }
@@ -323,6 +389,7 @@ impl From<std::io::Error> for Error {
       cause: None,
       maybe_raw: ptr::null_mut(),
       maybe_env: ptr::null_mut(),
+      extra: None,
     }
   }
 }
@@ -355,10 +422,19 @@ impl TryFrom<sys::napi_extended_error_info> for ExtendedErrorInfo {
 
 pub struct JsError<S: AsRef<str> = Status>(Error<S>);
 
+/// Append the backtrace set by `Error::set_backtrace` to the `stack` of `error`, it's left as is if it has no `stack`
+pub(crate) unsafe fn append_backtrace(env: sys::napi_env, error: sys::napi_value, backtrace: &str) {
+  let mut error = Object::from_raw(env, error);
+  if let Ok(stack) = error.get_named_property::<String>("stack") {
+    let stack = format!("{stack}\nRust backtrace:\n{}", backtrace.trim_end());
+    let _ = error.set_named_property("stack", stack);
+  }
+}
+
 #[cfg(feature = "anyhow")]
 impl From<anyhow::Error> for JsError {
   fn from(value: anyhow::Error) -> Self {
-    JsError(Error::new(Status::GenericFailure, value.to_string()))
+    JsError(Error::from(value))
   }
 }
 
@@ -445,6 +521,19 @@ macro_rules! impl_object_methods {
           }
         }
 
+        let backtrace = self.0.take_backtrace();
+        if let Some(create_value) = self.0.take_value() {
+          match create_value(env) {
+            Ok(value) => {
+              if let Some(backtrace) = backtrace {
+                unsafe { append_backtrace(env, value, &backtrace) };
+              }
+              return value;
+            }
+            Err(err) => self.0.reason = format!("{}, {}", self.0.reason, err.reason),
+          }
+        }
+
         let error_status = self.0.status.as_ref();
         let status_len = error_status.len();
         let reason_len = self.0.reason.len();
@@ -481,6 +570,9 @@ macro_rules! impl_object_methods {
             "Set cause property failed"
           );
         }
+        if let Some(backtrace) = backtrace {
+          unsafe { append_backtrace(env, js_error, &backtrace) };
+        }
         js_error
       }
 
@@ -553,6 +645,96 @@ impl_object_methods!(JsRangeError, sys::napi_create_range_error);
 #[cfg(feature = "napi9")]
 impl_object_methods!(JsSyntaxError, sys::node_api_create_syntax_error);
 
+/// Represent JavaScript `AggregateError`, it reports all the failures of a batched operation at once.
+///
+/// Convert it into `Error` to return it from a `#[napi]` function, or throw it with `throw_into`.
+/// A JavaScript `AggregateError` passed to Rust is read back as its `errors` and `message`.
+pub struct JsAggregateError {
+  pub errors: Vec<Error>,
+  pub message: String,
+}
+
+impl JsAggregateError {
+  pub fn new<M: ToString>(errors: Vec<Error>, message: M) -> Self {
+    Self {
+      errors,
+      message: message.to_string(),
+    }
+  }
+
+  /// # Safety
+  ///
+  /// This function is safety if env is not null ptr.
+  pub unsafe fn throw_into(self, env: sys::napi_env) {
+    match unsafe { ToNapiValue::to_napi_value(env, self) } {
+      Ok(js_error) => {
+        let throw_status = unsafe { sys::napi_throw(env, js_error) };
+        debug_assert!(
+          throw_status == sys::Status::napi_ok,
+          "Throw AggregateError failed"
+        );
+      }
+      Err(err) => unsafe { JsError::from(err).throw_into(env) },
+    }
+  }
+}
+
+impl ToNapiValue for JsAggregateError {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    let args = [
+      unsafe { ToNapiValue::to_napi_value(env, val.errors) }?,
+      unsafe { ToNapiValue::to_napi_value(env, val.message) }?,
+    ];
+    let global = Env::from_raw(env).get_global()?;
+    let aggregate_error: Unknown = global.get_named_property_unchecked("AggregateError")?;
+    if aggregate_error.get_type()? != crate::ValueType::Function {
+      return Err(Error::new(
+        Status::GenericFailure,
+        "AggregateError is not supported by the JavaScript engine",
+      ));
+    }
+    let mut js_error = ptr::null_mut();
+    check_status!(
+      unsafe {
+        sys::napi_new_instance(
+          env,
+          aggregate_error.0.value,
+          args.len(),
+          args.as_ptr(),
+          &mut js_error,
+        )
+      },
+      "Failed to create AggregateError"
+    )?;
+    Ok(js_error)
+  }
+}
+
+impl FromNapiValue for JsAggregateError {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    let error = Object::from_raw(env, napi_val);
+    let errors: Unknown = error.get_named_property_unchecked("errors")?;
+    if !errors.is_array()? {
+      return Err(Error::new(
+        Status::InvalidArg,
+        "Value is not an AggregateError, its `errors` is not an array",
+      ));
+    }
+    let errors = unsafe { Vec::<Unknown>::from_napi_value(env, errors.0.value) }?;
+    let message: Unknown = error.get_named_property_unchecked("message")?;
+    Ok(Self {
+      errors: errors.into_iter().map(Error::from).collect(),
+      message: message.coerce_to_string()?.into_utf8()?.into_owned()?,
+    })
+  }
+}
+
+impl From<JsAggregateError> for Error {
+  fn from(err: JsAggregateError) -> Self {
+    Error::with_value(err.message.clone(), err)
+  }
+}
+
 #[doc(hidden)]
 #[macro_export]
 macro_rules! error {
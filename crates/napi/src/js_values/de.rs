@@ -4,14 +4,22 @@ use serde::de::{DeserializeSeed, EnumAccess, MapAccess, SeqAccess, Unexpected, V
 #[cfg(feature = "napi6")]
 use crate::bindgen_runtime::BigInt;
 use crate::{
-  bindgen_runtime::{ArrayBuffer, BufferSlice, FromNapiValue, JsObjectValue, Object, Unknown},
-  type_of, Error, JsValue, Result, Status, Value, ValueType,
+  bindgen_runtime::{
+    for_each_js_map_entry, is_js_map, ArrayBuffer, BufferSlice, FromNapiValue, JsObjectValue,
+    Object, Unknown,
+  },
+  type_of, Error, JsValue, Result, SerdeOptions, Status, Value, ValueType,
 };
 
-pub struct De<'env>(pub(crate) &'env Value);
+pub struct De<'env>(pub(crate) &'env Value, pub(crate) SerdeOptions);
 impl<'env> De<'env> {
   pub fn new(value: &'env Object<'env>) -> Self {
-    Self(&value.0)
+    Self(&value.0, SerdeOptions::default())
+  }
+
+  /// `BigInt` and typed arrays are always accepted, the `map` option makes JavaScript `Map` deserializable as maps
+  pub fn with_options(value: &'env Object<'env>, options: SerdeOptions) -> Self {
+    Self(&value.0, options)
   }
 }
 
@@ -45,7 +53,7 @@ impl<'x> serde::de::Deserializer<'x> for &mut De<'_> {
         let js_object = Object::from_raw(self.0.env, self.0.value);
         if js_object.is_array()? {
           let mut deserializer =
-            JsArrayAccess::new(&js_object, js_object.get_array_length_unchecked()?);
+            JsArrayAccess::new(&js_object, js_object.get_array_length_unchecked()?, self.1);
           visitor.visit_seq(&mut deserializer)
         } else if js_object.is_typedarray()? {
           visitor.visit_bytes(unsafe { FromNapiValue::from_napi_value(self.0.env, self.0.value)? })
@@ -57,8 +65,11 @@ impl<'x> serde::de::Deserializer<'x> for &mut De<'_> {
             return visitor.visit_bytes(&[]);
           }
           visitor.visit_bytes(array_buf.data)
+        } else if self.1.map && is_js_map(self.0.env, self.0.value)? {
+          let mut deserializer = JsMapAccess::new(&js_object, self.1)?;
+          visitor.visit_map(&mut deserializer)
         } else {
-          let mut deserializer = JsObjectAccess::new(&js_object)?;
+          let mut deserializer = JsObjectAccess::new(&js_object, self.1)?;
           visitor.visit_map(&mut deserializer)
         }
       }
@@ -160,6 +171,7 @@ impl<'x> serde::de::Deserializer<'x> for &mut De<'_> {
       ValueType::String => visitor.visit_enum(JsEnumAccess::new(
         unsafe { FromNapiValue::from_napi_value(self.0.env, self.0.value) }?,
         None,
+        self.1,
       )),
       ValueType::Object => {
         let js_object = Object::from_raw(self.0.env, self.0.value);
@@ -173,7 +185,7 @@ impl<'x> serde::de::Deserializer<'x> for &mut De<'_> {
         } else {
           let key = properties.get_element::<String>(0)?;
           let value: Unknown = js_object.get_named_property_unchecked(&key)?;
-          visitor.visit_enum(JsEnumAccess::new(key, Some(&value.0)))
+          visitor.visit_enum(JsEnumAccess::new(key, Some(&value.0), self.1))
         }
       }
       _ => Err(Error::new(
@@ -202,12 +214,17 @@ impl<'x> serde::de::Deserializer<'x> for &mut De<'_> {
 pub(crate) struct JsEnumAccess<'env> {
   variant: String,
   value: Option<&'env Value>,
+  options: SerdeOptions,
 }
 
 #[doc(hidden)]
 impl<'env> JsEnumAccess<'env> {
-  fn new(variant: String, value: Option<&'env Value>) -> Self {
-    Self { variant, value }
+  fn new(variant: String, value: Option<&'env Value>, options: SerdeOptions) -> Self {
+    Self {
+      variant,
+      value,
+      options,
+    }
   }
 }
 
@@ -222,7 +239,10 @@ impl<'de, 'env> EnumAccess<'de> for JsEnumAccess<'env> {
   {
     use serde::de::IntoDeserializer;
     let variant = self.variant.into_deserializer();
-    let variant_access = JsVariantAccess { value: self.value };
+    let variant_access = JsVariantAccess {
+      value: self.value,
+      options: self.options,
+    };
     seed.deserialize(variant).map(|v| (v, variant_access))
   }
 }
@@ -230,6 +250,7 @@ impl<'de, 'env> EnumAccess<'de> for JsEnumAccess<'env> {
 #[doc(hidden)]
 pub(crate) struct JsVariantAccess<'env> {
   value: Option<&'env Value>,
+  options: SerdeOptions,
 }
 
 #[doc(hidden)]
@@ -238,7 +259,7 @@ impl<'de> VariantAccess<'de> for JsVariantAccess<'_> {
   fn unit_variant(self) -> Result<()> {
     match self.value {
       Some(val) => {
-        let mut deserializer = De(val);
+        let mut deserializer = De(val, self.options);
         serde::de::Deserialize::deserialize(&mut deserializer)
       }
       None => Ok(()),
@@ -251,7 +272,7 @@ impl<'de> VariantAccess<'de> for JsVariantAccess<'_> {
   {
     match self.value {
       Some(val) => {
-        let mut deserializer = De(val);
+        let mut deserializer = De(val, self.options);
         seed.deserialize(&mut deserializer)
       }
       None => Err(serde::de::Error::invalid_type(
@@ -269,8 +290,11 @@ impl<'de> VariantAccess<'de> for JsVariantAccess<'_> {
       Some(js_value) => {
         let js_object = Object::from_raw(js_value.env, js_value.value);
         if js_object.is_array()? {
-          let mut deserializer =
-            JsArrayAccess::new(&js_object, js_object.get_array_length_unchecked()?);
+          let mut deserializer = JsArrayAccess::new(
+            &js_object,
+            js_object.get_array_length_unchecked()?,
+            self.options,
+          );
           visitor.visit_seq(&mut deserializer)
         } else {
           Err(serde::de::Error::invalid_type(
@@ -293,7 +317,7 @@ impl<'de> VariantAccess<'de> for JsVariantAccess<'_> {
     match self.value {
       Some(js_value) => {
         if let Ok(val) = unsafe { Object::from_napi_value(js_value.env, js_value.value) } {
-          let mut deserializer = JsObjectAccess::new(&val)?;
+          let mut deserializer = JsObjectAccess::new(&val, self.options)?;
           visitor.visit_map(&mut deserializer)
         } else {
           Err(serde::de::Error::invalid_type(
@@ -315,12 +339,18 @@ struct JsArrayAccess<'env> {
   input: &'env Object<'env>,
   idx: u32,
   len: u32,
+  options: SerdeOptions,
 }
 
 #[doc(hidden)]
 impl<'env> JsArrayAccess<'env> {
-  fn new(input: &'env Object, len: u32) -> Self {
-    Self { input, idx: 0, len }
+  fn new(input: &'env Object, len: u32, options: SerdeOptions) -> Self {
+    Self {
+      input,
+      idx: 0,
+      len,
+      options,
+    }
   }
 }
 
@@ -338,7 +368,7 @@ impl<'de> SeqAccess<'de> for JsArrayAccess<'_> {
     let v = self.input.get_element::<Unknown>(self.idx)?;
     self.idx += 1;
 
-    let mut de = De(&v.0);
+    let mut de = De(&v.0, self.options);
     seed.deserialize(&mut de).map(Some)
   }
 }
@@ -349,11 +379,12 @@ pub(crate) struct JsObjectAccess<'env> {
   properties: Object<'env>,
   idx: u32,
   property_len: u32,
+  options: SerdeOptions,
 }
 
 #[doc(hidden)]
 impl<'env> JsObjectAccess<'env> {
-  fn new(value: &'env Object) -> Result<Self> {
+  fn new(value: &'env Object, options: SerdeOptions) -> Result<Self> {
     let properties = value.get_property_names()?;
     let property_len = properties.get_array_length_unchecked()?;
     Ok(Self {
@@ -361,6 +392,7 @@ impl<'env> JsObjectAccess<'env> {
       properties,
       idx: 0,
       property_len,
+      options,
     })
   }
 }
@@ -379,7 +411,7 @@ impl<'de> MapAccess<'de> for JsObjectAccess<'_> {
 
     let prop_name = self.properties.get_element::<Unknown>(self.idx)?;
 
-    let mut de = De(&prop_name.0);
+    let mut de = De(&prop_name.0, self.options);
     seed.deserialize(&mut de).map(Some)
   }
 
@@ -397,8 +429,61 @@ impl<'de> MapAccess<'de> for JsObjectAccess<'_> {
     let value: Unknown = self.value.get_named_property_unchecked(&prop_name)?;
 
     self.idx += 1;
-    let mut de = De(&value.0);
+    let mut de = De(&value.0, self.options);
     let res = seed.deserialize(&mut de)?;
     Ok(res)
   }
 }
+
+#[doc(hidden)]
+struct JsMapAccess<'env> {
+  entries: std::vec::IntoIter<(Unknown<'env>, Unknown<'env>)>,
+  value: Option<Unknown<'env>>,
+  options: SerdeOptions,
+}
+
+#[doc(hidden)]
+impl<'env> JsMapAccess<'env> {
+  fn new(map: &Object<'env>, options: SerdeOptions) -> Result<Self> {
+    let mut entries = Vec::new();
+    for_each_js_map_entry(map.0.env, map.0.value, |key, value| {
+      entries.push((key, value));
+    })?;
+    Ok(Self {
+      entries: entries.into_iter(),
+      value: None,
+      options,
+    })
+  }
+}
+
+#[doc(hidden)]
+impl<'de> MapAccess<'de> for JsMapAccess<'_> {
+  type Error = Error;
+
+  fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>>
+  where
+    K: DeserializeSeed<'de>,
+  {
+    let Some((key, value)) = self.entries.next() else {
+      return Ok(None);
+    };
+    self.value = Some(value);
+    let mut de = De(&key.0, self.options);
+    seed.deserialize(&mut de).map(Some)
+  }
+
+  fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value>
+  where
+    V: DeserializeSeed<'de>,
+  {
+    let value = self.value.take().ok_or_else(|| {
+      Error::new(
+        Status::InvalidArg,
+        "Map value requested before its key".to_owned(),
+      )
+    })?;
+    let mut de = De(&value.0, self.options);
+    seed.deserialize(&mut de)
+  }
+}
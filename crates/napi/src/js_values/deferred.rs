@@ -6,7 +6,7 @@ use std::{
 };
 
 #[cfg(feature = "deferred_trace")]
-use crate::{bindgen_runtime::JsObjectValue, JsValue};
+use crate::{bindgen_runtime::JsObjectValue, error::append_backtrace, JsValue};
 use crate::{
   bindgen_runtime::{Object, ToNapiValue},
   check_status, sys, Env, Error, Result,
@@ -54,7 +54,10 @@ impl DeferredTrace {
     )?;
 
     let mut obj = Object::from_raw(raw_env, raw);
-    let err_value = if !err.maybe_raw.is_null() {
+    let backtrace = err.take_backtrace();
+    let err_value = if let Some(create_value) = err.take_value() {
+      create_value(raw_env)
+    } else if !err.maybe_raw.is_null() {
       let mut err_raw_value = std::ptr::null_mut();
       check_status!(
         unsafe { sys::napi_get_reference_value(raw_env, err.maybe_raw, &mut err_raw_value) },
@@ -91,8 +94,14 @@ impl DeferredTrace {
         "code",
         env.create_string_from_std(format!("{}", err.status))?,
       )?;
+      if let Some(cause) = err.cause.take() {
+        obj.set_named_property("cause", *cause)?;
+      }
       Ok(raw)
     };
+    if let (Ok(value), Some(backtrace)) = (&err_value, backtrace) {
+      unsafe { append_backtrace(raw_env, *value, &backtrace) };
+    }
     check_status!(
       unsafe { sys::napi_delete_reference(raw_env, self.0) },
       "Failed to get referenced value in DeferredTrace"
@@ -109,6 +118,7 @@ struct DeferredData<Data: ToNapiValue, Resolver: FnOnce(Env) -> Result<Data>> {
   trace: DeferredTrace,
   tsfn: sys::napi_threadsafe_function,
   finalize_callback: FinalizeCallback,
+  aborted: bool,
 }
 
 pub struct JsDeferred<Data: ToNapiValue, Resolver: FnOnce(Env) -> Result<Data>> {
@@ -161,12 +171,24 @@ impl<Data: ToNapiValue, Resolver: FnOnce(Env) -> Result<Data>> JsDeferred<Data,
   /// Consumes the deferred, and resolves the promise. The provided function will be called
   /// from the JavaScript thread, and should return the resolved value.
   pub fn resolve(self, resolver: Resolver) {
-    self.call_tsfn(Ok(resolver))
+    self.call_tsfn(Ok(resolver), false)
   }
 
   /// Consumes the deferred, and rejects the promise with the provided error.
   pub fn reject(self, error: Error) {
-    self.call_tsfn(Err(error))
+    self.call_tsfn(Err(error), false)
+  }
+
+  #[cfg(all(feature = "tokio_rt", not(feature = "noop")))]
+  /// Consumes the deferred, and rejects the promise with an `AbortError`.
+  pub(crate) fn abort(self) {
+    self.call_tsfn(
+      Err(Error::new(
+        crate::Status::Cancelled,
+        "AbortError".to_owned(),
+      )),
+      true,
+    )
   }
 
   #[allow(clippy::arc_with_non_send_sync)]
@@ -177,13 +199,14 @@ impl<Data: ToNapiValue, Resolver: FnOnce(Env) -> Result<Data>> JsDeferred<Data,
     self.finalize_callback = Arc::new(RwLock::new(finalize_callback));
   }
 
-  fn call_tsfn(self, result: Result<Resolver>) {
+  fn call_tsfn(self, result: Result<Resolver>, aborted: bool) {
     let data = DeferredData {
       resolver: result,
       #[cfg(feature = "deferred_trace")]
       trace: self.trace,
       tsfn: self.tsfn,
       finalize_callback: self.finalize_callback.clone(),
+      aborted,
     };
 
     // Call back into the JS thread via a threadsafe function. This results in napi_resolve_deferred being called.
@@ -293,10 +316,22 @@ extern "C" fn napi_resolve_deferred<Data: ToNapiValue, Resolver: FnOnce(Env) ->
       }
     })
   }) {
-    #[cfg(feature = "deferred_trace")]
-    let error = deferred_data.trace.into_rejected(env, e);
-    #[cfg(not(feature = "deferred_trace"))]
-    let error = Ok::<sys::napi_value, Error>(unsafe { crate::JsError::from(e).into_value(env) });
+    let error = if deferred_data.aborted {
+      #[cfg(feature = "deferred_trace")]
+      unsafe {
+        sys::napi_delete_reference(env, deferred_data.trace.0)
+      };
+      crate::async_work::create_abort_error(env)
+    } else {
+      #[cfg(feature = "deferred_trace")]
+      {
+        deferred_data.trace.into_rejected(env, e)
+      }
+      #[cfg(not(feature = "deferred_trace"))]
+      {
+        Ok::<sys::napi_value, Error>(unsafe { crate::JsError::from(e).into_value(env) })
+      }
+    };
 
     match error {
       Ok(error) => {
@@ -84,7 +84,7 @@ pub use number::JsNumber;
 pub use object::*;
 pub use object_property::*;
 #[cfg(feature = "serde-json")]
-pub use ser::Ser;
+pub use ser::{ExternalBytes, Ser, SerdeOptions};
 pub use string::*;
 pub use symbol::*;
 pub(crate) use tagged_object::TaggedObject;
@@ -41,6 +41,7 @@ pub struct Property {
   attrs: PropertyAttributes,
   value: sys::napi_value,
   pub(crate) is_ctor: bool,
+  pub(crate) well_known_symbol: Option<&'static str>,
   #[cfg(feature = "napi5")]
   pub(crate) closures: PropertyClosures,
 }
@@ -56,6 +57,7 @@ impl Default for Property {
       attrs: Default::default(),
       value: ptr::null_mut(),
       is_ctor: Default::default(),
+      well_known_symbol: None,
       #[cfg(feature = "napi5")]
       closures: PropertyClosures::default(),
     }
@@ -95,6 +97,14 @@ impl Property {
     Ok(self)
   }
 
+  #[doc(hidden)]
+  /// Use the well-known symbol `Symbol[symbol]` as the key of the class property,
+  /// the property is skipped if the symbol doesn't exist in the current runtime
+  pub fn with_well_known_symbol(mut self, symbol: &'static str) -> Self {
+    self.well_known_symbol = Some(symbol);
+    self
+  }
+
   pub fn with_name<T: ToNapiValue>(mut self, env: &Env, name: T) -> Result<Self> {
     self.name = unsafe { T::to_napi_value(env.0, name)? };
     Ok(self)
@@ -194,6 +204,30 @@ impl Property {
     }
   }
 
+  /// Key used to detect the properties overridden by the subclass
+  #[cfg(not(feature = "noop"))]
+  pub(crate) fn key(&self) -> Option<String> {
+    match (self.well_known_symbol, &self.utf8_name) {
+      (Some(symbol), _) => Some(format!("[Symbol.{symbol}]")),
+      (None, Some(name)) => Some(name.to_string_lossy().into_owned()),
+      (None, None) => None,
+    }
+  }
+
+  /// The name of the property, if it's given by [`Property::with_utf8_name`]
+  #[cfg(not(feature = "noop"))]
+  pub(crate) fn utf8_name(&self) -> Option<&str> {
+    self
+      .utf8_name
+      .as_deref()
+      .and_then(|name| name.to_str().ok())
+  }
+
+  #[cfg(not(feature = "noop"))]
+  pub(crate) fn is_static(&self) -> bool {
+    self.attrs.contains(PropertyAttributes::Static)
+  }
+
   pub fn with_ctor(mut self, callback: Callback) -> Self {
     self.method = Some(callback);
     self.is_ctor = true;
@@ -1,20 +1,300 @@
-use std::{marker::PhantomData, result::Result as StdResult};
+use std::{
+  ffi::c_void, fmt, marker::PhantomData, ops::Deref, ptr, result::Result as StdResult, sync::Arc,
+};
 
-use serde::{ser, Serialize, Serializer};
+use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
 
 use crate::{
-  bindgen_runtime::{Array, BufferSlice, JsObjectValue, Null, Object, ToNapiValue},
-  Env, Error, JsString, JsValue, Result, Unknown, Value, ValueType,
+  bindgen_runtime::{create_js_map, Array, JsObjectValue, Null, Object, ToNapiValue},
+  check_status, sys, Env, Error, JsString, JsValue, Result, Unknown, Value, ValueType,
 };
 
-pub struct Ser<'env>(pub(crate) &'env Env);
+/// Options of the serde [`Ser`] and [`De`](crate::De)
+///
+/// ```
+/// use napi::SerdeOptions;
+///
+/// let options = SerdeOptions::new().with_bigint(true).with_map(true);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SerdeOptions {
+  pub(crate) bytes_as_uint8_array: bool,
+  pub(crate) bigint: bool,
+  pub(crate) map: bool,
+  /// The bytes being serialized are lent by an `ExternalBytes`
+  pub(crate) shared_bytes: bool,
+}
+
+impl SerdeOptions {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Serialize bytes (`serde_bytes` fields, `ByteBuf`...) as `Uint8Array` instead of `Buffer`.
+  ///
+  /// serde only lends the bytes to the serializer, so they are copied once, straight into the memory owned by JavaScript.
+  /// Wrap owned bytes in [`ExternalBytes`] to move them into JavaScript without copying.
+  pub fn with_bytes_as_uint8_array(mut self, bytes_as_uint8_array: bool) -> Self {
+    self.bytes_as_uint8_array = bytes_as_uint8_array;
+    self
+  }
+
+  /// Serialize `i64`, `u64`, `i128` and `u128` as `BigInt`, even if the value fits in a `number`.
+  ///
+  /// Requires the `napi6` feature.
+  pub fn with_bigint(mut self, bigint: bool) -> Self {
+    self.bigint = bigint;
+    self
+  }
+
+  /// Serialize maps as JavaScript `Map` instead of plain objects, and deserialize `Map` as maps.
+  ///
+  /// Keys of a `Map` keep their type, so they are not limited to strings.
+  pub fn with_map(mut self, map: bool) -> Self {
+    self.map = map;
+    self
+  }
+}
+
+/// The name of the newtype struct `ExternalBytes` is serialized as, [`Ser`] shares the bytes in it instead of copying them
+const EXTERNAL_BYTES_TOKEN: &str = "$napi::private::ExternalBytes";
+
+/// Immutable bytes shared with a JavaScript `Buffer` (or `Uint8Array`, see [`SerdeOptions::with_bytes_as_uint8_array`])
+/// by [`Ser`] without copying them.
+///
+/// serde only lends `Vec<u8>`, `serde_bytes::ByteBuf`... to the serializer, so their bytes have to be copied.
+/// Serializing an `ExternalBytes` with [`Ser`] moves a clone of it into the JavaScript value instead,
+/// which drops it when it's garbage collected. Cloning is cheap, the bytes are reference counted.
+/// The JavaScript value must be treated as read-only, writing to it would change the bytes of all the clones.
+/// Other serializers see it as plain bytes, and it's deserialized from bytes.
+///
+/// ```no_run
+/// use napi::{bindgen_prelude::*, ExternalBytes};
+/// use napi_derive::napi;
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct Frame {
+///   id: u32,
+///   payload: ExternalBytes,
+/// }
+///
+/// #[napi]
+/// pub fn read_frame(env: &Env) -> Result<Unknown<'_>> {
+///   let payload = vec![0; 1024 * 1024];
+///   env.to_js_value(&Frame { id: 1, payload: payload.into() })
+/// }
+/// ```
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct ExternalBytes(Arc<[u8]>);
+
+impl ExternalBytes {
+  pub fn new(bytes: Vec<u8>) -> Self {
+    Self(bytes.into())
+  }
+
+  pub fn as_bytes(&self) -> &[u8] {
+    &self.0
+  }
+}
+
+impl Deref for ExternalBytes {
+  type Target = [u8];
+
+  fn deref(&self) -> &[u8] {
+    &self.0
+  }
+}
+
+impl AsRef<[u8]> for ExternalBytes {
+  fn as_ref(&self) -> &[u8] {
+    &self.0
+  }
+}
+
+impl From<Vec<u8>> for ExternalBytes {
+  fn from(bytes: Vec<u8>) -> Self {
+    Self::new(bytes)
+  }
+}
+
+impl From<Arc<[u8]>> for ExternalBytes {
+  fn from(bytes: Arc<[u8]>) -> Self {
+    Self(bytes)
+  }
+}
+
+impl From<ExternalBytes> for Arc<[u8]> {
+  fn from(bytes: ExternalBytes) -> Self {
+    bytes.0
+  }
+}
+
+impl fmt::Debug for ExternalBytes {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_tuple("ExternalBytes").field(&&*self.0).finish()
+  }
+}
+
+impl Serialize for ExternalBytes {
+  fn serialize<S: Serializer>(&self, serializer: S) -> StdResult<S::Ok, S::Error> {
+    serializer.serialize_newtype_struct(EXTERNAL_BYTES_TOKEN, &SharedBytes(&self.0))
+  }
+}
+
+/// Serialized as the bytes of the `Arc`, which `Ser` recovers from them
+struct SharedBytes<'a>(&'a Arc<[u8]>);
+
+impl Serialize for SharedBytes<'_> {
+  fn serialize<S: Serializer>(&self, serializer: S) -> StdResult<S::Ok, S::Error> {
+    serializer.serialize_bytes(self.0)
+  }
+}
+
+impl<'de> Deserialize<'de> for ExternalBytes {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> StdResult<Self, D::Error> {
+    struct BytesVisitor;
+
+    impl<'de> de::Visitor<'de> for BytesVisitor {
+      type Value = ExternalBytes;
+
+      fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("bytes")
+      }
+
+      fn visit_bytes<E: de::Error>(self, v: &[u8]) -> StdResult<Self::Value, E> {
+        Ok(v.to_vec().into())
+      }
+
+      fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> StdResult<Self::Value, E> {
+        Ok(v.into())
+      }
+
+      fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> StdResult<Self::Value, A::Error> {
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+        while let Some(byte) = seq.next_element()? {
+          bytes.push(byte);
+        }
+        Ok(bytes.into())
+      }
+    }
+
+    deserializer.deserialize_byte_buf(BytesVisitor)
+  }
+}
+
+pub struct Ser<'env>(pub(crate) &'env Env, pub(crate) SerdeOptions);
 
 impl<'env> Ser<'env> {
   pub fn new(env: &'env Env) -> Self {
-    Self(env)
+    Self(env, SerdeOptions::default())
+  }
+
+  pub fn with_options(env: &'env Env, options: SerdeOptions) -> Self {
+    Self(env, options)
+  }
+
+  /// Share the bytes of an `ExternalBytes` with an external `Buffer` or `Uint8Array`,
+  /// `v` is lent from its `Arc<[u8]>` by `SharedBytes`
+  fn serialize_shared_bytes(self, v: &[u8]) -> Result<Value> {
+    let options = SerdeOptions {
+      shared_bytes: false,
+      ..self.1
+    };
+    if v.is_empty() {
+      return Ser(self.0, options).serialize_bytes(v);
+    }
+    let env = self.0.raw();
+    let data = ptr::slice_from_raw_parts(v.as_ptr(), v.len());
+    // SAFETY: the private `EXTERNAL_BYTES_TOKEN` is only serialized by `ExternalBytes`,
+    // with the bytes of its `Arc<[u8]>`, which is alive during the serialization
+    let bytes = unsafe {
+      Arc::increment_strong_count(data);
+      Arc::from_raw(data)
+    };
+    let finalize_hint = Box::into_raw(Box::new(bytes));
+    let mut value = ptr::null_mut();
+    let status = if self.1.bytes_as_uint8_array {
+      let mut arraybuffer = ptr::null_mut();
+      let status = unsafe {
+        sys::napi_create_external_arraybuffer(
+          env,
+          v.as_ptr().cast_mut().cast(),
+          v.len(),
+          Some(drop_shared_bytes),
+          finalize_hint.cast(),
+          &mut arraybuffer,
+        )
+      };
+      if status == sys::Status::napi_ok {
+        check_status!(
+          unsafe {
+            sys::napi_create_typedarray(
+              env,
+              sys::TypedarrayType::uint8_array,
+              v.len(),
+              arraybuffer,
+              0,
+              &mut value,
+            )
+          },
+          "Failed to create Uint8Array in serialize_bytes"
+        )?;
+      }
+      status
+    } else {
+      unsafe {
+        sys::napi_create_external_buffer(
+          env,
+          v.len(),
+          v.as_ptr().cast_mut().cast(),
+          Some(drop_shared_bytes),
+          finalize_hint.cast(),
+          &mut value,
+        )
+      }
+    };
+    if status == sys::Status::napi_no_external_buffers_allowed {
+      drop(unsafe { Box::from_raw(finalize_hint) });
+      return Ser(self.0, options).serialize_bytes(v);
+    }
+    if status != sys::Status::napi_ok {
+      drop(unsafe { Box::from_raw(finalize_hint) });
+    }
+    check_status!(status, "Failed to create external bytes in serialize_bytes")?;
+    Ok(Value {
+      env,
+      value,
+      value_type: ValueType::Object,
+    })
+  }
+
+  #[cfg(feature = "napi6")]
+  fn serialize_bigint(self, v: i128) -> Result<Value> {
+    Ok(Value {
+      env: self.0.raw(),
+      value: unsafe { ToNapiValue::to_napi_value(self.0 .0, v)? },
+      value_type: ValueType::BigInt,
+    })
+  }
+
+  #[cfg(not(feature = "napi6"))]
+  fn serialize_bigint(self, _v: i128) -> Result<Value> {
+    Err(Error::new(
+      crate::Status::InvalidArg,
+      "Serialize as BigInt requires the napi6 feature",
+    ))
   }
 }
 
+unsafe extern "C" fn drop_shared_bytes(
+  _env: sys::napi_env,
+  _finalize_data: *mut c_void,
+  finalize_hint: *mut c_void,
+) {
+  drop(unsafe { Box::<Arc<[u8]>>::from_raw(finalize_hint.cast()) });
+}
+
 impl<'env> Serializer for Ser<'env> {
   type Ok = Value;
   type Error = Error;
@@ -36,9 +316,45 @@ impl<'env> Serializer for Ser<'env> {
   }
 
   fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok> {
-    BufferSlice::from_data(self.0, v.to_owned()).map(|bs| Value {
-      env: self.0.raw(),
-      value: bs.raw_value,
+    if self.1.shared_bytes {
+      return self.serialize_shared_bytes(v);
+    }
+    let env = self.0.raw();
+    let mut value = ptr::null_mut();
+    if self.1.bytes_as_uint8_array {
+      let mut data = ptr::null_mut();
+      let mut arraybuffer = ptr::null_mut();
+      check_status!(
+        unsafe { sys::napi_create_arraybuffer(env, v.len(), &mut data, &mut arraybuffer) },
+        "Failed to create ArrayBuffer in serialize_bytes"
+      )?;
+      if !v.is_empty() {
+        unsafe { ptr::copy_nonoverlapping(v.as_ptr(), data.cast(), v.len()) };
+      }
+      check_status!(
+        unsafe {
+          sys::napi_create_typedarray(
+            env,
+            sys::TypedarrayType::uint8_array,
+            v.len(),
+            arraybuffer,
+            0,
+            &mut value,
+          )
+        },
+        "Failed to create Uint8Array in serialize_bytes"
+      )?;
+    } else {
+      check_status!(
+        unsafe {
+          sys::napi_create_buffer_copy(env, v.len(), v.as_ptr().cast(), ptr::null_mut(), &mut value)
+        },
+        "Failed to create Buffer in serialize_bytes"
+      )?;
+    }
+    Ok(Value {
+      env,
+      value,
       value_type: ValueType::Object,
     })
   }
@@ -82,6 +398,9 @@ impl<'env> Serializer for Ser<'env> {
   }
 
   fn serialize_i64(self, v: i64) -> Result<Self::Ok> {
+    if self.1.bigint {
+      return self.serialize_bigint(v.into());
+    }
     Ok(Value {
       env: self.0.raw(),
       value: unsafe { ToNapiValue::to_napi_value(self.0 .0, v)? },
@@ -131,7 +450,9 @@ impl<'env> Serializer for Ser<'env> {
     not(feature = "napi6")
   ))]
   fn serialize_u64(self, v: u64) -> Result<Self::Ok> {
-    if v <= u32::MAX.into() {
+    if self.1.bigint {
+      self.serialize_bigint(v.into())
+    } else if v <= u32::MAX.into() {
       self.serialize_u32(v as u32)
     } else {
       Err(Error::new(
@@ -147,7 +468,7 @@ impl<'env> Serializer for Ser<'env> {
     // serde_json::Value by default uses u64 for positive integers. This results in napirs using a BigInt instead of a number when converting to a js value.
     // To avoid this, we need to check if the value fits into a smaller number type.
     // If this is the case, we use the smaller type instead.
-    if v <= u32::MAX.into() {
+    if v <= u32::MAX.into() && !self.1.bigint {
       self.serialize_u32(v as u32)
     } else {
       Ok(Value {
@@ -237,16 +558,25 @@ impl<'env> Serializer for Ser<'env> {
     value.serialize(self)
   }
 
-  fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap> {
+  fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap> {
     let env = self.0;
     let key = env.create_string("")?;
-    let obj = Object::new(env)?;
-    Ok(MapSerializer { key, obj })
+    let target = if self.1.map {
+      MapTarget::Map(Vec::with_capacity(len.unwrap_or_default()))
+    } else {
+      MapTarget::Object(Object::new(env)?)
+    };
+    Ok(MapSerializer {
+      key,
+      target,
+      options: self.1,
+    })
   }
 
   fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq> {
     let array = Array::new(self.0.raw(), len.unwrap_or(0) as u32)?;
     Ok(SeqSerializer {
+      options: self.1,
       current_index: 0,
       array,
     })
@@ -274,6 +604,7 @@ impl<'env> Serializer for Ser<'env> {
       ),
     )?;
     Ok(SeqSerializer {
+      options: self.1,
       current_index: 0,
       array,
     })
@@ -296,10 +627,17 @@ impl<'env> Serializer for Ser<'env> {
     self.0.create_string(variant).map(|string| string.0)
   }
 
-  fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<Self::Ok>
+  fn serialize_newtype_struct<T>(self, name: &'static str, value: &T) -> Result<Self::Ok>
   where
     T: ?Sized + Serialize,
   {
+    if name == EXTERNAL_BYTES_TOKEN {
+      let options = SerdeOptions {
+        shared_bytes: true,
+        ..self.1
+      };
+      return value.serialize(Ser(self.0, options));
+    }
     value.serialize(self)
   }
 
@@ -323,6 +661,7 @@ impl<'env> Serializer for Ser<'env> {
 
   fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple> {
     Ok(SeqSerializer {
+      options: self.1,
       array: Array::new(self.0.raw(), len as u32)?,
       current_index: 0,
     })
@@ -334,6 +673,7 @@ impl<'env> Serializer for Ser<'env> {
     len: usize,
   ) -> Result<Self::SerializeTupleStruct> {
     Ok(SeqSerializer {
+      options: self.1,
       array: Array::new(self.0.raw(), len as u32)?,
       current_index: 0,
     })
@@ -341,6 +681,7 @@ impl<'env> Serializer for Ser<'env> {
 
   fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeStruct> {
     Ok(StructSerializer {
+      options: self.1,
       obj: Object::new(self.0)?,
     })
   }
@@ -366,6 +707,7 @@ impl<'env> Serializer for Ser<'env> {
       ),
     )?;
     Ok(StructSerializer {
+      options: self.1,
       obj: Object::new(self.0)?,
     })
   }
@@ -374,6 +716,7 @@ impl<'env> Serializer for Ser<'env> {
 pub struct SeqSerializer<'env> {
   array: Array<'env>,
   current_index: usize,
+  options: SerdeOptions,
 }
 
 impl ser::SerializeSeq for SeqSerializer<'_> {
@@ -387,7 +730,10 @@ impl ser::SerializeSeq for SeqSerializer<'_> {
     let env = Env::from_raw(self.array.env);
     self.array.set_element(
       self.current_index as _,
-      Unknown(value.serialize(Ser::new(&env))?, std::marker::PhantomData),
+      Unknown(
+        value.serialize(Ser(&env, self.options))?,
+        std::marker::PhantomData,
+      ),
     )?;
     self.current_index += 1;
     Ok(())
@@ -410,7 +756,10 @@ impl ser::SerializeTuple for SeqSerializer<'_> {
     let env = Env::from_raw(self.array.env);
     self.array.set_element(
       self.current_index as _,
-      Unknown(value.serialize(Ser::new(&env))?, std::marker::PhantomData),
+      Unknown(
+        value.serialize(Ser(&env, self.options))?,
+        std::marker::PhantomData,
+      ),
     )?;
     self.current_index += 1;
     Ok(())
@@ -433,7 +782,10 @@ impl ser::SerializeTupleStruct for SeqSerializer<'_> {
     let env = Env::from_raw(self.array.env);
     self.array.set_element(
       self.current_index as _,
-      Unknown(value.serialize(Ser::new(&env))?, std::marker::PhantomData),
+      Unknown(
+        value.serialize(Ser(&env, self.options))?,
+        std::marker::PhantomData,
+      ),
     )?;
     self.current_index += 1;
     Ok(())
@@ -456,7 +808,10 @@ impl ser::SerializeTupleVariant for SeqSerializer<'_> {
     let env = Env::from_raw(self.array.env);
     self.array.set_element(
       self.current_index as _,
-      Unknown(value.serialize(Ser::new(&env))?, std::marker::PhantomData),
+      Unknown(
+        value.serialize(Ser(&env, self.options))?,
+        std::marker::PhantomData,
+      ),
     )?;
     self.current_index += 1;
     Ok(())
@@ -469,7 +824,27 @@ impl ser::SerializeTupleVariant for SeqSerializer<'_> {
 
 pub struct MapSerializer<'env> {
   key: JsString<'env>,
-  obj: Object<'env>,
+  target: MapTarget<'env>,
+  options: SerdeOptions,
+}
+
+/// What a map is serialized into
+enum MapTarget<'env> {
+  Object(Object<'env>),
+  /// entries of the JavaScript `Map`, if maps are serialized as `Map`
+  Map(Vec<(Unknown<'env>, Unknown<'env>)>),
+}
+
+impl<'env> MapTarget<'env> {
+  fn insert(&mut self, key: Value, value: Unknown<'env>) -> Result<()> {
+    match self {
+      MapTarget::Object(obj) => obj.set_property(JsString(key, std::marker::PhantomData), value),
+      MapTarget::Map(entries) => {
+        entries.push((Unknown(key, std::marker::PhantomData), value));
+        Ok(())
+      }
+    }
+  }
 }
 
 #[doc(hidden)]
@@ -481,8 +856,11 @@ impl ser::SerializeMap for MapSerializer<'_> {
   where
     T: ?Sized + Serialize,
   {
-    let env = Env::from_raw(self.obj.0.env);
-    self.key = JsString(key.serialize(Ser::new(&env))?, std::marker::PhantomData);
+    let env = Env::from_raw(self.key.0.env);
+    self.key = JsString(
+      key.serialize(Ser(&env, self.options))?,
+      std::marker::PhantomData,
+    );
     Ok(())
   }
 
@@ -490,12 +868,13 @@ impl ser::SerializeMap for MapSerializer<'_> {
   where
     T: ?Sized + Serialize,
   {
-    let env = Env::from_raw(self.obj.0.env);
-    self.obj.set_property(
-      JsString::from_raw(self.key.0.env, self.key.0.value),
-      Unknown(value.serialize(Ser::new(&env))?, std::marker::PhantomData),
-    )?;
-    Ok(())
+    let env = Env::from_raw(self.key.0.env);
+    let key = JsString::from_raw(self.key.0.env, self.key.0.value);
+    let value = Unknown(
+      value.serialize(Ser(&env, self.options))?,
+      std::marker::PhantomData,
+    );
+    self.target.insert(key.0, value)
   }
 
   fn serialize_entry<K, V>(&mut self, key: &K, value: &V) -> StdResult<(), Self::Error>
@@ -503,21 +882,33 @@ impl ser::SerializeMap for MapSerializer<'_> {
     K: ?Sized + Serialize,
     V: ?Sized + Serialize,
   {
-    let env = Env::from_raw(self.obj.0.env);
-    self.obj.set_property(
-      JsString(key.serialize(Ser::new(&env))?, std::marker::PhantomData),
-      Unknown(value.serialize(Ser::new(&env))?, std::marker::PhantomData),
-    )?;
-    Ok(())
+    let env = Env::from_raw(self.key.0.env);
+    let key = key.serialize(Ser(&env, self.options))?;
+    let value = Unknown(
+      value.serialize(Ser(&env, self.options))?,
+      std::marker::PhantomData,
+    );
+    self.target.insert(key, value)
   }
 
   fn end(self) -> Result<Self::Ok> {
-    Ok(self.obj.0)
+    match self.target {
+      MapTarget::Object(obj) => Ok(obj.0),
+      MapTarget::Map(entries) => {
+        let env = self.key.0.env;
+        Ok(Value {
+          env,
+          value: create_js_map(env, entries)?,
+          value_type: ValueType::Object,
+        })
+      }
+    }
   }
 }
 
 pub struct StructSerializer<'env> {
   obj: Object<'env>,
+  options: SerdeOptions,
 }
 
 #[doc(hidden)]
@@ -532,7 +923,10 @@ impl ser::SerializeStruct for StructSerializer<'_> {
     let env = Env::from_raw(self.obj.0.env);
     self.obj.set_named_property(
       key,
-      Unknown(value.serialize(Ser::new(&env))?, std::marker::PhantomData),
+      Unknown(
+        value.serialize(Ser(&env, self.options))?,
+        std::marker::PhantomData,
+      ),
     )?;
     Ok(())
   }
@@ -554,7 +948,10 @@ impl ser::SerializeStructVariant for StructSerializer<'_> {
     let env = Env::from_raw(self.obj.0.env);
     self.obj.set_named_property(
       key,
-      Unknown(value.serialize(Ser::new(&env))?, std::marker::PhantomData),
+      Unknown(
+        value.serialize(Ser(&env, self.options))?,
+        std::marker::PhantomData,
+      ),
     )?;
     Ok(())
   }
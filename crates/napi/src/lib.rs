@@ -83,6 +83,7 @@ mod async_work;
 mod bindgen_runtime;
 #[cfg(feature = "compat-mode")]
 mod call_context;
+mod capability;
 #[cfg(feature = "napi3")]
 mod cleanup_env;
 mod env;
@@ -111,10 +112,13 @@ pub use async_work::AsyncWorkPromise;
 pub use call_context::CallContext;
 
 pub use bindgen_runtime::iterator;
+pub use capability::Capability;
 pub use env::*;
 pub use error::*;
 pub use js_values::*;
 pub use status::Status;
+#[cfg(feature = "napi5")]
+pub use task::ProgressTask;
 pub use task::{ScopedTask, Task};
 pub use value_type::*;
 pub use version::NodeVersion;
@@ -160,6 +164,8 @@ pub mod bindgen_prelude {
   pub use crate::bindgen_runtime::register_module_exports;
   #[cfg(feature = "tokio_rt")]
   pub use crate::tokio_runtime::*;
+  #[cfg(feature = "napi5")]
+  pub use crate::ProgressTask;
   pub use crate::{
     assert_type_of, bindgen_runtime::*, check_pending_exception, check_status,
     check_status_or_throw, error, error::*, sys, type_of, JsError, JsValue, Property,
@@ -182,7 +188,8 @@ pub mod bindgen_prelude {
 #[doc(hidden)]
 pub mod __private {
   pub use crate::bindgen_runtime::{
-    get_class_constructor, iterator::create_iterator, register_class, ___CALL_FROM_FACTORY,
+    get_class_constructor, iterator::create_iterator, register_class, register_class_extends,
+    ___CALL_FROM_FACTORY,
   };
 
   #[cfg(feature = "tokio_rt")]
@@ -224,6 +231,10 @@ pub mod __private {
 
 pub extern crate ctor;
 
+/// `#[napi(flags)]` enums are expanded to `bitflags` structs
+#[doc(hidden)]
+pub extern crate bitflags;
+
 #[cfg(feature = "tokio_rt")]
 pub extern crate tokio;
 
@@ -1,3 +1,5 @@
+#[cfg(feature = "napi5")]
+use crate::bindgen_runtime::TaskProgress;
 use crate::{
   bindgen_runtime::{ToNapiValue, TypeName},
   Env, Error, Result,
@@ -72,3 +74,33 @@ pub trait ScopedTask<'task>: Send + Sized {
     Ok(())
   }
 }
+
+/// A `Task` which reports progress to JavaScript while it computes
+///
+/// Return it from `#[napi]` functions with `AsyncProgressTask`, the reported values are delivered to the
+/// `progress` async iterable of the returned `Promise` and to the callback passed to `AsyncProgressTask::on_progress`.
+#[cfg(feature = "napi5")]
+pub trait ProgressTask: Send + Sized {
+  type Output: Send + Sized + 'static;
+  type JsValue: ToNapiValue + TypeName;
+  /// The value reported by `compute`
+  type Progress: ToNapiValue + Send + 'static;
+
+  /// Compute logic in libuv thread, report the progress with `progress`
+  fn compute(&mut self, progress: &TaskProgress<Self::Progress>) -> Result<Self::Output>;
+
+  /// Into this method if `compute` return `Ok`
+  fn resolve(&mut self, env: Env, output: Self::Output) -> Result<Self::JsValue>;
+
+  #[allow(unused_variables)]
+  /// Into this method if `compute` return `Err`
+  fn reject(&mut self, env: Env, err: Error) -> Result<Self::JsValue> {
+    Err(err)
+  }
+
+  #[allow(unused_variables)]
+  /// after resolve or reject
+  fn finally(self, env: Env) -> Result<()> {
+    Ok(())
+  }
+}
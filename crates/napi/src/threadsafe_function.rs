@@ -16,6 +16,10 @@ use crate::{
   check_status, get_error_message_and_stack_trace, sys, Env, Error, JsError, Result, Status,
 };
 
+mod object;
+
+pub use object::ThreadsafeObject;
+
 #[deprecated(since = "2.17.0", note = "Please use `ThreadsafeFunction` instead")]
 pub type ThreadSafeCallContext<T> = ThreadsafeCallContext<T>;
 
@@ -749,6 +753,7 @@ unsafe extern "C" fn call_js_cb<
             Err(Error {
               maybe_raw: error_reference,
               maybe_env: raw_env,
+              extra: None,
               cause: None,
               status: Status::from(raw_status),
               reason,
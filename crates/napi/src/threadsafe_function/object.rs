@@ -0,0 +1,281 @@
+use std::future::Future;
+use std::os::raw::c_void;
+use std::ptr;
+use std::sync::{self, atomic::Ordering, Arc};
+
+use futures::channel::oneshot::channel;
+
+use super::{ThreadsafeFunctionCallMode, ThreadsafeFunctionHandle};
+use crate::{
+  bindgen_runtime::{
+    call_object_method, FromNapiValue, JsValuesTupleIntoVec, Promise, TypeName, ValidateNapiValue,
+  },
+  check_status, sys, Env, Error, Result, Status, ValueType,
+};
+
+type ThreadsafeObjectCall = Box<dyn FnOnce(sys::napi_env, sys::napi_value) + Send>;
+
+enum MethodReturn<Return: 'static + FromNapiValue> {
+  Value(Return),
+  Promise(Promise<Return>),
+}
+
+/// A `Send + Sync` handle to a JavaScript object, its methods can be called from any thread.
+///
+/// All the clones of a `ThreadsafeObject` share one threadsafe function queue,
+/// so the method calls are executed on the JavaScript thread in the order they were made.
+/// The JavaScript object is released after the last clone is dropped.
+///
+/// Like the `ThreadsafeFunction`, a `ThreadsafeObject` keeps the event loop alive until it's released,
+/// a `ThreadsafeObject<true>` is unreferenced when it's created so it doesn't. It can be changed later with
+/// [`refer`](ThreadsafeObject::refer) and [`unref`](ThreadsafeObject::unref).
+///
+/// `#[napi(threadsafe)] trait` generates a typed wrapper of it, whose methods are the methods of the trait.
+///
+/// ```no_run
+/// use napi::{bindgen_prelude::*, threadsafe_function::ThreadsafeObject};
+/// use napi_derive::napi;
+///
+/// #[napi]
+/// pub async fn resolve_all(resolver: ThreadsafeObject, ids: Vec<u32>) -> Result<Vec<String>> {
+///   let mut resolved = Vec::with_capacity(ids.len());
+///   for id in ids {
+///     // `resolve` may return a `Promise<string>` or a `string`
+///     resolved.push(resolver.call_method::<_, String>("resolve", id).await?);
+///   }
+///   Ok(resolved)
+/// }
+/// ```
+#[derive(Clone)]
+pub struct ThreadsafeObject<const Weak: bool = false> {
+  handle: Arc<ThreadsafeFunctionHandle>,
+}
+
+impl<const Weak: bool> ThreadsafeObject<Weak> {
+  /// Queue a call of the method `name` on the JavaScript object, `this` in the method will be the object.
+  ///
+  /// The call is queued immediately, the returned future resolves with the return value of the method,
+  /// if the method returns a `Promise`, the future resolves with the settled value of it.
+  pub fn call_method<
+    Args: 'static + JsValuesTupleIntoVec + Send,
+    Return: 'static + FromNapiValue + Send,
+  >(
+    &self,
+    name: impl Into<String>,
+    args: Args,
+  ) -> impl Future<Output = Result<Return>> + Send + 'static {
+    let name = name.into();
+    let (sender, receiver) = channel::<Result<MethodReturn<Return>>>();
+    let call: ThreadsafeObjectCall = Box::new(move |env, object| {
+      let result = unsafe { call_method_in_js::<Args, Return>(env, object, &name, args) };
+      // the receiver may have been dropped, nobody cares about the result anymore
+      let _ = sender.send(result);
+    });
+    let status = self.handle.with_read_aborted(|aborted| {
+      if aborted {
+        return Err(Error::from_status(Status::Closing));
+      }
+      check_status!(
+        unsafe {
+          sys::napi_call_threadsafe_function(
+            self.handle.get_raw(),
+            Box::into_raw(Box::new(call)).cast(),
+            ThreadsafeFunctionCallMode::NonBlocking.into(),
+          )
+        },
+        "ThreadsafeObject call_method failed"
+      )
+    });
+    async move {
+      status?;
+      let method_return = receiver.await.map_err(|_| {
+        Error::new(
+          Status::Closing,
+          "ThreadsafeObject was closed before the method was called",
+        )
+      })??;
+      match method_return {
+        MethodReturn::Value(value) => Ok(value),
+        MethodReturn::Promise(promise) => promise.await,
+      }
+    }
+  }
+
+  /// Make the queue of the object keep the event loop alive, it's shared by all the clones.
+  ///
+  /// See [napi_ref_threadsafe_function](https://nodejs.org/api/n-api.html#n_api_napi_ref_threadsafe_function)
+  /// for more information.
+  pub fn refer(&self, env: &Env) -> Result<()> {
+    self.handle.with_read_aborted(|aborted| {
+      if !aborted && !self.handle.referred.load(Ordering::Relaxed) {
+        check_status!(unsafe { sys::napi_ref_threadsafe_function(env.0, self.handle.get_raw()) })?;
+        self.handle.referred.store(true, Ordering::Relaxed);
+      }
+      Ok(())
+    })
+  }
+
+  /// Let the event loop exit while the object is still referenced, it's shared by all the clones.
+  ///
+  /// See [napi_unref_threadsafe_function](https://nodejs.org/api/n-api.html#n_api_napi_unref_threadsafe_function)
+  /// for more information.
+  pub fn unref(&self, env: &Env) -> Result<()> {
+    self.handle.with_read_aborted(|aborted| {
+      if !aborted && self.handle.referred.load(Ordering::Relaxed) {
+        check_status!(unsafe {
+          sys::napi_unref_threadsafe_function(env.0, self.handle.get_raw())
+        })?;
+        self.handle.referred.store(false, Ordering::Relaxed);
+      }
+      Ok(())
+    })
+  }
+
+  pub fn aborted(&self) -> bool {
+    self.handle.with_read_aborted(|aborted| aborted)
+  }
+}
+
+/// Copy the `Error` and its `cause` chain without the JavaScript values, they can't be dropped on the other thread
+fn detach_error(err: &Error) -> Error {
+  let mut detached = Error::new(err.status, err.reason.clone());
+  if let Some(cause) = &err.cause {
+    detached.set_cause(detach_error(cause));
+  }
+  detached
+}
+
+unsafe fn call_method_in_js<Args: JsValuesTupleIntoVec, Return: 'static + FromNapiValue>(
+  env: sys::napi_env,
+  object: sys::napi_value,
+  name: &str,
+  args: Args,
+) -> Result<MethodReturn<Return>> {
+  let return_value =
+    unsafe { call_object_method(env, object, name, args) }.map_err(|err| detach_error(&err))?;
+  let mut is_promise = false;
+  check_status!(
+    unsafe { sys::napi_is_promise(env, return_value, &mut is_promise) },
+    "Failed to check if the return value of `{}` is a Promise",
+    name
+  )?;
+  if is_promise {
+    Ok(MethodReturn::Promise(unsafe {
+      Promise::from_napi_value(env, return_value)
+    }?))
+  } else {
+    Ok(MethodReturn::Value(unsafe {
+      Return::from_napi_value(env, return_value)
+    }?))
+  }
+}
+
+impl<const Weak: bool> TypeName for ThreadsafeObject<Weak> {
+  fn type_name() -> &'static str {
+    "ThreadsafeObject"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Object
+  }
+}
+
+impl<const Weak: bool> ValidateNapiValue for ThreadsafeObject<Weak> {}
+
+impl<const Weak: bool> FromNapiValue for ThreadsafeObject<Weak> {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    unsafe { Self::validate(env, napi_val) }?;
+    let mut object_ref = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_create_reference(env, napi_val, 1, &mut object_ref) },
+      "Failed to create reference in ThreadsafeObject::from_napi_value"
+    )?;
+
+    const ASYNC_RESOURCE_NAME: &str = "napi_rs_threadsafe_object";
+    let mut async_resource_name = ptr::null_mut();
+    check_status!(
+      unsafe {
+        sys::napi_create_string_utf8(
+          env,
+          ASYNC_RESOURCE_NAME.as_ptr().cast(),
+          ASYNC_RESOURCE_NAME.len() as isize,
+          &mut async_resource_name,
+        )
+      },
+      "Create string utf8 in ThreadsafeObject::from_napi_value failed"
+    )?;
+
+    let handle = ThreadsafeFunctionHandle::null();
+    let mut raw_tsfn = ptr::null_mut();
+    check_status!(
+      unsafe {
+        sys::napi_create_threadsafe_function(
+          env,
+          ptr::null_mut(),
+          ptr::null_mut(),
+          async_resource_name,
+          0,
+          1,
+          Arc::downgrade(&handle).into_raw().cast_mut().cast(), // pass handler to thread_finalize_cb
+          Some(thread_finalize_cb),
+          object_ref.cast(),
+          Some(call_js_cb),
+          &mut raw_tsfn,
+        )
+      },
+      "Create threadsafe function in ThreadsafeObject::from_napi_value failed"
+    )?;
+    handle.set_raw(raw_tsfn);
+
+    // Weak ThreadsafeObject will not prevent the event loop from exiting
+    if Weak {
+      check_status!(
+        unsafe { sys::napi_unref_threadsafe_function(env, raw_tsfn) },
+        "Unref threadsafe function failed in Weak mode"
+      )?;
+      handle.referred.store(false, Ordering::Relaxed);
+    }
+
+    Ok(Self { handle })
+  }
+}
+
+unsafe extern "C" fn thread_finalize_cb(
+  env: sys::napi_env,
+  finalize_data: *mut c_void,
+  finalize_hint: *mut c_void,
+) {
+  let handle_option: Option<Arc<ThreadsafeFunctionHandle>> =
+    unsafe { sync::Weak::from_raw(finalize_data.cast()).upgrade() };
+
+  if let Some(handle) = handle_option {
+    handle.with_write_aborted(|mut aborted_guard| {
+      if !*aborted_guard {
+        *aborted_guard = true;
+      }
+    });
+  }
+
+  unsafe { sys::napi_delete_reference(env, finalize_hint.cast()) };
+}
+
+unsafe extern "C" fn call_js_cb(
+  raw_env: sys::napi_env,
+  _js_callback: sys::napi_value,
+  context: *mut c_void,
+  data: *mut c_void,
+) {
+  let call = unsafe { Box::<ThreadsafeObjectCall>::from_raw(data.cast()) };
+  // env can be null when shutting down, drop the call to reject the pending future
+  if raw_env.is_null() {
+    return;
+  }
+
+  let mut object = ptr::null_mut();
+  let status = unsafe { sys::napi_get_reference_value(raw_env, context.cast(), &mut object) };
+  if status != sys::Status::napi_ok || object.is_null() {
+    return;
+  }
+
+  call(raw_env, object);
+}
@@ -4,9 +4,14 @@ use std::{future::Future, marker::PhantomData};
 
 use tokio::runtime::Runtime;
 
-use crate::{bindgen_runtime::ToNapiValue, sys, Env, Error, Result};
+use crate::{
+  bindgen_runtime::{AbortSignal, ToNapiValue},
+  sys, Env, Error, Result,
+};
 #[cfg(not(feature = "noop"))]
 use crate::{JsDeferred, SendableResolver, Unknown};
+#[cfg(not(feature = "noop"))]
+use futures::future::{AbortHandle, Abortable, Aborted};
 
 #[cfg(not(feature = "noop"))]
 fn create_runtime() -> Runtime {
@@ -262,6 +267,25 @@ pub fn execute_tokio_future_with_finalize_callback<
   fut: Fut,
   resolver: Resolver,
   finalize_callback: Option<Box<dyn FnOnce(sys::napi_env)>>,
+) -> Result<sys::napi_value> {
+  execute_tokio_future_with_abort_signals(env, fut, resolver, finalize_callback, vec![])
+}
+
+#[doc(hidden)]
+#[cfg(not(feature = "noop"))]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+/// Same as `execute_tokio_future_with_finalize_callback`, but the future will be dropped
+/// and the returned `Promise` will be rejected with `AbortError` once any of the `abort_signals` is aborted.
+pub fn execute_tokio_future_with_abort_signals<
+  Data: 'static + Send,
+  Fut: 'static + Send + Future<Output = std::result::Result<Data, impl Into<Error>>>,
+  Resolver: 'static + FnOnce(sys::napi_env, Data) -> Result<sys::napi_value>,
+>(
+  env: sys::napi_env,
+  fut: Fut,
+  resolver: Resolver,
+  finalize_callback: Option<Box<dyn FnOnce(sys::napi_env)>>,
+  abort_signals: Vec<AbortSignal>,
 ) -> Result<sys::napi_value> {
   let env = Env::from_raw(env);
   let (mut deferred, promise) = JsDeferred::new(&env)?;
@@ -273,14 +297,25 @@ pub fn execute_tokio_future_with_finalize_callback<
   let deferred_for_panic = deferred.clone();
   let sendable_resolver = SendableResolver::new(resolver);
 
+  let (abort_handle, abort_registration) = AbortHandle::new_pair();
+  for signal in abort_signals {
+    let abort_handle = abort_handle.clone();
+    if signal.aborted() {
+      abort_handle.abort();
+    } else {
+      signal.on_abort_send(move || abort_handle.abort());
+    }
+  }
+
   let inner = async move {
-    match fut.await {
-      Ok(v) => deferred.resolve(move |env| {
+    match Abortable::new(fut, abort_registration).await {
+      Ok(Ok(v)) => deferred.resolve(move |env| {
         sendable_resolver
           .resolve(env.raw(), v)
           .map(|v| unsafe { Unknown::from_raw_unchecked(env.raw(), v) })
       }),
-      Err(e) => deferred.reject(e.into()),
+      Ok(Err(e)) => deferred.reject(e.into()),
+      Err(Aborted) => deferred.abort(),
     }
   };
 
@@ -334,6 +369,22 @@ pub fn execute_tokio_future_with_finalize_callback<
   Ok(std::ptr::null_mut())
 }
 
+#[cfg(feature = "noop")]
+#[doc(hidden)]
+pub fn execute_tokio_future_with_abort_signals<
+  Data: 'static + Send,
+  Fut: 'static + Send + Future<Output = std::result::Result<Data, impl Into<Error>>>,
+  Resolver: 'static + FnOnce(sys::napi_env, Data) -> Result<sys::napi_value>,
+>(
+  _env: sys::napi_env,
+  _fut: Fut,
+  _resolver: Resolver,
+  _finalize_callback: Option<Box<dyn FnOnce(sys::napi_env)>>,
+  _abort_signals: Vec<AbortSignal>,
+) -> Result<sys::napi_value> {
+  Ok(std::ptr::null_mut())
+}
+
 pub struct AsyncBlockBuilder<
   V: Send + 'static,
   F: Future<Output = Result<V>> + Send + 'static,
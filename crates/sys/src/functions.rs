@@ -694,6 +694,19 @@ mod napi8 {
       fn napi_object_freeze(env: napi_env, object: napi_value) -> napi_status;
 
       fn napi_object_seal(env: napi_env, object: napi_value) -> napi_status;
+
+      fn napi_type_tag_object(
+        env: napi_env,
+        value: napi_value,
+        type_tag: *const napi_type_tag,
+      ) -> napi_status;
+
+      fn napi_check_object_type_tag(
+        env: napi_env,
+        value: napi_value,
+        type_tag: *const napi_type_tag,
+        result: *mut bool,
+      ) -> napi_status;
     }
   );
 }
@@ -838,6 +851,78 @@ pub use napi8::*;
 #[cfg(feature = "napi9")]
 pub use napi9::*;
 
+pub(super) fn is_loaded(name: &str) -> bool {
+  if napi1::loaded(name) {
+    return true;
+  }
+  #[cfg(feature = "napi2")]
+  if napi2::loaded(name) {
+    return true;
+  }
+  #[cfg(feature = "napi3")]
+  if napi3::loaded(name) {
+    return true;
+  }
+  #[cfg(feature = "napi4")]
+  if napi4::loaded(name) {
+    return true;
+  }
+  #[cfg(feature = "napi5")]
+  if napi5::loaded(name) {
+    return true;
+  }
+  #[cfg(feature = "napi6")]
+  if napi6::loaded(name) {
+    return true;
+  }
+  #[cfg(feature = "napi7")]
+  if napi7::loaded(name) {
+    return true;
+  }
+  #[cfg(feature = "napi8")]
+  if napi8::loaded(name) {
+    return true;
+  }
+  #[cfg(feature = "napi9")]
+  if napi9::loaded(name) {
+    return true;
+  }
+  #[cfg(feature = "napi10")]
+  if napi10::loaded(name) {
+    return true;
+  }
+  #[cfg(feature = "experimental")]
+  if experimental::loaded(name) {
+    return true;
+  }
+  false
+}
+
+pub(super) fn is_group_loaded(version: u32) -> bool {
+  match version {
+    1 => napi1::all_loaded(),
+    #[cfg(feature = "napi2")]
+    2 => napi2::all_loaded(),
+    #[cfg(feature = "napi3")]
+    3 => napi3::all_loaded(),
+    #[cfg(feature = "napi4")]
+    4 => napi4::all_loaded(),
+    #[cfg(feature = "napi5")]
+    5 => napi5::all_loaded(),
+    #[cfg(feature = "napi6")]
+    6 => napi6::all_loaded(),
+    #[cfg(feature = "napi7")]
+    7 => napi7::all_loaded(),
+    #[cfg(feature = "napi8")]
+    8 => napi8::all_loaded(),
+    #[cfg(feature = "napi9")]
+    9 => napi9::all_loaded(),
+    #[cfg(feature = "napi10")]
+    10 => napi10::all_loaded(),
+    _ => false,
+  }
+}
+
 #[cfg(all(windows, not(target_env = "msvc"), feature = "dyn-symbols"))]
 fn test_library(
   lib_result: Result<libloading::os::windows::Library, libloading::Error>,
@@ -913,3 +998,33 @@ pub(super) unsafe fn load_all() -> Result<libloading::Library, libloading::Error
   experimental::load(&host)?;
   Ok(host)
 }
+
+#[cfg(any(
+  target_env = "msvc",
+  all(not(target_family = "wasm"), feature = "dyn-symbols")
+))]
+pub(super) unsafe fn load_all_with(
+  resolve: &mut dyn FnMut(&str) -> Option<*const std::os::raw::c_void>,
+) {
+  napi1::load_with(resolve);
+  #[cfg(feature = "napi2")]
+  napi2::load_with(resolve);
+  #[cfg(feature = "napi3")]
+  napi3::load_with(resolve);
+  #[cfg(feature = "napi4")]
+  napi4::load_with(resolve);
+  #[cfg(feature = "napi5")]
+  napi5::load_with(resolve);
+  #[cfg(feature = "napi6")]
+  napi6::load_with(resolve);
+  #[cfg(feature = "napi7")]
+  napi7::load_with(resolve);
+  #[cfg(feature = "napi8")]
+  napi8::load_with(resolve);
+  #[cfg(feature = "napi9")]
+  napi9::load_with(resolve);
+  #[cfg(feature = "napi10")]
+  napi10::load_with(resolve);
+  #[cfg(feature = "experimental")]
+  experimental::load_with(resolve);
+}
@@ -35,6 +35,19 @@ macro_rules! generate {
       )*
     }
 
+    struct Loaded {
+      $(
+        $name: std::sync::atomic::AtomicBool,
+      )*
+    }
+
+    /// Which symbols have been resolved, the others are still the stub functions
+    static LOADED: Loaded = Loaded {
+      $(
+        $name: std::sync::atomic::AtomicBool::new(false),
+      )*
+    };
+
     static mut NAPI: Napi = {
       $(
         generate!(@stub_fn $name($($param: $ptype,)*) $( -> $rtype)?);
@@ -56,7 +69,10 @@ macro_rules! generate {
           $name: {
             let symbol: Result<libloading::Symbol<unsafe extern "C" fn ($(_: $ptype,)*)$( -> $rtype)*>, libloading::Error> = host.get(stringify!($name).as_bytes());
             match symbol {
-              Ok(f) => *f,
+              Ok(f) => {
+                LOADED.$name.store(true, std::sync::atomic::Ordering::Relaxed);
+                *f
+              }
               Err(_) => {
                 // ignore error, use the stub function
                 NAPI.$name
@@ -69,6 +85,41 @@ macro_rules! generate {
       Ok(())
     }
 
+    #[allow(clippy::missing_safety_doc)]
+    pub unsafe fn load_with(
+      resolve: &mut dyn FnMut(&str) -> Option<*const std::os::raw::c_void>,
+    ) {
+      $(
+        if let Some(f) = resolve(stringify!($name)) {
+          NAPI.$name = unsafe {
+            std::mem::transmute::<
+              *const std::os::raw::c_void,
+              unsafe extern "C" fn($(_: $ptype,)*)$( -> $rtype)*,
+            >(f)
+          };
+          LOADED.$name.store(true, std::sync::atomic::Ordering::Relaxed);
+        }
+      )*
+    }
+
+    /// Whether the symbol `name` of this group has been resolved
+    pub(super) fn loaded(name: &str) -> bool {
+      match name {
+        $(
+          stringify!($name) => LOADED.$name.load(std::sync::atomic::Ordering::Relaxed),
+        )*
+        _ => false,
+      }
+    }
+
+    /// Whether all the symbols of this group have been resolved
+    #[allow(dead_code)]
+    pub(super) fn all_loaded() -> bool {
+      $(
+        LOADED.$name.load(std::sync::atomic::Ordering::Relaxed)
+      )&&*
+    }
+
     $(
       #[inline]
       #[allow(clippy::missing_safety_doc)]
@@ -92,6 +143,17 @@ macro_rules! generate {
         pub fn $name($($param: $ptype,)*)$( -> $rtype)*;
       ) *
     }
+
+    /// The symbols are linked with the module, so all of them are reported as available
+    /// even if the host doesn't provide them
+    pub(super) fn loaded(name: &str) -> bool {
+      matches!(name, $(stringify!($name))|*)
+    }
+
+    #[allow(dead_code)]
+    pub(super) fn all_loaded() -> bool {
+      true
+    }
   };
 }
 
@@ -118,3 +180,47 @@ pub unsafe fn setup() -> libloading::Library {
     Ok(l) => l,
   }
 }
+
+#[cfg(any(
+  target_env = "msvc",
+  all(not(target_family = "wasm"), feature = "dyn-symbols")
+))]
+/// Loads N-API symbols from `resolve` instead of the host process, like the
+/// in-process host of `napi-mock` in tests.
+/// The symbols `resolve` returns `None` for are left as they are.
+///
+/// # Safety
+///
+/// The pointers returned by `resolve` must be functions with the signatures
+/// of the N-API symbols of the same names.
+pub unsafe fn setup_with(mut resolve: impl FnMut(&str) -> Option<*const std::os::raw::c_void>) {
+  unsafe { load_all_with(&mut resolve) }
+}
+
+/// Whether the N-API symbols are resolved from the host process when the module is loaded.
+///
+/// Only then [`is_loaded`] and [`is_version_loaded`] tell which symbols the host provides,
+/// otherwise they are linked with the module and the host has to be asked for its version.
+pub const SYMBOLS_RESOLVED_AT_RUNTIME: bool = cfg!(any(
+  target_env = "msvc",
+  all(not(target_family = "wasm"), feature = "dyn-symbols")
+));
+
+/// Whether the N-API symbol `name` is available.
+///
+/// With `dyn-symbols`, the symbols missing from the host process are replaced by
+/// stub functions which fail when called, it tells them apart.
+/// Otherwise the symbols are linked with the module and always considered available,
+/// see [`SYMBOLS_RESOLVED_AT_RUNTIME`].
+/// Symbols of the `napiN` versions and `experimental` features which are not
+/// enabled are never available.
+pub fn is_loaded(name: &str) -> bool {
+  functions::is_loaded(name)
+}
+
+/// Whether all the N-API symbols of `version` and the versions before it are available.
+///
+/// The versions whose `napiN` features are not enabled are never available.
+pub fn is_version_loaded(version: u32) -> bool {
+  version > 0 && (1..=version).all(functions::is_group_loaded)
+}
@@ -214,6 +214,13 @@ pub type napi_async_cleanup_hook_handle = *mut napi_async_cleanup_hook_handle__;
 #[cfg(feature = "napi8")]
 pub type napi_async_cleanup_hook =
   Option<unsafe extern "C" fn(handle: napi_async_cleanup_hook_handle, data: *mut c_void)>;
+#[cfg(feature = "napi8")]
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct napi_type_tag {
+  pub lower: u64,
+  pub upper: u64,
+}
 
 #[repr(C)]
 #[derive(Copy, Clone)]
@@ -1,3 +1,7 @@
+use napi::bindgen_prelude::*;
+use napi::threadsafe_function::{
+  ThreadsafeFunction, ThreadsafeFunctionCallMode, UnknownReturnValue,
+};
 use napi_derive::napi;
 
 #[napi]
@@ -12,6 +16,13 @@ pub enum MyEnum {
   B,
 }
 
+#[napi(flags)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MyFlags {
+  A = 1 << 0,
+  B = 1 << 1,
+}
+
 #[napi(object)]
 #[derive(Debug, PartialEq, Eq)]
 pub struct MyObject {
@@ -19,6 +30,103 @@ pub struct MyObject {
   pub b: i32,
 }
 
+#[napi]
+pub fn sum_object(object: MyObject) -> i32 {
+  object.a + object.b
+}
+
+#[napi]
+pub fn fail(reason: String) -> Result<()> {
+  Err(Error::from_reason(reason))
+}
+
+#[napi]
+pub struct Counter {
+  count: u32,
+}
+
+#[napi]
+impl Counter {
+  #[napi(constructor)]
+  pub fn new(initial: u32) -> Self {
+    Counter { count: initial }
+  }
+
+  #[napi]
+  pub fn increment(&mut self) -> u32 {
+    self.count += 1;
+    self.count
+  }
+
+  #[napi(getter)]
+  pub fn count(&self) -> u32 {
+    self.count
+  }
+}
+
+#[napi]
+pub struct Base {
+  pub name: String,
+}
+
+#[napi]
+impl Base {
+  #[napi(constructor)]
+  pub fn new(name: String) -> Self {
+    Base { name }
+  }
+}
+
+/// `repr(C)` keeps the base after `level`, not at the start of the struct
+#[napi(extends = Base)]
+#[repr(C)]
+pub struct Derived {
+  pub level: u32,
+  base: Base,
+}
+
+#[napi]
+impl Derived {
+  #[napi(constructor)]
+  pub fn new(name: String, level: u32) -> Self {
+    Derived {
+      level,
+      base: Base::new(name),
+    }
+  }
+}
+
+pub struct Double {
+  value: u32,
+}
+
+impl Task for Double {
+  type Output = u32;
+  type JsValue = u32;
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    Ok(self.value * 2)
+  }
+
+  fn resolve(&mut self, _: Env, output: Self::Output) -> Result<Self::JsValue> {
+    Ok(output)
+  }
+}
+
+#[napi]
+pub fn double_async(value: u32) -> AsyncTask<Double> {
+  AsyncTask::new(Double { value })
+}
+
+#[napi]
+pub fn count_in_thread(callback: ThreadsafeFunction<u32, UnknownReturnValue>) {
+  std::thread::spawn(move || {
+    for n in 0..3 {
+      callback.call(Ok(n), ThreadsafeFunctionCallMode::NonBlocking);
+    }
+  });
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -41,3 +149,121 @@ mod tests {
     assert_eq!(result, MyObject { a: 1, b: 2 });
   }
 }
+
+#[cfg(all(test, not(feature = "noop")))]
+mod mock_tests {
+  use std::cell::RefCell;
+  use std::rc::Rc;
+
+  use napi::Capability;
+  use napi_mock::MockEnv;
+
+  use super::*;
+
+  #[test]
+  fn test_plus_from_js() {
+    let env = MockEnv::new();
+    let plus: Function<FnArgs<(i32, i32)>, i32> = env.get_export("plus").unwrap();
+    assert_eq!(plus.call((1, 2).into()).unwrap(), 3);
+  }
+
+  #[test]
+  fn test_enum_from_js() {
+    let env = MockEnv::new();
+    let my_enum: Object = env.get_export("MyEnum").unwrap();
+    assert_eq!(my_enum.get_named_property::<u32>("B").unwrap(), 1);
+  }
+
+  #[test]
+  fn test_flags_from_js() {
+    let env = MockEnv::new();
+    let my_flags: Object = env.get_export("MyFlags").unwrap();
+    assert_eq!(my_flags.get_named_property::<u32>("B").unwrap(), 2);
+    let flags: MyFlags = env.round_trip(MyFlags::A | MyFlags::B).unwrap();
+    assert_eq!(flags, MyFlags::all());
+    let err = env.round_trip::<u32, MyFlags>(4).unwrap_err();
+    assert_eq!(err.status, Status::InvalidArg);
+  }
+
+  #[test]
+  fn test_struct_from_js() {
+    let env = MockEnv::new();
+    let object: MyObject = env.round_trip(MyObject { a: 1, b: 2 }).unwrap();
+    assert_eq!(object, MyObject { a: 1, b: 2 });
+    let sum_object: Function<MyObject, i32> = env.get_export("sumObject").unwrap();
+    assert_eq!(sum_object.call(object).unwrap(), 3);
+  }
+
+  #[test]
+  fn test_error_from_js() {
+    let env = MockEnv::new();
+    let fail: Function<String, ()> = env.get_export("fail").unwrap();
+    let err = fail.call("Boom".to_owned()).unwrap_err();
+    assert_eq!(err.reason, "Error: Boom");
+  }
+
+  #[test]
+  fn test_class_from_js() {
+    let env = MockEnv::new();
+    let counter_class: Function<u32, Unknown> = env.get_export("Counter").unwrap();
+    let counter: Object = counter_class
+      .new_instance(1)
+      .unwrap()
+      .coerce_to_object()
+      .unwrap();
+    let increment: Function<(), u32> = counter.get_named_property("increment").unwrap();
+    assert_eq!(increment.apply(counter, ()).unwrap(), 2);
+    assert_eq!(counter.get_named_property::<u32>("count").unwrap(), 2);
+    let counter: &Counter = env.round_trip(counter).unwrap();
+    assert_eq!(counter.count, 2);
+  }
+
+  #[test]
+  fn test_base_reference_from_derived() {
+    let env = MockEnv::new();
+    let derived_class: Function<FnArgs<(String, u32)>, Unknown> =
+      env.get_export("Derived").unwrap();
+    let derived = derived_class
+      .new_instance(("derived".to_owned(), 2).into())
+      .unwrap();
+    let base: Reference<Base> = env.round_trip(derived).unwrap();
+    assert_eq!(base.name, "derived");
+  }
+
+  #[test]
+  fn test_async_task_from_js() {
+    let env = MockEnv::new();
+    let double_async: Function<u32, Unknown> = env.get_export("doubleAsync").unwrap();
+    let promise = double_async.call(21).unwrap();
+    assert_eq!(env.await_promise::<u32>(promise).unwrap(), 42);
+  }
+
+  #[test]
+  fn test_capabilities() {
+    let mock = MockEnv::new();
+    let env = mock.env();
+    assert!(!env.supports(Capability::NapiVersion(11)));
+    assert!(env.supports(Capability::CreatePropertyKey));
+  }
+
+  #[test]
+  fn test_threadsafe_function_from_js() {
+    let mock = MockEnv::new();
+    let env = mock.env();
+    let received = Rc::new(RefCell::new(Vec::new()));
+    let callback = {
+      let received = received.clone();
+      env
+        .create_function_from_closure::<Unknown, (), _>("callback", move |ctx| {
+          received.borrow_mut().push(ctx.get::<u32>(1)?);
+          Ok(())
+        })
+        .unwrap()
+    };
+    let count_in_thread: Function<Function<Unknown, ()>, ()> =
+      mock.get_export("countInThread").unwrap();
+    count_in_thread.call(callback).unwrap();
+    mock.run_pending_jobs();
+    assert_eq!(*received.borrow(), vec![0, 1, 2]);
+  }
+}
@@ -73,3 +73,43 @@ impl ComplexClass {
     self.value.to_uppercase()
   }
 }
+
+// Plain Rust types without #[napi], exposed by the examples through #[napi(object, remote = ...)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Coordinate {
+  pub latitude: f64,
+  pub longitude: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SemVer {
+  major: u32,
+  minor: u32,
+  patch: u32,
+}
+
+impl SemVer {
+  pub fn new(major: u32, minor: u32, patch: u32) -> Self {
+    Self {
+      major,
+      minor,
+      patch,
+    }
+  }
+
+  pub fn major(&self) -> u32 {
+    self.major
+  }
+
+  pub fn minor(&self) -> u32 {
+    self.minor
+  }
+
+  pub fn patch(&self) -> u32 {
+    self.patch
+  }
+
+  pub fn bump_minor(&self) -> Self {
+    Self::new(self.major, self.minor + 1, 0)
+  }
+}
@@ -5,6 +5,8 @@ import { fileURLToPath } from 'node:url'
 import { createReadStream } from 'node:fs'
 import { readFile as nodeReadFile } from 'node:fs/promises'
 import { Readable } from 'node:stream'
+import { setFlagsFromString } from 'node:v8'
+import { runInNewContext } from 'node:vm'
 import { Subject, take } from 'rxjs'
 import Sinon, { spy } from 'sinon'
 
@@ -52,6 +54,20 @@ import {
   getIndexMapping,
   sumIndexMapping,
   indexmapPassthrough,
+  getJsMap,
+  DisposableResource,
+  AsyncDisposableResource,
+  Shape,
+  Circle,
+  Ring,
+  getShapeName,
+  getShapeNameByReference,
+  sumJsMap,
+  jsMapPassthrough,
+  logWith,
+  logTwice,
+  logAll,
+  type Logger,
   passSetToJs,
   passSetToRust,
   btreeSetToJs,
@@ -65,13 +81,32 @@ import {
   Context,
   GetterSetterWithClosures,
   enumToI32,
+  WatchEvent,
+  type WatchEventFlags,
+  watchEventsWithoutRemove,
   listObjKeys,
   createObj,
+  createObjFromEntries,
   mapOption,
   readFile,
   throwError,
   throwErrorWithCause,
   jsErrorCallback,
+  storeGet,
+  storeGetAsync,
+  storePut,
+  parseConfigLine,
+  parsePort,
+  parsePortAsync,
+  loadSettings,
+  validatePorts,
+  createAggregateError,
+  getAggregateErrorMessages,
+  StoreError,
+  StoreErrorNotFound,
+  StoreErrorQuotaExceeded,
+  StoreErrorReadOnly,
+  ConfigError,
   customStatusCode,
   panic,
   readPackageJson,
@@ -90,12 +125,15 @@ import {
   eitherF64OrU32,
   withoutAbortController,
   withAbortController,
+  countTo,
+  countToWithCallback,
   asyncTaskReadFile,
   asyncTaskOptionalReturn,
   asyncTaskFinally,
   asyncResolveArray,
   asyncTaskArraybuffer,
   asyncMultiTwo,
+  asyncWithAbortSignal,
   bigintAdd,
   createBigInt,
   createBigIntI64,
@@ -105,6 +143,10 @@ import {
   threadsafeFunctionThrowErrorWithStatus,
   threadsafeFunctionBuildThrowErrorWithStatus,
   threadsafeFunctionClosureCapture,
+  resolveWithThreadsafeObject,
+  resolveWithResolver,
+  callWeakThreadsafeObject,
+  type Resolver,
   tsfnCallWithCallback,
   tsfnAsyncCall,
   tsfnThrowFromJs,
@@ -118,6 +160,7 @@ import {
   createSymbolRef,
   threadsafeFunctionFatalMode,
   createExternal,
+  createExternalWithFinalize,
   getExternal,
   mutateExternal,
   createExternalString,
@@ -155,6 +198,12 @@ import {
   Bird,
   Assets,
   receiveStrictObject,
+  resolveBuildOptions,
+  countClusterServers,
+  withDefaultBackoff,
+  retryCountOfFetchTask,
+  pingTask,
+  downloadTask,
   receiveClassOrNumber,
   JsClassForEither,
   receiveMutClassOrNumber,
@@ -162,6 +211,9 @@ import {
   testSerdeRoundtrip,
   testSerdeBigNumberPrecision,
   testSerdeBufferBytes,
+  testSerdeWithOptions,
+  testSerdeExternalBytes,
+  testSerdeExternalBytesTwice,
   getBigintJsonValue,
   createObjWithProperty,
   receiveObjectOnlyFromJs,
@@ -195,6 +247,7 @@ import {
   getNumArr,
   getNestedNumArr,
   CustomFinalize,
+  FinalizeNotifier,
   plusOne,
   Width,
   captureErrorInCallback,
@@ -208,8 +261,16 @@ import {
   tsfnReturnPromise,
   tsfnReturnPromiseTimeout,
   returnFromSharedCrate,
+  moveCoordinate,
+  bumpMinorVersion,
+  routeEnd,
+  reverseRoute,
   chronoNativeDateTime,
   chronoNativeDateTimeReturn,
+  systemTimeAddSeconds,
+  instantElapsedMillis,
+  doubleDuration,
+  doubleDurationNanos,
   throwAsyncError,
   getModuleFileName,
   throwSyntaxError,
@@ -414,6 +475,86 @@ test('map', (t) => {
   })
 })
 
+test('js map', (t) => {
+  const map = getJsMap()
+  t.true(map instanceof Map)
+  t.deepEqual(Array.from(map), [
+    [2, 'b'],
+    [1, 'a'],
+  ])
+  t.is(
+    sumJsMap(
+      new Map([
+        [1, 2],
+        [10, 20],
+      ]),
+    ),
+    33,
+  )
+  t.deepEqual(
+    jsMapPassthrough(
+      new Map([
+        ['b', 1],
+        ['a', 2],
+      ]),
+    ),
+    new Map([
+      ['a', 2],
+      ['b', 1],
+    ]),
+  )
+  // @ts-expect-error
+  t.throws(() => sumJsMap([[1, 2]]), {
+    code: 'InvalidArg',
+    message: 'Expected a Map',
+  })
+  // @ts-expect-error
+  t.throws(() => jsMapPassthrough({ a: 1 }), {
+    code: 'InvalidArg',
+    message: 'Expected a Map',
+  })
+})
+
+test('trait object', (t) => {
+  const logs: [number, string][] = []
+  const logger: Logger = {
+    log(level, message) {
+      t.is(this, logger)
+      logs.push([level, message])
+    },
+    prefix() {
+      return '> '
+    },
+  }
+  logWith(logger, 'hello')
+  t.is(logTwice(logger, 'twice'), '> ')
+  logAll(logger, ['a', 'b'])
+  t.deepEqual(logs, [
+    [1, '> hello'],
+    [0, 'twice'],
+    [1, 'twice'],
+    [0, 'a'],
+    [1, 'b'],
+  ])
+  t.throws(() => logWith({ prefix: () => '' } as unknown as Logger, 'x'), {
+    code: 'FunctionExpected',
+    message: '`log` is not a function',
+  })
+  t.throws(
+    () =>
+      logWith(
+        {
+          log() {
+            throw new Error('boom')
+          },
+          prefix: () => '',
+        },
+        'x',
+      ),
+    { message: 'boom' },
+  )
+})
+
 test('set', (t) => {
   t.notThrows(() => {
     passSetToRust(new Set(['a', 'b', 'c']))
@@ -431,6 +572,29 @@ test('enum', (t) => {
   t.is(enumToI32(CustomNumEnum.Eight), 8)
 })
 
+test('flags enum', (t) => {
+  t.true(Object.isFrozen(WatchEvent))
+  t.deepEqual(Object.keys(WatchEvent), [
+    'Create',
+    'Modify',
+    'Remove',
+    'Rename',
+    'Write',
+  ])
+  const events = watchEventsWithoutRemove(
+    (WatchEvent.Create | WatchEvent.Remove) as WatchEventFlags,
+  )
+  t.is(events, WatchEvent.Create)
+  t.is(watchEventsWithoutRemove(events), WatchEvent.Create)
+  t.throws(() => watchEventsWithoutRemove(16 as WatchEventFlags), {
+    code: 'InvalidArg',
+    message: 'value `16` has bits `0x10` which are not flags of `WatchEvent`',
+  })
+  t.throws(() => watchEventsWithoutRemove(1.5 as WatchEventFlags), {
+    code: 'InvalidArg',
+  })
+})
+
 test('structured enum', (t) => {
   const hello: StructuredKind = {
     type2: 'Hello',
@@ -605,6 +769,67 @@ test('class', (t) => {
   }
 })
 
+test('class instance type tag', (t) => {
+  const fakeAnimal = Object.create(Animal.prototype)
+  t.true(fakeAnimal instanceof Animal)
+  t.throws(() => apply0(fakeAnimal, () => {}), {
+    code: 'InvalidArg',
+  })
+  t.throws(
+    // @ts-expect-error
+    () => apply0(new Dog('Doge'), () => {}),
+    {
+      code: 'InvalidArg',
+      message: /Value is not an instance of class/,
+    },
+  )
+  class SubAnimal extends Animal {}
+  t.notThrows(() => apply0(new SubAnimal(Kind.Dog, '旺财'), () => {}))
+  t.notThrows(() => apply0(Animal.withKind(Kind.Cat), () => {}))
+  t.throws(() => plusOne.call(Object.create(Width.prototype)), {
+    code: 'InvalidArg',
+  })
+})
+
+test('class extends', (t) => {
+  const shape = new Shape('square')
+  const circle = new Circle(2)
+  const ring = new Ring(2, 1)
+  t.true(circle instanceof Shape)
+  t.true(ring instanceof Circle)
+  t.true(ring instanceof Shape)
+  t.is(Object.getPrototypeOf(Ring), Circle)
+  t.is(shape.describe(), 'Shape: square')
+  t.is(circle.describe(), 'Shape: circle')
+  t.is(ring.describe(), 'Shape: ring')
+  t.is(shape.area(), 0)
+  t.is(circle.area(), Math.PI * 4)
+  t.is(ring.area(), Math.PI * 3)
+  t.is(ring.radius, 2)
+  t.is(ring.innerRadius, 1)
+  ring.rename('donut')
+  t.is(ring.name, 'donut')
+  t.is(getShapeName(ring), 'donut')
+  t.is(getShapeName(circle), 'circle')
+  t.is(getShapeNameByReference(ring), 'donut')
+  t.is(getShapeNameByReference(circle), 'circle')
+  t.throws(() => getShapeNameByReference({}))
+  t.throws(() => Circle.prototype.area.call(shape))
+})
+
+test('class dispose', async (t) => {
+  const resource = new DisposableResource()
+  t.false(resource.disposed)
+  t.is(typeof resource[Symbol.dispose], 'function')
+  resource[Symbol.dispose]()
+  t.true(resource.disposed)
+
+  const asyncResource = new AsyncDisposableResource()
+  t.false(asyncResource.disposed)
+  await asyncResource[Symbol.asyncDispose]()
+  t.true(asyncResource.disposed)
+})
+
 test('class with js_name', (t) => {
   // Test class instantiation and basic functionality
   const instance = new MyJsNamedClass('test_value')
@@ -852,6 +1077,18 @@ test('PromiseRaw::reject', async (t) => {
 test('object', (t) => {
   t.deepEqual(listObjKeys({ name: 'John Doe', age: 20 }), ['name', 'age'])
   t.deepEqual(createObj(), { test: 1 })
+  const versionPrototype = {
+    toString(this: { major: number; minor: number }) {
+      return `${this.major}.${this.minor}`
+    },
+  }
+  const version = createObjFromEntries(versionPrototype)
+  t.is(Object.getPrototypeOf(version), versionPrototype)
+  t.deepEqual(Object.entries(version), [
+    ['major', 3],
+    ['minor', 0],
+  ])
+  t.is(String(version), '3.0')
   t.throws(
     () =>
       objectGetNamedPropertyShouldPerformTypecheck({
@@ -1029,6 +1266,108 @@ test('Async error with stack trace', async (t) => {
   }
 })
 
+test('custom error classes', async (t) => {
+  t.is(storeGet('answer'), '42')
+  const notFound = t.throws(() => storeGet('question'), {
+    instanceOf: StoreErrorNotFound,
+    message: '`question` is not found',
+  })
+  t.true(notFound instanceof StoreError)
+  t.is(notFound?.name, 'StoreErrorNotFound')
+  t.is(notFound?.code, 'NOT_FOUND')
+  t.is(notFound?.key, 'question')
+  t.regex(notFound!.stack!, /^StoreErrorNotFound: `question` is not found\n/)
+
+  const quotaExceeded = t.throws(() => storePut(2048), {
+    instanceOf: StoreErrorQuotaExceeded,
+  })
+  t.is(quotaExceeded?.code, 'E_QUOTA')
+  t.is(quotaExceeded?.used, 2048)
+  t.false('limit' in quotaExceeded!)
+  t.throws(() => storePut(1, 0), {
+    instanceOf: StoreErrorReadOnly,
+    code: 'READ_ONLY',
+  })
+  t.notThrows(() => storePut(1))
+
+  await t.throwsAsync(() => storeGetAsync('question'), {
+    instanceOf: StoreErrorNotFound,
+    code: 'NOT_FOUND',
+  })
+
+  const configError = t.throws(() => parseConfigLine('napi.toml', 'x'), {
+    instanceOf: ConfigError,
+    code: 'CONFIG_ERROR',
+    message: 'Invalid config at napi.toml:1',
+  })
+  t.is(configError?.path, 'napi.toml')
+  t.is(configError?.line, 1)
+
+  const error = new StoreErrorNotFound('created in JS', { cause: configError })
+  t.true(error instanceof Error)
+  t.is(error.message, 'created in JS')
+  t.is(error.cause, configError)
+})
+
+test('error source chain as cause', async (t) => {
+  t.is(parsePort('8080'), 8080)
+  const error = t.throws(() => parsePort('http'), {
+    message: '`http` is not a valid port',
+  })
+  t.true(error?.cause instanceof Error)
+  t.is((error?.cause as Error).message, 'invalid digit found in string')
+  t.is((error?.cause as Error).cause, undefined)
+
+  const asyncError = await t.throwsAsync(() => parsePortAsync('http'), {
+    message: '`http` is not a valid port',
+  })
+  t.is((asyncError?.cause as Error).message, 'invalid digit found in string')
+
+  const settingsError = t.throws(() => loadSettings('http'), {
+    message: 'Failed to load settings',
+  })
+  const messages = []
+  for (let e: any = settingsError; e; e = e.cause) {
+    messages.push(e.message)
+  }
+  t.deepEqual(messages, [
+    'Failed to load settings',
+    '`http` is not a valid port',
+    'invalid digit found in string',
+  ])
+})
+
+test('AggregateError', (t) => {
+  t.deepEqual(validatePorts(['80', '443']), [80, 443])
+  const error = t.throws(() => validatePorts(['80', 'http', '99999']), {
+    instanceOf: AggregateError,
+    message: '2 of the ports are invalid',
+  }) as AggregateError
+  t.deepEqual(
+    error.errors.map((e) => e.message),
+    ['`http` is not a valid port', '`99999` is not a valid port'],
+  )
+  t.is(error.errors[0].cause.message, 'invalid digit found in string')
+
+  const created = createAggregateError(['a', 'b'])
+  t.true(created instanceof AggregateError)
+  t.is(created.message, 'Created in Rust')
+  t.deepEqual(
+    created.errors.map((e) => e.message),
+    ['a', 'b'],
+  )
+
+  t.deepEqual(
+    getAggregateErrorMessages(
+      new AggregateError([new Error('x'), new TypeError('y')], 'batch'),
+    ),
+    ['Error: x', 'TypeError: y'],
+  )
+  t.throws(() => getAggregateErrorMessages(new Error('not aggregate') as any), {
+    message: 'Value is not an AggregateError, its `errors` is not an array',
+  })
+})
+
 test('custom status code in Error', (t) => {
   t.throws(() => customStatusCode(), {
     code: 'Panic',
@@ -1077,6 +1416,132 @@ test('should throw if object type is not matched', (t) => {
   t.is(err2!.message, 'Missing field `name`')
 })
 
+test('object rename_all and default fields', (t) => {
+  t.deepEqual(resolveBuildOptions({ target_dir: 'target' }), {
+    target_dir: 'target',
+    release: false,
+    jobs: 4,
+    RUSTFLAGS: [],
+  })
+  t.deepEqual(
+    resolveBuildOptions({
+      target_dir: 'target',
+      release: true,
+      jobs: 8,
+      RUSTFLAGS: ['-Ctarget-cpu=native'],
+    }),
+    {
+      target_dir: 'target',
+      release: true,
+      jobs: 8,
+      RUSTFLAGS: ['-Ctarget-cpu=native'],
+    },
+  )
+  // @ts-expect-error
+  const err = t.throws(() => resolveBuildOptions({ targetDir: 'target' }))
+  t.is(err!.message, 'Missing field `target_dir`')
+})
+
+test('object collect_errors', (t) => {
+  t.is(
+    countClusterServers({
+      name: 'primary',
+      servers: [{ host: 'localhost', port: 8080 }],
+      replicas: 2,
+    }),
+    2,
+  )
+  const err = t.throws(
+    () =>
+      countClusterServers({
+        // @ts-expect-error
+        name: 1,
+        servers: [
+          { host: 'localhost', port: 8080 },
+          // @ts-expect-error
+          { port: '8081' },
+        ],
+      }),
+    { instanceOf: TypeError },
+  )
+  t.is(
+    err!.message,
+    [
+      'Found 3 invalid values when converting into `ClusterConfig`',
+      '  /name: Failed to convert JavaScript value `Number 1 ` into rust type `String`',
+      '  /servers/1/host: Missing field `host`',
+      '  /servers/1/port: Failed to convert napi value String into rust type `u16`',
+    ].join('\n'),
+  )
+  t.deepEqual(
+    (err as TypeError & { errors: { path: string; message: string }[] }).errors.map(
+      ({ path }) => path,
+    ),
+    ['/name', '/servers/1/host', '/servers/1/port'],
+  )
+  // a conversion running in a getter reports its own errors only
+  let innerError: unknown
+  const outerError = t.throws(
+    () =>
+      countClusterServers({
+        get name() {
+          try {
+            // @ts-expect-error
+            countClusterServers({ servers: [{}] })
+          } catch (e) {
+            innerError = e
+          }
+          return 'primary'
+        },
+        // @ts-expect-error
+        servers: [{ host: 'localhost' }],
+      }),
+    { instanceOf: TypeError },
+  )
+  t.deepEqual(
+    (outerError as TypeError & { errors: { path: string }[] }).errors.map(
+      ({ path }) => path,
+    ),
+    ['/servers/0/port'],
+  )
+  t.deepEqual(
+    (innerError as TypeError & { errors: { path: string }[] }).errors.map(
+      ({ path }) => path,
+    ),
+    ['/name', '/servers/0/host', '/servers/0/port'],
+  )
+})
+
+test('flatten fields', (t) => {
+  t.deepEqual(withDefaultBackoff({ url: 'https://napi.rs', retries: 3 }), {
+    url: 'https://napi.rs',
+    retries: 3,
+    backoffMs: 100,
+  })
+  // @ts-expect-error
+  t.throws(() => withDefaultBackoff({ url: 'https://napi.rs' }), {
+    message: 'Missing field `retries`',
+  })
+  t.is(
+    retryCountOfFetchTask({
+      kind: 'Download',
+      url: 'https://napi.rs',
+      retries: 2,
+      path: '/tmp/napi',
+    }),
+    2,
+  )
+  t.is(retryCountOfFetchTask({ kind: 'Ping', retries: 7 }), 7)
+  t.deepEqual(pingTask(4), { kind: 'Ping', retries: 4 })
+  t.deepEqual(downloadTask('https://napi.rs', '/tmp/napi', 2), {
+    kind: 'Download',
+    url: 'https://napi.rs',
+    retries: 2,
+    backoffMs: 100,
+    path: '/tmp/napi',
+  })
+})
+
 test('aliased rust struct and enum', (t) => {
   const a = ALIAS.A
   const b: AliasedStruct = {
@@ -1165,6 +1630,31 @@ test('serde-buffer-bytes', (t) => {
   t.is(testSerdeBufferBytes({ code: new ArrayBuffer(0) }), 0n)
 })
 
+test('serde with options', (t) => {
+  const telemetry = testSerdeWithOptions({
+    payload: new Uint8Array([1, 2, 3]),
+    timestamp: 10n,
+    counters: new Map([['requests', 2n]]),
+  })
+  t.true(telemetry.payload instanceof Uint8Array)
+  t.false(Buffer.isBuffer(telemetry.payload))
+  t.deepEqual(Array.from(telemetry.payload), [3, 2, 1])
+  t.is(telemetry.timestamp, 11n)
+  t.true(telemetry.counters instanceof Map)
+  t.deepEqual(Array.from(telemetry.counters), [['requests', 4n]])
+})
+
+test('serde external bytes', (t) => {
+  const frame = testSerdeExternalBytes(3)
+  t.is(frame.id, 3)
+  t.true(Buffer.isBuffer(frame.payload))
+  t.deepEqual(Array.from(frame.payload), [1, 1, 1])
+  t.is(testSerdeExternalBytes(0).payload.length, 0)
+  const [first, second] = testSerdeExternalBytesTwice(3)
+  t.deepEqual(Array.from(first.payload), [1, 1, 1])
+  t.deepEqual(Array.from(second.payload), [1, 1, 1])
+})
+
 test('get bigint json value', (t) => {
   t.notThrows(() => {
     getBigintJsonValue(-1n)
@@ -1475,6 +1965,29 @@ test('external', (t) => {
   t.is(getExternal(extRef), FX)
 })
 
+test('finalizers calling into JavaScript', async (t) => {
+  // expose `gc` in a new context, the test doesn't depend on `--expose-gc`
+  setFlagsFromString('--expose-gc')
+  const gc: () => void = runInNewContext('gc')
+  let finalized = 0
+  ;(() => {
+    for (let i = 0; i < 10; i++) {
+      new FinalizeNotifier(() => {
+        finalized++
+      })
+      createExternalWithFinalize(() => {
+        finalized++
+      })
+    }
+  })()
+  // a single gc() may not collect all of them, retry until they are finalized
+  for (let i = 0; i < 20 && finalized < 20; i++) {
+    gc()
+    await new Promise((resolve) => setTimeout(resolve, 10))
+  }
+  t.is(finalized, 20)
+})
+
 test('optional external', (t) => {
   const FX = 42
   const extEmpty = createOptionalExternal()
@@ -1502,6 +2015,41 @@ test('should be able to return object from shared crate', (t) => {
   })
 })
 
+test('should convert remote types through their mirror object', (t) => {
+  t.deepEqual(moveCoordinate({ latitude: 1, longitude: 2 }, 0.5), {
+    latitude: 1.5,
+    longitude: 2.5,
+  })
+  t.deepEqual(bumpMinorVersion({ major: 1, minor: 2, patch: 3 }), {
+    major: 1,
+    minor: 3,
+    patch: 0,
+  })
+  t.throws(() => bumpMinorVersion({ major: 1, minor: 2 ** 32 - 1, patch: 0 }), {
+    message: 'minor version overflow',
+  })
+  // @ts-expect-error
+  t.throws(() => bumpMinorVersion(1), {
+    code: 'InvalidArg',
+  })
+  const route = {
+    from: { latitude: 0, longitude: 0 },
+    stops: [{ latitude: 1, longitude: 1 }],
+    to: { latitude: 2, longitude: 2 },
+  }
+  t.deepEqual(routeEnd(route), { latitude: 2, longitude: 2 })
+  t.is(routeEnd({ ...route, to: undefined }), null)
+  t.deepEqual(reverseRoute(route), {
+    from: { latitude: 2, longitude: 2 },
+    stops: [{ latitude: 1, longitude: 1 }],
+    to: { latitude: 0, longitude: 0 },
+  })
+  // @ts-expect-error
+  t.throws(() => routeEnd({ ...route, stops: [{ longitude: 1 }] }), {
+    message: 'Missing field `latitude` on Route.stops',
+  })
+})
+
 const AbortSignalTest =
   typeof AbortController !== 'undefined' ? test : test.skip
 
@@ -1509,6 +2057,30 @@ test('async task without abort controller', async (t) => {
   t.is(await withoutAbortController(1, 2), 3)
 })
 
+test('async task with progress', async (t) => {
+  const task = countTo(5)
+  const progress = []
+  for await (const value of task.progress) {
+    progress.push(value)
+  }
+  t.deepEqual(progress, [1, 2, 3, 4, 5])
+  t.is(await task, 5)
+
+  const reported: number[] = []
+  t.is(await countToWithCallback(3, (value) => reported.push(value)), 3)
+  t.deepEqual(reported, [1, 2, 3])
+
+  // a slow consumer only gets the latest progress, the last one is always delivered
+  const slowTask = countTo(20)
+  const slowProgress = []
+  for await (const value of slowTask.progress) {
+    slowProgress.push(value)
+    await new Promise((resolve) => setTimeout(resolve, 30))
+  }
+  t.true(slowProgress.length < 20)
+  t.is(slowProgress[slowProgress.length - 1], 20)
+})
+
 // schedule async task always start immediately, hard to create a case that async task is scheduled but not started
 test.skip('async task with abort controller', async (t) => {
   const ctrl = new AbortController()
@@ -1596,6 +2168,32 @@ test('abort signal should be able to reuse with different tasks', async (t) => {
   })
 })
 
+AbortSignalTest('abort signal in async fn', async (t) => {
+  t.is(await asyncWithAbortSignal(1), 1)
+  const ctrl = new AbortController()
+  const promise = asyncWithAbortSignal(10_000, ctrl.signal)
+  setTimeout(() => ctrl.abort(), 10)
+  const err = await t.throwsAsync(() => promise, {
+    message: 'AbortError',
+  })
+  t.is(err?.name, 'AbortError')
+})
+
+AbortSignalTest('already aborted signal in async fn', async (t) => {
+  const ctrl = new AbortController()
+  ctrl.abort()
+  await t.throwsAsync(() => asyncWithAbortSignal(10_000, ctrl.signal), {
+    name: 'AbortError',
+  })
+})
+
+AbortSignalTest('abort resolved async fn', async (t) => {
+  const ctrl = new AbortController()
+  t.is(await asyncWithAbortSignal(1, ctrl.signal), 1)
+  ctrl.abort()
+  t.pass('should not throw')
+})
+
 test('async task finally must be called', async (t) => {
   const obj = {
     finally: false,
@@ -1701,6 +2299,69 @@ Napi4Test('ThreadsafeFunction closure capture data', (t) => {
   })
 })
 
+Napi4Test('call methods of ThreadsafeObject in order', async (t) => {
+  const calls: (number | string)[] = []
+  const resolver = {
+    resolve(id: number) {
+      calls.push(id)
+      return id % 2 ? Promise.resolve(`async-${id}`) : `sync-${id}`
+    },
+    done(count: number) {
+      calls.push(`done:${count}`)
+    },
+  }
+  t.deepEqual(await resolveWithThreadsafeObject(resolver, [1, 2, 3, 4]), [
+    'async-1',
+    'sync-2',
+    'async-3',
+    'sync-4',
+  ])
+  t.deepEqual(calls, [1, 2, 3, 4, 'done:4'])
+  await t.throwsAsync(
+    () =>
+      resolveWithThreadsafeObject(
+        { resolve: () => Promise.reject(new Error('rejected')), done() {} },
+        [1],
+      ),
+    { message: 'rejected' },
+  )
+})
+
+Napi4Test('call methods of #[napi(threadsafe)] trait in order', async (t) => {
+  const calls: (number | string)[] = []
+  const resolver: Resolver = {
+    resolve(id: number) {
+      calls.push(id)
+      return id % 2 ? Promise.resolve(`async-${id}`) : `sync-${id}`
+    },
+    done(count: number) {
+      calls.push(`done:${count}`)
+    },
+  }
+  t.deepEqual(await resolveWithResolver(resolver, [1, 2, 3]), [
+    'async-1',
+    'sync-2',
+    'async-3',
+  ])
+  t.deepEqual(calls, [1, 2, 3, 'done:3'])
+  const err = await t.throwsAsync(() =>
+    resolveWithResolver(
+      {
+        resolve: () => {
+          throw new Error('outer', { cause: new Error('inner') })
+        },
+        done() {},
+      },
+      [1],
+    ),
+  )
+  t.is((err?.cause as Error)?.message, 'Error: inner')
+})
+
+Napi4Test('call methods of weak ThreadsafeObject', async (t) => {
+  t.is(await callWeakThreadsafeObject({ ping: () => 'pong' }), 'pong')
+})
+
 Napi4Test('resolve value from thread safe function fatal mode', async (t) => {
   const tsfnFatalMode = new Promise<boolean>((resolve) => {
     threadsafeFunctionFatalMode(resolve)
@@ -1986,6 +2647,31 @@ Napi5Test('Date from chrono::NativeDateTime test', (t) => {
   t.is(fixture?.toISOString(), '2016-12-23T15:25:59.325Z')
 })
 
+Napi5Test('Date to std::time::SystemTime and Instant', (t) => {
+  const fixture = new Date('2016-12-23T15:25:59.325Z')
+  const later = systemTimeAddSeconds(fixture, 60)
+  t.true(later instanceof Date)
+  t.is(later.toISOString(), '2016-12-23T15:26:59.325Z')
+  t.is(systemTimeAddSeconds(new Date(-1500), 1).valueOf(), -500)
+  t.throws(() => systemTimeAddSeconds(new Date(NaN), 1), {
+    code: 'DateExpected',
+  })
+  const elapsed = instantElapsedMillis(new Date(Date.now() - 1000))
+  t.true(elapsed >= 1000 && elapsed < 60_000)
+})
+
+test('std::time::Duration to milliseconds', (t) => {
+  t.is(doubleDuration(1.25), 2.5)
+  t.is(doubleDuration(0), 0)
+  t.throws(() => doubleDuration(-1), { code: 'InvalidArg' })
+  t.throws(() => doubleDuration(NaN), { code: 'InvalidArg' })
+})
+
+BigIntTest('DurationNanos to BigInt nanoseconds', (t) => {
+  t.is(doubleDurationNanos(1_234_567_890_123n), 2_469_135_780_246n)
+  t.throws(() => doubleDurationNanos(-1n), { code: 'InvalidArg' })
+})
+
 const Napi9Test = Number(process.versions.napi) >= 9 ? test : test.skip
 
 Napi9Test('create symbol for', (t) => {
@@ -586,6 +586,7 @@ module.exports.JsAsset = nativeBinding.JsAsset
 module.exports.Assets = nativeBinding.Assets
 module.exports.JsAssets = nativeBinding.JsAssets
 module.exports.AsyncDataSource = nativeBinding.AsyncDataSource
+module.exports.AsyncDisposableResource = nativeBinding.AsyncDisposableResource
 module.exports.AsyncFib = nativeBinding.AsyncFib
 module.exports.AsyncThrowClass = nativeBinding.AsyncThrowClass
 module.exports.Bird = nativeBinding.Bird
@@ -595,10 +596,12 @@ module.exports.Blake2BKey = nativeBinding.Blake2BKey
 module.exports.Blake2bKey = nativeBinding.Blake2bKey
 module.exports.CatchOnConstructor = nativeBinding.CatchOnConstructor
 module.exports.CatchOnConstructor2 = nativeBinding.CatchOnConstructor2
+module.exports.Circle = nativeBinding.Circle
 module.exports.ClassInArray = nativeBinding.ClassInArray
 module.exports.ClassReturnInPromise = nativeBinding.ClassReturnInPromise
 module.exports.ClassWithFactory = nativeBinding.ClassWithFactory
 module.exports.ClassWithLifetime = nativeBinding.ClassWithLifetime
+module.exports.ConfigError = nativeBinding.ConfigError
 module.exports.Context = nativeBinding.Context
 module.exports.CounterRepro = nativeBinding.CounterRepro
 module.exports.CreateStringClass = nativeBinding.CreateStringClass
@@ -610,11 +613,13 @@ module.exports.CustomFinalize = nativeBinding.CustomFinalize
 module.exports.CustomStruct = nativeBinding.CustomStruct
 module.exports.DefaultUseNullableClass = nativeBinding.DefaultUseNullableClass
 module.exports.DelayedCounter = nativeBinding.DelayedCounter
+module.exports.DisposableResource = nativeBinding.DisposableResource
 module.exports.Dog = nativeBinding.Dog
 module.exports.Fib = nativeBinding.Fib
 module.exports.Fib2 = nativeBinding.Fib2
 module.exports.Fib3 = nativeBinding.Fib3
 module.exports.Fib4 = nativeBinding.Fib4
+module.exports.FinalizeNotifier = nativeBinding.FinalizeNotifier
 module.exports.GetterSetterWithClosures = nativeBinding.GetterSetterWithClosures
 module.exports.JsClassForEither = nativeBinding.JsClassForEither
 module.exports.JSOnlyMethodsClass = nativeBinding.JSOnlyMethodsClass
@@ -629,7 +634,13 @@ module.exports.NotWritableClass = nativeBinding.NotWritableClass
 module.exports.Optional = nativeBinding.Optional
 module.exports.PackageJsonReader = nativeBinding.PackageJsonReader
 module.exports.Reader = nativeBinding.Reader
+module.exports.Ring = nativeBinding.Ring
 module.exports.Selector = nativeBinding.Selector
+module.exports.Shape = nativeBinding.Shape
+module.exports.StoreError = nativeBinding.StoreError
+module.exports.StoreErrorNotFound = nativeBinding.StoreErrorNotFound
+module.exports.StoreErrorQuotaExceeded = nativeBinding.StoreErrorQuotaExceeded
+module.exports.StoreErrorReadOnly = nativeBinding.StoreErrorReadOnly
 module.exports.Thing = nativeBinding.Thing
 module.exports.ThingList = nativeBinding.ThingList
 module.exports.UseNullableClass = nativeBinding.UseNullableClass
@@ -663,6 +674,7 @@ module.exports.asyncTaskFinally = nativeBinding.asyncTaskFinally
 module.exports.asyncTaskOptionalReturn = nativeBinding.asyncTaskOptionalReturn
 module.exports.asyncTaskReadFile = nativeBinding.asyncTaskReadFile
 module.exports.asyncTaskVoidReturn = nativeBinding.asyncTaskVoidReturn
+module.exports.asyncWithAbortSignal = nativeBinding.asyncWithAbortSignal
 module.exports.bigintAdd = nativeBinding.bigintAdd
 module.exports.bigintFromI128 = nativeBinding.bigintFromI128
 module.exports.bigintFromI64 = nativeBinding.bigintFromI64
@@ -673,6 +685,7 @@ module.exports.bufferPassThrough = nativeBinding.bufferPassThrough
 module.exports.bufferWithAsyncBlock = nativeBinding.bufferWithAsyncBlock
 module.exports.buildThreadsafeFunctionFromFunction = nativeBinding.buildThreadsafeFunctionFromFunction
 module.exports.buildThreadsafeFunctionFromFunctionCalleeHandle = nativeBinding.buildThreadsafeFunctionFromFunctionCalleeHandle
+module.exports.bumpMinorVersion = nativeBinding.bumpMinorVersion
 module.exports.call0 = nativeBinding.call0
 module.exports.call1 = nativeBinding.call1
 module.exports.call2 = nativeBinding.call2
@@ -689,6 +702,7 @@ module.exports.callLongThreadsafeFunction = nativeBinding.callLongThreadsafeFunc
 module.exports.callRuleHandler = nativeBinding.callRuleHandler
 module.exports.callThenOnPromise = nativeBinding.callThenOnPromise
 module.exports.callThreadsafeFunction = nativeBinding.callThreadsafeFunction
+module.exports.callWeakThreadsafeObject = nativeBinding.callWeakThreadsafeObject
 module.exports.captureErrorInCallback = nativeBinding.captureErrorInCallback
 module.exports.chronoDateAdd1Minute = nativeBinding.chronoDateAdd1Minute
 module.exports.chronoDateFixtureReturn1 = nativeBinding.chronoDateFixtureReturn1
@@ -707,6 +721,10 @@ module.exports.concatStr = nativeBinding.concatStr
 module.exports.concatUtf16 = nativeBinding.concatUtf16
 module.exports.contains = nativeBinding.contains
 module.exports.convertU32Array = nativeBinding.convertU32Array
+module.exports.countClusterServers = nativeBinding.countClusterServers
+module.exports.countTo = nativeBinding.countTo
+module.exports.countToWithCallback = nativeBinding.countToWithCallback
+module.exports.createAggregateError = nativeBinding.createAggregateError
 module.exports.createArraybuffer = nativeBinding.createArraybuffer
 module.exports.createBigInt = nativeBinding.createBigInt
 module.exports.createBigIntI64 = nativeBinding.createBigIntI64
@@ -723,11 +741,13 @@ module.exports.createExternalRef = nativeBinding.createExternalRef
 module.exports.createExternalString = nativeBinding.createExternalString
 module.exports.createExternalTypedArray = nativeBinding.createExternalTypedArray
 module.exports.createExternalUtf16String = nativeBinding.createExternalUtf16String
+module.exports.createExternalWithFinalize = nativeBinding.createExternalWithFinalize
 module.exports.createFunction = nativeBinding.createFunction
 module.exports.createI32ArrayFromExternal = nativeBinding.createI32ArrayFromExternal
 module.exports.createObj = nativeBinding.createObj
 module.exports.createObjectRef = nativeBinding.createObjectRef
 module.exports.createObjectWithClassField = nativeBinding.createObjectWithClassField
+module.exports.createObjFromEntries = nativeBinding.createObjFromEntries
 module.exports.createObjWithProperty = nativeBinding.createObjWithProperty
 module.exports.createOptionalExternal = nativeBinding.createOptionalExternal
 module.exports.createReadableStream = nativeBinding.createReadableStream
@@ -752,6 +772,9 @@ module.exports.dateToNumber = nativeBinding.dateToNumber
 module.exports.DEFAULT_COST = nativeBinding.DEFAULT_COST
 module.exports.defineClass = nativeBinding.defineClass
 module.exports.derefUint8Array = nativeBinding.derefUint8Array
+module.exports.doubleDuration = nativeBinding.doubleDuration
+module.exports.doubleDurationNanos = nativeBinding.doubleDurationNanos
+module.exports.downloadTask = nativeBinding.downloadTask
 module.exports.either3 = nativeBinding.either3
 module.exports.either4 = nativeBinding.either4
 module.exports.eitherBoolOrFunction = nativeBinding.eitherBoolOrFunction
@@ -772,6 +795,7 @@ module.exports.fetch = nativeBinding.fetch
 module.exports.fibonacci = nativeBinding.fibonacci
 module.exports.fnReceivedAliased = nativeBinding.fnReceivedAliased
 module.exports.generateFunctionAndCallIt = nativeBinding.generateFunctionAndCallIt
+module.exports.getAggregateErrorMessages = nativeBinding.getAggregateErrorMessages
 module.exports.getBigintJsonValue = nativeBinding.getBigintJsonValue
 module.exports.getBtreeMapping = nativeBinding.getBtreeMapping
 module.exports.getBuffer = nativeBinding.getBuffer
@@ -784,6 +808,7 @@ module.exports.getExternal = nativeBinding.getExternal
 module.exports.getGlobal = nativeBinding.getGlobal
 module.exports.getIndexMapping = nativeBinding.getIndexMapping
 module.exports.getIndexMappingWithHasher = nativeBinding.getIndexMappingWithHasher
+module.exports.getJsMap = nativeBinding.getJsMap
 module.exports.getMapping = nativeBinding.getMapping
 module.exports.getMappingWithHasher = nativeBinding.getMappingWithHasher
 module.exports.getModuleFileName = nativeBinding.getModuleFileName
@@ -795,6 +820,8 @@ module.exports.getNumArr = nativeBinding.getNumArr
 module.exports.getNums = nativeBinding.getNums
 module.exports.getOptionalExternal = nativeBinding.getOptionalExternal
 module.exports.getPackageJsonName = nativeBinding.getPackageJsonName
+module.exports.getShapeName = nativeBinding.getShapeName
+module.exports.getShapeNameByReference = nativeBinding.getShapeNameByReference
 module.exports.getStrFromObject = nativeBinding.getStrFromObject
 module.exports.getterFromObj = nativeBinding.getterFromObj
 module.exports.getTuple = nativeBinding.getTuple
@@ -807,13 +834,20 @@ module.exports.i8ArrayToArray = nativeBinding.i8ArrayToArray
 module.exports.indexmapPassthrough = nativeBinding.indexmapPassthrough
 module.exports.indexSetToJs = nativeBinding.indexSetToJs
 module.exports.indexSetToRust = nativeBinding.indexSetToRust
+module.exports.instantElapsedMillis = nativeBinding.instantElapsedMillis
 module.exports.intoUtf8 = nativeBinding.intoUtf8
 module.exports.jsErrorCallback = nativeBinding.jsErrorCallback
+module.exports.jsMapPassthrough = nativeBinding.jsMapPassthrough
 module.exports.Kind = nativeBinding.Kind
 module.exports.KindInValidate = nativeBinding.KindInValidate
 module.exports.listObjKeys = nativeBinding.listObjKeys
+module.exports.loadSettings = nativeBinding.loadSettings
+module.exports.logAll = nativeBinding.logAll
+module.exports.logTwice = nativeBinding.logTwice
+module.exports.logWith = nativeBinding.logWith
 module.exports.mapOption = nativeBinding.mapOption
 module.exports.mergeTupleArray = nativeBinding.mergeTupleArray
+module.exports.moveCoordinate = nativeBinding.moveCoordinate
 module.exports.mutateArraybuffer = nativeBinding.mutateArraybuffer
 module.exports.mutateExternal = nativeBinding.mutateExternal
 module.exports.mutateOptionalExternal = nativeBinding.mutateOptionalExternal
@@ -830,9 +864,13 @@ module.exports.overrideIndividualArgOnFunctionWithCbArg = nativeBinding.override
 module.exports.overrideWholeFunctionType = nativeBinding.overrideWholeFunctionType
 module.exports.panic = nativeBinding.panic
 module.exports.panicInAsync = nativeBinding.panicInAsync
+module.exports.parseConfigLine = nativeBinding.parseConfigLine
+module.exports.parsePort = nativeBinding.parsePort
+module.exports.parsePortAsync = nativeBinding.parsePortAsync
 module.exports.passSetToJs = nativeBinding.passSetToJs
 module.exports.passSetToRust = nativeBinding.passSetToRust
 module.exports.passSetWithHasherToJs = nativeBinding.passSetWithHasherToJs
+module.exports.pingTask = nativeBinding.pingTask
 module.exports.plusOne = nativeBinding.plusOne
 module.exports.promiseInEither = nativeBinding.promiseInEither
 module.exports.promiseRawReturnClassInstance = nativeBinding.promiseRawReturnClassInstance
@@ -850,6 +888,10 @@ module.exports.receiveObjectWithClassField = nativeBinding.receiveObjectWithClas
 module.exports.receiveStrictObject = nativeBinding.receiveStrictObject
 module.exports.receiveString = nativeBinding.receiveString
 module.exports.referenceAsCallback = nativeBinding.referenceAsCallback
+module.exports.resolveBuildOptions = nativeBinding.resolveBuildOptions
+module.exports.resolveWithResolver = nativeBinding.resolveWithResolver
+module.exports.resolveWithThreadsafeObject = nativeBinding.resolveWithThreadsafeObject
+module.exports.retryCountOfFetchTask = nativeBinding.retryCountOfFetchTask
 module.exports.returnCString = nativeBinding.returnCString
 module.exports.returnEither = nativeBinding.returnEither
 module.exports.returnEitherClass = nativeBinding.returnEitherClass
@@ -859,7 +901,9 @@ module.exports.returnObjectOnlyToJs = nativeBinding.returnObjectOnlyToJs
 module.exports.returnUndefined = nativeBinding.returnUndefined
 module.exports.returnUndefinedIfInvalid = nativeBinding.returnUndefinedIfInvalid
 module.exports.returnUndefinedIfInvalidPromise = nativeBinding.returnUndefinedIfInvalidPromise
+module.exports.reverseRoute = nativeBinding.reverseRoute
 module.exports.roundtripStr = nativeBinding.roundtripStr
+module.exports.routeEnd = nativeBinding.routeEnd
 module.exports.runScript = nativeBinding.runScript
 module.exports.setNullByteProperty = nativeBinding.setNullByteProperty
 module.exports.setSymbolInObj = nativeBinding.setSymbolInObj
@@ -870,16 +914,24 @@ module.exports.spawnFutureLifetime = nativeBinding.spawnFutureLifetime
 module.exports.spawnThreadInThread = nativeBinding.spawnThreadInThread
 module.exports.Status = nativeBinding.Status
 module.exports.StatusInValidate = nativeBinding.StatusInValidate
+module.exports.storeGet = nativeBinding.storeGet
+module.exports.storeGetAsync = nativeBinding.storeGetAsync
+module.exports.storePut = nativeBinding.storePut
 module.exports.StringEnum = nativeBinding.StringEnum
 module.exports.sumBtreeMapping = nativeBinding.sumBtreeMapping
 module.exports.sumIndexMapping = nativeBinding.sumIndexMapping
+module.exports.sumJsMap = nativeBinding.sumJsMap
 module.exports.sumMapping = nativeBinding.sumMapping
 module.exports.sumNums = nativeBinding.sumNums
+module.exports.systemTimeAddSeconds = nativeBinding.systemTimeAddSeconds
 module.exports.testEscapedQuotesInComments = nativeBinding.testEscapedQuotesInComments
 module.exports.testLatin1Methods = nativeBinding.testLatin1Methods
 module.exports.testSerdeBigNumberPrecision = nativeBinding.testSerdeBigNumberPrecision
 module.exports.testSerdeBufferBytes = nativeBinding.testSerdeBufferBytes
+module.exports.testSerdeExternalBytes = nativeBinding.testSerdeExternalBytes
+module.exports.testSerdeExternalBytesTwice = nativeBinding.testSerdeExternalBytesTwice
 module.exports.testSerdeRoundtrip = nativeBinding.testSerdeRoundtrip
+module.exports.testSerdeWithOptions = nativeBinding.testSerdeWithOptions
 module.exports.testWorkers = nativeBinding.testWorkers
 module.exports.threadsafeFunctionBuildThrowErrorWithStatus = nativeBinding.threadsafeFunctionBuildThrowErrorWithStatus
 module.exports.threadsafeFunctionClosureCapture = nativeBinding.threadsafeFunctionClosureCapture
@@ -922,6 +974,7 @@ module.exports.validateHashMap = nativeBinding.validateHashMap
 module.exports.validateNull = nativeBinding.validateNull
 module.exports.validateNumber = nativeBinding.validateNumber
 module.exports.validateOptional = nativeBinding.validateOptional
+module.exports.validatePorts = nativeBinding.validatePorts
 module.exports.validatePromise = nativeBinding.validatePromise
 module.exports.validateString = nativeBinding.validateString
 module.exports.validateStringEnum = nativeBinding.validateStringEnum
@@ -932,8 +985,11 @@ module.exports.validateTypedArray = nativeBinding.validateTypedArray
 module.exports.validateTypedArraySlice = nativeBinding.validateTypedArraySlice
 module.exports.validateUint8ClampedSlice = nativeBinding.validateUint8ClampedSlice
 module.exports.validateUndefined = nativeBinding.validateUndefined
+module.exports.WatchEvent = nativeBinding.WatchEvent
+module.exports.watchEventsWithoutRemove = nativeBinding.watchEventsWithoutRemove
 module.exports.withAbortController = nativeBinding.withAbortController
 module.exports.withAbortSignalHandle = nativeBinding.withAbortSignalHandle
+module.exports.withDefaultBackoff = nativeBinding.withDefaultBackoff
 module.exports.withinAsyncRuntimeIfAvailable = nativeBinding.withinAsyncRuntimeIfAvailable
 module.exports.withoutAbortController = nativeBinding.withoutAbortController
 module.exports.xxh64Alias = nativeBinding.xxh64Alias
@@ -96,6 +96,12 @@ export declare class AsyncDataSource {
   static fromData(data: Array<string>, delayMs: number): AsyncDataSource
 }
 
+export declare class AsyncDisposableResource implements AsyncDisposable {
+  constructor()
+  get disposed(): boolean
+  [Symbol.asyncDispose](): Promise<void>
+}
+
 /**
  * This type implements JavaScript's async iterable protocol.
  * It can be used with `for await...of` loops.
@@ -141,6 +147,12 @@ export declare class CatchOnConstructor2 {
   constructor()
 }
 
+export declare class Circle extends Shape {
+  radius: number
+  constructor(radius: number)
+  area(): number
+}
+
 export declare class ClassInArray {
   constructor(value: number)
 }
@@ -162,6 +174,12 @@ export declare class ClassWithLifetime {
   getName(): string
 }
 
+export declare class ConfigError extends Error {
+  code: 'CONFIG_ERROR'
+  path: string
+  line: number
+}
+
 export declare class Context {
   maybeNeed?: boolean
   buffer: Uint8Array
@@ -231,6 +249,12 @@ export declare class DelayedCounter {
   constructor(max: number, delayMs: number)
 }
 
+export declare class DisposableResource implements Disposable {
+  constructor()
+  get disposed(): boolean
+  [Symbol.dispose](): void
+}
+
 export declare class Dog {
   name: string
   constructor(name: string)
@@ -288,6 +312,11 @@ export declare class Fib4 extends Iterator<unknown, void, number> {
   next(value?: number): IteratorResult<unknown, void>
 }
 
+/** Calls `onFinalize` after it's garbage collected */
+export declare class FinalizeNotifier {
+  constructor(onFinalize: () => void)
+}
+
 export declare class GetterSetterWithClosures {
   constructor()
 }
@@ -362,6 +391,12 @@ export declare class Reader {
   read(): Buffer
 }
 
+export declare class Ring extends Circle {
+  innerRadius: number
+  constructor(radius: number, innerRadius: number)
+  area(): number
+}
+
 export declare class Selector {
   orderBy: Array<string>
   select: Array<string>
@@ -370,6 +405,35 @@ export declare class Selector {
   constructor(orderBy: Array<string>, select: Array<string>, struct: string, where?: string)
 }
 
+export declare class Shape {
+  name: string
+  constructor(name: string)
+  area(): number
+  describe(): string
+  rename(name: string): void
+}
+
+/** Failed to access the store */
+export declare class StoreError extends Error {
+  code: 'NOT_FOUND' | 'E_QUOTA' | 'READ_ONLY'
+}
+
+/** The key doesn't exist in the store */
+export declare class StoreErrorNotFound extends StoreError {
+  code: 'NOT_FOUND'
+  key: string
+}
+
+export declare class StoreErrorQuotaExceeded extends StoreError {
+  code: 'E_QUOTA'
+  used: number
+  limit?: number
+}
+
+export declare class StoreErrorReadOnly extends StoreError {
+  code: 'READ_ONLY'
+}
+
 export declare class Thing {
 
 }
@@ -465,6 +529,8 @@ export declare function asyncTaskReadFile(path: string): Promise<Buffer>
 
 export declare function asyncTaskVoidReturn(): Promise<void>
 
+export declare function asyncWithAbortSignal(ms: number, signal?: AbortSignal | undefined | null): Promise<number>
+
 export interface B {
   bar: number
 }
@@ -489,10 +555,19 @@ export declare function bufferPassThrough(buf: Buffer): Promise<Buffer>
 
 export declare function bufferWithAsyncBlock(buf: Buffer): Promise<number>
 
+export interface BuildOptions {
+  target_dir: string
+  release?: boolean
+  jobs?: number
+  RUSTFLAGS?: Array<string>
+}
+
 export declare function buildThreadsafeFunctionFromFunction(callback: (arg0: number, arg1: number) => number): void
 
 export declare function buildThreadsafeFunctionFromFunctionCalleeHandle(callback: () => void): void
 
+export declare function bumpMinorVersion(version: SemVer): SemVer
+
 export interface C {
   baz: number
 }
@@ -529,6 +604,8 @@ export declare function callThenOnPromise(input: Promise<number>): Promise<strin
 
 export declare function callThreadsafeFunction(tsfn: ((err: Error | null, arg: number) => unknown)): void
 
+export declare function callWeakThreadsafeObject(object: { ping(): string }): Promise<string>
+
 export declare function captureErrorInCallback(cb1: () => void, cb2: (arg0: Error) => void): void
 
 export declare function chronoDateAdd1Minute(input: Date): Date
@@ -553,6 +630,12 @@ export declare function chronoUtcDateReturn(): Date | null
 
 export declare function chronoUtcDateToMillis(input: Date): number
 
+export interface ClusterConfig {
+  name: string
+  servers: Array<ServerConfig>
+  replicas?: number
+}
+
 export interface CompilerAssumptions {
   ignoreFunctionLength?: boolean
   noDocumentAll?: boolean
@@ -614,6 +697,19 @@ export declare function contains(source: string, target: string): boolean
 
 export declare function convertU32Array(input: Uint32Array): Array<number>
 
+export interface Coordinate {
+  latitude: number
+  longitude: number
+}
+
+export declare function countClusterServers(config: ClusterConfig): number
+
+export declare function countTo(target: number): Promise<number> & { progress: AsyncIterable<number> }
+
+export declare function countToWithCallback(target: number, onProgress: (arg: number) => void): Promise<number> & { progress: AsyncIterable<number> }
+
+export declare function createAggregateError(messages: Array<string>): AggregateError
+
 export declare function createArraybuffer(): ArrayBuffer
 
 export declare function createBigInt(): bigint
@@ -646,6 +742,8 @@ export declare function createExternalTypedArray(): Uint32Array
 
 export declare function createExternalUtf16String(): string
 
+export declare function createExternalWithFinalize(onFinalize: () => void): ExternalObject<() => void>
+
 export declare function createFunction(): (arg: number) => number
 
 export declare function createI32ArrayFromExternal(): Int32Array
@@ -656,6 +754,8 @@ export declare function createObjectRef(): object
 
 export declare function createObjectWithClassField(): ObjectFieldClassInstance
 
+export declare function createObjFromEntries(prototype: object): object
+
 export declare function createObjWithProperty(): { value: ArrayBuffer, get getter(): number }
 
 export declare function createOptionalExternal(size?: number | undefined | null): ExternalObject<number> | null
@@ -747,6 +847,12 @@ class DynamicRustClass {
 
 export declare function derefUint8Array(a: Uint8Array, b: Uint8ClampedArray): number
 
+export declare function doubleDuration(duration: number): number
+
+export declare function doubleDurationNanos(duration: bigint): bigint
+
+export declare function downloadTask(url: string, path: string, retries: number): FetchTask
+
 export declare function either3(input: string | number | boolean): number
 
 export declare function either4(input: string | number | boolean | Obj): number
@@ -789,6 +895,14 @@ export declare function f64ArrayToArray(input: Float64Array): Array<number>
 
 export declare function fetch(url: string, requestInit?: RequestInit | undefined | null): Promise<import('undici-types').Response>
 
+export type FetchOptions = RetryOptions & {
+  url: string
+}
+
+export type FetchTask =
+  | { kind: 'Download', path: string } & FetchOptions
+  | { kind: 'Ping' } & RetryOptions
+
 export declare function fibonacci(n: number): number
 
 export declare function fnReceivedAliased(s: AliasedStruct, e: ALIAS): void
@@ -799,6 +913,8 @@ export interface FunctionData {
 
 export declare function generateFunctionAndCallIt(): FunctionData
 
+export declare function getAggregateErrorMessages(error: AggregateError): Array<string>
+
 export declare function getBigintJsonValue(value: bigint): void
 
 export declare function getBtreeMapping(): Record<string, number>
@@ -823,6 +939,8 @@ export declare function getIndexMapping(): Record<string, number>
 
 export declare function getIndexMappingWithHasher(): Record<string, number>
 
+export declare function getJsMap(): Map<number, string>
+
 export declare function getMapping(): Record<string, number>
 
 export declare function getMappingWithHasher(): Record<string, number>
@@ -846,6 +964,10 @@ export declare function getOptionalExternal(external?: ExternalObject<number> |
 
 export declare function getPackageJsonName(packageJson: PackageJson): string
 
+export declare function getShapeName(shape: Shape): string
+
+export declare function getShapeNameByReference(shape: unknown): string
+
 export declare function getStrFromObject(): void
 
 export declare function getterFromObj(): number
@@ -870,10 +992,14 @@ export declare function indexSetToJs(): Set<string>
 
 export declare function indexSetToRust(set: Set<string>): void
 
+export declare function instantElapsedMillis(since: Date): number
+
 export declare function intoUtf8(s: string): string
 
 export declare function jsErrorCallback(value: unknown): Array<Error>
 
+export declare function jsMapPassthrough(fixture: Map<string, number>): Map<string, number>
+
 /** default enum values are continuos i32s start from 0 */
 export declare const enum Kind {
   /** Barks */
@@ -902,11 +1028,26 @@ export interface Latin1MethodsResult {
 
 export declare function listObjKeys(obj: object): Array<string>
 
+export declare function loadSettings(port: string): number
+
 export interface LocalDates {
   start: Date
   end?: Date
 }
 
+export declare function logAll(logger: Logger, messages: Array<string>): void
+
+/** A logger provided by JavaScript */
+export interface Logger {
+  /** Log a message with the given level */
+  log(level: number, message: string): void
+  prefix(): string
+}
+
+export declare function logTwice(logger: Logger, message: string): string
+
+export declare function logWith(logger: Logger, message: string): void
+
 export declare function mapOption(val?: number | undefined | null): number | null
 
 export declare function mergeTupleArray(t1: TupleToArray, t2: TupleToArray): TupleToArray
@@ -915,6 +1056,8 @@ export interface Meta {
   merge: boolean
 }
 
+export declare function moveCoordinate(coordinate: Coordinate, delta: number): Coordinate
+
 export declare function mutateArraybuffer(buf: ArrayBuffer): void
 
 export declare function mutateExternal(external: ExternalObject<number>, newVal: number): void
@@ -995,6 +1138,12 @@ export declare function panic(): void
 
 export declare function panicInAsync(): Promise<void>
 
+export declare function parseConfigLine(path: string, source: string): number
+
+export declare function parsePort(input: string): number
+
+export declare function parsePortAsync(input: string): Promise<number>
+
 export declare function passSetToJs(): Set<string>
 
 export declare function passSetToRust(set: Set<string>): void
@@ -1007,6 +1156,8 @@ export interface Pet {
   eitherTsfn: string | ((err: Error | null, arg: number) => number)
 }
 
+export declare function pingTask(retries: number): FetchTask
+
 export interface PluginLoadResult {
   name: string
   version: string
@@ -1083,6 +1234,25 @@ export interface RequestInit {
   headers?: Record<string, string>
 }
 
+export declare function resolveBuildOptions(options: BuildOptions): BuildOptions
+
+/** Resolve the ids from any thread */
+export interface Resolver {
+  resolve(id: number): string | Promise<string>
+  done(count: number): void
+}
+
+export declare function resolveWithResolver(resolver: Resolver, ids: Array<number>): Promise<Array<string>>
+
+export declare function resolveWithThreadsafeObject(resolver: { resolve(id: number): string | Promise<string>, done(count: number): void }, ids: Array<number>): Promise<Array<string>>
+
+export declare function retryCountOfFetchTask(task: FetchTask): number
+
+export interface RetryOptions {
+  retries: number
+  backoffMs?: number
+}
+
 export declare function returnCString(): string
 
 export declare function returnEither(input: number): string | number
@@ -1101,8 +1271,19 @@ export declare function returnUndefinedIfInvalid(input: boolean): boolean
 
 export declare function returnUndefinedIfInvalidPromise(input: Promise<boolean>): Promise<boolean>
 
+/** Swap the ends of the route, a route without destination keeps its start */
+export declare function reverseRoute(route: Route): Route
+
 export declare function roundtripStr(s: string): string
 
+export interface Route {
+  from: Coordinate
+  stops: Array<Coordinate>
+  to?: Coordinate
+}
+
+export declare function routeEnd(route: Route): Coordinate | null
+
 export interface Rule {
   name: string
   handler: RuleHandler<number, number>
@@ -1113,6 +1294,18 @@ export type RuleHandler<Args, Ret> =
 
 export declare function runScript(script: string): unknown
 
+/** Fields of `SemVer` are private, they are read through its getters */
+export interface SemVer {
+  major: number
+  minor: number
+  patch: number
+}
+
+export interface ServerConfig {
+  host: string
+  port: number
+}
+
 export declare function setNullByteProperty(obj: object): void
 
 export declare function setSymbolInObj(symbol: symbol): object
@@ -1139,6 +1332,12 @@ export declare const enum StatusInValidate {
   Done = 'Done'
 }
 
+export declare function storeGet(key: string): string
+
+export declare function storeGetAsync(key: string): Promise<string>
+
+export declare function storePut(size: number, limit?: number | undefined | null): void
+
 /** Example struct demonstrating object streaming with nested types */
 export interface StreamItem {
   something: NestedMetadata
@@ -1172,10 +1371,14 @@ export declare function sumBtreeMapping(nums: Record<string, number>): number
 
 export declare function sumIndexMapping(nums: Record<string, number>): number
 
+export declare function sumJsMap(nums: Map<number, number>): number
+
 export declare function sumMapping(nums: Record<string, number>): number
 
 export declare function sumNums(nums: Array<number>): number
 
+export declare function systemTimeAddSeconds(time: Date, seconds: number): Date
+
 /**
  * Function to test escaped quotes in comments.
  * This comment contains escaped quotes: \\"g+sx\\" and should not break JSON parsing.
@@ -1190,8 +1393,14 @@ export declare function testSerdeBigNumberPrecision(number: string): any
 
 export declare function testSerdeBufferBytes(obj: object): bigint
 
+export declare function testSerdeExternalBytes(len: number): { id: number, payload: Buffer }
+
+export declare function testSerdeExternalBytesTwice(len: number): [{ id: number, payload: Buffer }, { id: number, payload: Buffer }]
+
 export declare function testSerdeRoundtrip(data: any): any
 
+export declare function testSerdeWithOptions(telemetry: { payload: Uint8Array, timestamp: bigint, counters: Map<string, bigint> }): { payload: Uint8Array, timestamp: bigint, counters: Map<string, bigint> }
+
 export declare function testWorkers(amount: number, completeCallback: ((err: Error | null, ) => void)): void
 
 export declare function threadsafeFunctionBuildThrowErrorWithStatus(cb: (arg?: unknown) => unknown): void
@@ -1301,6 +1510,8 @@ export declare function validateNumber(i: number): number
 
 export declare function validateOptional(input1?: string | undefined | null, input2?: boolean | undefined | null): boolean
 
+export declare function validatePorts(ports: Array<string>): Array<number>
+
 export declare function validatePromise(p: Promise<number>): Promise<number>
 
 export declare function validateString(s: string): string
@@ -1328,10 +1539,29 @@ export interface ViteImportGlobMeta {
 export type VoidNullable<T = void> =
   Nullable<T>
 
+/** The events reported by a file watcher */
+export declare const enum WatchEvent {
+  /** A file was created */
+  Create = 1,
+  Modify = 2,
+  Remove = 4,
+  Rename = 8,
+  /** All the changes of the file content */
+  Write = 3
+}
+
+/** A bit mask of `WatchEvent` flags, e.g. `(WatchEvent.A | WatchEvent.B) as WatchEventFlags` */
+export type WatchEventFlags =
+  number & { readonly __flags: 'WatchEvent' }
+
+export declare function watchEventsWithoutRemove(events: WatchEventFlags): WatchEventFlags
+
 export declare function withAbortController(a: number, b: number, signal: AbortSignal): Promise<number>
 
 export declare function withAbortSignalHandle(signal: AbortSignal): Promise<number>
 
+export declare function withDefaultBackoff(options: FetchOptions): FetchOptions
+
 export declare function withinAsyncRuntimeIfAvailable(): void
 
 export declare function withoutAbortController(a: number, b: number): Promise<number>
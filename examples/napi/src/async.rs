@@ -37,6 +37,12 @@ async fn panic_in_async() {
   panic!("panic in async function");
 }
 
+#[napi]
+async fn async_with_abort_signal(ms: u32, _signal: Option<AbortSignal>) -> Result<u32> {
+  tokio::time::sleep(std::time::Duration::from_millis(ms as u64)).await;
+  Ok(ms)
+}
+
 #[napi(async_runtime)]
 pub fn within_async_runtime_if_available() {
   tokio::spawn(async {
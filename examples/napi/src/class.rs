@@ -1,7 +1,7 @@
 use napi::{
   bindgen_prelude::{
-    Buffer, ClassInstance, Function, JavaScriptClassExt, JsObjectValue, JsValue, ObjectFinalize,
-    This, Uint8Array, Unknown,
+    Buffer, ClassInstance, FromNapiValue, Function, FunctionRef, JavaScriptClassExt, JsObjectValue,
+    JsValue, ObjectFinalize, Reference, This, Uint8Array, Unknown,
   },
   Env, Property, PropertyAttributes, Result,
 };
@@ -431,6 +431,27 @@ impl ObjectFinalize for CustomFinalize {
   }
 }
 
+/// Calls `onFinalize` after it's garbage collected
+#[napi(custom_finalize = "post")]
+pub struct FinalizeNotifier {
+  on_finalize: FunctionRef<(), ()>,
+}
+
+#[napi]
+impl FinalizeNotifier {
+  #[napi(constructor)]
+  pub fn new(on_finalize: FunctionRef<(), ()>) -> Self {
+    Self { on_finalize }
+  }
+}
+
+// `finalize` calls into JavaScript, it can't be run in the GC callback
+impl ObjectFinalize for FinalizeNotifier {
+  fn finalize(self, env: Env) -> Result<()> {
+    self.on_finalize.borrow_back(&env)?.call(())
+  }
+}
+
 #[napi(constructor)]
 pub struct Width {
   pub value: i32,
@@ -609,3 +630,139 @@ fn rust_class_constructor(value: i32, mut this: This) -> Result<()> {
 fn rust_class_method(this: This) -> Result<i32> {
   this.get_named_property_unchecked::<i32>("dynamicValue")
 }
+
+#[napi]
+pub struct Shape {
+  pub name: String,
+}
+
+#[napi]
+impl Shape {
+  #[napi(constructor)]
+  pub fn new(name: String) -> Self {
+    Shape { name }
+  }
+
+  #[napi]
+  pub fn area(&self) -> f64 {
+    0.0
+  }
+
+  #[napi]
+  pub fn describe(&self) -> String {
+    format!("Shape: {}", self.name)
+  }
+
+  #[napi]
+  pub fn rename(&mut self, name: String) {
+    self.name = name;
+  }
+}
+
+#[napi(extends = Shape)]
+pub struct Circle {
+  shape: Shape,
+  pub radius: f64,
+}
+
+#[napi]
+impl Circle {
+  #[napi(constructor)]
+  pub fn new(radius: f64) -> Self {
+    Circle {
+      shape: Shape::new("circle".to_owned()),
+      radius,
+    }
+  }
+
+  #[napi]
+  pub fn area(&self) -> f64 {
+    std::f64::consts::PI * self.radius * self.radius
+  }
+}
+
+#[napi(extends = Circle)]
+pub struct Ring {
+  circle: Circle,
+  pub inner_radius: f64,
+}
+
+#[napi]
+impl Ring {
+  #[napi(constructor)]
+  pub fn new(radius: f64, inner_radius: f64) -> Self {
+    let mut circle = Circle::new(radius);
+    circle.shape.name = "ring".to_owned();
+    Ring {
+      circle,
+      inner_radius,
+    }
+  }
+
+  #[napi]
+  pub fn area(&self) -> f64 {
+    self.circle.area() - std::f64::consts::PI * self.inner_radius * self.inner_radius
+  }
+}
+
+#[napi]
+pub fn get_shape_name(shape: &Shape) -> String {
+  shape.name.clone()
+}
+
+#[napi]
+pub fn get_shape_name_by_reference(env: &Env, shape: Unknown) -> Result<String> {
+  let shape = unsafe { Reference::<Shape>::from_napi_value(env.raw(), shape.raw()) }?;
+  Ok(shape.name.clone())
+}
+
+#[napi]
+pub struct DisposableResource {
+  disposed: bool,
+}
+
+#[napi]
+impl DisposableResource {
+  #[napi(constructor)]
+  pub fn new() -> Self {
+    DisposableResource { disposed: false }
+  }
+
+  #[napi(getter)]
+  pub fn disposed(&self) -> bool {
+    self.disposed
+  }
+
+  #[napi(dispose)]
+  pub fn dispose(&mut self) {
+    self.disposed = true;
+  }
+}
+
+#[napi]
+pub struct AsyncDisposableResource {
+  disposed: std::sync::Arc<std::sync::atomic::AtomicBool>,
+}
+
+#[napi]
+impl AsyncDisposableResource {
+  #[napi(constructor)]
+  pub fn new() -> Self {
+    AsyncDisposableResource {
+      disposed: Default::default(),
+    }
+  }
+
+  #[napi(getter)]
+  pub fn disposed(&self) -> bool {
+    self.disposed.load(std::sync::atomic::Ordering::Relaxed)
+  }
+
+  #[napi(async_dispose)]
+  pub async fn dispose(&self) -> Result<()> {
+    let disposed = self.disposed.clone();
+    tokio::time::sleep(std::time::Duration::from_millis(1)).await;
+    disposed.store(true, std::sync::atomic::Ordering::Relaxed);
+    Ok(())
+  }
+}
@@ -90,3 +90,23 @@ pub fn chrono_date_fixture_return2() -> chrono::DateTime<FixedOffset> {
     .single()
     .unwrap()
 }
+
+#[napi]
+pub fn system_time_add_seconds(time: std::time::SystemTime, seconds: u32) -> std::time::SystemTime {
+  time + std::time::Duration::from_secs(seconds as u64)
+}
+
+#[napi]
+pub fn instant_elapsed_millis(since: std::time::Instant) -> f64 {
+  since.elapsed().as_secs_f64() * 1000.0
+}
+
+#[napi]
+pub fn double_duration(duration: std::time::Duration) -> std::time::Duration {
+  duration * 2
+}
+
+#[napi]
+pub fn double_duration_nanos(duration: DurationNanos) -> DurationNanos {
+  DurationNanos(duration.0 * 2)
+}
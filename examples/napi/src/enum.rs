@@ -83,9 +83,72 @@ pub fn validate_structured_enum(kind: StructuredKind) -> StructuredKind {
   kind
 }
 
+#[napi(discriminant = "kind")]
+pub enum FetchTask {
+  Download {
+    #[napi(flatten)]
+    options: crate::object::FetchOptions,
+    path: String,
+  },
+  Ping {
+    #[napi(flatten)]
+    retry: crate::object::RetryOptions,
+  },
+}
+
+#[napi]
+pub fn retry_count_of_fetch_task(task: FetchTask) -> u32 {
+  match task {
+    FetchTask::Download { options, .. } => options.retry.retries,
+    FetchTask::Ping { retry } => retry.retries,
+  }
+}
+
+#[napi]
+pub fn ping_task(retries: u32) -> FetchTask {
+  FetchTask::Ping {
+    retry: crate::object::RetryOptions {
+      retries,
+      backoff_ms: None,
+    },
+  }
+}
+
+#[napi]
+pub fn download_task(url: String, path: String, retries: u32) -> FetchTask {
+  FetchTask::Download {
+    options: crate::object::FetchOptions {
+      url,
+      retry: crate::object::RetryOptions {
+        retries,
+        backoff_ms: Some(100),
+      },
+    },
+    path,
+  }
+}
+
 #[napi]
 pub fn validate_structured_enum_lowercase(
   kind: StructuredKindLowercase,
 ) -> StructuredKindLowercase {
   kind
 }
+
+/// The events reported by a file watcher
+#[napi(flags)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchEvent {
+  /// A file was created
+  Create = 1 << 0,
+  Modify = 1 << 1,
+  Remove = 1 << 2,
+  Rename = 1 << 3,
+  /// All the changes of the file content
+  Write = 0b11,
+}
+
+#[napi]
+pub fn watch_events_without_remove(events: WatchEvent) -> WatchEvent {
+  events.difference(WatchEvent::Remove)
+}
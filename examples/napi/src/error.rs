@@ -85,3 +85,140 @@ pub fn extends_javascript_error(env: Env, error_class: Function<String>) -> Resu
   env.throw(error_object)?;
   Ok(())
 }
+
+/// Failed to access the store
+#[napi(error)]
+#[derive(Debug)]
+pub enum StoreError {
+  /// The key doesn't exist in the store
+  NotFound {
+    key: String,
+  },
+  #[napi(code = "E_QUOTA")]
+  QuotaExceeded {
+    used: u32,
+    limit: Option<u32>,
+  },
+  ReadOnly,
+}
+
+impl std::fmt::Display for StoreError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      StoreError::NotFound { key } => write!(f, "`{key}` is not found"),
+      StoreError::QuotaExceeded { used, .. } => write!(f, "{used} bytes exceed the quota"),
+      StoreError::ReadOnly => write!(f, "The store is read only"),
+    }
+  }
+}
+
+#[napi(error)]
+#[derive(Debug)]
+pub struct ConfigError {
+  pub path: String,
+  pub line: u32,
+}
+
+impl std::fmt::Display for ConfigError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "Invalid config at {}:{}", self.path, self.line)
+  }
+}
+
+#[napi]
+pub fn store_get(key: String) -> std::result::Result<String, StoreError> {
+  match key.as_str() {
+    "answer" => Ok("42".to_owned()),
+    _ => Err(StoreError::NotFound { key }),
+  }
+}
+
+#[napi]
+pub fn store_put(size: u32, limit: Option<u32>) -> std::result::Result<(), StoreError> {
+  match limit {
+    Some(0) => Err(StoreError::ReadOnly),
+    _ if size > limit.unwrap_or(1024) => Err(StoreError::QuotaExceeded { used: size, limit }),
+    _ => Ok(()),
+  }
+}
+
+#[napi]
+pub async fn store_get_async(key: String) -> std::result::Result<String, StoreError> {
+  store_get(key)
+}
+
+#[napi]
+pub fn parse_config_line(path: String, source: String) -> Result<u32> {
+  let line = source
+    .parse::<u32>()
+    .map_err(|_| ConfigError { path, line: 1 })?;
+  Ok(line)
+}
+
+#[derive(Debug)]
+pub struct PortError {
+  input: String,
+  source: std::num::ParseIntError,
+}
+
+impl std::fmt::Display for PortError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "`{}` is not a valid port", self.input)
+  }
+}
+
+impl std::error::Error for PortError {
+  fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+    Some(&self.source)
+  }
+}
+
+#[napi]
+pub fn parse_port(input: String) -> std::result::Result<u16, PortError> {
+  input.parse().map_err(|source| PortError { input, source })
+}
+
+#[napi]
+pub async fn parse_port_async(input: String) -> std::result::Result<u16, PortError> {
+  parse_port(input)
+}
+
+#[napi]
+pub fn load_settings(port: String) -> anyhow::Result<u16> {
+  use anyhow::Context;
+
+  let port = parse_port(port).context("Failed to load settings")?;
+  Ok(port)
+}
+
+#[napi]
+pub fn validate_ports(ports: Vec<String>) -> Result<Vec<u16>> {
+  let mut valid = vec![];
+  let mut errors = vec![];
+  for port in ports {
+    match parse_port(port) {
+      Ok(port) => valid.push(port),
+      Err(err) => errors.push(Error::from_error_chain(&err)),
+    }
+  }
+  if errors.is_empty() {
+    return Ok(valid);
+  }
+  let message = format!("{} of the ports are invalid", errors.len());
+  Err(JsAggregateError::new(errors, message).into())
+}
+
+#[napi]
+pub fn create_aggregate_error(messages: Vec<String>) -> JsAggregateError {
+  let errors = messages.into_iter().map(Error::from_reason).collect();
+  JsAggregateError::new(errors, "Created in Rust")
+}
+
+#[napi]
+pub fn get_aggregate_error_messages(error: JsAggregateError) -> Vec<String> {
+  error
+    .errors
+    .iter()
+    .map(|error| error.reason.clone())
+    .collect()
+}
@@ -10,6 +10,15 @@ pub fn create_external_string(content: String) -> External<String> {
   External::new(content)
 }
 
+#[napi]
+pub fn create_external_with_finalize(
+  on_finalize: FunctionRef<(), ()>,
+) -> External<FunctionRef<(), ()>> {
+  External::new_with_finalize(on_finalize, |env, on_finalize| {
+    on_finalize.borrow_back(&env)?.call(())
+  })
+}
+
 #[napi]
 pub fn get_external(external: &External<u32>) -> u32 {
   **external
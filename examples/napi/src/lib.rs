@@ -91,6 +91,7 @@ mod number;
 mod object;
 mod promise;
 mod reference;
+mod remote;
 mod scope;
 mod serde;
 mod set;
@@ -100,6 +101,7 @@ mod string;
 mod symbol;
 mod task;
 mod threadsafe_function;
+mod r#trait;
 mod transparent;
 mod r#type;
 mod typed_array;
@@ -2,6 +2,7 @@ use rustc_hash::FxBuildHasher;
 use std::collections::{BTreeMap, HashMap};
 
 use indexmap::IndexMap;
+use napi::bindgen_prelude::JsMap;
 
 #[napi]
 fn get_mapping() -> HashMap<String, u32> {
@@ -65,3 +66,21 @@ fn sum_index_mapping(nums: IndexMap<String, u32>) -> u32 {
 fn indexmap_passthrough(fixture: IndexMap<String, u32>) -> IndexMap<String, u32> {
   fixture
 }
+
+#[napi]
+fn get_js_map() -> JsMap<IndexMap<u32, String>> {
+  let mut map = IndexMap::new();
+  map.insert(2, "b".to_string());
+  map.insert(1, "a".to_string());
+  JsMap(map)
+}
+
+#[napi]
+fn sum_js_map(nums: JsMap<HashMap<i64, u32>>) -> i64 {
+  nums.iter().map(|(k, v)| k + *v as i64).sum()
+}
+
+#[napi]
+fn js_map_passthrough(fixture: JsMap<BTreeMap<String, u32>>) -> JsMap<BTreeMap<String, u32>> {
+  fixture
+}
@@ -13,6 +13,11 @@ fn create_obj(env: &Env) -> Object<'_> {
   obj
 }
 
+#[napi]
+fn create_obj_from_entries<'env>(env: &'env Env, prototype: Object) -> Result<Object<'env>> {
+  Object::from_entries_with_prototype(env, &prototype, [("major", 3), ("minor", 0)])
+}
+
 #[napi]
 fn get_global(env: &Env) -> Result<JsGlobal<'_>> {
   env.get_global()
@@ -78,6 +83,65 @@ pub fn get_str_from_object(env: &Env) {
   assert_eq!(obj.get("name").unwrap(), Some("value".to_string()));
 }
 
+#[napi(object, rename_all = "snake_case")]
+pub struct BuildOptions {
+  pub target_dir: String,
+  #[napi(default)]
+  pub release: bool,
+  #[napi(default = 4)]
+  pub jobs: u32,
+  #[napi(js_name = "RUSTFLAGS", default)]
+  pub rust_flags: Vec<String>,
+}
+
+#[napi]
+pub fn resolve_build_options(options: BuildOptions) -> BuildOptions {
+  options
+}
+
+#[napi(object, collect_errors)]
+pub struct ServerConfig {
+  pub host: String,
+  pub port: u16,
+}
+
+#[napi(object, collect_errors)]
+pub struct ClusterConfig {
+  pub name: String,
+  pub servers: Vec<ServerConfig>,
+  #[napi(default)]
+  pub replicas: u32,
+}
+
+#[napi]
+pub fn count_cluster_servers(config: ClusterConfig) -> u32 {
+  config.servers.len() as u32 * config.replicas.max(1)
+}
+
+#[napi(object)]
+pub struct RetryOptions {
+  pub retries: u32,
+  pub backoff_ms: Option<u32>,
+}
+
+#[napi(object)]
+pub struct FetchOptions {
+  pub url: String,
+  #[napi(flatten)]
+  pub retry: RetryOptions,
+}
+
+#[napi]
+pub fn with_default_backoff(options: FetchOptions) -> FetchOptions {
+  FetchOptions {
+    retry: RetryOptions {
+      backoff_ms: options.retry.backoff_ms.or(Some(100)),
+      ..options.retry
+    },
+    ..options
+  }
+}
+
 #[napi(object)]
 pub struct TsTypeChanged {
   #[napi(ts_type = "object")]
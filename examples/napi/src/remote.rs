@@ -0,0 +1,82 @@
+use napi::bindgen_prelude::*;
+use napi_shared::{Coordinate, SemVer};
+
+#[napi(object, remote = napi_shared::Coordinate)]
+pub struct CoordinateDef {
+  pub latitude: f64,
+  pub longitude: f64,
+}
+
+/// Fields of `SemVer` are private, they are read through its getters
+#[napi(object, remote = SemVer)]
+pub struct SemVerDef {
+  #[napi(getter = major)]
+  pub major: u32,
+  #[napi(getter = minor)]
+  pub minor: u32,
+  #[napi(getter = patch)]
+  pub patch: u32,
+}
+
+impl From<SemVerDef> for SemVer {
+  fn from(value: SemVerDef) -> Self {
+    SemVer::new(value.major, value.minor, value.patch)
+  }
+}
+
+#[napi(object)]
+pub struct Route {
+  #[napi(with = CoordinateDef)]
+  pub from: Coordinate,
+  #[napi(with = CoordinateDef)]
+  pub stops: Vec<Coordinate>,
+  #[napi(with = CoordinateDef)]
+  pub to: Option<Coordinate>,
+}
+
+#[napi(return_with = CoordinateDef)]
+pub fn move_coordinate(
+  #[napi(with = CoordinateDef)] coordinate: Coordinate,
+  delta: f64,
+) -> Coordinate {
+  Coordinate {
+    latitude: coordinate.latitude + delta,
+    longitude: coordinate.longitude + delta,
+  }
+}
+
+#[napi(return_with = CoordinateDef)]
+pub fn route_end(route: Route) -> Option<Coordinate> {
+  route.to.or_else(|| route.stops.last().cloned())
+}
+
+/// Swap the ends of the route, a route without destination keeps its start
+#[napi]
+pub fn reverse_route(route: Route) -> Route {
+  let Route {
+    from,
+    mut stops,
+    to,
+  } = route;
+  stops.reverse();
+  match to {
+    Some(to) => Route {
+      from: to,
+      stops,
+      to: Some(from),
+    },
+    None => Route {
+      from,
+      stops,
+      to: None,
+    },
+  }
+}
+
+#[napi(strict, return_with = SemVerDef)]
+pub fn bump_minor_version(#[napi(with = SemVerDef)] version: SemVer) -> Result<SemVer> {
+  if version.minor() == u32::MAX {
+    return Err(Error::new(Status::InvalidArg, "minor version overflow"));
+  }
+  Ok(version.bump_minor())
+}
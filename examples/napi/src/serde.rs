@@ -92,3 +92,54 @@ pub fn get_bigint_json_value(bigint_json_value: Value) {
     }
   }
 }
+
+#[derive(Serialize, Debug, Deserialize)]
+struct Telemetry {
+  payload: napi::ExternalBytes,
+  timestamp: u64,
+  counters: std::collections::HashMap<String, u64>,
+}
+
+#[napi(
+  ts_args_type = "telemetry: { payload: Uint8Array, timestamp: bigint, counters: Map<string, bigint> }",
+  ts_return_type = "{ payload: Uint8Array, timestamp: bigint, counters: Map<string, bigint> }"
+)]
+fn test_serde_with_options<'env>(env: &'env Env, telemetry: Unknown) -> Result<Unknown<'env>> {
+  let options = napi::SerdeOptions::new()
+    .with_bytes_as_uint8_array(true)
+    .with_bigint(true)
+    .with_map(true);
+  let mut telemetry: Telemetry = env.from_js_value_with_options(telemetry, options)?;
+  let mut payload = telemetry.payload.to_vec();
+  payload.reverse();
+  telemetry.payload = payload.into();
+  telemetry.timestamp += 1;
+  for count in telemetry.counters.values_mut() {
+    *count *= 2;
+  }
+  env.to_js_value_with_options(&telemetry, options)
+}
+
+#[derive(Serialize)]
+struct Frame {
+  id: u32,
+  payload: napi::ExternalBytes,
+}
+
+#[napi(ts_return_type = "{ id: number, payload: Buffer }")]
+fn test_serde_external_bytes(env: &Env, len: u32) -> Result<Unknown<'_>> {
+  let frame = Frame {
+    id: len,
+    payload: vec![1; len as usize].into(),
+  };
+  env.to_js_value(&frame)
+}
+
+#[napi(ts_return_type = "[{ id: number, payload: Buffer }, { id: number, payload: Buffer }]")]
+fn test_serde_external_bytes_twice(env: &Env, len: u32) -> Result<Unknown<'_>> {
+  let frame = Frame {
+    id: len,
+    payload: vec![1; len as usize].into(),
+  };
+  env.to_js_value(&(&frame, &frame))
+}
@@ -217,3 +217,39 @@ impl<'task> ScopedTask<'task> for AsyncTaskArrayBuffer {
 pub fn async_task_arraybuffer(data: Vec<u8>) -> AsyncTask<AsyncTaskArrayBuffer> {
   AsyncTask::new(AsyncTaskArrayBuffer { data })
 }
+
+pub struct CountTo {
+  target: u32,
+}
+
+#[napi]
+impl ProgressTask for CountTo {
+  type Output = u32;
+  type JsValue = u32;
+  type Progress = u32;
+
+  fn compute(&mut self, progress: &TaskProgress<Self::Progress>) -> Result<Self::Output> {
+    for i in 1..=self.target {
+      sleep(std::time::Duration::from_millis(5));
+      progress.report(i);
+    }
+    Ok(self.target)
+  }
+
+  fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+    Ok(output)
+  }
+}
+
+#[napi]
+pub fn count_to(target: u32) -> AsyncProgressTask<CountTo> {
+  AsyncProgressTask::new(CountTo { target })
+}
+
+#[napi]
+pub fn count_to_with_callback(
+  target: u32,
+  on_progress: FunctionRef<u32, ()>,
+) -> AsyncProgressTask<CountTo> {
+  AsyncProgressTask::new(CountTo { target }).on_progress(on_progress)
+}
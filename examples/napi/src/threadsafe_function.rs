@@ -2,7 +2,9 @@ use std::{sync::Arc, thread, time::Duration};
 
 use napi::{
   bindgen_prelude::*,
-  threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode, UnknownReturnValue},
+  threadsafe_function::{
+    ThreadsafeFunction, ThreadsafeFunctionCallMode, ThreadsafeObject, UnknownReturnValue,
+  },
   UnknownRef,
 };
 
@@ -291,3 +293,29 @@ pub async fn tsfn_weak(
 ) -> napi::Result<()> {
   tsfn.call_async(()).await
 }
+
+#[napi]
+pub async fn resolve_with_threadsafe_object(
+  #[napi(
+    ts_arg_type = "{ resolve(id: number): string | Promise<string>, done(count: number): void }"
+  )]
+  resolver: ThreadsafeObject,
+  ids: Vec<u32>,
+) -> Result<Vec<String>> {
+  // all the calls are queued before awaiting, the JavaScript methods are called in order
+  let pending = ids
+    .into_iter()
+    .map(|id| resolver.call_method::<_, String>("resolve", id))
+    .collect::<Vec<_>>();
+  let resolved = futures::future::try_join_all(pending).await?;
+  let count = resolved.len() as u32;
+  resolver.call_method::<_, ()>("done", count).await?;
+  Ok(resolved)
+}
+
+#[napi]
+pub async fn call_weak_threadsafe_object(
+  #[napi(ts_arg_type = "{ ping(): string }")] object: ThreadsafeObject<true>,
+) -> Result<String> {
+  object.call_method::<_, String>("ping", ()).await
+}
@@ -0,0 +1,65 @@
+use napi::bindgen_prelude::*;
+
+/// A logger provided by JavaScript
+#[napi]
+pub trait Logger {
+  /// Log a message with the given level
+  fn log(&self, level: u32, message: String) -> Result<()>;
+
+  fn prefix(&self) -> Result<String>;
+
+  fn info(&self, message: String) -> Result<()> {
+    self.log(1, format!("{}{message}", self.prefix()?))
+  }
+}
+
+#[napi]
+pub fn log_with(logger: Box<dyn Logger>, message: String) -> Result<()> {
+  logger.info(message)
+}
+
+#[napi]
+pub fn log_twice(logger: LoggerProxy, message: String) -> Result<String> {
+  log_messages(&logger, &[message.as_str(), message.as_str()])?;
+  logger.prefix()
+}
+
+fn log_messages(logger: &impl Logger, messages: &[&str]) -> Result<()> {
+  for (level, message) in messages.iter().enumerate() {
+    logger.log(level as u32, message.to_string())?;
+  }
+  Ok(())
+}
+
+#[napi]
+pub fn log_all(logger: impl Logger, messages: Vec<String>) -> Result<()> {
+  let messages = messages.iter().map(String::as_str).collect::<Vec<_>>();
+  log_messages(&logger, &messages)
+}
+
+/// Resolve the ids from any thread
+#[napi(threadsafe)]
+pub trait Resolver {
+  #[napi(ts_return_type = "string | Promise<string>")]
+  fn resolve(&self, id: u32) -> Result<String>;
+
+  fn done(&self, count: u32) -> Result<()>;
+}
+
+#[napi]
+pub async fn resolve_with_resolver(
+  resolver: ResolverThreadsafeProxy,
+  ids: Vec<u32>,
+) -> Result<Vec<String>> {
+  let pending = ids
+    .into_iter()
+    .map(|id| resolver.resolve(id))
+    .collect::<Vec<_>>();
+  let resolved = futures::future::try_join_all(pending).await?;
+  let count = resolved.len() as u32;
+  // the proxy can be moved to and called from a blocking thread too
+  tokio::task::spawn_blocking(move || futures::executor::block_on(resolver.done(count)))
+    .await
+    .map_err(|err| Error::new(Status::GenericFailure, format!("{err}")))??;
+  Ok(resolved)
+}